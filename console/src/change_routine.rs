@@ -1,13 +1,12 @@
 use std::fmt::Display;
 
-use async_recursion::async_recursion;
 use chrono::Utc;
-use inquire::{InquireError, Select};
+use inquire::InquireError;
 use tokio::sync::mpsc::Sender;
 
 use crate::{
     base_data::{life_area::LifeArea, routine::Routine, BaseData},
-    menu::top_menu::present_top_menu,
+    menu::prompt::Select,
     surrealdb_layer::{surreal_tables::SurrealTables, DataLayerCommands},
 };
 
@@ -46,7 +45,6 @@ impl<'e> LifeAreaItem<'e> {
     }
 }
 
-#[async_recursion]
 pub(crate) async fn change_routine(
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
 ) -> Result<(), ()> {
@@ -77,7 +75,7 @@ pub(crate) async fn change_routine(
                 life_area.summary()
             )
         }
-        Err(InquireError::OperationCanceled) => present_top_menu(send_to_data_storage_layer).await,
+        Err(InquireError::OperationCanceled) => Ok(()),
         Err(InquireError::OperationInterrupted) => Err(()),
         Err(_) => {
             todo!("TODO: Implement cancelling")
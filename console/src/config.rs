@@ -0,0 +1,69 @@
+//! Settings from config.toml in the on_purpose folder of the user's config directory, for example
+//! ~/.config/on_purpose/config.toml on Linux. The file is optional and read each time it is used so changes apply
+//! without restarting.
+//!
+//! ```toml
+//! #default, lap_percentage, deadline_first, or life_area_balanced
+//! ranking_strategy = "life_area_balanced"
+//! ```
+
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::systems::bullet_list::ranking_strategy::RankingStrategyName;
+
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub(crate) struct Config {
+    /// How the bullet list is ordered
+    pub(crate) ranking_strategy: RankingStrategyName,
+}
+
+impl Config {
+    /// The defaults are used if there is no config file, if the file has a mistake that is shown and the defaults are
+    /// used so the menus are still usable
+    pub(crate) fn load() -> Self {
+        let path = match default_config_path() {
+            Some(path) => path,
+            None => return Config::default(),
+        };
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Config::default(),
+            Err(err) => {
+                println!("Unable to read {}: {}", path.display(), err);
+                return Config::default();
+            }
+        };
+        Config::parse(&text).unwrap_or_else(|err| {
+            println!("Ignoring {} because: {}", path.display(), err);
+            Config::default()
+        })
+    }
+
+    fn parse(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+}
+
+fn default_config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|x| x.join("on_purpose").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_missing_setting_uses_the_default_and_an_unknown_strategy_is_an_error() {
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+        assert_eq!(
+            Config::parse("ranking_strategy = \"life_area_balanced\"")
+                .unwrap()
+                .ranking_strategy,
+            RankingStrategyName::LifeAreaBalanced
+        );
+        assert!(Config::parse("ranking_strategy = \"alphabetical\"").is_err());
+    }
+}
@@ -0,0 +1,683 @@
+//! RocksDB only lets one process open the database so the daemon owns it and other on_purpose processes, like a
+//! quick capture from another terminal, send it their DataLayerCommands over a Unix socket. Each request and each
+//! response is one line of JSON.
+
+use std::{
+    future::Future,
+    io,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb::opt::RecordId;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines},
+    net::{
+        unix::{OwnedReadHalf, OwnedWriteHalf},
+        UnixListener, UnixStream,
+    },
+    sync::{
+        broadcast::{self, error::RecvError},
+        mpsc::{self, Receiver, Sender},
+    },
+    task::JoinHandle,
+};
+
+use crate::{
+    new_item::NewItem,
+    surrealdb_layer::{
+        surreal_covering::SurrealCovering,
+        surreal_covering_until_date_time::SurrealCoveringUntilDatetime,
+        surreal_item::{
+            CalendarEvent, DueDate, Facing, GitBranch, ItemType, Permanence, Responsibility,
+            Staging, SurrealOrderedSubItem, WaitingFor,
+        },
+        surreal_item_archive::SurrealItemArchive,
+        surreal_processed_text::SurrealProcessedText,
+        surreal_required_circumstance::SurrealRequiredCircumstance,
+        surreal_tables::{SurrealTables, WouldCreateACycle},
+        DataChanged, DataLayerCommands,
+    },
+};
+
+pub(crate) fn default_socket_path() -> PathBuf {
+    //XDG_RUNTIME_DIR is private to the user where the temp directory is usually shared
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir)
+        .join("on_purpose.sock")
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+enum DaemonRequest {
+    SendRawData,
+    /// The connection is used just for DataChanged responses after this
+    SubscribeToChanges,
+    SendProcessedText(RecordId),
+    SendAllProcessedText,
+    SendItemArchive,
+    /// Boxed because NewItem is much larger than the other requests
+    NewItemAndSendId(Box<NewItem>),
+    CoverItemWithAnExistingItem {
+        item_to_be_covered: RecordId,
+        item_that_should_do_the_covering: RecordId,
+    },
+    ParentItemWithExistingItem {
+        child: RecordId,
+        parent: RecordId,
+        higher_priority_than_this: Option<RecordId>,
+    },
+    /// Commands that do not have a response, boxed because NewItem is much larger than the other requests
+    Command(Box<DaemonCommand>),
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+enum DaemonResponse {
+    RawData(SurrealTables),
+    Subscribed,
+    DataChanged,
+    ProcessedText(Vec<SurrealProcessedText>),
+    ItemArchive(Vec<SurrealItemArchive>),
+    NewItemId(RecordId),
+    /// Whether a CoverItemWithAnExistingItem or ParentItemWithExistingItem was made or refused
+    ChangeMade(Result<(), WouldCreateACycle>),
+}
+
+/// The DataLayerCommands that do not have a response
+#[derive(Serialize, Deserialize, Debug)]
+enum DaemonCommand {
+    RemoveProcessedText(SurrealProcessedText),
+    AddProcessedText(String, RecordId),
+    FinishItem(RecordId),
+    UnfinishItem(RecordId),
+    DeleteItem(RecordId),
+    ArchiveFinishedItems {
+        finished_before: DateTime<Utc>,
+    },
+    NewItem(NewItem),
+    CoverItemWithANewItem {
+        cover_this: RecordId,
+        cover_with: NewItem,
+    },
+    RemoveCoveringItem(SurrealCovering),
+    RemoveCoveringUntilDatetime(SurrealCoveringUntilDatetime),
+    RemoveRequiredCircumstance(SurrealRequiredCircumstance),
+    CoverItemUntilAnExactDateTime(RecordId, DateTime<Utc>),
+    ParentItemWithANewChildItem {
+        child: NewItem,
+        parent: RecordId,
+        higher_priority_than_this: Option<RecordId>,
+    },
+    ParentNewItemWithAnExistingChildItem {
+        child: RecordId,
+        parent_new_item: NewItem,
+    },
+    UpdateResponsibilityAndItemType(RecordId, Responsibility, ItemType),
+    UpdateItemResponsibility(RecordId, Responsibility),
+    UpdateItemPermanence(RecordId, Permanence),
+    UpdateItemStaging(RecordId, Staging),
+    UpdateItemDueDate(RecordId, Option<DueDate>),
+    UpdateItemWaitingFor(RecordId, Option<WaitingFor>),
+    UpdateItemCalendarEvent(RecordId, Option<Box<CalendarEvent>>),
+    UpdateItemGitBranch(RecordId, Option<Box<GitBranch>>),
+    UpdateItemSummary(RecordId, String),
+    UpdateFacing(RecordId, Vec<Facing>),
+    UpdateSmallerItemsInPriorityOrder(RecordId, Vec<SurrealOrderedSubItem>),
+}
+
+impl From<DataLayerCommands> for DaemonCommand {
+    fn from(value: DataLayerCommands) -> Self {
+        match value {
+            DataLayerCommands::SendRawData(..)
+            | DataLayerCommands::SubscribeToChanges(..)
+            | DataLayerCommands::SendProcessedText(..)
+            | DataLayerCommands::SendAllProcessedText(..)
+            | DataLayerCommands::SendItemArchive(..)
+            | DataLayerCommands::NewItemAndSendId(..)
+            | DataLayerCommands::CoverItemWithAnExistingItem { .. }
+            | DataLayerCommands::ParentItemWithExistingItem { .. } => {
+                unreachable!("Commands with a response are sent as their own DaemonRequest")
+            }
+            DataLayerCommands::RemoveProcessedText(a) => DaemonCommand::RemoveProcessedText(a),
+            DataLayerCommands::AddProcessedText(a, b) => DaemonCommand::AddProcessedText(a, b),
+            DataLayerCommands::FinishItem(a) => DaemonCommand::FinishItem(a),
+            DataLayerCommands::UnfinishItem(a) => DaemonCommand::UnfinishItem(a),
+            DataLayerCommands::DeleteItem(a) => DaemonCommand::DeleteItem(a),
+            DataLayerCommands::ArchiveFinishedItems { finished_before } => {
+                DaemonCommand::ArchiveFinishedItems { finished_before }
+            }
+            DataLayerCommands::NewItem(a) => DaemonCommand::NewItem(a),
+            DataLayerCommands::CoverItemWithANewItem {
+                cover_this,
+                cover_with,
+            } => DaemonCommand::CoverItemWithANewItem {
+                cover_this,
+                cover_with,
+            },
+            DataLayerCommands::RemoveCoveringItem(a) => DaemonCommand::RemoveCoveringItem(a),
+            DataLayerCommands::RemoveCoveringUntilDatetime(a) => {
+                DaemonCommand::RemoveCoveringUntilDatetime(a)
+            }
+            DataLayerCommands::RemoveRequiredCircumstance(a) => {
+                DaemonCommand::RemoveRequiredCircumstance(a)
+            }
+            DataLayerCommands::CoverItemUntilAnExactDateTime(a, b) => {
+                DaemonCommand::CoverItemUntilAnExactDateTime(a, b)
+            }
+            DataLayerCommands::ParentItemWithANewChildItem {
+                child,
+                parent,
+                higher_priority_than_this,
+            } => DaemonCommand::ParentItemWithANewChildItem {
+                child,
+                parent,
+                higher_priority_than_this,
+            },
+            DataLayerCommands::ParentNewItemWithAnExistingChildItem {
+                child,
+                parent_new_item,
+            } => DaemonCommand::ParentNewItemWithAnExistingChildItem {
+                child,
+                parent_new_item,
+            },
+            DataLayerCommands::UpdateResponsibilityAndItemType(a, b, c) => {
+                DaemonCommand::UpdateResponsibilityAndItemType(a, b, c)
+            }
+            DataLayerCommands::UpdateItemResponsibility(a, b) => {
+                DaemonCommand::UpdateItemResponsibility(a, b)
+            }
+            DataLayerCommands::UpdateItemPermanence(a, b) => {
+                DaemonCommand::UpdateItemPermanence(a, b)
+            }
+            DataLayerCommands::UpdateItemStaging(a, b) => DaemonCommand::UpdateItemStaging(a, b),
+            DataLayerCommands::UpdateItemDueDate(a, b) => DaemonCommand::UpdateItemDueDate(a, b),
+            DataLayerCommands::UpdateItemWaitingFor(a, b) => {
+                DaemonCommand::UpdateItemWaitingFor(a, b)
+            }
+            DataLayerCommands::UpdateItemCalendarEvent(a, b) => {
+                DaemonCommand::UpdateItemCalendarEvent(a, b)
+            }
+            DataLayerCommands::UpdateItemGitBranch(a, b) => {
+                DaemonCommand::UpdateItemGitBranch(a, b)
+            }
+            DataLayerCommands::UpdateItemSummary(a, b) => DaemonCommand::UpdateItemSummary(a, b),
+            DataLayerCommands::UpdateFacing(a, b) => DaemonCommand::UpdateFacing(a, b),
+            DataLayerCommands::UpdateSmallerItemsInPriorityOrder(a, b) => {
+                DaemonCommand::UpdateSmallerItemsInPriorityOrder(a, b)
+            }
+        }
+    }
+}
+
+impl From<DaemonCommand> for DataLayerCommands {
+    fn from(value: DaemonCommand) -> Self {
+        match value {
+            DaemonCommand::RemoveProcessedText(a) => DataLayerCommands::RemoveProcessedText(a),
+            DaemonCommand::AddProcessedText(a, b) => DataLayerCommands::AddProcessedText(a, b),
+            DaemonCommand::FinishItem(a) => DataLayerCommands::FinishItem(a),
+            DaemonCommand::UnfinishItem(a) => DataLayerCommands::UnfinishItem(a),
+            DaemonCommand::DeleteItem(a) => DataLayerCommands::DeleteItem(a),
+            DaemonCommand::ArchiveFinishedItems { finished_before } => {
+                DataLayerCommands::ArchiveFinishedItems { finished_before }
+            }
+            DaemonCommand::NewItem(a) => DataLayerCommands::NewItem(a),
+            DaemonCommand::CoverItemWithANewItem {
+                cover_this,
+                cover_with,
+            } => DataLayerCommands::CoverItemWithANewItem {
+                cover_this,
+                cover_with,
+            },
+            DaemonCommand::RemoveCoveringItem(a) => DataLayerCommands::RemoveCoveringItem(a),
+            DaemonCommand::RemoveCoveringUntilDatetime(a) => {
+                DataLayerCommands::RemoveCoveringUntilDatetime(a)
+            }
+            DaemonCommand::RemoveRequiredCircumstance(a) => {
+                DataLayerCommands::RemoveRequiredCircumstance(a)
+            }
+            DaemonCommand::CoverItemUntilAnExactDateTime(a, b) => {
+                DataLayerCommands::CoverItemUntilAnExactDateTime(a, b)
+            }
+            DaemonCommand::ParentItemWithANewChildItem {
+                child,
+                parent,
+                higher_priority_than_this,
+            } => DataLayerCommands::ParentItemWithANewChildItem {
+                child,
+                parent,
+                higher_priority_than_this,
+            },
+            DaemonCommand::ParentNewItemWithAnExistingChildItem {
+                child,
+                parent_new_item,
+            } => DataLayerCommands::ParentNewItemWithAnExistingChildItem {
+                child,
+                parent_new_item,
+            },
+            DaemonCommand::UpdateResponsibilityAndItemType(a, b, c) => {
+                DataLayerCommands::UpdateResponsibilityAndItemType(a, b, c)
+            }
+            DaemonCommand::UpdateItemResponsibility(a, b) => {
+                DataLayerCommands::UpdateItemResponsibility(a, b)
+            }
+            DaemonCommand::UpdateItemPermanence(a, b) => {
+                DataLayerCommands::UpdateItemPermanence(a, b)
+            }
+            DaemonCommand::UpdateItemStaging(a, b) => DataLayerCommands::UpdateItemStaging(a, b),
+            DaemonCommand::UpdateItemDueDate(a, b) => DataLayerCommands::UpdateItemDueDate(a, b),
+            DaemonCommand::UpdateItemWaitingFor(a, b) => {
+                DataLayerCommands::UpdateItemWaitingFor(a, b)
+            }
+            DaemonCommand::UpdateItemCalendarEvent(a, b) => {
+                DataLayerCommands::UpdateItemCalendarEvent(a, b)
+            }
+            DaemonCommand::UpdateItemGitBranch(a, b) => {
+                DataLayerCommands::UpdateItemGitBranch(a, b)
+            }
+            DaemonCommand::UpdateItemSummary(a, b) => DataLayerCommands::UpdateItemSummary(a, b),
+            DaemonCommand::UpdateFacing(a, b) => DataLayerCommands::UpdateFacing(a, b),
+            DaemonCommand::UpdateSmallerItemsInPriorityOrder(a, b) => {
+                DataLayerCommands::UpdateSmallerItemsInPriorityOrder(a, b)
+            }
+        }
+    }
+}
+
+async fn write_line(write: &mut OwnedWriteHalf, value: &impl Serialize) -> io::Result<()> {
+    let mut line = serde_json::to_string(value)?;
+    line.push('\n');
+    write.write_all(line.as_bytes()).await
+}
+
+async fn read_line<T: for<'de> Deserialize<'de>>(
+    lines: &mut Lines<BufReader<OwnedReadHalf>>,
+) -> io::Result<Option<T>> {
+    match lines.next_line().await? {
+        Some(line) => Ok(Some(serde_json::from_str(&line)?)),
+        None => Ok(None),
+    }
+}
+
+/// Binds the socket and serves commands until shutdown completes. The socket file is removed on the way out.
+pub(crate) async fn run_daemon(
+    socket_path: &Path,
+    start_data_storage: impl FnOnce(Receiver<DataLayerCommands>) -> JoinHandle<()>,
+    shutdown: impl Future<Output = ()>,
+) -> io::Result<()> {
+    if UnixStream::connect(socket_path).await.is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AddrInUse,
+            format!("A daemon is already running at {}", socket_path.display()),
+        ));
+    }
+    //Nothing answered so this is left over from a daemon that did not exit cleanly
+    let _ = std::fs::remove_file(socket_path);
+    let listener = UnixListener::bind(socket_path)?;
+    std::fs::set_permissions(socket_path, std::fs::Permissions::from_mode(0o600))?;
+
+    let commands_in_flight_limit = 20;
+    let (send_to_data_storage_layer_tx, have_data_storage_layer_use_to_receive_rx) =
+        mpsc::channel(commands_in_flight_limit);
+    let data_storage_join_handle = start_data_storage(have_data_storage_layer_use_to_receive_rx);
+
+    tokio::pin!(shutdown);
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted?;
+                let send_to_data_storage_layer = send_to_data_storage_layer_tx.clone();
+                tokio::spawn(async move {
+                    if let Err(err) = serve_connection(stream, send_to_data_storage_layer).await {
+                        eprintln!("Connection closed because of an error: {}", err);
+                    }
+                });
+            }
+            _ = &mut shutdown => break,
+        }
+    }
+
+    drop(send_to_data_storage_layer_tx);
+    std::fs::remove_file(socket_path)?;
+    //Connections that are still open keep the data storage layer running until they close
+    data_storage_join_handle.await.unwrap();
+    Ok(())
+}
+
+async fn serve_connection(
+    stream: UnixStream,
+    send_to_data_storage_layer: Sender<DataLayerCommands>,
+) -> io::Result<()> {
+    let (read, mut write) = stream.into_split();
+    let mut lines = BufReader::new(read).lines();
+    while let Some(request) = read_line(&mut lines).await? {
+        let response = match request {
+            DaemonRequest::SendRawData => DaemonResponse::RawData(
+                SurrealTables::new(&send_to_data_storage_layer)
+                    .await
+                    .unwrap(),
+            ),
+            DaemonRequest::SubscribeToChanges => {
+                let data_changed =
+                    DataLayerCommands::subscribe_to_changes(&send_to_data_storage_layer)
+                        .await
+                        .unwrap();
+                //Otherwise the data storage layer would wait on this connection to exit and this connection would
+                //wait on the data storage layer to exit
+                drop(send_to_data_storage_layer);
+                return forward_data_changed(data_changed, write).await;
+            }
+            DaemonRequest::SendProcessedText(for_item) => DaemonResponse::ProcessedText(
+                DataLayerCommands::get_processed_text(&send_to_data_storage_layer, for_item)
+                    .await
+                    .unwrap(),
+            ),
+            DaemonRequest::SendAllProcessedText => DaemonResponse::ProcessedText(
+                DataLayerCommands::get_all_processed_text(&send_to_data_storage_layer)
+                    .await
+                    .unwrap(),
+            ),
+            DaemonRequest::SendItemArchive => DaemonResponse::ItemArchive(
+                DataLayerCommands::get_item_archive(&send_to_data_storage_layer)
+                    .await
+                    .unwrap(),
+            ),
+            DaemonRequest::NewItemAndSendId(new_item) => DaemonResponse::NewItemId(
+                DataLayerCommands::new_item_and_get_id(&send_to_data_storage_layer, *new_item)
+                    .await
+                    .unwrap(),
+            ),
+            DaemonRequest::CoverItemWithAnExistingItem {
+                item_to_be_covered,
+                item_that_should_do_the_covering,
+            } => DaemonResponse::ChangeMade(
+                DataLayerCommands::cover_item_with_an_existing_item(
+                    &send_to_data_storage_layer,
+                    item_to_be_covered,
+                    item_that_should_do_the_covering,
+                )
+                .await
+                .unwrap(),
+            ),
+            DaemonRequest::ParentItemWithExistingItem {
+                child,
+                parent,
+                higher_priority_than_this,
+            } => DaemonResponse::ChangeMade(
+                DataLayerCommands::parent_item_with_existing_item(
+                    &send_to_data_storage_layer,
+                    child,
+                    parent,
+                    higher_priority_than_this,
+                )
+                .await
+                .unwrap(),
+            ),
+            DaemonRequest::Command(command) => {
+                send_to_data_storage_layer
+                    .send((*command).into())
+                    .await
+                    .unwrap();
+                continue;
+            }
+        };
+        write_line(&mut write, &response).await?;
+    }
+    Ok(())
+}
+
+async fn forward_data_changed(
+    mut data_changed: broadcast::Receiver<DataChanged>,
+    mut write: OwnedWriteHalf,
+) -> io::Result<()> {
+    write_line(&mut write, &DaemonResponse::Subscribed).await?;
+    loop {
+        match data_changed.recv().await {
+            //If some changes were missed the client still just needs to know that something changed
+            Ok(DataChanged) | Err(RecvError::Lagged(_)) => {
+                write_line(&mut write, &DaemonResponse::DataChanged).await?
+            }
+            Err(RecvError::Closed) => return Ok(()),
+        }
+    }
+}
+
+/// Use this in place of data_storage_start_and_run when a daemon is running. The commands are sent on to the daemon
+/// over stream and the responses are sent back through the command's oneshot.
+pub(crate) async fn run_daemon_client(
+    mut data_storage_layer_receive_rx: Receiver<DataLayerCommands>,
+    stream: UnixStream,
+    socket_path: PathBuf,
+) {
+    let (read, mut write) = stream.into_split();
+    let mut lines = BufReader::new(read).lines();
+    while let Some(command) = data_storage_layer_receive_rx.recv().await {
+        match command {
+            DataLayerCommands::SendRawData(send_response_here) => {
+                match request_response(DaemonRequest::SendRawData, &mut write, &mut lines).await {
+                    DaemonResponse::RawData(surreal_tables) => {
+                        send_response_here.send(surreal_tables).unwrap()
+                    }
+                    response => panic!("Unexpected response {:?}", response),
+                }
+            }
+            DataLayerCommands::SubscribeToChanges(send_response_here) => send_response_here
+                .send(subscribe_to_changes(&socket_path).await)
+                .unwrap(),
+            DataLayerCommands::SendProcessedText(for_item, send_response_here) => {
+                match request_response(
+                    DaemonRequest::SendProcessedText(for_item),
+                    &mut write,
+                    &mut lines,
+                )
+                .await
+                {
+                    DaemonResponse::ProcessedText(processed_text) => {
+                        send_response_here.send(processed_text).unwrap()
+                    }
+                    response => panic!("Unexpected response {:?}", response),
+                }
+            }
+            DataLayerCommands::SendAllProcessedText(send_response_here) => {
+                match request_response(DaemonRequest::SendAllProcessedText, &mut write, &mut lines)
+                    .await
+                {
+                    DaemonResponse::ProcessedText(processed_text) => {
+                        send_response_here.send(processed_text).unwrap()
+                    }
+                    response => panic!("Unexpected response {:?}", response),
+                }
+            }
+            DataLayerCommands::SendItemArchive(send_response_here) => {
+                match request_response(DaemonRequest::SendItemArchive, &mut write, &mut lines).await
+                {
+                    DaemonResponse::ItemArchive(item_archive) => {
+                        send_response_here.send(item_archive).unwrap()
+                    }
+                    response => panic!("Unexpected response {:?}", response),
+                }
+            }
+            DataLayerCommands::NewItemAndSendId(new_item, send_response_here) => {
+                match request_response(
+                    DaemonRequest::NewItemAndSendId(Box::new(new_item)),
+                    &mut write,
+                    &mut lines,
+                )
+                .await
+                {
+                    DaemonResponse::NewItemId(id) => {
+                        let _ = send_response_here.send(id);
+                    }
+                    response => panic!("Unexpected response {:?}", response),
+                }
+            }
+            DataLayerCommands::CoverItemWithAnExistingItem {
+                item_to_be_covered,
+                item_that_should_do_the_covering,
+                send_response_here,
+            } => {
+                match request_response(
+                    DaemonRequest::CoverItemWithAnExistingItem {
+                        item_to_be_covered,
+                        item_that_should_do_the_covering,
+                    },
+                    &mut write,
+                    &mut lines,
+                )
+                .await
+                {
+                    DaemonResponse::ChangeMade(change_made) => {
+                        //An error just means that the caller did not wait for the response
+                        let _ = send_response_here.send(change_made);
+                    }
+                    response => panic!("Unexpected response {:?}", response),
+                }
+            }
+            DataLayerCommands::ParentItemWithExistingItem {
+                child,
+                parent,
+                higher_priority_than_this,
+                send_response_here,
+            } => {
+                match request_response(
+                    DaemonRequest::ParentItemWithExistingItem {
+                        child,
+                        parent,
+                        higher_priority_than_this,
+                    },
+                    &mut write,
+                    &mut lines,
+                )
+                .await
+                {
+                    DaemonResponse::ChangeMade(change_made) => {
+                        let _ = send_response_here.send(change_made);
+                    }
+                    response => panic!("Unexpected response {:?}", response),
+                }
+            }
+            command => write_line(
+                &mut write,
+                &DaemonRequest::Command(Box::new(command.into())),
+            )
+            .await
+            .unwrap(),
+        }
+    }
+}
+
+async fn request_response(
+    request: DaemonRequest,
+    write: &mut OwnedWriteHalf,
+    lines: &mut Lines<BufReader<OwnedReadHalf>>,
+) -> DaemonResponse {
+    write_line(write, &request).await.unwrap();
+    read_line(lines)
+        .await
+        .unwrap()
+        .expect("Daemon closed the connection before responding")
+}
+
+/// Changes are sent on their own connection so they can arrive at any time without being mixed up with responses
+async fn subscribe_to_changes(socket_path: &Path) -> broadcast::Receiver<DataChanged> {
+    let stream = UnixStream::connect(socket_path).await.unwrap();
+    let (read, mut write) = stream.into_split();
+    let mut lines = BufReader::new(read).lines();
+    //Waiting for Subscribed means that no change after this returns is missed
+    match request_response(DaemonRequest::SubscribeToChanges, &mut write, &mut lines).await {
+        DaemonResponse::Subscribed => (),
+        response => panic!("Unexpected response {:?}", response),
+    }
+
+    let (data_changed_tx, data_changed_rx) = broadcast::channel(16);
+    tokio::spawn(async move {
+        //Keep the write half so the daemon does not see the connection as closed
+        let _write = write;
+        while let Ok(Some(DaemonResponse::DataChanged)) = read_line(&mut lines).await {
+            if data_changed_tx.send(DataChanged).is_err() {
+                //Nobody is listening anymore
+                break;
+            }
+        }
+    });
+    data_changed_rx
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::Utc;
+    use tokio::sync::{mpsc, oneshot};
+
+    use crate::surrealdb_layer::data_storage_start_and_run;
+
+    use super::*;
+
+    fn start_in_memory(receiver: Receiver<DataLayerCommands>) -> JoinHandle<()> {
+        tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await })
+    }
+
+    #[tokio::test]
+    async fn capture_from_a_client_is_seen_by_other_clients() {
+        let socket_path =
+            std::env::temp_dir().join(format!("on_purpose_test_{}.sock", std::process::id()));
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let daemon_socket_path = socket_path.clone();
+        let daemon = tokio::spawn(async move {
+            run_daemon(&daemon_socket_path, start_in_memory, async {
+                let _ = shutdown_rx.await;
+            })
+            .await
+        });
+        while UnixStream::connect(&socket_path).await.is_err() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let start_client = || async {
+            let (sender, receiver) = mpsc::channel(1);
+            let stream = UnixStream::connect(&socket_path).await.unwrap();
+            tokio::spawn(run_daemon_client(receiver, stream, socket_path.clone()));
+            sender
+        };
+        let watching = start_client().await;
+        let capturing = start_client().await;
+
+        let mut data_changed = DataLayerCommands::subscribe_to_changes(&watching)
+            .await
+            .unwrap();
+        capturing
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Captured from another terminal".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+
+        tokio::time::timeout(Duration::from_secs(5), data_changed.recv())
+            .await
+            .expect("Should be told about the change")
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&watching).await.unwrap();
+        assert_eq!(surreal_tables.surreal_items.len(), 1);
+        assert_eq!(
+            surreal_tables.surreal_items[0].summary,
+            "Captured from another terminal"
+        );
+
+        assert!(
+            run_daemon(&socket_path, start_in_memory, async {})
+                .await
+                .is_err(),
+            "A second daemon should refuse to start"
+        );
+
+        shutdown_tx.send(()).unwrap();
+        drop(watching);
+        drop(capturing);
+        drop(data_changed);
+        daemon.await.unwrap().unwrap();
+        assert!(!socket_path.exists());
+    }
+}
@@ -1,6 +1,15 @@
+pub(crate) mod display_agenda;
+pub(crate) mod display_bullet_list_rank;
+pub(crate) mod display_calendar_event;
+pub(crate) mod display_due_date;
 pub(crate) mod display_duration;
 pub(crate) mod display_enter_list_reason;
+pub(crate) mod display_expectations_report;
+pub(crate) mod display_focus_suggestion;
 pub(crate) mod display_item;
 pub(crate) mod display_item_node;
 pub(crate) mod display_item_status;
+pub(crate) mod display_outline;
+pub(crate) mod display_snooze_reason;
 pub(crate) mod display_staging;
+pub(crate) mod display_waiting_for;
@@ -0,0 +1,50 @@
+use std::fmt::Display;
+
+use crate::systems::agenda::Agenda;
+
+use super::display_item::DisplayItem;
+
+/// Everything to discuss with a person or group, one heading for each reason it is on their agenda
+pub(crate) struct DisplayAgenda<'s> {
+    agenda: &'s Agenda<'s>,
+}
+
+impl Display for DisplayAgenda<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let agenda = self.agenda;
+        writeln!(
+            f,
+            "Agenda for {}",
+            DisplayItem::new(agenda.get_person_or_group())
+        )?;
+        if agenda.is_empty() {
+            return writeln!(f, "  Nothing to discuss");
+        }
+        let covered_by_them = agenda.get_covered_by_them().collect::<Vec<_>>();
+        if !covered_by_them.is_empty() {
+            writeln!(f, "  Needs them to be around:")?;
+            for item in covered_by_them {
+                writeln!(f, "    {}", DisplayItem::new(item))?;
+            }
+        }
+        if !agenda.get_facing_them().is_empty() {
+            writeln!(f, "  Done for them:")?;
+            for item in agenda.get_facing_them() {
+                writeln!(f, "    {}", DisplayItem::new(item))?;
+            }
+        }
+        if !agenda.get_waiting_for_them().is_empty() {
+            writeln!(f, "  Waiting on them:")?;
+            for item in agenda.get_waiting_for_them() {
+                writeln!(f, "    {}", DisplayItem::new(item))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'s> DisplayAgenda<'s> {
+    pub(crate) fn new(agenda: &'s Agenda<'s>) -> Self {
+        DisplayAgenda { agenda }
+    }
+}
@@ -0,0 +1,147 @@
+use std::fmt::Display;
+
+use crate::{
+    node::item_status::DueDateUrgency,
+    systems::bullet_list::{ranking_strategy::SortKey, BulletListRank},
+};
+
+use super::{
+    display_duration::DisplayDuration, display_item_status::DisplayItemStatus,
+    display_snooze_reason::DisplaySnoozeReason, display_staging::DisplayStaging,
+};
+
+/// Shows each of the bullet list's sort keys for an item and which one decided its place next to its neighbours
+pub(crate) struct DisplayBulletListRank<'s> {
+    bullet_list_rank: &'s BulletListRank<'s>,
+}
+
+impl Display for DisplayBulletListRank<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rank = self.bullet_list_rank;
+        let sort_keys = &rank.sort_keys;
+        writeln!(
+            f,
+            "#{} of {} on the bullet list",
+            rank.position, rank.out_of
+        )?;
+        writeln!(f, "Sorted by, in this order:")?;
+        for (index, sort_key) in rank.sorted_by.iter().enumerate() {
+            write!(f, "  {}. {}: ", index + 1, DisplaySortKey(Some(*sort_key)))?;
+            match sort_key {
+                SortKey::ResponsibilityReactive => {
+                    writeln!(f, "{}", yes_or_no(sort_keys.is_responsibility_reactive))?
+                }
+                SortKey::Snoozed => {
+                    writeln!(f, "{}", yes_or_no(sort_keys.is_snoozed))?;
+                    for snooze_reason in rank.snooze_reasons.iter() {
+                        writeln!(f, "       {}", DisplaySnoozeReason::new(snooze_reason))?;
+                    }
+                }
+                SortKey::TypeUndeclared => {
+                    writeln!(f, "{}", yes_or_no(sort_keys.is_type_undeclared))?
+                }
+                SortKey::StagingNotSet => {
+                    writeln!(f, "{}", yes_or_no(sort_keys.is_staging_not_set))?
+                }
+                SortKey::FirstLapFinished => {
+                    writeln!(f, "{}", yes_or_no(sort_keys.is_first_lap_finished))?
+                }
+                SortKey::Staging => writeln!(f, "{}", DisplayStaging::new(sort_keys.staging))?,
+                SortKey::ExpiredAmount => writeln!(
+                    f,
+                    "{:.2} from a lap count of {:.2}",
+                    sort_keys.expired_amount, sort_keys.lap_count
+                )?,
+                SortKey::LapCount => writeln!(f, "{:.2}", sort_keys.lap_count)?,
+                SortKey::TimeLeftInFirstLap => match sort_keys.time_left_in_first_lap {
+                    Some(time_left) => match time_left.to_std() {
+                        Ok(time_left) => writeln!(f, "{}", DisplayDuration::new(&time_left))?,
+                        Err(_) => writeln!(f, "the first lap is finished")?,
+                    },
+                    None => writeln!(f, "the first lap has not started")?,
+                },
+                SortKey::LifeAreaTurn => writeln!(f, "takes turns with the other top level items")?,
+                SortKey::DueDateUrgency => writeln!(
+                    f,
+                    "{}",
+                    match sort_keys.due_date_urgency {
+                        DueDateUrgency::NotPressing => "no due date coming up",
+                        DueDateUrgency::SoftApproaching => "soft due date approaching",
+                        DueDateUrgency::HardApproaching => "hard due date approaching",
+                        DueDateUrgency::SoftOverdue => "soft due date passed",
+                        DueDateUrgency::HardOverdue => "hard due date passed",
+                    }
+                )?,
+                SortKey::TimeUntilDue => match sort_keys.time_until_due {
+                    Some(time_until_due) => match time_until_due.to_std() {
+                        Ok(time_until_due) => {
+                            writeln!(f, "due in {}", DisplayDuration::new(&time_until_due))?
+                        }
+                        Err(_) => writeln!(f, "overdue")?,
+                    },
+                    None => writeln!(f, "no due date")?,
+                },
+            }
+        }
+        if let Some((above, sort_key)) = rank.above {
+            writeln!(
+                f,
+                "Below {}, decided by: {}",
+                DisplayItemStatus::new(above),
+                DisplaySortKey(sort_key)
+            )?;
+        }
+        if let Some((below, sort_key)) = rank.below {
+            writeln!(
+                f,
+                "Above {}, decided by: {}",
+                DisplayItemStatus::new(below),
+                DisplaySortKey(sort_key)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<'s> DisplayBulletListRank<'s> {
+    pub(crate) fn new(bullet_list_rank: &'s BulletListRank<'s>) -> Self {
+        DisplayBulletListRank { bullet_list_rank }
+    }
+}
+
+struct DisplaySortKey(Option<SortKey>);
+
+impl Display for DisplaySortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(SortKey::ResponsibilityReactive) => write!(f, "Reactive, goes to the bottom"),
+            Some(SortKey::Snoozed) => write!(f, "Snoozed, goes to the bottom"),
+            Some(SortKey::TypeUndeclared) => write!(f, "Type not declared, goes to the top"),
+            Some(SortKey::StagingNotSet) => write!(f, "Staging not set, goes to the top"),
+            Some(SortKey::FirstLapFinished) => write!(f, "First lap finished, goes to the top"),
+            Some(SortKey::Staging) => write!(f, "Staging, until the first lap is finished"),
+            Some(SortKey::ExpiredAmount) => write!(
+                f,
+                "Lap count, squared when mentally resident, higher goes to the top"
+            ),
+            Some(SortKey::LapCount) => write!(f, "Lap count, higher goes to the top"),
+            Some(SortKey::TimeLeftInFirstLap) => {
+                write!(f, "Time left in the first lap, less goes to the top")
+            }
+            Some(SortKey::LifeAreaTurn) => write!(f, "Life area"),
+            Some(SortKey::DueDateUrgency) => {
+                write!(f, "Due date, overdue or approaching goes to the top")
+            }
+            Some(SortKey::TimeUntilDue) => write!(f, "Time until due, less goes to the top"),
+            None => write!(f, "Tied, tied items are kept in a stable order"),
+        }
+    }
+}
+
+fn yes_or_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
@@ -0,0 +1,30 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Local, Utc};
+
+use crate::surrealdb_layer::surreal_item::{Attendance, CalendarEvent};
+
+/// When the event is in local time, for example "📅 Mon 10:00-10:30"
+pub(crate) struct DisplayCalendarEvent<'s> {
+    calendar_event: &'s CalendarEvent,
+}
+
+impl Display for DisplayCalendarEvent<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let start: DateTime<Local> =
+            DateTime::<Utc>::from(self.calendar_event.start.clone()).into();
+        let end: DateTime<Local> = DateTime::<Utc>::from(self.calendar_event.end.clone()).into();
+        write!(f, "📅 {}-{}", start.format("%a %H:%M"), end.format("%H:%M"))?;
+        match self.calendar_event.attendance {
+            Attendance::NotDecided => Ok(()),
+            Attendance::Attending => write!(f, " attending"),
+            Attendance::NotAttending => write!(f, " not attending"),
+        }
+    }
+}
+
+impl<'s> DisplayCalendarEvent<'s> {
+    pub(crate) fn new(calendar_event: &'s CalendarEvent) -> Self {
+        DisplayCalendarEvent { calendar_event }
+    }
+}
@@ -0,0 +1,51 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Utc};
+
+use crate::surrealdb_layer::surreal_item::{DueDate, DueDateStrictness};
+
+/// A countdown to the due date, for example "🚨 due in 2d 4h" or "📅 3h overdue"
+pub(crate) struct DisplayDueDate<'s> {
+    due_date: &'s DueDate,
+    now: DateTime<Utc>,
+}
+
+impl Display for DisplayDueDate<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.due_date.strictness {
+            DueDateStrictness::Hard => write!(f, "🚨 ")?,
+            DueDateStrictness::Soft => write!(f, "📅 ")?,
+        }
+        let due: DateTime<Utc> = self.due_date.due.clone().into();
+        let time_until_due = due - self.now;
+        if time_until_due < chrono::Duration::zero() {
+            write!(f, "{} overdue", DisplayCountdown(-time_until_due))
+        } else {
+            write!(f, "due in {}", DisplayCountdown(time_until_due))
+        }
+    }
+}
+
+impl<'s> DisplayDueDate<'s> {
+    pub(crate) fn new(due_date: &'s DueDate, now: DateTime<Utc>) -> Self {
+        DisplayDueDate { due_date, now }
+    }
+}
+
+/// Just the two largest units so it stays short enough for the bullet list
+pub(crate) struct DisplayCountdown(pub(crate) chrono::Duration);
+
+impl Display for DisplayCountdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let days = self.0.num_days();
+        let hours = self.0.num_hours() % 24;
+        let minutes = self.0.num_minutes() % 60;
+        if days > 0 {
+            write!(f, "{}d {}h", days, hours)
+        } else if hours > 0 {
+            write!(f, "{}h {}m", hours, minutes)
+        } else {
+            write!(f, "{}m", minutes)
+        }
+    }
+}
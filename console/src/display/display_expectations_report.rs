@@ -0,0 +1,91 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Local, Utc};
+
+use crate::{
+    surrealdb_layer::surreal_item::{HowWellDefined, Staging},
+    systems::expectations_report::{ExpectationsReport, Stakeholder},
+};
+
+/// The report as Markdown, one table for each person or group, which also reads well in the terminal
+pub(crate) struct DisplayExpectationsReport<'s> {
+    report: &'s ExpectationsReport<'s>,
+    now: DateTime<Utc>,
+}
+
+impl Display for DisplayExpectationsReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# Expectations")?;
+        if self.report.get_stakeholders().is_empty() {
+            writeln!(f)?;
+            writeln!(
+                f,
+                "Nothing is facing anyone yet, use Define Facing to say who an item is for."
+            )?;
+        }
+        for stakeholder in self.report.get_stakeholders() {
+            writeln!(f)?;
+            match stakeholder.stakeholder {
+                Stakeholder::PersonOrGroup(item) => {
+                    writeln!(f, "## {}", escape(item.get_summary()))?
+                }
+                Stakeholder::Missing(record_id) => {
+                    writeln!(f, "## Deleted person or group ({})", record_id)?
+                }
+                Stakeholder::Myself => writeln!(f, "## Myself")?,
+            }
+            writeln!(f)?;
+            writeln!(
+                f,
+                "| Expectation | How well defined | Staging | Last activity |"
+            )?;
+            writeln!(f, "| --- | --- | --- | --- |")?;
+            for expectation in stakeholder.expectations.iter() {
+                let last_activity: DateTime<Local> = expectation.last_activity.into();
+                let days_ago = (self.now - expectation.last_activity).num_days();
+                writeln!(
+                    f,
+                    "| {} | {} | {} | {} ({} days ago) |",
+                    escape(expectation.item.get_summary()),
+                    how_well_defined_name(expectation.how_well_defined),
+                    staging_name(expectation.item.get_staging()),
+                    last_activity.format("%Y-%m-%d"),
+                    days_ago
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'s> DisplayExpectationsReport<'s> {
+    pub(crate) fn new(report: &'s ExpectationsReport<'s>, now: DateTime<Utc>) -> Self {
+        DisplayExpectationsReport { report, now }
+    }
+}
+
+/// A | in a summary would otherwise end the table cell
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+fn how_well_defined_name(how_well_defined: &HowWellDefined) -> &'static str {
+    match how_well_defined {
+        HowWellDefined::NotSet => "Not set",
+        HowWellDefined::WellDefined => "Well defined",
+        HowWellDefined::RoughlyDefined => "Roughly defined",
+        HowWellDefined::LooselyDefined => "Loosely defined",
+    }
+}
+
+/// Just the name, DisplayStaging has the details that are too long for a table
+fn staging_name(staging: &Staging) -> &'static str {
+    match staging {
+        Staging::NotSet => "Not set",
+        Staging::MentallyResident { .. } => "Mentally resident",
+        Staging::OnDeck { .. } => "On deck",
+        Staging::Planned => "Planned",
+        Staging::ThinkingAbout => "Thinking about",
+        Staging::Released => "Released",
+    }
+}
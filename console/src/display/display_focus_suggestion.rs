@@ -0,0 +1,47 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Local};
+
+use crate::systems::busy_blocks::FocusSuggestion;
+
+use super::display_due_date::DisplayCountdown;
+
+/// For example "🎯 Focus for 20m, Standup starts at 10:00"
+pub(crate) struct DisplayFocusSuggestion<'s> {
+    focus_suggestion: &'s FocusSuggestion<'s>,
+}
+
+impl Display for DisplayFocusSuggestion<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.focus_suggestion {
+            FocusSuggestion::Busy(busy_block) => {
+                let end: DateTime<Local> = busy_block.end.into();
+                write!(
+                    f,
+                    "📅 This is during {}, which ends at {}",
+                    busy_block.item.get_summary(),
+                    end.format("%H:%M")
+                )
+            }
+            FocusSuggestion::FocusFor { length, before } => {
+                write!(f, "🎯 Focus for {}", DisplayCountdown(*length))?;
+                if let Some(before) = before {
+                    let start: DateTime<Local> = before.start.into();
+                    write!(
+                        f,
+                        ", {} starts at {}",
+                        before.item.get_summary(),
+                        start.format("%H:%M")
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<'s> DisplayFocusSuggestion<'s> {
+    pub(crate) fn new(focus_suggestion: &'s FocusSuggestion<'s>) -> Self {
+        DisplayFocusSuggestion { focus_suggestion }
+    }
+}
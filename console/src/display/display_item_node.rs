@@ -1,8 +1,13 @@
 use std::fmt::Display;
 
+use chrono::Utc;
+
 use crate::{node::item_node::ItemNode, surrealdb_layer::surreal_item::Staging};
 
-use super::display_item::DisplayItem;
+use super::{
+    display_due_date::DisplayDueDate, display_item::DisplayItem,
+    display_staging::DisplayStagingIcon,
+};
 
 pub struct DisplayItemNode<'s> {
     item_node: &'s ItemNode<'s>,
@@ -12,15 +17,7 @@ impl Display for DisplayItemNode<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let display_item = DisplayItem::new(self.item_node.get_item());
 
-        let staging = self.get_staging();
-        match staging {
-            Staging::OnDeck { .. } => write!(f, "🔜 ")?,
-            Staging::MentallyResident { .. } => write!(f, "🧠 ")?,
-            Staging::Planned { .. } => write!(f, "📝 ")?,
-            Staging::ThinkingAbout { .. } => write!(f, "🤔 ")?,
-            Staging::Released { .. } => write!(f, "🪽 ")?,
-            Staging::NotSet => write!(f, "❓ ")?,
-        }
+        write!(f, "{} ", DisplayStagingIcon::new(self.get_staging()))?;
 
         if self.item_node.is_person_or_group() {
             write!(f, "Is {} around?", display_item)?;
@@ -34,6 +31,9 @@ impl Display for DisplayItemNode<'_> {
             let display_item = DisplayItem::new(item);
             write!(f, " ⬅ {}", display_item)?;
         }
+        if let Some(due_date) = self.item_node.get_due_date() {
+            write!(f, " {}", DisplayDueDate::new(due_date, Utc::now()))?;
+        }
         Ok(())
     }
 }
@@ -2,7 +2,7 @@ use std::fmt::Display;
 
 use crate::node::item_status::ItemStatus;
 
-use super::display_item_node::DisplayItemNode;
+use super::{display_calendar_event::DisplayCalendarEvent, display_item_node::DisplayItemNode};
 
 pub struct DisplayItemStatus<'s> {
     item_status: &'s ItemStatus<'s>,
@@ -14,6 +14,17 @@ impl Display for DisplayItemStatus<'_> {
             write!(f, "⏰ ")?;
         }
 
+        if self.item_status.is_follow_up_due() {
+            match self.item_status.get_item_node().get_waiting_for_who() {
+                Some(who) => write!(f, "📨 Follow up with {} about ", who.get_summary())?,
+                None => write!(f, "📨 Follow up about ")?,
+            }
+        }
+
+        if let Some(calendar_event) = self.item_status.get_item().get_calendar_event() {
+            write!(f, "{} ", DisplayCalendarEvent::new(calendar_event))?;
+        }
+
         let display_node = DisplayItemNode::new(self.item_status.get_item_node());
         write!(f, "{}", display_node)?;
         Ok(())
@@ -0,0 +1,85 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Local, Utc};
+
+use crate::{
+    display::{display_item::DisplayItem, display_staging::DisplayStagingIcon},
+    surrealdb_layer::surreal_processed_text::SurrealProcessedText,
+    systems::outline::{Outline, OutlineEntry, Relation},
+};
+
+/// The outline as a nested Markdown list, for pasting into status reports and planning documents
+pub(crate) struct DisplayOutline<'s> {
+    outline: &'s Outline<'s>,
+    /// None to leave out the processed text
+    processed_text: Option<&'s [SurrealProcessedText]>,
+}
+
+impl Display for DisplayOutline<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for root in self.outline.get_roots() {
+            self.fmt_entry(f, root, 0)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'s> DisplayOutline<'s> {
+    pub(crate) fn new(
+        outline: &'s Outline<'s>,
+        processed_text: Option<&'s [SurrealProcessedText]>,
+    ) -> Self {
+        DisplayOutline {
+            outline,
+            processed_text,
+        }
+    }
+
+    fn fmt_entry(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        entry: &OutlineEntry<'_>,
+        depth: usize,
+    ) -> std::fmt::Result {
+        let indent = "  ".repeat(depth);
+        write!(f, "{}- ", indent)?;
+        if entry.item.is_finished() {
+            write!(f, "✅ ")?;
+        }
+        if entry.relation == Relation::Covering {
+            write!(f, "Waiting on ")?;
+        }
+        writeln!(
+            f,
+            "{} {}",
+            DisplayStagingIcon::new(entry.item.get_staging()),
+            DisplayItem::new(entry.item)
+        )?;
+        if let Some(processed_text) = self.processed_text {
+            let mut processed_text = processed_text
+                .iter()
+                .filter(|x| &x.for_item == entry.item.get_surreal_record_id())
+                .collect::<Vec<_>>();
+            processed_text.sort_by(|a, b| a.when_written.cmp(&b.when_written));
+            for processed_text in processed_text {
+                let when_written: DateTime<Utc> = processed_text.when_written.clone().into();
+                let when_written: DateTime<Local> = when_written.into();
+                let mut lines = processed_text.text.lines();
+                writeln!(
+                    f,
+                    "{}  > **{}** {}",
+                    indent,
+                    when_written.format("%Y-%m-%d"),
+                    lines.next().unwrap_or_default()
+                )?;
+                for line in lines {
+                    writeln!(f, "{}  > {}", indent, line)?;
+                }
+            }
+        }
+        for smaller in entry.smaller.iter() {
+            self.fmt_entry(f, smaller, depth + 1)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Local};
+
+use crate::node::item_status::SnoozeReason;
+
+pub(crate) struct DisplaySnoozeReason<'s> {
+    snooze_reason: &'s SnoozeReason,
+}
+
+impl Display for DisplaySnoozeReason<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.snooze_reason {
+            SnoozeReason::CoveredUntil(datetime) => {
+                write!(f, "Snoozed until {}", datetime.naive_local())
+            }
+            SnoozeReason::EntersListAt(datetime) => {
+                let datetime: DateTime<Local> = (*datetime).into();
+                write!(f, "Staging enters the list at {}", datetime.naive_local())
+            }
+            SnoozeReason::EarliestNotReached(datetime) => {
+                let datetime: DateTime<Local> = (*datetime).into();
+                write!(
+                    f,
+                    "Waiting to be the highest priority uncovered item but not before {}",
+                    datetime.naive_local()
+                )
+            }
+            SnoozeReason::NotHighestUncovered => {
+                write!(f, "Another item is the highest priority uncovered item")
+            }
+            SnoozeReason::FollowUpAt(datetime) => {
+                let datetime: DateTime<Local> = (*datetime).into();
+                write!(
+                    f,
+                    "Waiting for a response, follow up at {}",
+                    datetime.naive_local()
+                )
+            }
+        }
+    }
+}
+
+impl<'s> DisplaySnoozeReason<'s> {
+    pub(crate) fn new(snooze_reason: &'s SnoozeReason) -> Self {
+        DisplaySnoozeReason { snooze_reason }
+    }
+}
@@ -39,3 +39,27 @@ impl<'s> DisplayStaging<'s> {
         DisplayStaging { staging }
     }
 }
+
+/// The icon that starts each item on the bullet list
+pub(crate) struct DisplayStagingIcon<'s> {
+    staging: &'s Staging,
+}
+
+impl Display for DisplayStagingIcon<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.staging {
+            Staging::OnDeck { .. } => write!(f, "🔜"),
+            Staging::MentallyResident { .. } => write!(f, "🧠"),
+            Staging::Planned { .. } => write!(f, "📝"),
+            Staging::ThinkingAbout { .. } => write!(f, "🤔"),
+            Staging::Released { .. } => write!(f, "🪽"),
+            Staging::NotSet => write!(f, "❓"),
+        }
+    }
+}
+
+impl<'s> DisplayStagingIcon<'s> {
+    pub(crate) fn new(staging: &'s Staging) -> Self {
+        DisplayStagingIcon { staging }
+    }
+}
@@ -0,0 +1,47 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Local, Utc};
+
+use crate::node::item_node::ItemNode;
+
+use super::display_due_date::DisplayCountdown;
+
+/// Who is being waited on for what and when to follow up, for example
+/// "👤 Alex: Review the contract, waiting since 2024-05-01, follow up in 2d 4h"
+pub(crate) struct DisplayWaitingFor<'s> {
+    item_node: &'s ItemNode<'s>,
+    now: DateTime<Utc>,
+}
+
+impl Display for DisplayWaitingFor<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let waiting_for = match self.item_node.get_waiting_for() {
+            Some(waiting_for) => waiting_for,
+            None => return write!(f, "{}", self.item_node.get_item().get_summary()),
+        };
+        match self.item_node.get_waiting_for_who() {
+            Some(who) => write!(f, "👤 {}: ", who.get_summary())?,
+            None => write!(f, "👤 (deleted): ")?,
+        }
+        let since: DateTime<Local> = DateTime::<Utc>::from(waiting_for.since.clone()).into();
+        write!(
+            f,
+            "{}, waiting since {}, ",
+            self.item_node.get_item().get_summary(),
+            since.format("%Y-%m-%d")
+        )?;
+        let follow_up_after: DateTime<Utc> = waiting_for.follow_up_after.clone().into();
+        let time_until_follow_up = follow_up_after - self.now;
+        if time_until_follow_up > chrono::Duration::zero() {
+            write!(f, "follow up in {}", DisplayCountdown(time_until_follow_up))
+        } else {
+            write!(f, "📨 time to follow up")
+        }
+    }
+}
+
+impl<'s> DisplayWaitingFor<'s> {
+    pub(crate) fn new(item_node: &'s ItemNode<'s>, now: DateTime<Utc>) -> Self {
+        DisplayWaitingFor { item_node, now }
+    }
+}
@@ -0,0 +1,577 @@
+//! Checks the database for records that reference things that no longer exist, or that reference each other in a way
+//! that the rest of the code has to work around, and offers to repair them.
+
+use std::collections::HashSet;
+
+use inquire::InquireError;
+use itertools::Itertools;
+use surrealdb::opt::RecordId;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    menu::prompt::Select,
+    menu::YesOrNo,
+    surrealdb_layer::{
+        surreal_covering::SurrealCovering,
+        surreal_covering_until_date_time::SurrealCoveringUntilDatetime,
+        surreal_item::{Facing, SurrealItem, SurrealOrderedSubItem},
+        surreal_item_archive::SurrealItemArchive,
+        surreal_processed_text::SurrealProcessedText,
+        surreal_required_circumstance::SurrealRequiredCircumstance,
+        surreal_tables::{SurrealLink, SurrealTables},
+        DataLayerCommands,
+    },
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum IntegrityIssue<'s> {
+    DanglingCovering {
+        covering: &'s SurrealCovering,
+        missing: &'s RecordId,
+    },
+    DanglingCoveringUntilDatetime(&'s SurrealCoveringUntilDatetime),
+    DanglingRequiredCircumstance(&'s SurrealRequiredCircumstance),
+    DanglingSmallerItem {
+        item: &'s SurrealItem,
+        missing: &'s RecordId,
+    },
+    DanglingFacing {
+        item: &'s SurrealItem,
+        missing: &'s RecordId,
+    },
+    DuplicateSmallerItem {
+        item: &'s SurrealItem,
+        duplicate: &'s RecordId,
+    },
+    /// The links go from smaller to larger and the last link gets back to where the cycle started
+    Cycle(Vec<SurrealLink<'s>>),
+    OrphanProcessedText(&'s SurrealProcessedText),
+}
+
+impl IntegrityIssue<'_> {
+    pub(crate) fn describe(&self, surreal_tables: &SurrealTables) -> String {
+        let name = |record_id: &RecordId| surreal_tables.describe_record(record_id);
+        match self {
+            IntegrityIssue::DanglingCovering { covering, missing } => format!(
+                "Covering of {} by {} references {} which does not exist",
+                name(&covering.parent),
+                name(&covering.smaller),
+                missing
+            ),
+            IntegrityIssue::DanglingCoveringUntilDatetime(covering_until_date_time) => format!(
+                "Snooze until {} references {} which does not exist",
+                covering_until_date_time.until, covering_until_date_time.cover_this
+            ),
+            IntegrityIssue::DanglingRequiredCircumstance(required_circumstance) => format!(
+                "Required circumstance {:?} references {} which does not exist",
+                required_circumstance.circumstance_type, required_circumstance.required_for
+            ),
+            IntegrityIssue::DanglingSmallerItem { item, missing } => format!(
+                "{} has the smaller item {} which does not exist",
+                SurrealTables::describe_item(item),
+                missing
+            ),
+            IntegrityIssue::DanglingFacing { item, missing } => format!(
+                "{} is facing {} which does not exist",
+                SurrealTables::describe_item(item),
+                missing
+            ),
+            IntegrityIssue::DuplicateSmallerItem { item, duplicate } => format!(
+                "{} has the smaller item {} listed more than once",
+                SurrealTables::describe_item(item),
+                name(duplicate)
+            ),
+            IntegrityIssue::Cycle(links) => {
+                format!("Cycle: {}", surreal_tables.describe_chain(links))
+            }
+            IntegrityIssue::OrphanProcessedText(processed_text) => format!(
+                "Processed text written {} references {} which does not exist",
+                processed_text.when_written, processed_text.for_item
+            ),
+        }
+    }
+}
+
+/// item_archive is needed because processed text and facing are kept pointing at archived items on purpose
+pub(crate) fn find_integrity_issues<'s>(
+    surreal_tables: &'s SurrealTables,
+    all_processed_text: &'s [SurrealProcessedText],
+    item_archive: &[SurrealItemArchive],
+) -> Vec<IntegrityIssue<'s>> {
+    let item_ids = surreal_tables
+        .surreal_items
+        .iter()
+        .filter_map(|x| x.id.as_ref())
+        .map(|x| x.to_raw())
+        .collect::<HashSet<_>>();
+    let archived_ids = item_archive
+        .iter()
+        .filter_map(|x| x.item.id.as_ref())
+        .map(|x| x.to_raw())
+        .collect::<HashSet<_>>();
+    let exists = |x: &RecordId| item_ids.contains(&x.to_raw());
+    let exists_or_archived =
+        |x: &RecordId| item_ids.contains(&x.to_raw()) || archived_ids.contains(&x.to_raw());
+
+    let mut issues = Vec::default();
+    for covering in surreal_tables.surreal_coverings.iter() {
+        if let Some(missing) = [&covering.smaller, &covering.parent]
+            .into_iter()
+            .find(|x| !exists(x))
+        {
+            issues.push(IntegrityIssue::DanglingCovering { covering, missing });
+        }
+    }
+    issues.extend(
+        surreal_tables
+            .surreal_coverings_until_date_time
+            .iter()
+            .filter(|x| !exists(&x.cover_this))
+            .map(IntegrityIssue::DanglingCoveringUntilDatetime),
+    );
+    issues.extend(
+        surreal_tables
+            .surreal_required_circumstances
+            .iter()
+            .filter(|x| !exists(&x.required_for))
+            .map(IntegrityIssue::DanglingRequiredCircumstance),
+    );
+    for item in surreal_tables.surreal_items.iter() {
+        let smaller_items = item
+            .smaller_items_in_priority_order
+            .iter()
+            .filter_map(|x| match x {
+                SurrealOrderedSubItem::SubItem { surreal_item_id } => Some(surreal_item_id),
+                SurrealOrderedSubItem::Split { .. } => None,
+            });
+        let mut seen = Vec::default();
+        for smaller in smaller_items {
+            let already_seen = seen.contains(&smaller);
+            seen.push(smaller);
+            if !exists(smaller) {
+                if !already_seen {
+                    issues.push(IntegrityIssue::DanglingSmallerItem {
+                        item,
+                        missing: smaller,
+                    });
+                }
+            } else if already_seen {
+                issues.push(IntegrityIssue::DuplicateSmallerItem {
+                    item,
+                    duplicate: smaller,
+                });
+            }
+        }
+        for facing in item.facing.iter() {
+            if let Facing::Others { who, .. } = facing {
+                if !exists_or_archived(who) {
+                    issues.push(IntegrityIssue::DanglingFacing { item, missing: who });
+                }
+            }
+        }
+    }
+    issues.extend(
+        surreal_tables
+            .find_cycles()
+            .into_iter()
+            .map(IntegrityIssue::Cycle),
+    );
+    issues.extend(
+        all_processed_text
+            .iter()
+            .filter(|x| !exists_or_archived(&x.for_item))
+            .map(IntegrityIssue::OrphanProcessedText),
+    );
+    issues
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Repair<'s> {
+    RemoveCovering(&'s SurrealCovering),
+    RemoveCoveringUntilDatetime(&'s SurrealCoveringUntilDatetime),
+    RemoveRequiredCircumstance(&'s SurrealRequiredCircumstance),
+    RemoveProcessedText(&'s SurrealProcessedText),
+    UpdateSmallerItemsInPriorityOrder {
+        item: &'s SurrealItem,
+        smaller_items_in_priority_order: Vec<SurrealOrderedSubItem>,
+    },
+    UpdateFacing {
+        item: &'s SurrealItem,
+        facing: Vec<Facing>,
+    },
+}
+
+impl Repair<'_> {
+    pub(crate) fn describe(&self, surreal_tables: &SurrealTables) -> String {
+        let name = |record_id: &RecordId| surreal_tables.describe_record(record_id);
+        match self {
+            Repair::RemoveCovering(covering) => format!(
+                "Remove the covering of {} by {}",
+                name(&covering.parent),
+                name(&covering.smaller)
+            ),
+            Repair::RemoveCoveringUntilDatetime(covering_until_date_time) => format!(
+                "Remove the snooze of {}",
+                covering_until_date_time.cover_this
+            ),
+            Repair::RemoveRequiredCircumstance(required_circumstance) => format!(
+                "Remove the required circumstance for {}",
+                required_circumstance.required_for
+            ),
+            Repair::RemoveProcessedText(processed_text) => {
+                format!("Remove the processed text for {}", processed_text.for_item)
+            }
+            Repair::UpdateSmallerItemsInPriorityOrder {
+                item,
+                smaller_items_in_priority_order,
+            } => format!(
+                "Change the smaller items of {} to: {}",
+                SurrealTables::describe_item(item),
+                smaller_items_in_priority_order
+                    .iter()
+                    .map(|x| match x {
+                        SurrealOrderedSubItem::SubItem { surreal_item_id } => name(surreal_item_id),
+                        SurrealOrderedSubItem::Split { .. } => "Split".to_string(),
+                    })
+                    .join(", ")
+            ),
+            Repair::UpdateFacing { item, facing } => format!(
+                "Change {} to be facing {} instead",
+                SurrealTables::describe_item(item),
+                facing
+                    .iter()
+                    .map(|x| match x {
+                        Facing::Others { who, .. } => name(who),
+                        Facing::Myself(..) => "Myself".to_string(),
+                        Facing::InternalOrSmaller => "Internal or smaller".to_string(),
+                    })
+                    .join(", ")
+            ),
+        }
+    }
+
+    fn into_command(self) -> DataLayerCommands {
+        match self {
+            Repair::RemoveCovering(covering) => {
+                DataLayerCommands::RemoveCoveringItem(covering.clone())
+            }
+            Repair::RemoveCoveringUntilDatetime(covering_until_date_time) => {
+                DataLayerCommands::RemoveCoveringUntilDatetime(covering_until_date_time.clone())
+            }
+            Repair::RemoveRequiredCircumstance(required_circumstance) => {
+                DataLayerCommands::RemoveRequiredCircumstance(required_circumstance.clone())
+            }
+            Repair::RemoveProcessedText(processed_text) => {
+                DataLayerCommands::RemoveProcessedText(processed_text.clone())
+            }
+            Repair::UpdateSmallerItemsInPriorityOrder {
+                item,
+                smaller_items_in_priority_order,
+            } => DataLayerCommands::UpdateSmallerItemsInPriorityOrder(
+                item.id.clone().expect("Came from the DB"),
+                smaller_items_in_priority_order,
+            ),
+            Repair::UpdateFacing { item, facing } => {
+                DataLayerCommands::UpdateFacing(item.id.clone().expect("Came from the DB"), facing)
+            }
+        }
+    }
+}
+
+/// Several issues can be about the same item, so the changes to an item are combined into one repair
+pub(crate) fn plan_repairs<'s>(issues: &[IntegrityIssue<'s>]) -> Vec<Repair<'s>> {
+    let mut remove_coverings: Vec<&SurrealCovering> = Vec::default();
+    let mut smaller_items_to_remove: Vec<ItemChanges<'s>> = Vec::default();
+    let mut facing_to_remove: Vec<ItemChanges<'s>> = Vec::default();
+    let mut repairs = Vec::default();
+
+    for issue in issues.iter() {
+        match issue {
+            IntegrityIssue::DanglingCovering { covering, .. } => remove_coverings.push(covering),
+            IntegrityIssue::DanglingCoveringUntilDatetime(covering_until_date_time) => repairs
+                .push(Repair::RemoveCoveringUntilDatetime(
+                    covering_until_date_time,
+                )),
+            IntegrityIssue::DanglingRequiredCircumstance(required_circumstance) => {
+                repairs.push(Repair::RemoveRequiredCircumstance(required_circumstance))
+            }
+            IntegrityIssue::DanglingSmallerItem { item, missing } => {
+                changes_for(&mut smaller_items_to_remove, item).push(missing)
+            }
+            IntegrityIssue::DuplicateSmallerItem { item, .. } => {
+                //Duplicates are always removed when the list is rebuilt below
+                changes_for(&mut smaller_items_to_remove, item);
+            }
+            IntegrityIssue::DanglingFacing { item, missing } => {
+                changes_for(&mut facing_to_remove, item).push(missing)
+            }
+            IntegrityIssue::Cycle(links) => {
+                //Removing the link that closes the cycle is enough to break it
+                match links.last().expect("A cycle has at least one link") {
+                    SurrealLink::Covering(covering) => remove_coverings.push(covering),
+                    SurrealLink::SmallerItemInPriorityOrder { larger, smaller } => {
+                        changes_for(&mut smaller_items_to_remove, larger).push(smaller)
+                    }
+                }
+            }
+            IntegrityIssue::OrphanProcessedText(processed_text) => {
+                repairs.push(Repair::RemoveProcessedText(processed_text))
+            }
+        }
+    }
+
+    repairs.extend(
+        remove_coverings
+            .into_iter()
+            .unique_by(|x| &x.id)
+            .map(Repair::RemoveCovering),
+    );
+    repairs.extend(smaller_items_to_remove.into_iter().map(|(item, remove)| {
+        let mut seen = Vec::default();
+        let smaller_items_in_priority_order = item
+            .smaller_items_in_priority_order
+            .iter()
+            .filter(|x| match x {
+                SurrealOrderedSubItem::SubItem { surreal_item_id } => {
+                    let keep =
+                        !remove.contains(&surreal_item_id) && !seen.contains(&surreal_item_id);
+                    seen.push(surreal_item_id);
+                    keep
+                }
+                SurrealOrderedSubItem::Split { .. } => true,
+            })
+            .cloned()
+            .collect();
+        Repair::UpdateSmallerItemsInPriorityOrder {
+            item,
+            smaller_items_in_priority_order,
+        }
+    }));
+    repairs.extend(facing_to_remove.into_iter().map(|(item, remove)| {
+        Repair::UpdateFacing {
+            item,
+            facing: item
+                .facing
+                .iter()
+                .filter(|x| !matches!(x, Facing::Others { who, .. } if remove.contains(&who)))
+                .cloned()
+                .collect(),
+        }
+    }));
+    repairs
+}
+
+/// An item and the record ids that should be removed from it
+type ItemChanges<'s> = (&'s SurrealItem, Vec<&'s RecordId>);
+
+fn changes_for<'a, 's>(
+    changes: &'a mut Vec<ItemChanges<'s>>,
+    item: &'s SurrealItem,
+) -> &'a mut Vec<&'s RecordId> {
+    let index = match changes.iter().position(|(x, _)| x.id == item.id) {
+        Some(index) => index,
+        None => {
+            changes.push((item, Vec::default()));
+            changes.len() - 1
+        }
+    };
+    &mut changes[index].1
+}
+
+pub(crate) async fn run_doctor(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    let all_processed_text = DataLayerCommands::get_all_processed_text(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    let item_archive = DataLayerCommands::get_item_archive(send_to_data_storage_layer)
+        .await
+        .unwrap();
+
+    let issues = find_integrity_issues(&surreal_tables, &all_processed_text, &item_archive);
+    if issues.is_empty() {
+        println!("No issues found");
+        return Ok(());
+    }
+
+    println!("Found {} issue(s):", issues.len());
+    for issue in issues.iter() {
+        println!("  {}", issue.describe(&surreal_tables));
+    }
+
+    let repairs = plan_repairs(&issues);
+    println!();
+    println!("Repairs that would be made:");
+    for repair in repairs.iter() {
+        println!("  {}", repair.describe(&surreal_tables));
+    }
+
+    let selection = Select::new(
+        &format!("Make these {} repair(s)?|", repairs.len()),
+        YesOrNo::make_list(),
+    )
+    .with_starting_cursor(1)
+    .prompt();
+    match selection {
+        Ok(YesOrNo::Yes) => {
+            for repair in repairs.into_iter() {
+                send_to_data_storage_layer
+                    .send(repair.into_command())
+                    .await
+                    .unwrap();
+            }
+            println!("Repairs made");
+            Ok(())
+        }
+        Ok(YesOrNo::No) | Err(InquireError::OperationCanceled) => {
+            println!("No changes made");
+            Ok(())
+        }
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => todo!("Unexpected {}", err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    use crate::surrealdb_layer::{
+        surreal_item::SurrealItemBuilder, surreal_tables::SurrealTablesBuilder,
+    };
+
+    fn smaller_item(id: &str) -> SurrealOrderedSubItem {
+        SurrealOrderedSubItem::SubItem {
+            surreal_item_id: ("surreal_item", id).into(),
+        }
+    }
+
+    #[test]
+    fn dangling_and_duplicate_smaller_items_are_repaired_in_one_update() {
+        let surreal_items = vec![
+            SurrealItemBuilder::default()
+                .id(Some(("surreal_item", "1").into()))
+                .summary("Parent")
+                .smaller_items_in_priority_order(vec![
+                    smaller_item("2"),
+                    smaller_item("missing"),
+                    smaller_item("2"),
+                ])
+                .build()
+                .unwrap(),
+            SurrealItemBuilder::default()
+                .id(Some(("surreal_item", "2").into()))
+                .summary("Child")
+                .build()
+                .unwrap(),
+        ];
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(surreal_items)
+            .surreal_coverings(vec![SurrealCovering {
+                id: Some(("coverings", "1").into()),
+                smaller: ("surreal_item", "missing").into(),
+                parent: ("surreal_item", "1").into(),
+            }])
+            .build()
+            .unwrap();
+
+        let issues = find_integrity_issues(&surreal_tables, &[], &[]);
+        assert_eq!(issues.len(), 3);
+
+        let repairs = plan_repairs(&issues);
+        assert_eq!(
+            repairs,
+            vec![
+                Repair::RemoveCovering(surreal_tables.surreal_coverings.first().unwrap()),
+                Repair::UpdateSmallerItemsInPriorityOrder {
+                    item: surreal_tables.surreal_items.first().unwrap(),
+                    smaller_items_in_priority_order: vec![smaller_item("2")],
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn a_cycle_across_a_covering_and_a_child_is_found_once_and_broken_by_removing_one_link() {
+        let surreal_items = vec![
+            SurrealItemBuilder::default()
+                .id(Some(("surreal_item", "1").into()))
+                .summary("Parent")
+                .smaller_items_in_priority_order(vec![smaller_item("2")])
+                .build()
+                .unwrap(),
+            SurrealItemBuilder::default()
+                .id(Some(("surreal_item", "2").into()))
+                .summary("Child")
+                .build()
+                .unwrap(),
+        ];
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(surreal_items)
+            .surreal_coverings(vec![SurrealCovering {
+                id: Some(("coverings", "1").into()),
+                smaller: ("surreal_item", "1").into(),
+                parent: ("surreal_item", "2").into(),
+            }])
+            .build()
+            .unwrap();
+
+        let issues = find_integrity_issues(&surreal_tables, &[], &[]);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues.first().unwrap().describe(&surreal_tables),
+            "Cycle: \"Parent\" ⟶ \"Child\" ⟶ \"Parent\""
+        );
+
+        let repairs = plan_repairs(&issues);
+        assert_eq!(
+            repairs,
+            vec![Repair::UpdateSmallerItemsInPriorityOrder {
+                item: surreal_tables.surreal_items.first().unwrap(),
+                smaller_items_in_priority_order: vec![],
+            }]
+        );
+    }
+
+    #[test]
+    fn processed_text_for_an_archived_item_is_not_an_orphan() {
+        let archived_item = SurrealItemBuilder::default()
+            .id(Some(("surreal_item", "archived").into()))
+            .summary("Archived")
+            .build()
+            .unwrap();
+        let item_archive = vec![SurrealItemArchive {
+            id: Some(("item_archive", "archived").into()),
+            item: archived_item,
+            coverings: vec![],
+            coverings_until_date_time: vec![],
+            required_circumstances: vec![],
+            smaller_item_of: vec![],
+            archived: Utc::now().into(),
+        }];
+        let all_processed_text = ["archived", "deleted"]
+            .into_iter()
+            .map(|for_item| SurrealProcessedText {
+                id: Some(("processed_text", for_item).into()),
+                text: "Some notes".into(),
+                when_written: Utc::now().into(),
+                for_item: ("surreal_item", for_item).into(),
+            })
+            .collect::<Vec<_>>();
+        let surreal_tables = SurrealTablesBuilder::default().build().unwrap();
+
+        let issues = find_integrity_issues(&surreal_tables, &all_processed_text, &item_archive);
+
+        assert_eq!(
+            issues,
+            vec![IntegrityIssue::OrphanProcessedText(
+                all_processed_text.last().unwrap()
+            )]
+        );
+    }
+}
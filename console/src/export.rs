@@ -0,0 +1,3 @@
+//! Writing the data out in formats that other tools can read
+pub(crate) mod calendar;
+pub(crate) mod markdown;
@@ -0,0 +1,187 @@
+//! When On Purpose expects to revisit items, as an iCalendar (.ics) file so a calendar app can show them. Exporting
+//! again gives each entry the same UID so the calendar app updates it rather than adding it again.
+
+use std::{io, path::Path};
+
+use chrono::{DateTime, Duration, Utc};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::BaseData,
+    calculated_data::CalculatedData,
+    surrealdb_layer::{surreal_tables::SurrealTables, DataLayerCommands},
+    systems::upcoming_list::{find_upcoming, Upcoming, UpcomingReason},
+};
+
+/// Long enough for the event to show up in a calendar app
+const EVENT_LENGTH_MINUTES: i64 = 15;
+
+/// Returns how many entries were written
+pub(crate) async fn export_calendar(
+    path: &Path,
+    now: DateTime<Utc>,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> io::Result<usize> {
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+    let calculated_data = CalculatedData::new_from_base_data(base_data, &now);
+    let upcoming = find_upcoming(
+        calculated_data.get_item_status(),
+        calculated_data.get_active_snoozed(),
+        now,
+    );
+    std::fs::write(path, write_ics(&upcoming, now))?;
+    Ok(upcoming.len())
+}
+
+/// Lap ends are VTODOs that are due then, entering the list and snoozes ending are VEVENTs
+fn write_ics(upcoming: &[Upcoming<'_>], now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//On Purpose//On Purpose//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+    for upcoming in upcoming {
+        let record_id = upcoming.item.get_surreal_record_id();
+        let (component, uid, description) = match upcoming.reason {
+            UpcomingReason::EntersList => (
+                "VEVENT",
+                format!("{}-enters-list@on-purpose", record_id),
+                "Enters the bullet list",
+            ),
+            UpcomingReason::LapEnds => (
+                "VTODO",
+                format!("{}-lap-ends@on-purpose", record_id),
+                "The lap ends and it goes to the top of the bullet list",
+            ),
+            UpcomingReason::SnoozeEnds => (
+                "VEVENT",
+                //An item can be snoozed more than once
+                format!(
+                    "{}-snoozed-until-{}@on-purpose",
+                    record_id,
+                    format_date_time(upcoming.when)
+                ),
+                "Is no longer snoozed",
+            ),
+        };
+        lines.push(format!("BEGIN:{}", component));
+        lines.push(format!("UID:{}", uid));
+        lines.push(format!("DTSTAMP:{}", format_date_time(now)));
+        lines.push(format!(
+            "SUMMARY:{}",
+            escape_text(upcoming.item.get_summary())
+        ));
+        lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        match upcoming.reason {
+            UpcomingReason::LapEnds => {
+                lines.push(format!("DUE:{}", format_date_time(upcoming.when)))
+            }
+            UpcomingReason::EntersList | UpcomingReason::SnoozeEnds => {
+                lines.push(format!("DTSTART:{}", format_date_time(upcoming.when)));
+                lines.push(format!(
+                    "DTEND:{}",
+                    format_date_time(upcoming.when + Duration::minutes(EVENT_LENGTH_MINUTES))
+                ));
+            }
+        }
+        lines.push(format!("END:{}", component));
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|x| fold_line(x)).collect()
+}
+
+fn format_date_time(date_time: DateTime<Utc>) -> String {
+    date_time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Lines are at most 75 bytes, longer ones continue on the next line after a space, and every line ends with CRLF
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 2);
+    let mut line_length = 0;
+    for c in line.chars() {
+        if line_length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            line_length = 1;
+        }
+        folded.push(c);
+        line_length += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use surrealdb::sql::Duration as SurrealDuration;
+    use tokio::sync::mpsc;
+
+    use crate::{
+        new_item::NewItemBuilder,
+        surrealdb_layer::{
+            data_storage_start_and_run,
+            surreal_item::{EnterListReason, ItemType, Staging},
+            DataLayerCommands,
+        },
+    };
+
+    use super::export_calendar;
+
+    #[tokio::test]
+    async fn an_on_deck_item_is_exported_as_an_event_and_a_to_do() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+        let now = Utc::now();
+        sender
+            .send(DataLayerCommands::NewItem(
+                NewItemBuilder::default()
+                    .summary("Review the budget, again, with a summary long enough to be folded")
+                    .item_type(ItemType::Action)
+                    .staging(Staging::OnDeck {
+                        enter_list: EnterListReason::DateTime((now + Duration::days(1)).into()),
+                        lap: SurrealDuration::from_days(1),
+                    })
+                    .build()
+                    .unwrap(),
+            ))
+            .await
+            .unwrap();
+        let path =
+            std::env::temp_dir().join(format!("on_purpose_export_{}.ics", std::process::id()));
+
+        let exported = export_calendar(&path, now, &sender).await.unwrap();
+
+        let ics = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(exported, 2);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.lines().all(|x| x.len() <= 75));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert_eq!(ics.matches("BEGIN:VTODO").count(), 1);
+        assert!(ics.contains("-enters-list@on-purpose\r\n"));
+        assert!(ics.contains(&format!(
+            "DUE:{}\r\n",
+            (now + Duration::days(2)).format("%Y%m%dT%H%M%SZ")
+        )));
+        assert!(ics.replace("\r\n ", "").contains(
+            "SUMMARY:Review the budget\\, again\\, with a summary long enough to be folded\r\n"
+        ));
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+}
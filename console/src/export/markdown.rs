@@ -0,0 +1,154 @@
+//! The hierarchy of motivations, goals, and the smaller items under them as a nested Markdown list
+
+use chrono::Utc;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::{item::Item, BaseData},
+    display::display_outline::DisplayOutline,
+    surrealdb_layer::{surreal_tables::SurrealTables, DataLayerCommands},
+    systems::outline::Outline,
+};
+
+#[derive(Default)]
+pub(crate) struct MarkdownOptions {
+    /// The summary or the record id of the item to start from, otherwise everything
+    pub(crate) root: Option<String>,
+    /// 1 is just the root items
+    pub(crate) depth: Option<usize>,
+    pub(crate) include_finished: bool,
+    pub(crate) include_processed_text: bool,
+}
+
+/// None when no item matches the root
+pub(crate) async fn export_markdown(
+    options: &MarkdownOptions,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Option<String> {
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    let processed_text = if options.include_processed_text {
+        Some(
+            DataLayerCommands::get_all_processed_text(send_to_data_storage_layer)
+                .await
+                .unwrap(),
+        )
+    } else {
+        None
+    };
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, Utc::now());
+    let items = if options.include_finished {
+        base_data.get_items().iter().collect::<Vec<_>>()
+    } else {
+        base_data.get_active_items().to_vec()
+    };
+    let root = match &options.root {
+        Some(root) => Some(find_root(root, &items)?),
+        None => None,
+    };
+
+    let outline = Outline::new(&items, base_data.get_coverings(), root, options.depth);
+    Some(DisplayOutline::new(&outline, processed_text.as_deref()).to_string())
+}
+
+fn find_root<'a>(root: &str, items: &[&'a Item<'a>]) -> Option<&'a Item<'a>> {
+    items
+        .iter()
+        .find(|x| x.get_surreal_record_id().to_string() == root)
+        .or_else(|| {
+            items
+                .iter()
+                .find(|x| x.get_summary().eq_ignore_ascii_case(root))
+        })
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use tokio::sync::mpsc;
+
+    use crate::{
+        new_item::{NewItem, NewItemBuilder},
+        surrealdb_layer::{
+            data_storage_start_and_run, surreal_item::ItemType, surreal_tables::SurrealTables,
+            DataLayerCommands,
+        },
+    };
+
+    use super::{export_markdown, MarkdownOptions};
+
+    #[tokio::test]
+    async fn the_outline_is_nested_under_the_root_with_processed_text_when_asked() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+        sender
+            .send(DataLayerCommands::NewItem(
+                NewItemBuilder::default()
+                    .summary("Launch the website")
+                    .item_type(ItemType::Goal(Default::default()))
+                    .build()
+                    .unwrap(),
+            ))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let launch = surreal_tables.surreal_items[0].id.clone().unwrap();
+        sender
+            .send(DataLayerCommands::ParentItemWithANewChildItem {
+                child: NewItem::new("Pick a domain name".into(), Utc::now()),
+                parent: launch.clone(),
+                higher_priority_than_this: None,
+            })
+            .await
+            .unwrap();
+        sender
+            .send(DataLayerCommands::AddProcessedText(
+                "Narrowed it down to two\nAsk Alex".into(),
+                launch,
+            ))
+            .await
+            .unwrap();
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Unrelated".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+
+        let markdown = export_markdown(
+            &MarkdownOptions {
+                root: Some("launch the website".into()),
+                include_processed_text: true,
+                ..Default::default()
+            },
+            &sender,
+        )
+        .await
+        .unwrap();
+
+        let lines = markdown.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "- ❓ 🪧 Launch the website");
+        assert!(lines[1].starts_with("  > **"));
+        assert!(lines[1].ends_with("** Narrowed it down to two"));
+        assert_eq!(lines[2], "  > Ask Alex");
+        assert_eq!(lines[3], "  - ❓ ❓ Pick a domain name");
+
+        let markdown = export_markdown(
+            &MarkdownOptions {
+                root: Some("Nothing by this name".into()),
+                ..Default::default()
+            },
+            &sender,
+        )
+        .await;
+        assert!(markdown.is_none());
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+}
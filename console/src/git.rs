@@ -0,0 +1,202 @@
+//! Items linked to a branch of a git repository on this computer, through the git command line so no network is used.
+//! Commits are attached to an item by mentioning the item's id in the commit message.
+pub(crate) mod sync;
+
+use std::{
+    io,
+    path::Path,
+    process::{Command, Output},
+};
+
+use crate::{base_data::item::Item, surrealdb_layer::surreal_item::GitBranch};
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum CheckedOut {
+    Existing,
+    Created,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct Commit {
+    pub(crate) hash: String,
+    pub(crate) message: String,
+}
+
+/// What to mention in a commit message to attach the commit to the item
+pub(crate) fn get_item_reference(item: &Item<'_>) -> String {
+    item.get_surreal_record_id().id.to_raw()
+}
+
+/// Lowercase words joined by dashes, for example "Fix the login page" becomes "fix-the-login-page"
+pub(crate) fn suggest_branch_name(summary: &str) -> String {
+    summary
+        .split(|x: char| !x.is_ascii_alphanumeric())
+        .filter(|x| !x.is_empty())
+        .map(|x| x.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Creates the branch from what is checked out now if it does not exist yet
+pub(crate) fn check_out_branch(git_branch: &GitBranch) -> io::Result<CheckedOut> {
+    let repository = Path::new(&git_branch.repository);
+    if does_branch_exist(repository, &git_branch.branch)? {
+        run_git(repository, &["checkout", &git_branch.branch])?;
+        Ok(CheckedOut::Existing)
+    } else {
+        run_git(repository, &["checkout", "-b", &git_branch.branch])?;
+        Ok(CheckedOut::Created)
+    }
+}
+
+/// The commits on any branch that mention the reference, oldest first
+pub(crate) fn find_commits_mentioning(
+    repository: &Path,
+    reference: &str,
+) -> io::Result<Vec<Commit>> {
+    //The unit and record separators can't be in a commit message
+    let log = run_git(
+        repository,
+        &[
+            "log",
+            "--all",
+            "--reverse",
+            "--fixed-strings",
+            &format!("--grep={}", reference),
+            "--format=%H%x1f%B%x1e",
+        ],
+    )?;
+    Ok(log
+        .split('\x1e')
+        .filter_map(|x| x.trim().split_once('\x1f'))
+        .map(|(hash, message)| Commit {
+            hash: hash.to_string(),
+            message: message.trim().to_string(),
+        })
+        .collect())
+}
+
+/// main, or master for repositories from before main was the default, None when there is neither
+pub(crate) fn find_main_branch(repository: &Path) -> io::Result<Option<&'static str>> {
+    for main in ["main", "master"] {
+        if does_branch_exist(repository, main)? {
+            return Ok(Some(main));
+        }
+    }
+    Ok(None)
+}
+
+/// True when everything on the branch is also in main. A branch that was just created is in main as well so only ask
+/// this once there are commits for the item.
+pub(crate) fn is_merged_into(git_branch: &GitBranch, main: &str) -> io::Result<bool> {
+    let repository = Path::new(&git_branch.repository);
+    if git_branch.branch == main || !does_branch_exist(repository, &git_branch.branch)? {
+        return Ok(false);
+    }
+    let output = git(
+        repository,
+        &["merge-base", "--is-ancestor", &git_branch.branch, main],
+    )?;
+    Ok(output.status.success())
+}
+
+fn does_branch_exist(repository: &Path, branch: &str) -> io::Result<bool> {
+    let output = git(
+        repository,
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("refs/heads/{}", branch),
+        ],
+    )?;
+    Ok(output.status.success())
+}
+
+/// Fails with what git printed when git does not succeed
+fn run_git(repository: &Path, args: &[&str]) -> io::Result<String> {
+    let output = git(repository, args)?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        Err(io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+fn git(repository: &Path, args: &[&str]) -> io::Result<Output> {
+    Command::new("git")
+        .arg("-C")
+        .arg(repository)
+        .args(args)
+        .output()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::path::{Path, PathBuf};
+
+    use crate::surrealdb_layer::surreal_item::GitBranch;
+
+    use super::{
+        check_out_branch, find_commits_mentioning, find_main_branch, is_merged_into, run_git,
+        suggest_branch_name, CheckedOut,
+    };
+
+    /// A new repository with one commit on main, name keeps tests that run at the same time apart
+    pub(crate) fn create_repository(name: &str) -> PathBuf {
+        let repository =
+            std::env::temp_dir().join(format!("on_purpose_git_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&repository);
+        std::fs::create_dir_all(&repository).unwrap();
+        run_git(&repository, &["init", "--quiet", "--initial-branch=main"]).unwrap();
+        run_git(&repository, &["config", "user.name", "Test"]).unwrap();
+        run_git(&repository, &["config", "user.email", "test@example.com"]).unwrap();
+        commit(&repository, "Initial commit");
+        repository
+    }
+
+    pub(crate) fn commit(repository: &Path, message: &str) {
+        run_git(
+            repository,
+            &["commit", "--quiet", "--allow-empty", "-m", message],
+        )
+        .unwrap();
+    }
+
+    pub(crate) fn merge_into_main(repository: &Path, branch: &str) {
+        run_git(repository, &["checkout", "--quiet", "main"]).unwrap();
+        run_git(
+            repository,
+            &["merge", "--quiet", "--no-ff", "-m", "Merge", branch],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn a_branch_is_created_then_merged_with_the_commits_that_mention_the_item() {
+        let repository = create_repository("branch");
+        let git_branch = GitBranch {
+            repository: repository.to_string_lossy().into_owned(),
+            branch: suggest_branch_name("Fix the login page!"),
+        };
+        assert_eq!(git_branch.branch, "fix-the-login-page");
+
+        assert_eq!(check_out_branch(&git_branch).unwrap(), CheckedOut::Created);
+        assert_eq!(check_out_branch(&git_branch).unwrap(), CheckedOut::Existing);
+        let main = find_main_branch(&repository).unwrap().unwrap();
+
+        commit(&repository, "Validate the password\n\nFor abc123");
+        commit(&repository, "Unrelated");
+        assert!(!is_merged_into(&git_branch, main).unwrap());
+        merge_into_main(&repository, &git_branch.branch);
+
+        let commits = find_commits_mentioning(&repository, "abc123").unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].message, "Validate the password\n\nFor abc123");
+        assert!(is_merged_into(&git_branch, main).unwrap());
+
+        std::fs::remove_dir_all(&repository).unwrap();
+    }
+}
@@ -0,0 +1,210 @@
+//! Attaches the commits that mention an item to it as processed text and offers to finish the items whose branch was
+//! merged into main. Attaching again skips the commits that are already attached.
+
+use std::{fmt::Display, path::Path};
+
+use chrono::Utc;
+use inquire::InquireError;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::BaseData,
+    menu::prompt::Select,
+    surrealdb_layer::{surreal_tables::SurrealTables, DataLayerCommands},
+};
+
+use super::{find_commits_mentioning, find_main_branch, get_item_reference, is_merged_into};
+
+#[derive(Default, Debug, PartialEq)]
+pub(crate) struct GitSyncSummary {
+    pub(crate) attached: usize,
+    pub(crate) finished: usize,
+}
+
+impl Display for GitSyncSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Attached {} commits and finished {} items",
+            self.attached, self.finished
+        )
+    }
+}
+
+enum MergedSelection {
+    FinishIt,
+    NotYet,
+}
+
+impl Display for MergedSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergedSelection::FinishIt => write!(f, "Yes, finish it"),
+            MergedSelection::NotYet => write!(f, "Not yet"),
+        }
+    }
+}
+
+/// A repository that can't be read is reported and skipped so the others are still brought up to date
+pub(crate) async fn sync_git(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<GitSyncSummary, ()> {
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    let all_processed_text = DataLayerCommands::get_all_processed_text(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, Utc::now());
+    let mut summary = GitSyncSummary::default();
+    for item in base_data.get_active_items().iter() {
+        let git_branch = match item.get_git_branch() {
+            Some(git_branch) => git_branch,
+            None => continue,
+        };
+        let repository = Path::new(&git_branch.repository);
+        let commits = match find_commits_mentioning(repository, &get_item_reference(item)) {
+            Ok(commits) => commits,
+            Err(err) => {
+                println!("Unable to read {}: {}", git_branch.repository, err);
+                continue;
+            }
+        };
+        for commit in commits.iter() {
+            let is_attached = all_processed_text.iter().any(|x| {
+                &x.for_item == item.get_surreal_record_id() && x.text.contains(&commit.hash)
+            });
+            if !is_attached {
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::AddProcessedText(
+                        format!("Commit {}\n{}", commit.hash, commit.message),
+                        item.get_surreal_record_id().clone(),
+                    ))
+                    .await
+                    .unwrap();
+                summary.attached += 1;
+            }
+        }
+
+        if commits.is_empty() {
+            continue;
+        }
+        let main = match find_main_branch(repository) {
+            Ok(Some(main)) => main,
+            Ok(None) | Err(_) => continue,
+        };
+        if !is_merged_into(git_branch, main).unwrap_or(false) {
+            continue;
+        }
+        let message = format!(
+            "{} was merged into {}, is \"{}\" finished?",
+            git_branch.branch,
+            main,
+            item.get_summary()
+        );
+        let list = vec![MergedSelection::FinishIt, MergedSelection::NotYet];
+        match Select::new(&message, list).prompt() {
+            Ok(MergedSelection::FinishIt) => {
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::FinishItem(
+                        item.get_surreal_record_id().clone(),
+                    ))
+                    .await
+                    .unwrap();
+                summary.finished += 1;
+            }
+            Ok(MergedSelection::NotYet) | Err(InquireError::OperationCanceled) => {}
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => todo!("Unexpected {}", err),
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use tokio::sync::mpsc;
+
+    use crate::{
+        git::{
+            check_out_branch,
+            tests::{commit, create_repository, merge_into_main},
+        },
+        menu::prompt::scripted::{run_scripted, Answer},
+        new_item::NewItem,
+        surrealdb_layer::{
+            data_storage_start_and_run, surreal_item::GitBranch, surreal_tables::SurrealTables,
+            DataLayerCommands,
+        },
+    };
+
+    use super::{sync_git, GitSyncSummary};
+
+    #[tokio::test]
+    async fn commits_are_attached_once_and_a_merged_branch_offers_to_finish_the_item() {
+        let repository = create_repository("sync");
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Fix the login page".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let item = surreal_tables.surreal_items[0].id.clone().unwrap();
+        sender
+            .send(DataLayerCommands::UpdateItemGitBranch(
+                item.clone(),
+                Some(Box::new(GitBranch {
+                    repository: repository.to_string_lossy().into_owned(),
+                    branch: "fix-the-login-page".into(),
+                })),
+            ))
+            .await
+            .unwrap();
+        let message = format!("Validate the password for {}", item.id.to_raw());
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        check_out_branch(surreal_tables.surreal_items[0].git_branch.as_ref().unwrap()).unwrap();
+        commit(&repository, &message);
+
+        let summary = run_scripted([], sync_git(&sender)).await.unwrap();
+
+        assert_eq!(
+            summary,
+            GitSyncSummary {
+                attached: 1,
+                finished: 0
+            }
+        );
+        let processed_text = DataLayerCommands::get_processed_text(&sender, item.clone())
+            .await
+            .unwrap();
+        assert_eq!(processed_text.len(), 1);
+        assert!(processed_text[0].text.starts_with("Commit "));
+        assert!(processed_text[0].text.ends_with(&format!("\n{}", message)));
+
+        merge_into_main(&repository, "fix-the-login-page");
+
+        let summary = run_scripted([Answer::Select("Yes, finish it".into())], sync_git(&sender))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            summary,
+            GitSyncSummary {
+                attached: 0,
+                finished: 1
+            }
+        );
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert!(surreal_tables.surreal_items[0].finished.is_some());
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+        std::fs::remove_dir_all(&repository).unwrap();
+    }
+}
@@ -0,0 +1,7 @@
+//! Adding items from the files that other tools export, importing the same file again does not add what was imported
+//! before
+pub(crate) mod calendar;
+pub(crate) mod mail;
+pub(crate) mod task_list;
+pub(crate) mod taskwarrior;
+pub(crate) mod todo_txt;
@@ -0,0 +1,720 @@
+//! Meetings from iCalendar (.ics) files that were exported from a calendar app, no network is used. Each event becomes
+//! an item that enters the bullet list when the event starts. Importing again updates the items by the event's UID
+//! rather than adding them again.
+
+use std::{collections::HashSet, fmt::Display, io, path::Path, time::Duration as StdDuration};
+
+use chrono::{
+    DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    new_item::NewItemBuilder,
+    surrealdb_layer::{
+        surreal_item::{
+            Attendance, CalendarEvent, EnterListReason, ItemType, Staging, SurrealItem,
+        },
+        surreal_tables::SurrealTables,
+        DataLayerCommands,
+    },
+};
+
+/// How far ahead the occurrences of a recurring event are added, importing again later adds the ones after that
+const RECURRING_DAYS_AHEAD: i64 = 14;
+
+/// One occurrence of an event as read from the file
+#[derive(Debug, PartialEq)]
+struct ImportedEvent {
+    /// The UID, with the start of the occurrence added for recurring events so each occurrence is its own item
+    uid: String,
+    summary: String,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    is_cancelled: bool,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub(crate) struct CalendarImportSummary {
+    pub(crate) added: usize,
+    pub(crate) updated: usize,
+    pub(crate) cancelled: usize,
+    pub(crate) unchanged: usize,
+}
+
+impl Display for CalendarImportSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Added {}, updated {}, cancelled {}, and {} unchanged",
+            self.added, self.updated, self.cancelled, self.unchanged
+        )
+    }
+}
+
+/// path is an .ics file or a directory of them. Events that are already over are only updated, not added.
+pub(crate) async fn import_calendar(
+    path: &Path,
+    now: DateTime<Utc>,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> io::Result<CalendarImportSummary> {
+    let mut events = Vec::default();
+    for file in find_ics_files(path)? {
+        let text = std::fs::read_to_string(file)?;
+        events.extend(read_ics(&text, now));
+    }
+
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    let mut summary = CalendarImportSummary::default();
+    let mut seen = HashSet::new();
+    for event in events {
+        //The same event can be in more than one file
+        if !seen.insert(event.uid.clone()) {
+            continue;
+        }
+        let existing = surreal_tables.surreal_items.iter().find(|x| {
+            x.calendar_event
+                .as_ref()
+                .is_some_and(|calendar_event| calendar_event.uid == event.uid)
+        });
+        match existing {
+            Some(existing) => {
+                update_existing(existing, event, &mut summary, send_to_data_storage_layer).await
+            }
+            None if event.is_cancelled || event.end <= now => {}
+            None => {
+                let new_item = NewItemBuilder::default()
+                    .summary(event.summary)
+                    .item_type(ItemType::Action)
+                    .staging(staging_for(event.start, event.end))
+                    .calendar_event(Some(Box::new(CalendarEvent {
+                        uid: event.uid,
+                        start: event.start.into(),
+                        end: event.end.into(),
+                        attendance: Attendance::NotDecided,
+                    })))
+                    .created(now)
+                    .build()
+                    .unwrap();
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::NewItem(new_item))
+                    .await
+                    .unwrap();
+                summary.added += 1;
+            }
+        }
+    }
+    Ok(summary)
+}
+
+async fn update_existing(
+    existing: &SurrealItem,
+    event: ImportedEvent,
+    summary: &mut CalendarImportSummary,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) {
+    let record_id = existing.id.clone().expect("In the database");
+    if event.is_cancelled {
+        if existing.finished.is_none() {
+            send_to_data_storage_layer
+                .send(DataLayerCommands::FinishItem(record_id))
+                .await
+                .unwrap();
+            summary.cancelled += 1;
+        } else {
+            summary.unchanged += 1;
+        }
+        return;
+    }
+
+    let calendar_event = CalendarEvent {
+        uid: event.uid,
+        start: event.start.into(),
+        end: event.end.into(),
+        //What I said about attending is kept even if the event moved
+        attendance: existing
+            .calendar_event
+            .as_ref()
+            .map(|x| x.attendance)
+            .unwrap_or_default(),
+    };
+    let mut is_changed = false;
+    if existing.summary != event.summary {
+        send_to_data_storage_layer
+            .send(DataLayerCommands::UpdateItemSummary(
+                record_id.clone(),
+                event.summary,
+            ))
+            .await
+            .unwrap();
+        is_changed = true;
+    }
+    if existing.calendar_event.as_deref() != Some(&calendar_event) {
+        send_to_data_storage_layer
+            .send(DataLayerCommands::UpdateItemStaging(
+                record_id.clone(),
+                staging_for(event.start, event.end),
+            ))
+            .await
+            .unwrap();
+        send_to_data_storage_layer
+            .send(DataLayerCommands::UpdateItemCalendarEvent(
+                record_id,
+                Some(Box::new(calendar_event)),
+            ))
+            .await
+            .unwrap();
+        is_changed = true;
+    }
+    if is_changed {
+        summary.updated += 1;
+    } else {
+        summary.unchanged += 1;
+    }
+}
+
+/// Enters the list when the event starts and the lap is the length of the event
+fn staging_for(start: DateTime<Utc>, end: DateTime<Utc>) -> Staging {
+    let lap = (end - start)
+        .to_std()
+        .unwrap_or_default()
+        //A lap of zero would make the lap count infinite
+        .max(StdDuration::from_secs(60));
+    Staging::MentallyResident {
+        enter_list: EnterListReason::DateTime(start.into()),
+        lap: lap.into(),
+    }
+}
+
+fn find_ics_files(path: &Path) -> io::Result<Vec<std::path::PathBuf>> {
+    if path.is_dir() {
+        let mut files = std::fs::read_dir(path)?
+            .map(|x| x.map(|x| x.path()))
+            .collect::<io::Result<Vec<_>>>()?;
+        files.retain(|x| {
+            x.extension()
+                .is_some_and(|extension| extension.eq_ignore_ascii_case("ics"))
+        });
+        files.sort();
+        Ok(files)
+    } else {
+        Ok(vec![path.to_path_buf()])
+    }
+}
+
+/// The properties of one VEVENT, before recurrence is worked out
+#[derive(Default)]
+struct EventBeingRead {
+    uid: Option<String>,
+    summary: Option<String>,
+    /// The time zone is None for local time
+    start: Option<(DateTime<Utc>, bool, Option<Tz>)>,
+    end: Option<DateTime<Utc>>,
+    duration: Option<Duration>,
+    is_cancelled: bool,
+    recurrence_rule: Option<String>,
+    recurrence_id: Option<DateTime<Utc>>,
+    excluded: Vec<DateTime<Utc>>,
+}
+
+/// Every occurrence in the text, recurring events are expanded from the first occurrence until RECURRING_DAYS_AHEAD
+/// after now. Times with a TZID are read in that time zone, or as local time if the time zone is not known.
+fn read_ics(text: &str, now: DateTime<Utc>) -> Vec<ImportedEvent> {
+    let mut events: Vec<EventBeingRead> = Vec::default();
+    //The components that are open, properties only count when the VEVENT is the innermost so a VALARM's are skipped
+    let mut open_components: Vec<String> = Vec::default();
+    for line in unfold_lines(text) {
+        let Some(Property {
+            name,
+            parameters,
+            value,
+        }) = parse_property(&line)
+        else {
+            continue;
+        };
+        match name.as_str() {
+            "BEGIN" => {
+                let component = value.to_ascii_uppercase();
+                if component == "VEVENT" {
+                    events.push(EventBeingRead::default());
+                }
+                open_components.push(component);
+                continue;
+            }
+            "END" => {
+                open_components.pop();
+                continue;
+            }
+            _ => {}
+        }
+        if open_components.last().map(String::as_str) != Some("VEVENT") {
+            continue;
+        }
+        let event = events.last_mut().expect("Pushed on BEGIN:VEVENT");
+        match name.as_str() {
+            "UID" => event.uid = Some(value.to_string()),
+            "SUMMARY" => event.summary = Some(unescape_text(value)),
+            "DTSTART" => event.start = parse_date_time(value, &parameters),
+            "DTEND" => event.end = parse_date_time(value, &parameters).map(|(x, _, _)| x),
+            "DURATION" => event.duration = parse_duration(value),
+            "STATUS" => event.is_cancelled = value.eq_ignore_ascii_case("CANCELLED"),
+            "RRULE" => event.recurrence_rule = Some(value.to_ascii_uppercase()),
+            "RECURRENCE-ID" => {
+                event.recurrence_id = parse_date_time(value, &parameters).map(|(x, _, _)| x)
+            }
+            "EXDATE" => event.excluded.extend(
+                value
+                    .split(',')
+                    .filter_map(|x| parse_date_time(x, &parameters).map(|(x, _, _)| x)),
+            ),
+            _ => {}
+        }
+    }
+
+    //A moved or cancelled occurrence of a recurring event is its own VEVENT with a RECURRENCE-ID
+    let overridden = events
+        .iter()
+        .filter_map(|x| Some(occurrence_uid(x.uid.as_ref()?, x.recurrence_id?)))
+        .collect::<HashSet<_>>();
+    events
+        .iter()
+        .flat_map(|event| {
+            let (Some(uid), Some((start, is_all_day, time_zone))) = (&event.uid, event.start)
+            else {
+                return Vec::default();
+            };
+            let length = match (event.end, event.duration) {
+                (Some(end), _) => end - start,
+                (None, Some(duration)) => duration,
+                (None, None) if is_all_day => Duration::days(1),
+                (None, None) => Duration::zero(),
+            };
+            let summary = event.summary.clone().unwrap_or_default();
+            let starts = match (&event.recurrence_rule, event.recurrence_id) {
+                (_, Some(recurrence_id)) => {
+                    return vec![ImportedEvent {
+                        uid: occurrence_uid(uid, recurrence_id),
+                        summary,
+                        start,
+                        end: start + length,
+                        is_cancelled: event.is_cancelled,
+                    }]
+                }
+                (Some(recurrence_rule), None) => expand_recurrence(
+                    start,
+                    time_zone,
+                    recurrence_rule,
+                    now + Duration::days(RECURRING_DAYS_AHEAD),
+                ),
+                (None, None) => {
+                    return vec![ImportedEvent {
+                        uid: uid.clone(),
+                        summary,
+                        start,
+                        end: start + length,
+                        is_cancelled: event.is_cancelled,
+                    }]
+                }
+            };
+            starts
+                .into_iter()
+                .filter(|x| !event.excluded.contains(x))
+                .map(|x| ImportedEvent {
+                    uid: occurrence_uid(uid, x),
+                    summary: summary.clone(),
+                    start: x,
+                    end: x + length,
+                    is_cancelled: event.is_cancelled,
+                })
+                .filter(|x| !overridden.contains(&x.uid))
+                .collect()
+        })
+        .collect()
+}
+
+fn occurrence_uid(uid: &str, start: DateTime<Utc>) -> String {
+    format!("{}/{}", uid, start.format("%Y%m%dT%H%M%SZ"))
+}
+
+/// Lines that start with a space or tab continue the line before
+fn unfold_lines(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::default();
+    for line in text.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continued), Some(last)) => last.push_str(continued),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// A line like "DTSTART;TZID=Europe/London:20240603T090000"
+struct Property<'a> {
+    /// Upper case
+    name: String,
+    /// The names are upper case and the values have their quotes removed
+    parameters: Vec<(String, String)>,
+    value: &'a str,
+}
+
+fn parse_property(line: &str) -> Option<Property<'_>> {
+    let mut in_quotes = false;
+    let colon = line.char_indices().find_map(|(index, c)| match c {
+        '"' => {
+            in_quotes = !in_quotes;
+            None
+        }
+        ':' if !in_quotes => Some(index),
+        _ => None,
+    })?;
+    let mut name_and_parameters = line[..colon].split(';');
+    let name = name_and_parameters.next()?.trim().to_ascii_uppercase();
+    let parameters = name_and_parameters
+        .filter_map(|x| {
+            let (key, value) = x.split_once('=')?;
+            Some((
+                key.to_ascii_uppercase(),
+                value.trim_matches('"').to_string(),
+            ))
+        })
+        .collect();
+    Some(Property {
+        name,
+        parameters,
+        value: &line[colon + 1..],
+    })
+}
+
+/// The bool is true for a date without a time, which starts at local midnight. The time zone is the one named by the
+/// TZID, None when the time is in local time.
+fn parse_date_time(
+    value: &str,
+    parameters: &[(String, String)],
+) -> Option<(DateTime<Utc>, bool, Option<Tz>)> {
+    let is_date = parameters
+        .iter()
+        .any(|(key, value)| key == "VALUE" && value.eq_ignore_ascii_case("DATE"))
+        || value.len() == 8;
+    if is_date {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Some((local_to_utc(date.and_hms_opt(0, 0, 0)?)?, true, None));
+    }
+    match value.strip_suffix(['Z', 'z']) {
+        Some(utc) => {
+            let date_time = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+            Some((Utc.from_utc_datetime(&date_time), false, None))
+        }
+        None => {
+            let date_time = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+            //Calendar apps that use Windows names, like "Eastern Standard Time", fall back to local time
+            let time_zone = parameters
+                .iter()
+                .find(|(key, _)| key == "TZID")
+                .and_then(|(_, x)| x.trim_start_matches('/').parse::<Tz>().ok());
+            Some((zoned_to_utc(date_time, time_zone)?, false, time_zone))
+        }
+    }
+}
+
+fn local_to_utc(date_time: NaiveDateTime) -> Option<DateTime<Utc>> {
+    Local
+        .from_local_datetime(&date_time)
+        .earliest()
+        .map(|x| x.with_timezone(&Utc))
+}
+
+/// None for the time zone is local time
+fn zoned_to_utc(date_time: NaiveDateTime, time_zone: Option<Tz>) -> Option<DateTime<Utc>> {
+    match time_zone {
+        Some(time_zone) => time_zone
+            .from_local_datetime(&date_time)
+            .earliest()
+            .map(|x| x.with_timezone(&Utc)),
+        None => local_to_utc(date_time),
+    }
+}
+
+/// For example "PT1H30M", "P1D", or "-PT15M"
+fn parse_duration(value: &str) -> Option<Duration> {
+    let (is_negative, value) = match value.strip_prefix('-') {
+        Some(value) => (true, value),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let value = value.strip_prefix('P')?;
+    let mut duration = Duration::zero();
+    let mut number = String::default();
+    for c in value.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => {}
+            'W' | 'D' | 'H' | 'M' | 'S' => {
+                let amount: i64 = number.parse().ok()?;
+                number.clear();
+                duration = duration
+                    + match c {
+                        'W' => Duration::weeks(amount),
+                        'D' => Duration::days(amount),
+                        'H' => Duration::hours(amount),
+                        'M' => Duration::minutes(amount),
+                        _ => Duration::seconds(amount),
+                    };
+            }
+            _ => return None,
+        }
+    }
+    Some(if is_negative { -duration } else { duration })
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n' | 'N') => text.push('\n'),
+                Some(escaped) => text.push(escaped),
+                None => {}
+            },
+            _ => text.push(c),
+        }
+    }
+    text
+}
+
+/// The starts of each occurrence up to until, worked out in the event's time zone, or local time when it has none, so a
+/// daylight saving change does not move the meeting. Only FREQ=DAILY and FREQ=WEEKLY are understood, for anything else
+/// just the first occurrence is used.
+fn expand_recurrence(
+    first: DateTime<Utc>,
+    time_zone: Option<Tz>,
+    recurrence_rule: &str,
+    until: DateTime<Utc>,
+) -> Vec<DateTime<Utc>> {
+    let parts = recurrence_rule
+        .split(';')
+        .filter_map(|x| x.split_once('='))
+        .collect::<Vec<_>>();
+    let get = |key: &str| parts.iter().find(|(x, _)| *x == key).map(|(_, x)| *x);
+    let interval = get("INTERVAL")
+        .and_then(|x| x.parse::<i64>().ok())
+        .unwrap_or(1)
+        .max(1);
+    let count = get("COUNT").and_then(|x| x.parse::<usize>().ok());
+    let until = match get("UNTIL").and_then(|x| parse_date_time(x, &[])) {
+        Some((rule_until, _, _)) => rule_until.min(until),
+        None => until,
+    };
+    let first_local = match time_zone {
+        Some(time_zone) => first.with_timezone(&time_zone).naive_local(),
+        None => first.with_timezone(&Local).naive_local(),
+    };
+    let by_day = get("BYDAY")
+        .map(|x| x.split(',').filter_map(parse_weekday).collect::<Vec<_>>())
+        .filter(|x| !x.is_empty())
+        .unwrap_or_else(|| vec![first_local.weekday()]);
+    let is_on = |days_after_first: i64, weekday: Weekday| match get("FREQ") {
+        Some("DAILY") => days_after_first % interval == 0,
+        Some("WEEKLY") => {
+            //Weeks start on Monday
+            let weeks =
+                (days_after_first + first_local.weekday().num_days_from_monday() as i64) / 7;
+            weeks % interval == 0 && by_day.contains(&weekday)
+        }
+        _ => days_after_first == 0,
+    };
+
+    let mut starts = Vec::default();
+    for days_after_first in 0.. {
+        let local = first_local + Duration::days(days_after_first);
+        let Some(start) = zoned_to_utc(local, time_zone) else {
+            continue;
+        };
+        if start > until || count.is_some_and(|count| starts.len() >= count) {
+            break;
+        }
+        if is_on(days_after_first, local.weekday()) {
+            starts.push(start);
+        }
+    }
+    starts
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    //Something like "1MO" for the first Monday of the month is not understood so the number is ignored
+    match value.trim_start_matches(|c: char| c.is_ascii_digit() || c == '-' || c == '+') {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, Utc};
+    use tokio::sync::mpsc;
+
+    use crate::surrealdb_layer::{
+        data_storage_start_and_run, surreal_item::Attendance, surreal_tables::SurrealTables,
+    };
+
+    use super::{import_calendar, read_ics, CalendarImportSummary};
+
+    fn utc(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text).unwrap().into()
+    }
+
+    #[test]
+    fn folded_lines_alarms_durations_and_weekly_recurrence_are_read() {
+        let text = "BEGIN:VCALENDAR\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:review@example.com\r\n\
+            SUMMARY:Quarterly review\\, with\r\n  the whole team\r\n\
+            DTSTART:20240603T150000Z\r\n\
+            DURATION:PT1H30M\r\n\
+            BEGIN:VALARM\r\n\
+            SUMMARY:Not the event\r\n\
+            TRIGGER:-PT15M\r\n\
+            END:VALARM\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:standup@example.com\r\n\
+            SUMMARY:Standup\r\n\
+            DTSTART:20240603T090000Z\r\n\
+            DTEND:20240603T091500Z\r\n\
+            RRULE:FREQ=WEEKLY;BYDAY=MO,WE;COUNT=4\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:standup@example.com\r\n\
+            RECURRENCE-ID:20240605T090000Z\r\n\
+            SUMMARY:Standup\r\n\
+            STATUS:CANCELLED\r\n\
+            DTSTART:20240605T090000Z\r\n\
+            DTEND:20240605T091500Z\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        let events = read_ics(text, utc("2024-06-01T00:00:00Z"));
+
+        let review = &events[0];
+        assert_eq!(review.uid, "review@example.com");
+        assert_eq!(review.summary, "Quarterly review, with the whole team");
+        assert_eq!(review.end - review.start, Duration::minutes(90));
+        let standups = events
+            .iter()
+            .filter(|x| x.summary == "Standup")
+            .map(|x| (x.uid.as_str(), x.is_cancelled))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            standups,
+            [
+                ("standup@example.com/20240603T090000Z", false),
+                ("standup@example.com/20240610T090000Z", false),
+                ("standup@example.com/20240612T090000Z", false),
+                ("standup@example.com/20240605T090000Z", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn times_are_read_in_the_time_zone_of_their_tzid() {
+        //Far from any local time zone the tests could be run in, and on the other side of UTC from New York
+        let text = "BEGIN:VCALENDAR\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:kiritimati@example.com\r\n\
+            SUMMARY:Call with Kiritimati\r\n\
+            DTSTART;TZID=Pacific/Kiritimati:20240603T090000\r\n\
+            DTEND;TZID=\"America/New_York\":20240602T153000\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:weekly@example.com\r\n\
+            SUMMARY:Weekly call with Kiritimati\r\n\
+            DTSTART;TZID=Pacific/Kiritimati:20240603T090000\r\n\
+            DURATION:PT30M\r\n\
+            RRULE:FREQ=WEEKLY;BYDAY=MO;COUNT=2\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        let events = read_ics(text, utc("2024-06-01T00:00:00Z"));
+
+        assert_eq!(events[0].start, utc("2024-06-02T19:00:00Z"));
+        assert_eq!(events[0].end, utc("2024-06-02T19:30:00Z"));
+        //Monday in Kiritimati is still Sunday in UTC
+        assert_eq!(
+            events[1..].iter().map(|x| x.start).collect::<Vec<_>>(),
+            [utc("2024-06-02T19:00:00Z"), utc("2024-06-09T19:00:00Z")]
+        );
+    }
+
+    #[tokio::test]
+    async fn importing_again_updates_the_same_items_by_uid() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+        let directory =
+            std::env::temp_dir().join(format!("on_purpose_calendar_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let file = directory.join("work.ics");
+        let calendar = |summary: &str, status: &str| {
+            format!(
+                "BEGIN:VCALENDAR\nBEGIN:VEVENT\nUID:planning@example.com\nSUMMARY:{}\n{}\
+                DTSTART:20240603T150000Z\nDTEND:20240603T160000Z\nEND:VEVENT\nBEGIN:VEVENT\n\
+                UID:old@example.com\nSUMMARY:Already over\nDTSTART:20240501T150000Z\n\
+                DTEND:20240501T160000Z\nEND:VEVENT\nEND:VCALENDAR\n",
+                summary, status
+            )
+        };
+        let now = utc("2024-06-01T00:00:00Z");
+
+        std::fs::write(&file, calendar("Sprint planning", "")).unwrap();
+        let first = import_calendar(&directory, now, &sender).await.unwrap();
+        let second = import_calendar(&directory, now, &sender).await.unwrap();
+        std::fs::write(&file, calendar("Sprint planning and demo", "")).unwrap();
+        let renamed = import_calendar(&file, now, &sender).await.unwrap();
+        std::fs::write(
+            &file,
+            calendar("Sprint planning and demo", "STATUS:CANCELLED\n"),
+        )
+        .unwrap();
+        let cancelled = import_calendar(&file, now, &sender).await.unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(
+            first,
+            CalendarImportSummary {
+                added: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            second,
+            CalendarImportSummary {
+                unchanged: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(renamed.updated, 1);
+        assert_eq!(cancelled.cancelled, 1);
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(surreal_tables.surreal_items.len(), 1);
+        let planning = &surreal_tables.surreal_items[0];
+        assert_eq!(planning.summary, "Sprint planning and demo");
+        assert!(planning.finished.is_some());
+        let calendar_event = planning.calendar_event.as_ref().unwrap();
+        assert_eq!(calendar_event.attendance, Attendance::NotDecided);
+        assert_eq!(
+            DateTime::<Utc>::from(calendar_event.start.clone()),
+            utc("2024-06-03T15:00:00Z")
+        );
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+}
@@ -0,0 +1,563 @@
+//! Flagged mail from a local Maildir folder or mbox file, no network is used. Each flagged message becomes an item with
+//! a mid: link (RFC 2392) to the message, followed by the mailbox it came from, as its notes. Importing a mailbox again
+//! finishes the items from that mailbox for mail that is no longer flagged, or is no longer there because it was
+//! archived or deleted. Items from mailboxes that are not given are left alone.
+
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    new_item::NewItemBuilder,
+    surrealdb_layer::{
+        surreal_item::{NotesLocation, SurrealItem},
+        surreal_tables::SurrealTables,
+        DataLayerCommands,
+    },
+};
+
+const MESSAGE_ID_LINK_PREFIX: &str = "mid:";
+
+/// Thunderbird's X-Mozilla-Status bit for a flagged, what it calls starred, message
+const MOZILLA_STATUS_FLAGGED: u32 = 0x0004;
+
+/// Thunderbird's X-Mozilla-Status bit for a message that is deleted but not yet removed from the file
+const MOZILLA_STATUS_DELETED: u32 = 0x0008;
+
+#[derive(Debug, PartialEq)]
+struct Mail {
+    message_id: String,
+    subject: String,
+    is_flagged: bool,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub(crate) struct MailImportSummary {
+    pub(crate) added: usize,
+    pub(crate) finished: usize,
+    pub(crate) unchanged: usize,
+}
+
+impl Display for MailImportSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Added {}, finished {}, and {} unchanged",
+            self.added, self.finished, self.unchanged
+        )
+    }
+}
+
+/// Each path is a Maildir folder, the one with cur and new in it, or an mbox file. With a tag, messages with that
+/// keyword count as flagged too.
+pub(crate) async fn import_mail(
+    paths: &[PathBuf],
+    tag: Option<&str>,
+    now: DateTime<Utc>,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> io::Result<MailImportSummary> {
+    let mut mail = Vec::default();
+    let mut mailboxes = Vec::default();
+    for path in paths {
+        //Canonical so the same mailbox is recognized however the path is written
+        let mailbox = std::fs::canonicalize(path)?.display().to_string();
+        let read = if path.join("cur").is_dir() || path.join("new").is_dir() {
+            read_maildir(path, tag)?
+        } else {
+            read_mbox(&std::fs::read(path)?, tag)
+        };
+        mail.extend(read.into_iter().map(|x| (mailbox.clone(), x)));
+        mailboxes.push(mailbox);
+    }
+    let mut seen = HashSet::new();
+    let flagged = mail
+        .into_iter()
+        .filter(|(_, x)| x.is_flagged && seen.insert(x.message_id.clone()))
+        .collect::<Vec<_>>();
+
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    let imported = surreal_tables
+        .surreal_items
+        .iter()
+        .filter_map(|x| get_message_id(x).map(|link| (link, x)))
+        .collect::<Vec<_>>();
+    let mut summary = MailImportSummary::default();
+    for (mailbox, mail) in flagged.iter() {
+        //Once finished it is not added again even if it is still flagged
+        if imported
+            .iter()
+            .any(|((message_id, _), _)| message_id == &mail.message_id)
+        {
+            summary.unchanged += 1;
+            continue;
+        }
+        let summary_text = if mail.subject.trim().is_empty() {
+            "(no subject)".to_string()
+        } else {
+            mail.subject.trim().to_string()
+        };
+        send_to_data_storage_layer
+            .send(DataLayerCommands::NewItem(
+                NewItemBuilder::default()
+                    .summary(summary_text)
+                    .notes_location(NotesLocation::WebLink(format!(
+                        "{}{} {}",
+                        MESSAGE_ID_LINK_PREFIX, mail.message_id, mailbox
+                    )))
+                    .created(now)
+                    .build()
+                    .unwrap(),
+            ))
+            .await
+            .unwrap();
+        summary.added += 1;
+    }
+    for ((message_id, mailbox), item) in imported.iter() {
+        //Items imported before the mailbox was kept in the link have no mailbox, so any import can finish them
+        let is_mailbox_read = mailbox.is_none_or(|mailbox| mailboxes.iter().any(|x| x == mailbox));
+        if item.finished.is_none()
+            && is_mailbox_read
+            && !flagged.iter().any(|(_, x)| &x.message_id == message_id)
+        {
+            send_to_data_storage_layer
+                .send(DataLayerCommands::FinishItem(
+                    item.id.clone().expect("In the database"),
+                ))
+                .await
+                .unwrap();
+            summary.finished += 1;
+        }
+    }
+    Ok(summary)
+}
+
+/// The message id and the mailbox it was imported from, None when the item was not imported from mail. A Message-ID
+/// cannot contain a space so the first one is where the mailbox starts.
+fn get_message_id(item: &SurrealItem) -> Option<(&str, Option<&str>)> {
+    match &item.notes_location {
+        NotesLocation::WebLink(link) => {
+            let link = link.strip_prefix(MESSAGE_ID_LINK_PREFIX)?;
+            Some(match link.split_once(' ') {
+                Some((message_id, mailbox)) => (message_id, Some(mailbox)),
+                None => (link, None),
+            })
+        }
+        NotesLocation::None | NotesLocation::OneNoteLink(..) => None,
+    }
+}
+
+/// The flags are at the end of the file name, for example ":2,FS" is flagged and seen. Mail in new has no flags yet.
+fn read_maildir(path: &Path, tag: Option<&str>) -> io::Result<Vec<Mail>> {
+    let mut mail = Vec::default();
+    for folder in ["new", "cur"] {
+        let folder = path.join(folder);
+        if !folder.is_dir() {
+            continue;
+        }
+        for file in std::fs::read_dir(folder)? {
+            let file = file?.path();
+            if !file.is_file() {
+                continue;
+            }
+            let file_name = file.file_name().unwrap_or_default().to_string_lossy();
+            //Windows does not allow : in file names so ! is used instead
+            let flags = file_name
+                .rsplit_once(":2,")
+                .or_else(|| file_name.rsplit_once("!2,"))
+                .map(|(_, flags)| flags)
+                .unwrap_or_default();
+            let is_flagged = flags.contains('F') && !flags.contains('T');
+            if let Some(message) = read_message(&std::fs::read(&file)?, is_flagged, tag) {
+                mail.push(message);
+            }
+        }
+    }
+    Ok(mail)
+}
+
+/// Each message starts with a "From " line, flags are in X-Status as written by mutt or in X-Mozilla-Status as written
+/// by Thunderbird
+fn read_mbox(bytes: &[u8], tag: Option<&str>) -> Vec<Mail> {
+    let text = String::from_utf8_lossy(bytes);
+    let mut messages: Vec<String> = Vec::default();
+    let mut previous_line_is_blank = true;
+    for line in text.lines() {
+        if line.starts_with("From ") && previous_line_is_blank {
+            messages.push(String::default());
+        } else if let Some(message) = messages.last_mut() {
+            message.push_str(line);
+            message.push('\n');
+        }
+        previous_line_is_blank = line.trim_end_matches('\r').is_empty();
+    }
+    messages
+        .iter()
+        .filter_map(|message| {
+            let headers = read_headers(message);
+            let x_status = find_header(&headers, "x-status").unwrap_or_default();
+            let mozilla_status = find_header(&headers, "x-mozilla-status")
+                .and_then(|x| u32::from_str_radix(x.trim(), 16).ok())
+                .unwrap_or_default();
+            let is_flagged = (x_status.contains('F') && !x_status.contains('D'))
+                || (mozilla_status & MOZILLA_STATUS_FLAGGED != 0
+                    && mozilla_status & MOZILLA_STATUS_DELETED == 0);
+            read_message(message.as_bytes(), is_flagged, tag)
+        })
+        .collect()
+}
+
+/// None when there is no Message-ID as then there is no way to find the message again
+fn read_message(bytes: &[u8], is_flagged: bool, tag: Option<&str>) -> Option<Mail> {
+    let text = String::from_utf8_lossy(bytes);
+    let headers = read_headers(&text);
+    let message_id = find_header(&headers, "message-id")?
+        .trim()
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+        .to_string();
+    if message_id.is_empty() {
+        return None;
+    }
+    let is_tagged = tag.is_some_and(|tag| {
+        ["keywords", "x-keywords", "x-label"].iter().any(|name| {
+            headers
+                .iter()
+                .filter(|(header, _)| header == name)
+                .flat_map(|(_, value)| value.split(|x: char| x == ',' || x.is_whitespace()))
+                .any(|keyword| keyword.eq_ignore_ascii_case(tag))
+        })
+    });
+    Some(Mail {
+        message_id,
+        subject: decode_header(find_header(&headers, "subject").unwrap_or_default()),
+        is_flagged: is_flagged || is_tagged,
+    })
+}
+
+/// The header names are lowercase and lines that continue a header are joined to it
+fn read_headers(text: &str) -> Vec<(String, String)> {
+    let mut headers: Vec<(String, String)> = Vec::default();
+    for line in text.lines().map(|x| x.trim_end_matches('\r')) {
+        if line.is_empty() {
+            break;
+        }
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_lowercase(), value.trim().to_string()));
+        }
+    }
+    headers
+}
+
+fn find_header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(header, _)| header == name)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Decodes the encoded words (RFC 2047) that are used for subjects that are not plain ASCII, for example
+/// "=?UTF-8?Q?Caf=C3=A9?="
+fn decode_header(value: &str) -> String {
+    let mut decoded = String::default();
+    let mut rest = value;
+    let mut is_after_encoded_word = false;
+    while let Some(start) = rest.find("=?") {
+        let (before, candidate) = rest.split_at(start);
+        match decode_encoded_word(candidate) {
+            Some((word, length)) => {
+                //The space between two encoded words is not part of the text
+                if !(is_after_encoded_word && before.trim().is_empty()) {
+                    decoded.push_str(before);
+                }
+                decoded.push_str(&word);
+                rest = &candidate[length..];
+                is_after_encoded_word = true;
+            }
+            None => {
+                decoded.push_str(before);
+                decoded.push_str("=?");
+                rest = &candidate[2..];
+                is_after_encoded_word = false;
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Returns the text and how many bytes the encoded word took up
+fn decode_encoded_word(text: &str) -> Option<(String, usize)> {
+    let mut parts = text.strip_prefix("=?")?.splitn(3, '?');
+    let charset = parts.next()?;
+    let encoding = parts.next()?;
+    let rest = parts.next()?;
+    let end = rest.find("?=")?;
+    let bytes = match encoding {
+        "B" | "b" => decode_base64(&rest[..end])?,
+        "Q" | "q" => decode_q(&rest[..end])?,
+        _ => return None,
+    };
+    let word = if charset.eq_ignore_ascii_case("iso-8859-1") {
+        bytes.iter().map(|x| *x as char).collect()
+    } else {
+        String::from_utf8_lossy(&bytes).into_owned()
+    };
+    Some((
+        word,
+        "=?".len() + charset.len() + encoding.len() + end + "??".len() + "?=".len(),
+    ))
+}
+
+fn decode_q(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::default();
+    let mut chars = text.bytes();
+    while let Some(c) = chars.next() {
+        match c {
+            b'_' => bytes.push(b' '),
+            b'=' => {
+                let hex = [chars.next()?, chars.next()?];
+                bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+            }
+            c => bytes.push(c),
+        }
+    }
+    Some(bytes)
+}
+
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::default();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in text.bytes().filter(|x| *x != b'=') {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use tokio::sync::mpsc;
+
+    use crate::surrealdb_layer::{
+        data_storage_start_and_run, surreal_item::NotesLocation, surreal_tables::SurrealTables,
+    };
+
+    use super::{decode_header, import_mail};
+
+    /// extra_headers end with a new line
+    fn message(message_id: &str, subject: &str, extra_headers: &str) -> String {
+        format!(
+            "From: Alex <alex@example.com>\nMessage-ID: <{}>\nSubject: {}\n{}\nThe body\n",
+            message_id, subject, extra_headers
+        )
+    }
+
+    #[test]
+    fn encoded_subjects_are_decoded() {
+        assert_eq!(
+            decode_header("=?UTF-8?Q?Caf=C3=A9_menu?= =?utf-8?B?8J+NsA==?= for Friday"),
+            "Café menu🍰 for Friday"
+        );
+        assert_eq!(decode_header("Plain =? text"), "Plain =? text");
+    }
+
+    #[tokio::test]
+    async fn flagged_mail_is_added_and_finished_once_unflagged_or_archived() {
+        let directory =
+            std::env::temp_dir().join(format!("on_purpose_mail_{}", std::process::id()));
+        let maildir = directory.join("Inbox");
+        for folder in ["cur", "new", "tmp"] {
+            std::fs::create_dir_all(maildir.join(folder)).unwrap();
+        }
+        let flagged_file = maildir.join("cur").join("1.host:2,FS");
+        std::fs::write(
+            &flagged_file,
+            message("1@example.com", "Review the contract", ""),
+        )
+        .unwrap();
+        std::fs::write(
+            maildir.join("cur").join("2.host:2,S"),
+            message("2@example.com", "Lunch?", ""),
+        )
+        .unwrap();
+        std::fs::write(
+            maildir.join("new").join("3.host"),
+            message("3@example.com", "Tagged for later", "X-Keywords: todo\n"),
+        )
+        .unwrap();
+        let mbox = directory.join("Archive.mbox");
+        std::fs::write(
+            &mbox,
+            format!(
+                "From alex@example.com Mon Jan  1 09:00:00 2024\n{}\nFrom sam@example.com Mon Jan  1 10:00:00 2024\n{}",
+                message("4@example.com", "Send the invoice", "X-Mozilla-Status: 0005\n"),
+                message("5@example.com", "Newsletter", "X-Status: A\n"),
+            ),
+        )
+        .unwrap();
+        let paths = [maildir.clone(), mbox.clone()];
+
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        let summary = import_mail(&paths, Some("todo"), Utc::now(), &sender)
+            .await
+            .unwrap();
+
+        assert_eq!(summary.to_string(), "Added 3, finished 0, and 0 unchanged");
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let mut summaries = surreal_tables
+            .surreal_items
+            .iter()
+            .map(|x| x.summary.as_str())
+            .collect::<Vec<_>>();
+        summaries.sort();
+        assert_eq!(
+            summaries,
+            [
+                "Review the contract",
+                "Send the invoice",
+                "Tagged for later"
+            ]
+        );
+        let contract = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "Review the contract")
+            .unwrap();
+        assert_eq!(
+            contract.notes_location,
+            NotesLocation::WebLink(format!(
+                "mid:1@example.com {}",
+                std::fs::canonicalize(&maildir).unwrap().display()
+            ))
+        );
+
+        //Unflag the contract and archive the invoice somewhere that is not imported
+        std::fs::rename(&flagged_file, maildir.join("cur").join("1.host:2,S")).unwrap();
+        std::fs::write(&mbox, "").unwrap();
+
+        let summary = import_mail(&paths, Some("todo"), Utc::now(), &sender)
+            .await
+            .unwrap();
+
+        assert_eq!(summary.to_string(), "Added 0, finished 2, and 1 unchanged");
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(
+            surreal_tables
+                .surreal_items
+                .iter()
+                .filter(|x| x.finished.is_none())
+                .map(|x| x.summary.as_str())
+                .collect::<Vec<_>>(),
+            ["Tagged for later"]
+        );
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn importing_one_mailbox_leaves_the_mail_from_other_mailboxes_alone() {
+        let directory =
+            std::env::temp_dir().join(format!("on_purpose_mailboxes_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let work = directory.join("Work.mbox");
+        std::fs::write(
+            &work,
+            format!(
+                "From alex@example.com Mon Jan  1 09:00:00 2024\n{}",
+                message("1@example.com", "Review the contract", "X-Status: F\n"),
+            ),
+        )
+        .unwrap();
+        let home = directory.join("Home.mbox");
+        std::fs::write(
+            &home,
+            format!(
+                "From sam@example.com Mon Jan  1 10:00:00 2024\n{}",
+                message("2@example.com", "Book the dentist", "X-Status: F\n"),
+            ),
+        )
+        .unwrap();
+
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        let summary = import_mail(std::slice::from_ref(&work), None, Utc::now(), &sender)
+            .await
+            .unwrap();
+        assert_eq!(summary.to_string(), "Added 1, finished 0, and 0 unchanged");
+
+        let summary = import_mail(std::slice::from_ref(&home), None, Utc::now(), &sender)
+            .await
+            .unwrap();
+        assert_eq!(summary.to_string(), "Added 1, finished 0, and 0 unchanged");
+
+        //Unflag the mail at work, importing home again must not notice
+        std::fs::write(
+            &work,
+            format!(
+                "From alex@example.com Mon Jan  1 09:00:00 2024\n{}",
+                message("1@example.com", "Review the contract", "X-Status: A\n"),
+            ),
+        )
+        .unwrap();
+
+        let summary = import_mail(std::slice::from_ref(&home), None, Utc::now(), &sender)
+            .await
+            .unwrap();
+        assert_eq!(summary.to_string(), "Added 0, finished 0, and 1 unchanged");
+
+        let summary = import_mail(std::slice::from_ref(&work), None, Utc::now(), &sender)
+            .await
+            .unwrap();
+        assert_eq!(summary.to_string(), "Added 0, finished 1, and 0 unchanged");
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(
+            surreal_tables
+                .surreal_items
+                .iter()
+                .filter(|x| x.finished.is_none())
+                .map(|x| x.summary.as_str())
+                .collect::<Vec<_>>(),
+            ["Book the dentist"]
+        );
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,384 @@
+//! Tasks from another task manager, as read by todo_txt or taskwarrior, added as items. Projects become goals and
+//! contexts become top level motivations, which stand in for life areas until items can be linked to life areas. A
+//! task is skipped when an item with the same summary is already there so importing the same list again adds nothing.
+
+use std::{collections::HashMap, fmt::Display};
+
+use chrono::{DateTime, Duration, Utc};
+use surrealdb::opt::RecordId;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    new_item::NewItemBuilder,
+    surrealdb_layer::{
+        surreal_item::{
+            DueDate, DueDateStrictness, EnterListReason, ItemType, Staging, SurrealItem,
+        },
+        surreal_tables::SurrealTables,
+        DataLayerCommands,
+    },
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Priority {
+    High,
+    Medium,
+    Low,
+    Lowest,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub(crate) struct ImportedTask {
+    pub(crate) summary: String,
+    /// Each becomes a goal that this is a smaller item of
+    pub(crate) projects: Vec<String>,
+    /// Each becomes a top level motivation that this is a smaller item of
+    pub(crate) contexts: Vec<String>,
+    pub(crate) priority: Option<Priority>,
+    pub(crate) due: Option<DateTime<Utc>>,
+    pub(crate) finished: Option<DateTime<Utc>>,
+    pub(crate) created: Option<DateTime<Utc>>,
+}
+
+#[derive(Default, Debug)]
+pub(crate) struct TaskListImportSummary {
+    pub(crate) dry_run: bool,
+    /// Each task that was, or would be, added with the goals and motivations it is under
+    pub(crate) added: Vec<String>,
+    pub(crate) goals_added: usize,
+    pub(crate) motivations_added: usize,
+    /// Already there by summary
+    pub(crate) skipped: usize,
+}
+
+impl Display for TaskListImportSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.dry_run {
+            for added in self.added.iter() {
+                writeln!(f, "{}", added)?;
+            }
+        }
+        write!(
+            f,
+            "{} {} items, {} goals, and {} motivations, skipped {} already there",
+            if self.dry_run { "Would add" } else { "Added" },
+            self.added.len(),
+            self.goals_added,
+            self.motivations_added,
+            self.skipped
+        )
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum ParentKind {
+    Goal,
+    Motivation,
+}
+
+impl ParentKind {
+    fn item_type(&self) -> ItemType {
+        match self {
+            ParentKind::Goal => ItemType::Goal(Default::default()),
+            ParentKind::Motivation => ItemType::Motivation,
+        }
+    }
+
+    fn is_kind_of(&self, item: &SurrealItem) -> bool {
+        match self {
+            ParentKind::Goal => matches!(item.item_type, ItemType::Goal(..)),
+            ParentKind::Motivation => item.item_type == ItemType::Motivation,
+        }
+    }
+}
+
+/// With dry_run nothing is changed, the summary says what would be added
+pub(crate) async fn import_tasks(
+    tasks: Vec<ImportedTask>,
+    dry_run: bool,
+    now: DateTime<Utc>,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> TaskListImportSummary {
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    let mut summaries = surreal_tables
+        .surreal_items
+        .iter()
+        .map(|x| normalize(&x.summary))
+        .collect::<Vec<_>>();
+    //By kind and normalized summary, None is a goal or motivation that a dry run would add
+    let mut known_parents: HashMap<(ParentKind, String), Option<RecordId>> = HashMap::default();
+    for item in surreal_tables
+        .surreal_items
+        .iter()
+        .filter(|x| x.finished.is_none())
+    {
+        for kind in [ParentKind::Goal, ParentKind::Motivation] {
+            if kind.is_kind_of(item) {
+                known_parents
+                    .entry((kind, normalize(&item.summary)))
+                    .or_insert_with(|| item.id.clone());
+            }
+        }
+    }
+    let mut summary = TaskListImportSummary {
+        dry_run,
+        ..Default::default()
+    };
+    for task in tasks {
+        let normalized = normalize(&task.summary);
+        if normalized.is_empty() || summaries.contains(&normalized) {
+            summary.skipped += 1;
+            continue;
+        }
+        summaries.push(normalized);
+
+        let parents = task
+            .projects
+            .iter()
+            .map(|x| (ParentKind::Goal, x))
+            .chain(task.contexts.iter().map(|x| (ParentKind::Motivation, x)))
+            .collect::<Vec<_>>();
+        let mut parent_record_ids = Vec::default();
+        for (kind, name) in parents {
+            let key = (kind, normalize(name));
+            let record_id = match known_parents.get(&key) {
+                Some(record_id) => record_id.clone(),
+                None => {
+                    match kind {
+                        ParentKind::Goal => summary.goals_added += 1,
+                        ParentKind::Motivation => summary.motivations_added += 1,
+                    }
+                    //So a later task with the same summary is skipped rather than added again
+                    summaries.push(key.1.clone());
+                    let record_id = if dry_run {
+                        None
+                    } else {
+                        Some(add_parent(kind, name, now, send_to_data_storage_layer).await)
+                    };
+                    known_parents.insert(key, record_id.clone());
+                    record_id
+                }
+            };
+            parent_record_ids.extend(record_id);
+        }
+        summary.added.push(describe(&task));
+
+        if !dry_run {
+            add_task(task, &parent_record_ids, now, send_to_data_storage_layer).await;
+        }
+    }
+    summary
+}
+
+async fn add_task(
+    task: ImportedTask,
+    parents: &[RecordId],
+    now: DateTime<Utc>,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) {
+    let new_item = NewItemBuilder::default()
+        .summary(task.summary)
+        .item_type(ItemType::Action)
+        .staging(staging_for(task.priority, now))
+        .due_date(task.due.map(|due| DueDate {
+            due: due.into(),
+            strictness: DueDateStrictness::Soft,
+        }))
+        .finished(task.finished.map(|x| x.into()))
+        .created(task.created.unwrap_or(now))
+        .build()
+        .unwrap();
+    let child = DataLayerCommands::new_item_and_get_id(send_to_data_storage_layer, new_item)
+        .await
+        .unwrap();
+    for parent in parents {
+        DataLayerCommands::parent_item_with_existing_item(
+            send_to_data_storage_layer,
+            child.clone(),
+            parent.clone(),
+            None,
+        )
+        .await
+        .unwrap()
+        .expect("A new item has no smaller items so it cannot create a cycle");
+    }
+}
+
+async fn add_parent(
+    kind: ParentKind,
+    name: &str,
+    now: DateTime<Utc>,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> RecordId {
+    DataLayerCommands::new_item_and_get_id(
+        send_to_data_storage_layer,
+        NewItemBuilder::default()
+            .summary(name)
+            .item_type(kind.item_type())
+            .created(now)
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap()
+}
+
+/// The more important the task the sooner it is on the bullet list
+fn staging_for(priority: Option<Priority>, now: DateTime<Utc>) -> Staging {
+    match priority {
+        Some(Priority::High) => Staging::MentallyResident {
+            enter_list: EnterListReason::DateTime(now.into()),
+            lap: Duration::days(1).to_std().unwrap().into(),
+        },
+        Some(Priority::Medium) => Staging::OnDeck {
+            enter_list: EnterListReason::DateTime(now.into()),
+            lap: Duration::weeks(1).to_std().unwrap().into(),
+        },
+        Some(Priority::Low) => Staging::Planned,
+        Some(Priority::Lowest) => Staging::ThinkingAbout,
+        None => Staging::NotSet,
+    }
+}
+
+/// How the task will look in the outline, for example "🪜 Fix the gate ⬅ 🪧 Garden ⬅ 🎯 home"
+fn describe(task: &ImportedTask) -> String {
+    let mut description = format!("🪜 {}", task.summary);
+    for project in task.projects.iter() {
+        description.push_str(&format!(" ⬅ 🪧 {}", project));
+    }
+    for context in task.contexts.iter() {
+        description.push_str(&format!(" ⬅ 🎯 {}", context));
+    }
+    description
+}
+
+fn normalize(summary: &str) -> String {
+    summary.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use tokio::sync::mpsc;
+
+    use crate::{
+        new_item::NewItem,
+        surrealdb_layer::{
+            data_storage_start_and_run, surreal_item::ItemType, surreal_tables::SurrealTables,
+            DataLayerCommands,
+        },
+    };
+
+    use super::{import_tasks, ImportedTask, Priority};
+
+    fn tasks() -> Vec<ImportedTask> {
+        vec![
+            ImportedTask {
+                summary: "Fix the gate".into(),
+                projects: vec!["Garden".into()],
+                contexts: vec!["home".into()],
+                priority: Some(Priority::High),
+                ..Default::default()
+            },
+            ImportedTask {
+                summary: "Plant tulips".into(),
+                projects: vec!["Garden".into()],
+                ..Default::default()
+            },
+            ImportedTask {
+                summary: "call the bank".into(),
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[tokio::test]
+    async fn a_dry_run_changes_nothing_and_tasks_already_there_are_skipped() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Call the bank".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+
+        let summary = import_tasks(tasks(), true, Utc::now(), &sender).await;
+
+        assert_eq!(
+            summary.to_string(),
+            "🪜 Fix the gate ⬅ 🪧 Garden ⬅ 🎯 home\n\
+            🪜 Plant tulips ⬅ 🪧 Garden\n\
+            Would add 2 items, 1 goals, and 1 motivations, skipped 1 already there"
+        );
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(surreal_tables.surreal_items.len(), 1);
+
+        import_tasks(tasks(), false, Utc::now(), &sender).await;
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(surreal_tables.surreal_items.len(), 5);
+        let items = surreal_tables.make_items();
+        let garden = items.iter().find(|x| x.get_summary() == "Garden").unwrap();
+        assert!(garden.is_type_goal());
+        let home = items.iter().find(|x| x.get_summary() == "home").unwrap();
+        assert_eq!(home.get_item_type(), &ItemType::Motivation);
+        let fix_the_gate = items
+            .iter()
+            .find(|x| x.get_summary() == "Fix the gate")
+            .unwrap();
+        assert!(garden.is_this_a_smaller_item(fix_the_gate));
+        assert!(home.is_this_a_smaller_item(fix_the_gate));
+        assert!(fix_the_gate.is_mentally_resident());
+
+        let summary = import_tasks(tasks(), false, Utc::now(), &sender).await;
+        assert_eq!(summary.added.len(), 0);
+        assert_eq!(summary.skipped, 3);
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn a_task_named_like_a_project_added_before_it_is_skipped() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+        let tasks = vec![
+            ImportedTask {
+                summary: "Fix the gate".into(),
+                projects: vec!["Garden".into(), "Fence".into()],
+                ..Default::default()
+            },
+            ImportedTask {
+                summary: "garden".into(),
+                ..Default::default()
+            },
+        ];
+
+        let summary = import_tasks(tasks, false, Utc::now(), &sender).await;
+
+        assert_eq!(summary.added.len(), 1);
+        assert_eq!(summary.goals_added, 2);
+        assert_eq!(summary.skipped, 1);
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(surreal_tables.surreal_items.len(), 3);
+        let items = surreal_tables.make_items();
+        let fix_the_gate = items
+            .iter()
+            .find(|x| x.get_summary() == "Fix the gate")
+            .unwrap();
+        for project in ["Garden", "Fence"] {
+            let project = items.iter().find(|x| x.get_summary() == project).unwrap();
+            assert!(project.is_type_goal());
+            assert!(project.is_this_a_smaller_item(fix_the_gate));
+        }
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+}
@@ -0,0 +1,127 @@
+//! The JSON from "task export", either an array of tasks or, from older versions, one task per line. Tags become
+//! contexts as Taskwarrior does not have contexts on each task.
+
+use std::{io, path::Path};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Deserialize;
+use tokio::sync::mpsc::Sender;
+
+use crate::surrealdb_layer::DataLayerCommands;
+
+use super::task_list::{import_tasks, ImportedTask, Priority, TaskListImportSummary};
+
+#[derive(Deserialize)]
+struct TaskwarriorTask {
+    description: String,
+    status: String,
+    project: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    priority: Option<String>,
+    due: Option<String>,
+    end: Option<String>,
+    entry: Option<String>,
+}
+
+pub(crate) async fn import_taskwarrior(
+    path: &Path,
+    dry_run: bool,
+    now: DateTime<Utc>,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> io::Result<TaskListImportSummary> {
+    let text = std::fs::read_to_string(path)?;
+    let tasks = read_taskwarrior(&text)?;
+    Ok(import_tasks(tasks, dry_run, now, send_to_data_storage_layer).await)
+}
+
+/// Deleted tasks, and the templates that recurring tasks are made from, are left out
+fn read_taskwarrior(text: &str) -> serde_json::Result<Vec<ImportedTask>> {
+    let tasks: Vec<TaskwarriorTask> = if text.trim_start().starts_with('[') {
+        serde_json::from_str(text)?
+    } else {
+        text.lines()
+            .filter(|x| !x.trim().is_empty())
+            .map(|x| serde_json::from_str(x.trim_end_matches(',')))
+            .collect::<serde_json::Result<_>>()?
+    };
+    Ok(tasks
+        .into_iter()
+        .filter(|x| x.status != "deleted" && x.status != "recurring")
+        .map(|x| ImportedTask {
+            summary: x.description,
+            projects: x.project.into_iter().collect(),
+            contexts: x.tags,
+            priority: match x.priority.as_deref() {
+                Some("H") => Some(Priority::High),
+                Some("M") => Some(Priority::Medium),
+                Some("L") => Some(Priority::Low),
+                _ => None,
+            },
+            due: x.due.as_deref().and_then(read_date_time),
+            finished: if x.status == "completed" {
+                Some(
+                    x.end
+                        .as_deref()
+                        .and_then(read_date_time)
+                        .unwrap_or_else(Utc::now),
+                )
+            } else {
+                None
+            },
+            created: x.entry.as_deref().and_then(read_date_time),
+        })
+        .collect())
+}
+
+/// Dates are like 20240131T090000Z
+fn read_date_time(text: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(text, "%Y%m%dT%H%M%SZ")
+        .ok()
+        .map(|x| x.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_date_time, read_taskwarrior, ImportedTask, Priority};
+
+    #[test]
+    fn tags_are_contexts_and_deleted_tasks_are_left_out() {
+        let text = r#"[
+            {"id":1,"description":"Fix the gate","entry":"20240102T090000Z","status":"pending","project":"Garden","tags":["home"],"priority":"H","due":"20240201T000000Z","uuid":"a"},
+            {"id":0,"description":"Plant tulips","entry":"20240101T090000Z","end":"20240105T120000Z","status":"completed","project":"Garden","uuid":"b"},
+            {"id":0,"description":"Never mind","entry":"20240101T090000Z","status":"deleted","uuid":"c"}
+        ]"#;
+
+        let tasks = read_taskwarrior(text).unwrap();
+
+        assert_eq!(
+            tasks,
+            [
+                ImportedTask {
+                    summary: "Fix the gate".into(),
+                    projects: vec!["Garden".into()],
+                    contexts: vec!["home".into()],
+                    priority: Some(Priority::High),
+                    due: read_date_time("20240201T000000Z"),
+                    finished: None,
+                    created: read_date_time("20240102T090000Z"),
+                },
+                ImportedTask {
+                    summary: "Plant tulips".into(),
+                    projects: vec!["Garden".into()],
+                    finished: read_date_time("20240105T120000Z"),
+                    created: read_date_time("20240101T090000Z"),
+                    ..Default::default()
+                },
+            ]
+        );
+        //Older versions have one task per line
+        let one_per_line = text
+            .trim()
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .replace("},", "}");
+        assert_eq!(read_taskwarrior(&one_per_line).unwrap(), tasks);
+    }
+}
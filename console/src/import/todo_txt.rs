@@ -0,0 +1,131 @@
+//! The todo.txt format, one task per line, see https://github.com/todotxt/todo.txt
+
+use std::{io, path::Path};
+
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
+use tokio::sync::mpsc::Sender;
+
+use crate::surrealdb_layer::DataLayerCommands;
+
+use super::task_list::{import_tasks, ImportedTask, Priority, TaskListImportSummary};
+
+pub(crate) async fn import_todo_txt(
+    path: &Path,
+    dry_run: bool,
+    now: DateTime<Utc>,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> io::Result<TaskListImportSummary> {
+    let text = std::fs::read_to_string(path)?;
+    Ok(import_tasks(
+        read_todo_txt(&text),
+        dry_run,
+        now,
+        send_to_data_storage_layer,
+    )
+    .await)
+}
+
+fn read_todo_txt(text: &str) -> Vec<ImportedTask> {
+    text.lines().filter_map(read_line).collect()
+}
+
+/// None for a blank line
+fn read_line(line: &str) -> Option<ImportedTask> {
+    let mut task = ImportedTask::default();
+    let mut words = line.split_whitespace().peekable();
+    words.peek()?;
+
+    if words.next_if_eq(&"x").is_some() {
+        //A completed task has the completion date first and then the creation date
+        task.finished = Some(
+            words
+                .next_if(|x| read_date(x).is_some())
+                .and_then(read_date)
+                .unwrap_or_else(Utc::now),
+        );
+    } else if let Some(priority) = words.next_if(|x| read_priority(x).is_some()) {
+        task.priority = read_priority(priority);
+    }
+    task.created = words
+        .next_if(|x| read_date(x).is_some())
+        .and_then(read_date);
+
+    let mut summary = Vec::default();
+    for word in words {
+        match (word.strip_prefix('+'), word.strip_prefix('@')) {
+            (Some(project), _) if !project.is_empty() => task.projects.push(project.to_string()),
+            (_, Some(context)) if !context.is_empty() => task.contexts.push(context.to_string()),
+            _ => match word.strip_prefix("due:").and_then(read_date) {
+                Some(due) => task.due = Some(due),
+                None => summary.push(word),
+            },
+        }
+    }
+    task.summary = summary.join(" ");
+    Some(task)
+}
+
+/// "(A)" is the most important
+fn read_priority(word: &str) -> Option<Priority> {
+    let letter = word.strip_prefix('(')?.strip_suffix(')')?;
+    match letter {
+        "A" => Some(Priority::High),
+        "B" => Some(Priority::Medium),
+        "C" => Some(Priority::Low),
+        _ if letter.len() == 1 && letter.chars().all(|x| x.is_ascii_uppercase()) => {
+            Some(Priority::Lowest)
+        }
+        _ => None,
+    }
+}
+
+/// A date like 2024-01-31, as the start of that day here
+fn read_date(word: &str) -> Option<DateTime<Utc>> {
+    let date = NaiveDate::parse_from_str(word, "%Y-%m-%d").ok()?;
+    Local
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .earliest()
+        .map(|x| x.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_date, read_todo_txt, ImportedTask, Priority};
+
+    #[test]
+    fn projects_contexts_priority_and_dates_are_read_out_of_each_line() {
+        let text = "(A) 2024-01-02 Fix the gate +Garden @home due:2024-02-01 t:2024-01-20\n\
+            \n\
+            x 2024-01-05 2024-01-01 Plant tulips +Garden\n\
+            (D) Someday learn the banjo\n";
+
+        let tasks = read_todo_txt(text);
+
+        assert_eq!(
+            tasks,
+            [
+                ImportedTask {
+                    summary: "Fix the gate t:2024-01-20".into(),
+                    projects: vec!["Garden".into()],
+                    contexts: vec!["home".into()],
+                    priority: Some(Priority::High),
+                    due: read_date("2024-02-01"),
+                    finished: None,
+                    created: read_date("2024-01-02"),
+                },
+                ImportedTask {
+                    summary: "Plant tulips".into(),
+                    projects: vec!["Garden".into()],
+                    finished: read_date("2024-01-05"),
+                    created: read_date("2024-01-01"),
+                    ..Default::default()
+                },
+                ImportedTask {
+                    summary: "Someday learn the banjo".into(),
+                    priority: Some(Priority::Lowest),
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+}
@@ -1,55 +1,364 @@
-pub(crate) mod base_data;
-pub(crate) mod calculated_data;
 mod change_routine;
+mod config;
+#[cfg(unix)]
+mod daemon;
 pub(crate) mod display;
+mod doctor;
+mod export;
+mod git;
+mod import;
 pub(crate) mod menu;
-pub(crate) mod new_item;
-mod node;
-mod surrealdb_layer;
-pub(crate) mod systems;
+mod rest_api;
+mod tui;
 
-use tokio::sync::mpsc;
+//The engine lives in on_purpose_core so other tools can use it, these keep the crate:: paths the menus use working
+pub(crate) use on_purpose_core::{
+    base_data, calculated_data, data_storage, new_item, node, surrealdb_layer, systems,
+};
+
+use std::path::PathBuf;
+
+use chrono::Utc;
+use clap::{Parser, Subcommand};
+use tokio::{
+    sync::mpsc::{self, Receiver},
+    task::JoinHandle,
+};
 
 use crate::{
-    menu::bullet_list_menu::present_normal_bullet_list_menu,
-    surrealdb_layer::data_storage_start_and_run,
+    data_storage::{data_storage_run, file_storage::FileStorage},
+    doctor::run_doctor,
+    export::{
+        calendar::export_calendar,
+        markdown::{export_markdown, MarkdownOptions},
+    },
+    git::sync::sync_git,
+    import::{
+        calendar::import_calendar, mail::import_mail, taskwarrior::import_taskwarrior,
+        todo_txt::import_todo_txt,
+    },
+    menu::{
+        expectations::expectations_report::{
+            make_expectations_report_markdown, save_expectations_report,
+        },
+        navigation::present_menus,
+    },
+    new_item::NewItem,
+    rest_api::run_rest_api,
+    surrealdb_layer::{data_storage_start_and_run, DataLayerCommands},
+    tui::run_tui,
 };
 
+const DATABASE_ENDPOINT: &str = "file://c:/.on_purpose.db"; //TODO: Get a default file location that works for both Linux and Windows
+
+#[derive(Parser)]
+#[command(version, about = "Time Management Rethought")]
+struct Arguments {
+    /// When no command is given the normal menus are shown
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Keep the data as JSON files in this directory, for example a git repository, rather than in the database
+    #[arg(long, global = true, value_name = "DIRECTORY")]
+    files: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check the database for dangling references, duplicates, and cycles and offer to repair them
+    Doctor,
+    /// Add a new item without going through the menus
+    Capture { summary: String },
+    /// Serve the data as JSON on localhost, see /openapi.json for the endpoints
+    Serve {
+        #[arg(long, default_value_t = 7878)]
+        port: u16,
+    },
+    /// Write what each person or group expects as Markdown, to the terminal when no file is given
+    Expectations {
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Write the data out for other tools to read
+    Export {
+        #[command(subcommand)]
+        to: ExportTo,
+    },
+    /// Add or update items from files that other tools export
+    Import {
+        #[command(subcommand)]
+        from: ImportFrom,
+    },
+    /// Attach the commits that mention an item to it and offer to finish the items whose branch was merged into main
+    GitSync,
+    /// Show the bullet list full-screen with single key shortcuts instead of the menus
+    Tui,
+    /// Own the database and serve other on_purpose processes so more than one can be open at the same time
+    #[cfg(unix)]
+    Daemon,
+}
+
+#[derive(Subcommand)]
+enum ExportTo {
+    /// When items are expected to come up again, as an iCalendar (.ics) file for a calendar app
+    Calendar { path: PathBuf },
+    /// The motivations and goals with the smaller items under them as a nested Markdown list, to the terminal when no
+    /// file is given
+    Markdown {
+        /// Only this item and what is under it, by summary or record id
+        #[arg(long)]
+        root: Option<String>,
+        /// How many levels to include, 1 is just the top level
+        #[arg(long)]
+        depth: Option<usize>,
+        #[arg(long)]
+        include_finished: bool,
+        #[arg(long)]
+        include_processed_text: bool,
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ImportFrom {
+    /// Meetings from an iCalendar (.ics) file or a directory of them, importing again updates them by their UID
+    Calendar { path: PathBuf },
+    /// Flagged mail from Maildir folders or mbox files, mail that is no longer flagged or that was archived finishes
+    /// its item so give every mailbox that flagged mail can be in
+    Mail {
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// Mail with this keyword counts as flagged too
+        #[arg(long)]
+        tag: Option<String>,
+    },
+    /// Tasks from a todo.txt file, projects become goals and contexts become top level motivations
+    TodoTxt {
+        path: PathBuf,
+        /// Show what would be added without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Tasks from the JSON of "task export", projects become goals and tags become top level motivations
+    Taskwarrior {
+        path: PathBuf,
+        /// Show what would be added without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// Opens the files in the directory when there is one, otherwise the database
+fn start_data_storage(
+    have_data_storage_layer_use_to_receive_rx: Receiver<DataLayerCommands>,
+    files: Option<PathBuf>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        match files {
+            Some(directory) => {
+                let file_storage = FileStorage::open(&directory).unwrap_or_else(|err| {
+                    panic!(
+                        "Unable to read the files in {}: {}",
+                        directory.display(),
+                        err
+                    )
+                });
+                data_storage_run(have_data_storage_layer_use_to_receive_rx, file_storage).await
+            }
+            None => {
+                data_storage_start_and_run(
+                    have_data_storage_layer_use_to_receive_rx,
+                    DATABASE_ENDPOINT,
+                )
+                .await
+            }
+        }
+    })
+}
+
+/// When a daemon is running the commands go to it, otherwise this process opens the data itself
+async fn start_data_layer(
+    have_data_storage_layer_use_to_receive_rx: Receiver<DataLayerCommands>,
+    files: Option<PathBuf>,
+) -> JoinHandle<()> {
+    #[cfg(unix)]
+    {
+        let socket_path = daemon::default_socket_path();
+        if let Ok(stream) = tokio::net::UnixStream::connect(&socket_path).await {
+            eprintln!("Connected to the daemon at {}", socket_path.display());
+            if files.is_some() {
+                //Two processes writing the same files would overwrite each other's changes
+                eprintln!("--files is ignored, the daemon decides where the data is kept");
+            }
+            return tokio::spawn(async move {
+                daemon::run_daemon_client(
+                    have_data_storage_layer_use_to_receive_rx,
+                    stream,
+                    socket_path,
+                )
+                .await
+            });
+        }
+    }
+
+    start_data_storage(have_data_storage_layer_use_to_receive_rx, files)
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let arguments = Arguments::parse();
+
     const CARGO_PKG_VERSION: Option<&'static str> = option_env!("CARGO_PKG_VERSION");
 
-    println!("Welcome to On-Purpose: Time Management Rethought");
-    println!("Version {}", CARGO_PKG_VERSION.unwrap_or("UNKNOWN"));
+    //stderr so the output of commands like export markdown can be piped or redirected as is
+    eprintln!("Welcome to On-Purpose: Time Management Rethought");
+    eprintln!("Version {}", CARGO_PKG_VERSION.unwrap_or("UNKNOWN"));
+
+    #[cfg(unix)]
+    if let Some(Command::Daemon) = arguments.command {
+        let socket_path = daemon::default_socket_path();
+        eprintln!(
+            "Listening at {}, press Ctrl+C to stop",
+            socket_path.display()
+        );
+        let files = arguments.files;
+        daemon::run_daemon(
+            &socket_path,
+            |receiver| start_data_storage(receiver, files),
+            async {
+                tokio::signal::ctrl_c().await.unwrap();
+            },
+        )
+        .await?;
+        return Ok(());
+    }
 
     let commands_in_flight_limit = 20;
     let (send_to_data_storage_layer_tx, have_data_storage_layer_use_to_receive_rx) =
         mpsc::channel(commands_in_flight_limit);
 
-    let data_storage_join_handle = tokio::spawn(async move {
-        data_storage_start_and_run(
-            have_data_storage_layer_use_to_receive_rx,
-            "file://c:/.on_purpose.db", //TODO: Get a default file location that works for both Linux and Windows
+    let data_storage_join_handle =
+        start_data_layer(have_data_storage_layer_use_to_receive_rx, arguments.files).await;
+
+    match arguments.command {
+        None => present_menus(&send_to_data_storage_layer_tx, &data_storage_join_handle).await,
+        Some(Command::Doctor) => {
+            //Err only means the user interrupted, either way it is time to exit
+            let _ = run_doctor(&send_to_data_storage_layer_tx).await;
+        }
+        Some(Command::Capture { summary }) => {
+            send_to_data_storage_layer_tx
+                .send(DataLayerCommands::NewItem(NewItem::new(
+                    summary,
+                    Utc::now(),
+                )))
+                .await
+                .unwrap();
+        }
+        Some(Command::Serve { port }) => {
+            if let Err(err) = run_rest_api(port, send_to_data_storage_layer_tx.clone()).await {
+                eprintln!("Unable to serve: {}", err);
+            }
+        }
+        Some(Command::Expectations { output }) => {
+            let markdown = make_expectations_report_markdown(&send_to_data_storage_layer_tx).await;
+            match output {
+                Some(output) => save_expectations_report(&output, &markdown),
+                None => print!("{}", markdown),
+            }
+        }
+        Some(Command::Export {
+            to: ExportTo::Calendar { path },
+        }) => match export_calendar(&path, Utc::now(), &send_to_data_storage_layer_tx).await {
+            Ok(exported) => println!("Exported {} entries to {}", exported, path.display()),
+            Err(err) => eprintln!("Unable to export to {}: {}", path.display(), err),
+        },
+        Some(Command::Export {
+            to:
+                ExportTo::Markdown {
+                    root,
+                    depth,
+                    include_finished,
+                    include_processed_text,
+                    output,
+                },
+        }) => {
+            let options = MarkdownOptions {
+                root,
+                depth,
+                include_finished,
+                include_processed_text,
+            };
+            match export_markdown(&options, &send_to_data_storage_layer_tx).await {
+                Some(markdown) => match output {
+                    Some(output) => match std::fs::write(&output, markdown) {
+                        Ok(()) => println!("Exported the outline to {}", output.display()),
+                        Err(err) => eprintln!("Unable to export to {}: {}", output.display(), err),
+                    },
+                    None => print!("{}", markdown),
+                },
+                None => eprintln!(
+                    "No item matches {}",
+                    options.root.as_deref().unwrap_or_default()
+                ),
+            }
+        }
+        Some(Command::Import {
+            from: ImportFrom::Calendar { path },
+        }) => match import_calendar(&path, Utc::now(), &send_to_data_storage_layer_tx).await {
+            Ok(summary) => println!("{}", summary),
+            Err(err) => eprintln!("Unable to import {}: {}", path.display(), err),
+        },
+        Some(Command::Import {
+            from: ImportFrom::Mail { paths, tag },
+        }) => match import_mail(
+            &paths,
+            tag.as_deref(),
+            Utc::now(),
+            &send_to_data_storage_layer_tx,
         )
         .await
-    });
-
-    loop {
-        match present_normal_bullet_list_menu(&send_to_data_storage_layer_tx).await {
-            Result::Ok(..) => (),
-            Result::Err(..) => break,
-        };
-
-        if data_storage_join_handle.is_finished() {
-            println!("Data Storage Layer closed early, unexpectedly");
+        {
+            Ok(summary) => println!("{}", summary),
+            Err(err) => eprintln!("Unable to import mail: {}", err),
+        },
+        Some(Command::Import {
+            from: ImportFrom::TodoTxt { path, dry_run },
+        }) => match import_todo_txt(&path, dry_run, Utc::now(), &send_to_data_storage_layer_tx)
+            .await
+        {
+            Ok(summary) => println!("{}", summary),
+            Err(err) => eprintln!("Unable to import {}: {}", path.display(), err),
+        },
+        Some(Command::Import {
+            from: ImportFrom::Taskwarrior { path, dry_run },
+        }) => match import_taskwarrior(&path, dry_run, Utc::now(), &send_to_data_storage_layer_tx)
+            .await
+        {
+            Ok(summary) => println!("{}", summary),
+            Err(err) => eprintln!("Unable to import {}: {}", path.display(), err),
+        },
+        Some(Command::GitSync) => {
+            //Err only means the user interrupted, either way it is time to exit
+            if let Ok(summary) = sync_git(&send_to_data_storage_layer_tx).await {
+                println!("{}", summary);
+            }
+        }
+        Some(Command::Tui) => {
+            if let Err(err) = run_tui(&send_to_data_storage_layer_tx).await {
+                eprintln!("Unable to use the terminal: {}", err);
+            }
         }
+        #[cfg(unix)]
+        Some(Command::Daemon) => unreachable!("Handled before the data layer is started"),
     }
 
     drop(send_to_data_storage_layer_tx);
 
-    print!("Waiting for data storage layer to exit...");
+    eprint!("Waiting for data storage layer to exit...");
     data_storage_join_handle.await.unwrap();
-    println!("Done");
+    eprintln!("Done");
 
     Ok(())
 }
@@ -1,13 +1,24 @@
 use std::fmt::Display;
 
+use crate::{
+    base_data::item::Item,
+    surrealdb_layer::surreal_tables::{SurrealLink, SurrealTables},
+};
+
 pub(crate) mod bullet_list_menu;
 pub(crate) mod expectations;
+pub(crate) mod navigation;
+pub(crate) mod people;
+pub(crate) mod prompt;
+pub(crate) mod recently_finished;
 pub(crate) mod select_higher_priority_than_this;
 pub(crate) mod select_person_or_group;
+pub(crate) mod set_due_date;
 pub(crate) mod staging_query;
 pub(crate) mod top_menu;
 pub(crate) mod unable_to_work_on_item_right_now;
 pub(crate) mod update_item_summary;
+pub(crate) mod waiting_for;
 pub(crate) enum YesOrNo {
     Yes,
     No,
@@ -23,7 +34,56 @@ impl Display for YesOrNo {
 }
 
 impl YesOrNo {
-    fn make_list() -> Vec<Self> {
+    pub(crate) fn make_list() -> Vec<Self> {
         vec![YesOrNo::Yes, YesOrNo::No]
     }
 }
+
+/// Candidates that would create a cycle are left out of selection lists, this remembers them so one line can explain
+/// why once the list is made
+pub(crate) struct LeaveOutCycles<'s> {
+    surreal_tables: &'s SurrealTables,
+    //Made once for the whole list rather than for each candidate
+    links: Vec<SurrealLink<'s>>,
+    left_out: Vec<String>,
+}
+
+impl<'s> LeaveOutCycles<'s> {
+    pub(crate) fn new(surreal_tables: &'s SurrealTables) -> Self {
+        Self {
+            surreal_tables,
+            links: surreal_tables.make_links(),
+            left_out: Vec::default(),
+        }
+    }
+
+    /// False when linking smaller to larger would create a cycle
+    pub(crate) fn keep(&mut self, smaller: &Item<'_>, larger: &Item<'_>) -> bool {
+        match self.surreal_tables.describe_cycle_if_linked_in(
+            &self.links,
+            smaller.get_surreal_record_id(),
+            larger.get_surreal_record_id(),
+        ) {
+            Some(cycle) => {
+                self.left_out.push(cycle);
+                false
+            }
+            None => true,
+        }
+    }
+
+    pub(crate) fn print_summary(&self) {
+        match self.left_out.as_slice() {
+            [] => {}
+            [cycle] => println!(
+                "Left out of the list because it would create a cycle: {}",
+                cycle
+            ),
+            [cycle, ..] => println!(
+                "Left out {} items because they would create a cycle, for example: {}",
+                self.left_out.len(),
+                cycle
+            ),
+        }
+    }
+}
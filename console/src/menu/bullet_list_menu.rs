@@ -2,19 +2,22 @@ pub(crate) mod bullet_list_single_item;
 
 use std::{fmt::Display, iter::once};
 
-use async_recursion::async_recursion;
 use chrono::{DateTime, Local, Utc};
-use inquire::{InquireError, Select};
+use inquire::InquireError;
 use itertools::chain;
-use tokio::sync::mpsc::Sender;
+use tokio::sync::{broadcast, mpsc::Sender};
 
 use crate::{
     base_data::BaseData,
     calculated_data::CalculatedData,
+    config::Config,
     display::display_item_status::DisplayItemStatus,
-    menu::top_menu::present_top_menu,
+    menu::navigation::Navigation,
+    menu::prompt::Select,
     node::item_status::ItemStatus,
-    surrealdb_layer::{surreal_tables::SurrealTables, DataLayerCommands},
+    surrealdb_layer::{
+        has_data_changed, surreal_tables::SurrealTables, DataChanged, DataLayerCommands,
+    },
     systems::bullet_list::{BulletList, BulletListReason},
 };
 
@@ -67,11 +70,13 @@ impl<'a> InquireBulletListItem<'a> {
     }
 }
 
-#[async_recursion]
 pub(crate) async fn present_normal_bullet_list_menu(
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
-) -> Result<(), ()> {
+) -> Result<Navigation, ()> {
     let before_db_query = Local::now();
+    let data_changed = DataLayerCommands::subscribe_to_changes(send_to_data_storage_layer)
+        .await
+        .unwrap();
     let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
         .await
         .unwrap();
@@ -82,19 +87,22 @@ pub(crate) async fn present_normal_bullet_list_menu(
     let now = Utc::now();
     let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
     let calculated_data = CalculatedData::new_from_base_data(base_data, &now);
-    let bullet_list = BulletList::new_bullet_list(calculated_data);
+    let ranking_strategy = Config::load().ranking_strategy.create();
+    let bullet_list =
+        BulletList::new_bullet_list_ranked_by(calculated_data, ranking_strategy.as_ref());
     let elapsed = Utc::now() - now;
     if elapsed > chrono::Duration::seconds(1) {
         println!("Slow to create bullet list. Time taken: {}", elapsed);
     }
-    present_bullet_list_menu(bullet_list, &now, send_to_data_storage_layer).await
+    present_bullet_list_menu(bullet_list, &now, data_changed, send_to_data_storage_layer).await
 }
 
 pub(crate) async fn present_bullet_list_menu(
     bullet_list: BulletList,
     current_date_time: &DateTime<Utc>,
+    mut data_changed: broadcast::Receiver<DataChanged>,
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
-) -> Result<(), ()> {
+) -> Result<Navigation, ()> {
     let item_nodes = bullet_list.get_bullet_list();
 
     let inquire_bullet_list = InquireBulletListItem::create_list(item_nodes, current_date_time);
@@ -104,27 +112,40 @@ pub(crate) async fn present_bullet_list_menu(
             .with_page_size(10)
             .prompt();
 
+        if matches!(
+            selected,
+            Ok(InquireBulletListItem::Item(..) | InquireBulletListItem::SetStaging(..))
+        ) && has_data_changed(&mut data_changed)
+        {
+            //The selected item might have been finished or deleted somewhere else
+            println!("The list changed while it was open, showing the refreshed list");
+            return Ok(Navigation::Stay);
+        }
+
+        //Once an item's menus are done the list is shown again so it includes the changes
         match selected {
-            Ok(InquireBulletListItem::CaptureNewItem) => capture(send_to_data_storage_layer).await,
+            Ok(InquireBulletListItem::CaptureNewItem) => {
+                capture(send_to_data_storage_layer).await?;
+                Ok(Navigation::Stay)
+            }
             Ok(InquireBulletListItem::Item(item_status, current_date_time)) => {
                 if item_status.is_person_or_group() {
                     present_is_person_or_group_around_menu(
                         item_status.get_item_node(),
                         send_to_data_storage_layer,
                     )
-                    .await
+                    .await?;
                 } else {
                     present_bullet_list_item_selected(
                         item_status,
                         bullet_list.get_all_item_status(),
                         current_date_time,
-                        bullet_list.get_coverings(),
-                        bullet_list.get_active_snoozed(),
                         bullet_list.get_active_items(),
                         send_to_data_storage_layer,
                     )
-                    .await
+                    .await?;
                 }
+                Ok(Navigation::Stay)
             }
             Ok(InquireBulletListItem::SetStaging(item_status)) => {
                 present_set_staging_menu(
@@ -132,16 +153,15 @@ pub(crate) async fn present_bullet_list_menu(
                     send_to_data_storage_layer,
                     Some(StagingMenuSelection::OnDeck),
                 )
-                .await
-            }
-            Err(InquireError::OperationCanceled) => {
-                present_top_menu(send_to_data_storage_layer).await
+                .await?;
+                Ok(Navigation::Stay)
             }
+            Err(InquireError::OperationCanceled) => Ok(Navigation::Back),
             Err(InquireError::OperationInterrupted) => Err(()),
             Err(err) => todo!("Unexpected InquireError of {}", err),
         }
     } else {
         println!("To Do List is Empty, falling back to main menu");
-        present_top_menu(send_to_data_storage_layer).await
+        Ok(Navigation::Back)
     }
 }
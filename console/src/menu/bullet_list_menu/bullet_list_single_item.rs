@@ -1,4 +1,6 @@
+mod attend_event;
 mod create_or_update_children;
+mod link_to_git_branch;
 pub(crate) mod parent_to_a_goal_or_motivation;
 pub(crate) mod set_staging;
 mod something_else_should_be_done_first;
@@ -7,41 +9,48 @@ mod state_a_smaller_next_step;
 
 use std::fmt::Display;
 
-use async_recursion::async_recursion;
 use better_term::Style;
 use chrono::{DateTime, Utc};
-use inquire::{Editor, InquireError, Select, Text};
+use inquire::InquireError;
+use surrealdb::opt::RecordId;
 use tokio::sync::mpsc::Sender;
 
 use crate::{
-    base_data::{
-        covering::Covering, covering_until_date_time::CoveringUntilDateTime, item::Item, BaseData,
-    },
+    base_data::{item::Item, BaseData},
     calculated_data::CalculatedData,
+    config::Config,
     display::{
-        display_item::DisplayItem, display_item_node::DisplayItemNode,
-        display_staging::DisplayStaging,
+        display_bullet_list_rank::DisplayBulletListRank, display_item::DisplayItem,
+        display_item_node::DisplayItemNode, display_snooze_reason::DisplaySnoozeReason,
+        display_staging::DisplayStaging, display_waiting_for::DisplayWaitingFor,
     },
     menu::{
         bullet_list_menu::bullet_list_single_item::{
-            create_or_update_children::create_or_update_children,
+            attend_event::set_attendance, create_or_update_children::create_or_update_children,
+            link_to_git_branch::link_to_git_branch,
             parent_to_a_goal_or_motivation::parent_to_a_goal_or_motivation,
             something_else_should_be_done_first::something_else_should_be_done_first,
             starting_to_work_on_this_now::starting_to_work_on_this_now,
             state_a_smaller_next_step::state_a_smaller_next_step,
         },
+        people::{snooze_agenda, they_are_here_now},
+        prompt::{Editor, Select, Text},
         select_higher_priority_than_this::select_higher_priority_than_this,
+        set_due_date::set_due_date,
         top_menu::capture,
         unable_to_work_on_item_right_now::unable_to_work_on_item_right_now,
         update_item_summary::update_item_summary,
+        waiting_for::{present_waiting_for_selected, start_waiting_for},
+        LeaveOutCycles, YesOrNo,
     },
     new_item,
     node::{item_node::ItemNode, item_status::ItemStatus},
     surrealdb_layer::{
-        surreal_item::{HowMuchIsInMyControl, ItemType, Responsibility, Staging},
+        surreal_item::{Attendance, HowMuchIsInMyControl, ItemType, Responsibility, Staging},
         surreal_tables::SurrealTables,
         DataLayerCommands,
     },
+    systems::bullet_list::explain_bullet_list_rank,
 };
 
 use self::{
@@ -49,15 +58,18 @@ use self::{
     set_staging::{present_set_staging_menu, StagingMenuSelection},
 };
 
-use super::present_normal_bullet_list_menu;
-
 enum BulletListSingleItemSelection<'e> {
+    WillAttend,
+    WillNotAttend,
     DeclareItemType,
     StartingToWorkOnThisNow,
     ParentToAGoalOrMotivation,
     ParentToAMotivation,
     PlanWhenToDoThis,
     ChangeStaging,
+    SetDueDate,
+    WaitingOnSomeone,
+    WaitingFor(DisplayWaitingFor<'e>),
     EstimateHowManyFocusPeriodsThisWillTake,
     UnableToDoThisRightNow,
     NotInTheMoodToDoThisRightNow,
@@ -72,6 +84,7 @@ enum BulletListSingleItemSelection<'e> {
     CreateNotesForThisItem,
     LinkNotesForThisItem,
     OpenNotesForThisItem,
+    LinkToGitBranch,
     OpenNotesForParentItem {
         item_in_chain_with_notes: DisplayItem<'e>,
     },
@@ -84,12 +97,16 @@ enum BulletListSingleItemSelection<'e> {
     SwitchToParentItem(DisplayItem<'e>, ItemStatus<'e>),
     ParentToItem,
     CaptureAFork,
+    WhyIsThisHere,
     DebugPrintItem,
+    Delete,
 }
 
 impl Display for BulletListSingleItemSelection<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Self::WillAttend => write!(f, "I will attend this"),
+            Self::WillNotAttend => write!(f, "I will not attend this"),
             Self::ProcessAndFinish => write!(f, "Process & Finish 📕"),
             Self::UpdateSummary => write!(f, "Update Summary"),
             Self::SwitchToParentItem(parent_item, _) => write!(f, "Switch to: {}", parent_item),
@@ -104,7 +121,9 @@ impl Display for BulletListSingleItemSelection<'_> {
             Self::PlanWhenToDoThis => {
                 write!(f, "Plan when to do this")
             }
+            Self::WhyIsThisHere => write!(f, "Why is this here?"),
             Self::DebugPrintItem => write!(f, "Debug Print Item"),
+            Self::Delete => write!(f, "Delete 🗑"),
             Self::SomethingElseShouldBeDoneFirst => {
                 write!(f, "Something else should be done first")
             }
@@ -128,6 +147,7 @@ impl Display for BulletListSingleItemSelection<'_> {
             Self::CreateNotesForThisItem => write!(f, "Create a OneNote page for this"),
             Self::LinkNotesForThisItem => write!(f, "Provide a link to the notes for this"),
             Self::OpenNotesForThisItem => write!(f, "Open notes for this"),
+            Self::LinkToGitBranch => write!(f, "Link to a git branch"),
             Self::OpenNotesForParentItem {
                 item_in_chain_with_notes: parent,
             } => write!(f, "Open notes for parent item: {}", parent),
@@ -139,6 +159,11 @@ impl Display for BulletListSingleItemSelection<'_> {
             Self::CaptureAFork => write!(f, "Capture a fork"),
             Self::ChangeType => write!(f, "Change Type"),
             Self::ChangeStaging => write!(f, "Change Staging"),
+            Self::SetDueDate => write!(f, "Set a due date"),
+            Self::WaitingOnSomeone => write!(f, "I am waiting on someone else for this"),
+            Self::WaitingFor(waiting_for) => {
+                write!(f, "Waiting on {}", waiting_for)
+            }
         }
     }
 }
@@ -157,6 +182,15 @@ impl<'e> BulletListSingleItemSelection<'e> {
         let is_type_undeclared = item_node.is_type_undeclared();
         let has_active_children = item_node.has_active_children();
 
+        if item_node
+            .get_item()
+            .get_calendar_event()
+            .is_some_and(|x| x.attendance == Attendance::NotDecided)
+        {
+            list.push(Self::WillAttend);
+            list.push(Self::WillNotAttend);
+        }
+
         if (is_type_action) && has_no_parent {
             list.push(Self::ParentToAGoalOrMotivation);
         } else if (is_type_goal || is_type_motivation) && !has_active_children {
@@ -213,6 +247,10 @@ impl<'e> BulletListSingleItemSelection<'e> {
             }
         }
 
+        if is_type_action || is_type_goal {
+            list.push(Self::LinkToGitBranch);
+        }
+
         list.push(Self::CreateOrUpdateChildren);
 
         if is_type_goal {
@@ -257,8 +295,20 @@ impl<'e> BulletListSingleItemSelection<'e> {
         if is_type_action || is_type_goal || is_type_motivation {
             list.push(Self::ChangeType);
             list.push(Self::ChangeStaging);
+            list.push(Self::SetDueDate);
         }
 
+        if item_node.get_waiting_for().is_some() {
+            list.push(Self::WaitingFor(DisplayWaitingFor::new(
+                item_node,
+                Utc::now(),
+            )));
+        } else if is_type_action || is_type_goal {
+            list.push(Self::WaitingOnSomeone);
+        }
+
+        list.push(Self::WhyIsThisHere);
+
         if !is_type_undeclared {
             list.extend(vec![
                 Self::ProcessAndFinish,
@@ -268,20 +318,70 @@ impl<'e> BulletListSingleItemSelection<'e> {
             ]);
         }
 
+        list.push(Self::Delete);
+
         list
     }
 }
 
-#[async_recursion]
+/// What to show once the menu for an item is done
+pub(crate) enum ItemMenuDone {
+    /// Go back to whatever opened the menu for the item
+    Back,
+    /// Show the menu for this item next, for example the parent after finishing or the same item after Esc
+    ShowItem(RecordId),
+}
+
+/// Moving between items is a loop here, with the data reloaded each time, rather than the menus calling each other
 pub(crate) async fn present_bullet_list_item_selected(
     menu_for: &ItemStatus<'_>,
     all_item_status: &[ItemStatus<'_>],
     current_date_time: &DateTime<Utc>,
-    all_coverings: &[Covering<'_>],
-    all_snoozed: &[&CoveringUntilDateTime<'_>],
     all_items: &[&Item<'_>],
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
 ) -> Result<(), ()> {
+    let mut done = present_item_menu(
+        menu_for,
+        all_item_status,
+        current_date_time,
+        all_items,
+        send_to_data_storage_layer,
+    )
+    .await?;
+    while let ItemMenuDone::ShowItem(show_item) = done {
+        let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+            .await
+            .unwrap();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, Utc::now());
+        let calculated_data = CalculatedData::new_from_base_data(base_data, current_date_time);
+        let item_status = match calculated_data
+            .get_item_status()
+            .iter()
+            .find(|x| x.get_surreal_record_id() == &show_item)
+        {
+            Some(item_status) => item_status,
+            //Deleted or finished somewhere else
+            None => return Ok(()),
+        };
+        done = present_item_menu(
+            item_status,
+            calculated_data.get_item_status(),
+            current_date_time,
+            calculated_data.get_active_items(),
+            send_to_data_storage_layer,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+async fn present_item_menu(
+    menu_for: &ItemStatus<'_>,
+    all_item_status: &[ItemStatus<'_>],
+    current_date_time: &DateTime<Utc>,
+    all_items: &[&Item<'_>],
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<ItemMenuDone, ()> {
     let list =
         BulletListSingleItemSelection::create_list(menu_for.get_item_node(), all_item_status);
 
@@ -289,17 +389,30 @@ pub(crate) async fn present_bullet_list_item_selected(
         .with_page_size(14)
         .prompt();
 
-    match selection {
+    let done = match selection {
+        Ok(BulletListSingleItemSelection::WillAttend) => {
+            set_attendance(
+                menu_for.get_item(),
+                Attendance::Attending,
+                send_to_data_storage_layer,
+            )
+            .await
+        }
+        Ok(BulletListSingleItemSelection::WillNotAttend) => {
+            set_attendance(
+                menu_for.get_item(),
+                Attendance::NotAttending,
+                send_to_data_storage_layer,
+            )
+            .await
+        }
         Ok(BulletListSingleItemSelection::DeclareItemType) => {
             declare_item_type(menu_for.get_item(), send_to_data_storage_layer).await
         }
         Ok(BulletListSingleItemSelection::StartingToWorkOnThisNow) => {
-            starting_to_work_on_this_now(
+            return starting_to_work_on_this_now(
                 menu_for,
-                all_item_status,
                 current_date_time,
-                all_coverings,
-                all_snoozed,
                 all_items,
                 send_to_data_storage_layer,
             )
@@ -331,16 +444,8 @@ pub(crate) async fn present_bullet_list_item_selected(
                 .await
         }
         Ok(BulletListSingleItemSelection::CreateOrUpdateChildren) => {
-            create_or_update_children(
-                menu_for,
-                all_item_status,
-                current_date_time,
-                all_coverings,
-                all_snoozed,
-                all_items,
-                send_to_data_storage_layer,
-            )
-            .await
+            return create_or_update_children(menu_for, all_item_status, send_to_data_storage_layer)
+                .await
         }
         Ok(BulletListSingleItemSelection::DefineMilestones) => {
             todo!("TODO: Implement DefineMilestones");
@@ -357,16 +462,7 @@ pub(crate) async fn present_bullet_list_item_selected(
             .await
         }
         Ok(BulletListSingleItemSelection::Finished) => {
-            finish_bullet_item(
-                menu_for,
-                all_item_status,
-                all_coverings,
-                all_snoozed,
-                all_items,
-                current_date_time,
-                send_to_data_storage_layer,
-            )
-            .await
+            return finish_bullet_item(menu_for, send_to_data_storage_layer).await
         }
         Ok(BulletListSingleItemSelection::ThisIsARepeatingItem) => {
             todo!("TODO: Implement ThisIsARepeatingItem");
@@ -377,6 +473,9 @@ pub(crate) async fn present_bullet_list_item_selected(
         Ok(BulletListSingleItemSelection::LinkNotesForThisItem) => {
             todo!("TODO: Implement LinkNotes");
         }
+        Ok(BulletListSingleItemSelection::LinkToGitBranch) => {
+            link_to_git_branch(menu_for.get_item(), send_to_data_storage_layer).await
+        }
         Ok(BulletListSingleItemSelection::OpenNotesForThisItem) => {
             todo!("TODO: Implement OpenNotesForThisItem");
         }
@@ -415,28 +514,57 @@ pub(crate) async fn present_bullet_list_item_selected(
             update_item_summary(menu_for.get_item(), send_to_data_storage_layer).await
         }
         Ok(BulletListSingleItemSelection::SwitchToParentItem(_, selected)) => {
-            present_bullet_list_item_parent_selected(
-                &selected,
-                all_item_status,
-                current_date_time,
-                all_coverings,
-                all_snoozed,
-                all_items,
-                send_to_data_storage_layer,
-            )
-            .await
+            match selected.get_type() {
+                ItemType::Action | ItemType::Goal(..) | ItemType::Motivation => {
+                    return Ok(ItemMenuDone::ShowItem(
+                        selected.get_surreal_record_id().clone(),
+                    ))
+                }
+                ItemType::IdeaOrThought => todo!(),
+                ItemType::Undeclared => todo!(),
+                ItemType::PersonOrGroup => todo!(),
+            }
         }
         Ok(BulletListSingleItemSelection::ParentToItem) => {
             parent_to_item(menu_for.get_item(), send_to_data_storage_layer).await
         }
+        Ok(BulletListSingleItemSelection::SetDueDate) => {
+            set_due_date(menu_for.get_item(), send_to_data_storage_layer).await
+        }
+        Ok(BulletListSingleItemSelection::WaitingOnSomeone) => {
+            start_waiting_for(menu_for.get_item(), send_to_data_storage_layer).await
+        }
+        Ok(BulletListSingleItemSelection::WaitingFor(_)) => {
+            present_waiting_for_selected(menu_for.get_item_node(), send_to_data_storage_layer).await
+        }
+        Ok(BulletListSingleItemSelection::WhyIsThisHere) => {
+            let ranking_strategy = Config::load().ranking_strategy.create();
+            match explain_bullet_list_rank(menu_for, all_item_status, ranking_strategy.as_ref()) {
+                Some(bullet_list_rank) => {
+                    print!("{}", DisplayBulletListRank::new(&bullet_list_rank))
+                }
+                None => println!(
+                    "Not on the bullet list because it has smaller items, those are worked on first"
+                ),
+            }
+            Ok(())
+        }
         Ok(BulletListSingleItemSelection::DebugPrintItem) => {
+            println!("Lap count: {}", menu_for.get_lap_count());
+            for snooze_reason in menu_for.get_snooze_reasons() {
+                println!("Snoozed: {}", DisplaySnoozeReason::new(snooze_reason));
+            }
             println!("{:?}", menu_for);
             Ok(())
         }
+        Ok(BulletListSingleItemSelection::Delete) => {
+            delete_item(menu_for.get_item(), send_to_data_storage_layer).await
+        }
         Err(InquireError::OperationCanceled) => Ok(()), //Nothing to do we just want to return to the bullet list
         Err(InquireError::OperationInterrupted) => Err(()),
         Err(err) => todo!("Unexpected {}", err),
-    }
+    };
+    done.map(|()| ItemMenuDone::Back)
 }
 
 enum FinishSelection<'e> {
@@ -496,16 +624,10 @@ impl<'e> FinishSelection<'e> {
     }
 }
 
-#[async_recursion]
 async fn finish_bullet_item(
     finish_this: &ItemStatus<'_>,
-    all_item_status: &[ItemStatus<'_>],
-    all_coverings: &[Covering<'_>],
-    all_snoozed: &[&CoveringUntilDateTime<'_>],
-    all_items: &[&Item<'_>],
-    current_date_time: &DateTime<Utc>,
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
-) -> Result<(), ()> {
+) -> Result<ItemMenuDone, ()> {
     send_to_data_storage_layer
         .send(DataLayerCommands::FinishItem(
             finish_this.get_surreal_record_id().clone(),
@@ -513,121 +635,89 @@ async fn finish_bullet_item(
         .await
         .unwrap();
 
-    let list = FinishSelection::make_list(
-        &finish_this
-            .get_larger()
-            .iter()
-            .map(|x| x.get_item())
-            .collect::<Vec<_>>(),
-        finish_this.get_item(),
-    );
-    let selection = Select::new("Select from the below list|", list).prompt();
-
-    match selection {
-        Ok(FinishSelection::CaptureNewItem) => capture(send_to_data_storage_layer).await,
-        Ok(FinishSelection::CreateNextStepWithParent(parent)) => {
-            let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
-                .await
-                .unwrap();
-            let now = Utc::now();
-            let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
-            let items = base_data.get_active_items();
-            let parent_surreal_record_id = parent.get_surreal_record_id();
-            let updated_parent = items
-                .iter()
-                .filter(|x| x.get_surreal_record_id() == parent_surreal_record_id)
-                .map(|x| {
-                    ItemNode::new(
-                        x,
-                        base_data.get_coverings(),
-                        base_data.get_active_snoozed(),
-                        items,
-                    )
-                })
-                .next()
-                .expect("We will find this existing item once");
-
-            state_a_smaller_next_step(&updated_parent, send_to_data_storage_layer).await?;
-
-            //Recursively call as a way of creating a loop, we don't want to return to the main bullet list
-            finish_bullet_item(
-                finish_this,
-                all_item_status,
-                all_coverings,
-                all_snoozed,
-                all_items,
-                current_date_time,
-                send_to_data_storage_layer,
-            )
-            .await
-        }
-        Ok(FinishSelection::GoToParent(parent)) => {
-            let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
-                .await
-                .unwrap();
-            let now = Utc::now();
-            let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
-            let calculated_data = CalculatedData::new_from_base_data(base_data, current_date_time);
-            let parent_surreal_record_id = parent.get_surreal_record_id();
-            let updated_parent = calculated_data
-                .get_item_status()
+    //Once something has been done with the finished item Esc just leaves, undoing the finish would leave a next step
+    //next to an item that is no longer finished
+    let mut is_first_answer = true;
+    loop {
+        let list = FinishSelection::make_list(
+            &finish_this
+                .get_larger()
                 .iter()
-                .find(|x| x.get_surreal_record_id() == parent_surreal_record_id)
-                .expect("We will find this existing item once");
+                .map(|x| x.get_item())
+                .collect::<Vec<_>>(),
+            finish_this.get_item(),
+        );
+        let selection = Select::new("Select from the below list|", list).prompt();
 
-            present_bullet_list_item_selected(
-                updated_parent,
-                all_item_status,
-                current_date_time,
-                all_coverings,
-                all_snoozed,
-                all_items,
-                send_to_data_storage_layer,
-            )
-            .await
-        }
-        Ok(FinishSelection::UpdateStagingForParent(parent)) => {
-            present_set_staging_menu(parent, send_to_data_storage_layer, None).await?;
-            //Recursively call as a way of creating a loop, we don't want to return to the main bullet list
-            finish_bullet_item(
-                finish_this,
-                all_item_status,
-                all_coverings,
-                all_snoozed,
-                all_items,
-                current_date_time,
-                send_to_data_storage_layer,
-            )
-            .await
-        }
-        Ok(FinishSelection::ApplyStagingToParent(parent, staging)) => {
-            send_to_data_storage_layer
-                .send(DataLayerCommands::UpdateItemStaging(
-                    parent.get_surreal_record_id().clone(),
-                    staging,
-                ))
-                .await
-                .unwrap();
-            //Recursively call as a way of creating a loop, we don't want to return to the main bullet list
-            finish_bullet_item(
-                finish_this,
-                all_item_status,
-                all_coverings,
-                all_snoozed,
-                all_items,
-                current_date_time,
-                send_to_data_storage_layer,
-            )
-            .await
-        }
-        Ok(FinishSelection::ReturnToBulletList) => {
-            present_normal_bullet_list_menu(send_to_data_storage_layer).await
-        }
-        Err(InquireError::OperationCanceled) => {
-            todo!("This should undo the finish and put the item back to what it was before")
-        }
-        Err(InquireError::OperationInterrupted) => Err(()),
-        Err(err) => todo!("Unexpected {}", err),
+        return match selection {
+            Ok(FinishSelection::CaptureNewItem) => {
+                capture(send_to_data_storage_layer).await?;
+                Ok(ItemMenuDone::Back)
+            }
+            Ok(FinishSelection::CreateNextStepWithParent(parent)) => {
+                let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+                    .await
+                    .unwrap();
+                let now = Utc::now();
+                let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+                let items = base_data.get_active_items();
+                let parent_surreal_record_id = parent.get_surreal_record_id();
+                let updated_parent = items
+                    .iter()
+                    .filter(|x| x.get_surreal_record_id() == parent_surreal_record_id)
+                    .map(|x| {
+                        ItemNode::new(
+                            x,
+                            base_data.get_coverings(),
+                            base_data.get_active_snoozed(),
+                            items,
+                        )
+                    })
+                    .next()
+                    .expect("We will find this existing item once");
+
+                state_a_smaller_next_step(&updated_parent, send_to_data_storage_layer).await?;
+
+                is_first_answer = false;
+                //Show the finish menu again, we don't want to return to the main bullet list
+                continue;
+            }
+            Ok(FinishSelection::GoToParent(parent)) => Ok(ItemMenuDone::ShowItem(
+                parent.get_surreal_record_id().clone(),
+            )),
+            Ok(FinishSelection::UpdateStagingForParent(parent)) => {
+                present_set_staging_menu(parent, send_to_data_storage_layer, None).await?;
+                is_first_answer = false;
+                //Show the finish menu again, we don't want to return to the main bullet list
+                continue;
+            }
+            Ok(FinishSelection::ApplyStagingToParent(parent, staging)) => {
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::UpdateItemStaging(
+                        parent.get_surreal_record_id().clone(),
+                        staging,
+                    ))
+                    .await
+                    .unwrap();
+                is_first_answer = false;
+                //Show the finish menu again, we don't want to return to the main bullet list
+                continue;
+            }
+            Ok(FinishSelection::ReturnToBulletList) => Ok(ItemMenuDone::Back),
+            Err(InquireError::OperationCanceled) if is_first_answer => {
+                //Undo the finish and put the item back to what it was before
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::UnfinishItem(
+                        finish_this.get_surreal_record_id().clone(),
+                    ))
+                    .await
+                    .unwrap();
+                Ok(ItemMenuDone::Back)
+            }
+            Err(InquireError::OperationCanceled) => Ok(ItemMenuDone::Back),
+            Err(InquireError::OperationInterrupted) => Err(()),
+            Err(err) => todo!("Unexpected {}", err),
+        };
     }
 }
 
@@ -659,31 +749,32 @@ async fn process_and_finish_bullet_item(
     Ok(())
 }
 
-async fn present_bullet_list_item_parent_selected(
-    selected_item: &ItemStatus<'_>,
-    all_item_status: &[ItemStatus<'_>],
-    current_date_time: &DateTime<Utc>,
-    all_coverings: &[Covering<'_>],
-    all_snoozed: &[&CoveringUntilDateTime<'_>],
-    all_items: &[&Item<'_>],
+async fn delete_item(
+    item: &Item<'_>,
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
 ) -> Result<(), ()> {
-    match selected_item.get_type() {
-        ItemType::Action | ItemType::Goal(..) | ItemType::Motivation => {
-            present_bullet_list_item_selected(
-                selected_item,
-                all_item_status,
-                current_date_time,
-                all_coverings,
-                all_snoozed,
-                all_items,
-                send_to_data_storage_layer,
-            )
-            .await
+    let selection = Select::new(
+        &format!(
+            "Permanently delete {}? This cannot be undone, consider finishing instead|",
+            DisplayItem::new(item)
+        ),
+        YesOrNo::make_list(),
+    )
+    .with_starting_cursor(1)
+    .prompt();
+    match selection {
+        Ok(YesOrNo::Yes) => {
+            send_to_data_storage_layer
+                .send(DataLayerCommands::DeleteItem(
+                    item.get_surreal_record_id().clone(),
+                ))
+                .await
+                .unwrap();
+            Ok(())
         }
-        ItemType::IdeaOrThought => todo!(),
-        ItemType::Undeclared => todo!(),
-        ItemType::PersonOrGroup => todo!(),
+        Ok(YesOrNo::No) | Err(InquireError::OperationCanceled) => Ok(()),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => todo!("Unexpected {}", err),
     }
 }
 
@@ -696,9 +787,11 @@ async fn parent_to_item(
         .unwrap();
     let now = Utc::now();
     let base_data = BaseData::new_from_surreal_tables(raw_data, now);
+    let mut cycles = LeaveOutCycles::new(base_data.get_surreal_tables());
     let items = base_data.get_active_items();
     let item_nodes = items
         .iter()
+        .filter(|x| cycles.keep(parent_this, x))
         .map(|x| {
             ItemNode::new(
                 x,
@@ -709,6 +802,7 @@ async fn parent_to_item(
         })
         .collect::<Vec<_>>();
     let list = DisplayItemNode::make_list(&item_nodes);
+    cycles.print_summary();
 
     let selection = Select::new("Type to Search or Press Esc to enter a new one", list).prompt();
     match selection {
@@ -724,14 +818,17 @@ async fn parent_to_item(
             } else {
                 None
             };
-            send_to_data_storage_layer
-                .send(DataLayerCommands::ParentItemWithExistingItem {
-                    child: parent_this.get_surreal_record_id().clone(),
-                    parent: item_node.get_surreal_record_id().clone(),
-                    higher_priority_than_this,
-                })
-                .await
-                .unwrap();
+            if let Err(would_create_a_cycle) = DataLayerCommands::parent_item_with_existing_item(
+                send_to_data_storage_layer,
+                parent_this.get_surreal_record_id().clone(),
+                item_node.get_surreal_record_id().clone(),
+                higher_priority_than_this,
+            )
+            .await
+            .unwrap()
+            {
+                println!("{}", would_create_a_cycle);
+            }
             Ok(())
         }
         Err(InquireError::OperationCanceled | InquireError::InvalidConfiguration(_)) => {
@@ -751,9 +848,16 @@ pub(crate) async fn cover_with_item(
         .unwrap();
     let now = Utc::now();
     let base_data = BaseData::new_from_surreal_tables(raw_data, now);
-    let items = base_data.get_active_items();
+    let mut cycles = LeaveOutCycles::new(base_data.get_surreal_tables());
+    let items = base_data
+        .get_active_items()
+        .iter()
+        .filter(|x| cycles.keep(x, parent_this))
+        .copied()
+        .collect::<Vec<_>>();
 
-    let list = DisplayItem::make_list(items);
+    let list = DisplayItem::make_list(&items);
+    cycles.print_summary();
 
     let selection = Select::new("Type to Search or Press Esc to enter a new one", list).prompt();
     match selection {
@@ -764,14 +868,17 @@ pub(crate) async fn cover_with_item(
             } else {
                 None
             };
-            send_to_data_storage_layer
-                .send(DataLayerCommands::ParentItemWithExistingItem {
-                    child: item.get_surreal_record_id().clone(),
-                    parent: parent_this.get_surreal_record_id().clone(),
-                    higher_priority_than_this,
-                })
-                .await
-                .unwrap();
+            if let Err(would_create_a_cycle) = DataLayerCommands::parent_item_with_existing_item(
+                send_to_data_storage_layer,
+                item.get_surreal_record_id().clone(),
+                parent_this.get_surreal_record_id().clone(),
+                higher_priority_than_this,
+            )
+            .await
+            .unwrap()
+            {
+                println!("{}", would_create_a_cycle);
+            }
             Ok(())
         }
         Err(InquireError::OperationCanceled | InquireError::InvalidConfiguration(_)) => {
@@ -932,149 +1039,152 @@ impl ItemTypeSelection {
     }
 }
 
-#[async_recursion]
 pub(crate) async fn parent_to_new_item(
     parent_this: &Item<'_>,
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
 ) -> Result<(), ()> {
-    let list = ItemTypeSelection::create_list();
-
-    let selection = Select::new("Select from the below list|", list).prompt();
-    match selection {
-        Ok(ItemTypeSelection::NormalHelp) => {
-            ItemTypeSelection::print_normal_help();
-            parent_to_new_item(parent_this, send_to_data_storage_layer).await
-        }
-        Ok(ItemTypeSelection::ResponsiveHelp) => {
-            ItemTypeSelection::print_responsive_help();
-            parent_to_new_item(parent_this, send_to_data_storage_layer).await
-        }
-        Ok(item_type_selection) => {
-            let new_item = item_type_selection.create_new_item_prompt_user_for_summary();
-            send_to_data_storage_layer
-                .send(DataLayerCommands::ParentNewItemWithAnExistingChildItem {
-                    child: parent_this.get_surreal_record_id().clone(),
-                    parent_new_item: new_item,
-                })
-                .await
-                .unwrap();
-            Ok(())
-        }
-        Err(InquireError::OperationCanceled) => todo!(),
-        Err(InquireError::OperationInterrupted) => Err(()),
-        Err(err) => todo!("Unexpected {}", err),
+    loop {
+        let list = ItemTypeSelection::create_list();
+
+        let selection = Select::new("Select from the below list|", list).prompt();
+        return match selection {
+            Ok(ItemTypeSelection::NormalHelp) => {
+                ItemTypeSelection::print_normal_help();
+                continue;
+            }
+            Ok(ItemTypeSelection::ResponsiveHelp) => {
+                ItemTypeSelection::print_responsive_help();
+                continue;
+            }
+            Ok(item_type_selection) => {
+                let new_item = item_type_selection.create_new_item_prompt_user_for_summary();
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::ParentNewItemWithAnExistingChildItem {
+                        child: parent_this.get_surreal_record_id().clone(),
+                        parent_new_item: new_item,
+                    })
+                    .await
+                    .unwrap();
+                Ok(())
+            }
+            Err(InquireError::OperationCanceled) => Ok(()),
+            Err(InquireError::OperationInterrupted) => Err(()),
+            Err(err) => todo!("Unexpected {}", err),
+        };
     }
 }
 
-#[async_recursion]
 pub(crate) async fn cover_with_new_item(
     cover_this: &Item<'_>,
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
 ) -> Result<(), ()> {
-    let list = ItemTypeSelection::create_list();
-
-    let selection = Select::new("Select from the below list|", list).prompt();
-    match selection {
-        Ok(ItemTypeSelection::NormalHelp) => {
-            ItemTypeSelection::print_normal_help();
-            cover_with_new_item(cover_this, send_to_data_storage_layer).await
-        }
-        Ok(ItemTypeSelection::ResponsiveHelp) => {
-            ItemTypeSelection::print_responsive_help();
-            cover_with_new_item(cover_this, send_to_data_storage_layer).await
-        }
-        Ok(item_type_selection) => {
-            let new_item = item_type_selection.create_new_item_prompt_user_for_summary();
-            send_to_data_storage_layer
-                .send(DataLayerCommands::CoverItemWithANewItem {
-                    cover_this: cover_this.get_surreal_record_id().clone(),
-                    cover_with: new_item,
-                })
-                .await
-                .unwrap();
-            Ok(())
-        }
-        Err(InquireError::OperationCanceled) => todo!(),
-        Err(InquireError::OperationInterrupted) => Err(()),
-        Err(err) => todo!("Unexpected {}", err),
+    loop {
+        let list = ItemTypeSelection::create_list();
+
+        let selection = Select::new("Select from the below list|", list).prompt();
+        return match selection {
+            Ok(ItemTypeSelection::NormalHelp) => {
+                ItemTypeSelection::print_normal_help();
+                continue;
+            }
+            Ok(ItemTypeSelection::ResponsiveHelp) => {
+                ItemTypeSelection::print_responsive_help();
+                continue;
+            }
+            Ok(item_type_selection) => {
+                let new_item = item_type_selection.create_new_item_prompt_user_for_summary();
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::CoverItemWithANewItem {
+                        cover_this: cover_this.get_surreal_record_id().clone(),
+                        cover_with: new_item,
+                    })
+                    .await
+                    .unwrap();
+                Ok(())
+            }
+            Err(InquireError::OperationCanceled) => Ok(()),
+            Err(InquireError::OperationInterrupted) => Err(()),
+            Err(err) => todo!("Unexpected {}", err),
+        };
     }
 }
 
-#[async_recursion]
 pub(crate) async fn declare_item_type(
     item: &Item<'_>,
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
 ) -> Result<(), ()> {
-    let list = ItemTypeSelection::create_list();
-
-    let selection = Select::new("Select from the below list|", list).prompt();
-    match selection {
-        Ok(ItemTypeSelection::Action) => {
-            send_to_data_storage_layer
-                .send(DataLayerCommands::UpdateResponsibilityAndItemType(
-                    item.get_surreal_record_id().clone(),
-                    Responsibility::ProactiveActionToTake,
-                    ItemType::Action,
-                ))
-                .await
-                .unwrap();
-            Ok(())
-        }
-        Ok(ItemTypeSelection::Goal) => {
-            send_to_data_storage_layer
-                .send(DataLayerCommands::UpdateResponsibilityAndItemType(
-                    item.get_surreal_record_id().clone(),
-                    Responsibility::ProactiveActionToTake,
-                    ItemType::Goal(HowMuchIsInMyControl::default()),
-                ))
-                .await
-                .unwrap();
-            Ok(())
-        }
-        Ok(ItemTypeSelection::ResponsiveGoal) => {
-            send_to_data_storage_layer
-                .send(DataLayerCommands::UpdateResponsibilityAndItemType(
-                    item.get_surreal_record_id().clone(),
-                    Responsibility::ReactiveBeAvailableToAct,
-                    ItemType::Goal(HowMuchIsInMyControl::default()),
-                ))
-                .await
-                .unwrap();
-            Ok(())
-        }
-        Ok(ItemTypeSelection::Motivation) => {
-            send_to_data_storage_layer
-                .send(DataLayerCommands::UpdateResponsibilityAndItemType(
-                    item.get_surreal_record_id().clone(),
-                    Responsibility::ProactiveActionToTake,
-                    ItemType::Motivation,
-                ))
-                .await
-                .unwrap();
-            Ok(())
-        }
-        Ok(ItemTypeSelection::ResponsiveMotivation) => {
-            send_to_data_storage_layer
-                .send(DataLayerCommands::UpdateResponsibilityAndItemType(
-                    item.get_surreal_record_id().clone(),
-                    Responsibility::ReactiveBeAvailableToAct,
-                    ItemType::Motivation,
-                ))
-                .await
-                .unwrap();
-            Ok(())
-        }
-        Ok(ItemTypeSelection::NormalHelp) => {
-            ItemTypeSelection::print_normal_help();
-            declare_item_type(item, send_to_data_storage_layer).await
-        }
-        Ok(ItemTypeSelection::ResponsiveHelp) => {
-            ItemTypeSelection::print_responsive_help();
-            declare_item_type(item, send_to_data_storage_layer).await
-        }
-        Err(InquireError::OperationCanceled) => todo!(),
-        Err(InquireError::OperationInterrupted) => Err(()),
-        Err(err) => todo!("Unexpected {}", err),
+    loop {
+        let list = ItemTypeSelection::create_list();
+
+        let selection = Select::new("Select from the below list|", list).prompt();
+        return match selection {
+            Ok(ItemTypeSelection::Action) => {
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::UpdateResponsibilityAndItemType(
+                        item.get_surreal_record_id().clone(),
+                        Responsibility::ProactiveActionToTake,
+                        ItemType::Action,
+                    ))
+                    .await
+                    .unwrap();
+                Ok(())
+            }
+            Ok(ItemTypeSelection::Goal) => {
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::UpdateResponsibilityAndItemType(
+                        item.get_surreal_record_id().clone(),
+                        Responsibility::ProactiveActionToTake,
+                        ItemType::Goal(HowMuchIsInMyControl::default()),
+                    ))
+                    .await
+                    .unwrap();
+                Ok(())
+            }
+            Ok(ItemTypeSelection::ResponsiveGoal) => {
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::UpdateResponsibilityAndItemType(
+                        item.get_surreal_record_id().clone(),
+                        Responsibility::ReactiveBeAvailableToAct,
+                        ItemType::Goal(HowMuchIsInMyControl::default()),
+                    ))
+                    .await
+                    .unwrap();
+                Ok(())
+            }
+            Ok(ItemTypeSelection::Motivation) => {
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::UpdateResponsibilityAndItemType(
+                        item.get_surreal_record_id().clone(),
+                        Responsibility::ProactiveActionToTake,
+                        ItemType::Motivation,
+                    ))
+                    .await
+                    .unwrap();
+                Ok(())
+            }
+            Ok(ItemTypeSelection::ResponsiveMotivation) => {
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::UpdateResponsibilityAndItemType(
+                        item.get_surreal_record_id().clone(),
+                        Responsibility::ReactiveBeAvailableToAct,
+                        ItemType::Motivation,
+                    ))
+                    .await
+                    .unwrap();
+                Ok(())
+            }
+            Ok(ItemTypeSelection::NormalHelp) => {
+                ItemTypeSelection::print_normal_help();
+                continue;
+            }
+            Ok(ItemTypeSelection::ResponsiveHelp) => {
+                ItemTypeSelection::print_responsive_help();
+                continue;
+            }
+            Err(InquireError::OperationCanceled) => Ok(()),
+            Err(InquireError::OperationInterrupted) => Err(()),
+            Err(err) => todo!("Unexpected {}", err),
+        };
     }
 }
 
@@ -1107,16 +1217,20 @@ pub(crate) async fn present_is_person_or_group_around_menu(
     let selection = Select::new("Select from the below list|", list).prompt();
     match selection {
         Ok(IsAPersonOrGroupAroundSelection::Yes) => {
-            send_to_data_storage_layer
-                .send(DataLayerCommands::FinishItem(
-                    person_or_group_node.get_surreal_record_id().clone(),
-                ))
-                .await
-                .unwrap();
-            Ok(())
+            they_are_here_now(
+                person_or_group_node.get_surreal_record_id(),
+                send_to_data_storage_layer,
+            )
+            .await
+        }
+        Ok(IsAPersonOrGroupAroundSelection::No) => {
+            snooze_agenda(
+                person_or_group_node.get_surreal_record_id(),
+                send_to_data_storage_layer,
+            )
+            .await
         }
-        Ok(IsAPersonOrGroupAroundSelection::No) => todo!(),
-        Err(InquireError::OperationCanceled) => todo!(),
+        Err(InquireError::OperationCanceled) => Ok(()),
         Err(InquireError::OperationInterrupted) => Err(()),
         Err(err) => todo!("Unexpected {}", err),
     }
@@ -0,0 +1,93 @@
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::item::Item,
+    surrealdb_layer::{surreal_item::Attendance, DataLayerCommands},
+};
+
+/// An event that will not be attended is finished so it leaves the bullet list and is not a busy block
+pub(crate) async fn set_attendance(
+    event: &Item<'_>,
+    attendance: Attendance,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let mut calendar_event = event
+        .get_calendar_event()
+        .expect("Only offered for events")
+        .clone();
+    calendar_event.attendance = attendance;
+    send_to_data_storage_layer
+        .send(DataLayerCommands::UpdateItemCalendarEvent(
+            event.get_surreal_record_id().clone(),
+            Some(Box::new(calendar_event)),
+        ))
+        .await
+        .unwrap();
+    if attendance == Attendance::NotAttending {
+        send_to_data_storage_layer
+            .send(DataLayerCommands::FinishItem(
+                event.get_surreal_record_id().clone(),
+            ))
+            .await
+            .unwrap();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use tokio::sync::mpsc;
+
+    use crate::{
+        new_item::NewItemBuilder,
+        surrealdb_layer::{
+            data_storage_start_and_run,
+            surreal_item::{Attendance, CalendarEvent},
+            surreal_tables::SurrealTables,
+            DataLayerCommands,
+        },
+    };
+
+    use super::set_attendance;
+
+    #[tokio::test]
+    async fn an_event_that_will_not_be_attended_is_finished() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+        let now = Utc::now();
+        sender
+            .send(DataLayerCommands::NewItem(
+                NewItemBuilder::default()
+                    .summary("All hands")
+                    .calendar_event(Some(Box::new(CalendarEvent {
+                        uid: "all-hands@example.com".into(),
+                        start: (now + Duration::hours(1)).into(),
+                        end: (now + Duration::hours(2)).into(),
+                        attendance: Attendance::NotDecided,
+                    })))
+                    .build()
+                    .unwrap(),
+            ))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let items = surreal_tables.make_items();
+
+        set_attendance(&items[0], Attendance::NotAttending, &sender)
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let all_hands = &surreal_tables.surreal_items[0];
+        assert!(all_hands.finished.is_some());
+        assert_eq!(
+            all_hands.calendar_event.as_ref().unwrap().attendance,
+            Attendance::NotAttending
+        );
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+}
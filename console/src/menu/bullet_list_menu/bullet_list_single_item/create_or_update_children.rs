@@ -3,20 +3,17 @@ pub(crate) mod edit_order_of_children_items;
 use core::fmt;
 use std::fmt::{Display, Formatter};
 
-use async_recursion::async_recursion;
-use chrono::{DateTime, Utc};
-use inquire::{InquireError, Select};
+use inquire::InquireError;
 use tokio::sync::mpsc::Sender;
 
 use crate::{
-    base_data::{covering::Covering, covering_until_date_time::CoveringUntilDateTime, item::Item},
     display::display_item_status::DisplayItemStatus,
-    menu::bullet_list_menu::bullet_list_single_item::create_or_update_children::edit_order_of_children_items::edit_order_of_children_items,
+    menu::{prompt::Select, bullet_list_menu::bullet_list_single_item::create_or_update_children::edit_order_of_children_items::edit_order_of_children_items},
     node::item_status::ItemStatus,
     surrealdb_layer::DataLayerCommands,
 };
 
-use super::present_bullet_list_item_selected;
+use super::ItemMenuDone;
 
 enum CreateOrUpdateChildrenItem {
     ConfigureSchedulingPolicyForChildren,
@@ -58,16 +55,11 @@ impl CreateOrUpdateChildrenItem {
     }
 }
 
-#[async_recursion]
 pub(crate) async fn create_or_update_children(
     item_status: &ItemStatus<'_>,
     all_item_status: &[ItemStatus<'_>],
-    current_date_time: &DateTime<Utc>,
-    all_coverings: &[Covering<'_>],
-    all_snoozed: &[&CoveringUntilDateTime<'_>],
-    all_items: &[&Item<'_>],
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
-) -> Result<(), ()> {
+) -> Result<ItemMenuDone, ()> {
     if !item_status.has_active_children() {
         println!("No children found");
     } else {
@@ -92,28 +84,15 @@ pub(crate) async fn create_or_update_children(
         }
         Ok(CreateOrUpdateChildrenItem::EditOrderOfChildrenItems) => {
             edit_order_of_children_items(item_status.get_item_node(), send_to_data_storage_layer)
-                .await
-        }
-        Ok(CreateOrUpdateChildrenItem::ReturnToBulletList) => {
-            println!("Return to bullet list");
-            todo!()
-        }
-        Ok(CreateOrUpdateChildrenItem::ReturnToParentItem) => {
-            println!("Return to parent item");
-            todo!()
-        }
-        Err(InquireError::OperationCanceled) => {
-            present_bullet_list_item_selected(
-                item_status,
-                all_item_status,
-                current_date_time,
-                all_coverings,
-                all_snoozed,
-                all_items,
-                send_to_data_storage_layer,
-            )
-            .await
+                .await?;
+            Ok(ItemMenuDone::Back)
         }
+        Ok(CreateOrUpdateChildrenItem::ReturnToBulletList) => Ok(ItemMenuDone::Back),
+        //The parent of the children is the item this was opened from
+        Ok(CreateOrUpdateChildrenItem::ReturnToParentItem)
+        | Err(InquireError::OperationCanceled) => Ok(ItemMenuDone::ShowItem(
+            item_status.get_surreal_record_id().clone(),
+        )),
         Err(InquireError::OperationInterrupted) => Err(()),
         Err(err) => {
             todo!("Error: {:?}", err)
@@ -4,14 +4,14 @@ use std::{
     iter::once,
 };
 
-use async_recursion::async_recursion;
-use inquire::{InquireError, Select};
+use inquire::InquireError;
 use itertools::chain;
 use tokio::sync::mpsc::Sender;
 
 use crate::{
-    display::display_item::DisplayItem, menu::select_higher_priority_than_this::HigherPriorityThan,
-    node::item_node::ItemNode, surrealdb_layer::DataLayerCommands,
+    display::display_item::DisplayItem, menu::prompt::Select,
+    menu::select_higher_priority_than_this::HigherPriorityThan, node::item_node::ItemNode,
+    surrealdb_layer::DataLayerCommands,
 };
 
 enum EditOrderOfChildren<'e> {
@@ -28,54 +28,58 @@ impl Display for EditOrderOfChildren<'_> {
     }
 }
 
-#[async_recursion]
 pub(crate) async fn edit_order_of_children_items(
     item_node: &ItemNode<'_>,
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
 ) -> Result<(), ()> {
     let smaller = item_node.get_smaller();
-    let list = chain!(
-        once(EditOrderOfChildren::Done),
-        smaller
-            .iter()
-            .map(|item_node| EditOrderOfChildren::Item(DisplayItem::new(item_node.get_item())))
-    )
-    .collect::<Vec<_>>();
-    let selection = Select::new("Select an item to move", list).prompt();
-    match selection {
-        Ok(EditOrderOfChildren::Item(selection)) => {
-            let selected_item = selection.get_item();
-            let items = smaller
+    loop {
+        let list = chain!(
+            once(EditOrderOfChildren::Done),
+            smaller
                 .iter()
-                .map(|x| x.get_item())
-                //Don't include the item that was selected
-                .filter(|x| *x != selected_item)
-                .collect::<Vec<_>>();
-            let list = HigherPriorityThan::create_list(&items);
-            let selected =
-                Select::new("Select new position, higher priority than this|", list).prompt();
-            match selected {
-                Ok(selected) => {
-                    send_to_data_storage_layer
-                        .send(DataLayerCommands::ParentItemWithExistingItem {
-                            child: selected_item.get_surreal_record_id().clone(),
-                            parent: item_node.get_surreal_record_id().clone(),
-                            higher_priority_than_this: selected.into(),
-                        })
-                        .await
-                        .unwrap();
-                    Ok(())
+                .map(|item_node| EditOrderOfChildren::Item(DisplayItem::new(item_node.get_item())))
+        )
+        .collect::<Vec<_>>();
+        let selection = Select::new("Select an item to move", list).prompt();
+        return match selection {
+            Ok(EditOrderOfChildren::Item(selection)) => {
+                let selected_item = selection.get_item();
+                let items = smaller
+                    .iter()
+                    .map(|x| x.get_item())
+                    //Don't include the item that was selected
+                    .filter(|x| *x != selected_item)
+                    .collect::<Vec<_>>();
+                let list = HigherPriorityThan::create_list(&items);
+                let selected =
+                    Select::new("Select new position, higher priority than this|", list).prompt();
+                match selected {
+                    Ok(selected) => {
+                        if let Err(would_create_a_cycle) =
+                            DataLayerCommands::parent_item_with_existing_item(
+                                send_to_data_storage_layer,
+                                selected_item.get_surreal_record_id().clone(),
+                                item_node.get_surreal_record_id().clone(),
+                                selected.into(),
+                            )
+                            .await
+                            .unwrap()
+                        {
+                            println!("{}", would_create_a_cycle);
+                        }
+                        Ok(())
+                    }
+                    //Back to picking which item to move
+                    Err(InquireError::OperationCanceled) => continue,
+                    Err(InquireError::OperationInterrupted) => Err(()),
+                    Err(err) => todo!("Unexpected error: {:?}", err),
                 }
-                Err(InquireError::OperationCanceled) => {
-                    edit_order_of_children_items(item_node, send_to_data_storage_layer).await
-                }
-                Err(InquireError::OperationInterrupted) => Err(()),
-                Err(err) => todo!("Unexpected error: {:?}", err),
             }
-        }
-        Ok(EditOrderOfChildren::Done) => Ok(()),
-        Err(InquireError::OperationCanceled) => todo!("Return to what calls this"),
-        Err(InquireError::OperationInterrupted) => Err(()),
-        Err(err) => panic!("Unexpected error: {:?}", err),
+            Ok(EditOrderOfChildren::Done) => Ok(()),
+            Err(InquireError::OperationCanceled) => Ok(()),
+            Err(InquireError::OperationInterrupted) => Err(()),
+            Err(err) => panic!("Unexpected error: {:?}", err),
+        };
     }
 }
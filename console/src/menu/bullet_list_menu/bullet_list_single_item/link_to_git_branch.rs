@@ -0,0 +1,113 @@
+use inquire::InquireError;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::item::Item,
+    git::{get_item_reference, suggest_branch_name},
+    menu::prompt::Text,
+    surrealdb_layer::{surreal_item::GitBranch, DataLayerCommands},
+};
+
+/// Defaults to the branch already linked, otherwise to the current directory and a branch named after the item
+pub(crate) async fn link_to_git_branch(
+    item: &Item<'_>,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let default_repository = match item.get_git_branch() {
+        Some(git_branch) => git_branch.repository.clone(),
+        None => std::env::current_dir()
+            .map(|x| x.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+    };
+    let repository = match Text::new("Which git repository?")
+        .with_default(&default_repository)
+        .prompt()
+    {
+        Ok(repository) => repository,
+        Err(InquireError::OperationCanceled) => return Ok(()),
+        Err(InquireError::OperationInterrupted) => return Err(()),
+        Err(err) => todo!("Unexpected {}", err),
+    };
+
+    let default_branch = match item.get_git_branch() {
+        Some(git_branch) => git_branch.branch.clone(),
+        None => suggest_branch_name(item.get_summary()),
+    };
+    let branch = match Text::new("Which branch?")
+        .with_default(&default_branch)
+        .prompt()
+    {
+        Ok(branch) => branch,
+        Err(InquireError::OperationCanceled) => return Ok(()),
+        Err(InquireError::OperationInterrupted) => return Err(()),
+        Err(err) => todo!("Unexpected {}", err),
+    };
+
+    send_to_data_storage_layer
+        .send(DataLayerCommands::UpdateItemGitBranch(
+            item.get_surreal_record_id().clone(),
+            Some(Box::new(GitBranch { repository, branch })),
+        ))
+        .await
+        .unwrap();
+    println!(
+        "Mention {} in commit messages to attach them to this item",
+        get_item_reference(item)
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use tokio::sync::mpsc;
+
+    use crate::{
+        menu::prompt::scripted::{run_scripted, Answer},
+        new_item::NewItem,
+        surrealdb_layer::{
+            data_storage_start_and_run, surreal_item::GitBranch, surreal_tables::SurrealTables,
+            DataLayerCommands,
+        },
+    };
+
+    use super::link_to_git_branch;
+
+    #[tokio::test]
+    async fn the_current_directory_and_a_branch_named_after_the_item_are_the_defaults() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Fix the login page".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let items = surreal_tables.make_items();
+
+        run_scripted(
+            [Answer::Text("".into()), Answer::Text("".into())],
+            link_to_git_branch(&items[0], &sender),
+        )
+        .await
+        .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(
+            surreal_tables.surreal_items[0].git_branch.as_deref(),
+            Some(&GitBranch {
+                repository: std::env::current_dir()
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned(),
+                branch: "fix-the-login-page".into(),
+            })
+        );
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+}
@@ -1,8 +1,7 @@
 use std::cmp::Ordering;
 
-use async_recursion::async_recursion;
 use chrono::Utc;
-use inquire::{InquireError, Select};
+use inquire::InquireError;
 use tokio::sync::mpsc::Sender;
 
 use crate::{
@@ -12,8 +11,8 @@ use crate::{
     },
     display::display_item_node::DisplayItemNode,
     menu::{
-        bullet_list_menu::bullet_list_single_item::ItemTypeSelection,
-        select_higher_priority_than_this::select_higher_priority_than_this,
+        bullet_list_menu::bullet_list_single_item::ItemTypeSelection, prompt::Select,
+        select_higher_priority_than_this::select_higher_priority_than_this, LeaveOutCycles,
     },
     node::item_node::ItemNode,
     surrealdb_layer::{surreal_tables::SurrealTables, DataLayerCommands},
@@ -28,9 +27,11 @@ pub(crate) async fn parent_to_a_motivation(
         .unwrap();
     let now = Utc::now();
     let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+    let mut cycles = LeaveOutCycles::new(base_data.get_surreal_tables());
     let active_items = base_data.get_active_items();
     let items = active_items
         .filter_just_motivations()
+        .filter(|x| cycles.keep(parent_this, x))
         .map(|x| {
             ItemNode::new(
                 x,
@@ -41,6 +42,7 @@ pub(crate) async fn parent_to_a_motivation(
         })
         .collect::<Vec<_>>();
     let list = items.iter().map(DisplayItemNode::new).collect::<Vec<_>>();
+    cycles.print_summary();
 
     let selection = Select::new("Select from the below list|", list).prompt();
     match selection {
@@ -56,14 +58,17 @@ pub(crate) async fn parent_to_a_motivation(
             } else {
                 None
             };
-            send_to_data_storage_layer
-                .send(DataLayerCommands::ParentItemWithExistingItem {
-                    child: parent_this.get_surreal_record_id().clone(),
-                    parent: parent.get_surreal_record_id().clone(),
-                    higher_priority_than_this,
-                })
-                .await
-                .unwrap();
+            if let Err(would_create_a_cycle) = DataLayerCommands::parent_item_with_existing_item(
+                send_to_data_storage_layer,
+                parent_this.get_surreal_record_id().clone(),
+                parent.get_surreal_record_id().clone(),
+                higher_priority_than_this,
+            )
+            .await
+            .unwrap()
+            {
+                println!("{}", would_create_a_cycle);
+            }
             Ok(())
         }
         Err(InquireError::OperationCanceled | InquireError::InvalidConfiguration(_)) => {
@@ -84,10 +89,12 @@ pub(crate) async fn parent_to_a_goal_or_motivation(
         .unwrap();
     let now = Utc::now();
     let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+    let mut cycles = LeaveOutCycles::new(base_data.get_surreal_tables());
     let active_items = base_data.get_active_items();
     let mut list = active_items
         .iter()
         .filter(|x| x.is_type_goal() || x.is_type_motivation())
+        .filter(|x| cycles.keep(parent_this, x))
         .map(|item| {
             ItemNode::new(
                 item,
@@ -110,6 +117,7 @@ pub(crate) async fn parent_to_a_goal_or_motivation(
             Ordering::Equal
         }
     });
+    cycles.print_summary();
 
     let list = list.iter().map(DisplayItemNode::new).collect::<Vec<_>>();
 
@@ -128,14 +136,17 @@ pub(crate) async fn parent_to_a_goal_or_motivation(
             } else {
                 None
             };
-            send_to_data_storage_layer
-                .send(DataLayerCommands::ParentItemWithExistingItem {
-                    child: parent_this.get_surreal_record_id().clone(),
-                    parent: parent.get_surreal_record_id().clone(),
-                    higher_priority_than_this,
-                })
-                .await
-                .unwrap();
+            if let Err(would_create_a_cycle) = DataLayerCommands::parent_item_with_existing_item(
+                send_to_data_storage_layer,
+                parent_this.get_surreal_record_id().clone(),
+                parent.get_surreal_record_id().clone(),
+                higher_priority_than_this,
+            )
+            .await
+            .unwrap()
+            {
+                println!("{}", would_create_a_cycle);
+            }
             Ok(())
         }
         Err(InquireError::OperationCanceled) => {
@@ -152,84 +163,74 @@ pub(crate) async fn parent_to_a_goal_or_motivation(
     }
 }
 
-#[async_recursion]
 async fn parent_to_a_motivation_new_motivation(
     parent_this: &Item<'_>,
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
 ) -> Result<(), ()> {
-    let list = ItemTypeSelection::create_list_just_motivations();
-    let selection = Select::new("Select from the below list|", list).prompt();
-    match selection {
-        Ok(ItemTypeSelection::NormalHelp) => {
-            ItemTypeSelection::print_normal_help();
-            parent_to_a_motivation_new_motivation(parent_this, send_to_data_storage_layer).await
-        }
-        Ok(ItemTypeSelection::ResponsiveHelp) => {
-            ItemTypeSelection::print_responsive_help();
-            parent_to_a_motivation_new_motivation(parent_this, send_to_data_storage_layer).await
-        }
-        Ok(item_type_selection) => {
-            let new_item = item_type_selection.create_new_item_prompt_user_for_summary();
-            send_to_data_storage_layer
-                .send(DataLayerCommands::ParentNewItemWithAnExistingChildItem {
-                    child: parent_this.get_surreal_record_id().clone(),
-                    parent_new_item: new_item,
-                })
-                .await
-                .unwrap();
-            Ok(())
-        }
-        Err(InquireError::OperationCanceled) => {
-            todo!("I need to go back to what first called this");
-        }
-        Err(InquireError::OperationInterrupted) => Err(()),
-        Err(err) => {
-            todo!("Error: {:?}", err);
-        }
+    loop {
+        let list = ItemTypeSelection::create_list_just_motivations();
+        let selection = Select::new("Select from the below list|", list).prompt();
+        return match selection {
+            Ok(ItemTypeSelection::NormalHelp) => {
+                ItemTypeSelection::print_normal_help();
+                continue;
+            }
+            Ok(ItemTypeSelection::ResponsiveHelp) => {
+                ItemTypeSelection::print_responsive_help();
+                continue;
+            }
+            Ok(item_type_selection) => {
+                let new_item = item_type_selection.create_new_item_prompt_user_for_summary();
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::ParentNewItemWithAnExistingChildItem {
+                        child: parent_this.get_surreal_record_id().clone(),
+                        parent_new_item: new_item,
+                    })
+                    .await
+                    .unwrap();
+                Ok(())
+            }
+            Err(InquireError::OperationCanceled) => Ok(()),
+            Err(InquireError::OperationInterrupted) => Err(()),
+            Err(err) => {
+                todo!("Error: {:?}", err);
+            }
+        };
     }
 }
 
-#[async_recursion]
 async fn parent_to_a_goal_or_motivation_new_goal_or_motivation(
     parent_this: &Item<'_>,
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
 ) -> Result<(), ()> {
-    let list = ItemTypeSelection::create_list_goals_and_motivations();
-    let selection = Select::new("Select from the below list|", list).prompt();
-    match selection {
-        Ok(ItemTypeSelection::NormalHelp) => {
-            ItemTypeSelection::print_normal_help();
-            parent_to_a_goal_or_motivation_new_goal_or_motivation(
-                parent_this,
-                send_to_data_storage_layer,
-            )
-            .await
-        }
-        Ok(ItemTypeSelection::ResponsiveHelp) => {
-            ItemTypeSelection::print_responsive_help();
-            parent_to_a_goal_or_motivation_new_goal_or_motivation(
-                parent_this,
-                send_to_data_storage_layer,
-            )
-            .await
-        }
-        Ok(item_type_selection) => {
-            let new_item = item_type_selection.create_new_item_prompt_user_for_summary();
-            send_to_data_storage_layer
-                .send(DataLayerCommands::ParentNewItemWithAnExistingChildItem {
-                    child: parent_this.get_surreal_record_id().clone(),
-                    parent_new_item: new_item,
-                })
-                .await
-                .unwrap();
-            Ok(())
-        }
-        Err(InquireError::OperationCanceled) => {
-            todo!("I need to go back to what first called this");
-        }
-        Err(InquireError::OperationInterrupted) => Err(()),
-        Err(err) => {
-            todo!("Error: {:?}", err);
-        }
+    loop {
+        let list = ItemTypeSelection::create_list_goals_and_motivations();
+        let selection = Select::new("Select from the below list|", list).prompt();
+        return match selection {
+            Ok(ItemTypeSelection::NormalHelp) => {
+                ItemTypeSelection::print_normal_help();
+                continue;
+            }
+            Ok(ItemTypeSelection::ResponsiveHelp) => {
+                ItemTypeSelection::print_responsive_help();
+                continue;
+            }
+            Ok(item_type_selection) => {
+                let new_item = item_type_selection.create_new_item_prompt_user_for_summary();
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::ParentNewItemWithAnExistingChildItem {
+                        child: parent_this.get_surreal_record_id().clone(),
+                        parent_new_item: new_item,
+                    })
+                    .await
+                    .unwrap();
+                Ok(())
+            }
+            Err(InquireError::OperationCanceled) => Ok(()),
+            Err(InquireError::OperationInterrupted) => Err(()),
+            Err(err) => {
+                todo!("Error: {:?}", err);
+            }
+        };
     }
 }
@@ -1,15 +1,17 @@
-use async_recursion::async_recursion;
 use tokio::sync::mpsc::Sender;
 
 use crate::{
     base_data::item::Item,
-    menu::staging_query::{mentally_resident_query, on_deck_query},
+    menu::{
+        prompt::Select,
+        staging_query::{mentally_resident_query, on_deck_query},
+    },
     surrealdb_layer::{
         surreal_item::{Responsibility, Staging},
         DataLayerCommands,
     },
 };
-use inquire::{InquireError, Select};
+use inquire::InquireError;
 use std::fmt::Display;
 
 #[derive(PartialEq, Eq, Copy, Clone)]
@@ -61,7 +63,6 @@ impl StagingMenuSelection {
     }
 }
 
-#[async_recursion]
 pub(crate) async fn present_set_staging_menu(
     selected: &Item<'_>,
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
@@ -79,14 +80,7 @@ pub(crate) async fn present_set_staging_menu(
                 let result = mentally_resident_query().await;
                 match result {
                     Ok(mentally_resident) => mentally_resident,
-                    Err(InquireError::OperationCanceled) => {
-                        return present_set_staging_menu(
-                            selected,
-                            send_to_data_storage_layer,
-                            default_selection,
-                        )
-                        .await
-                    }
+                    Err(InquireError::OperationCanceled) => continue,
                     Err(InquireError::OperationInterrupted) => return Err(()),
                     Err(err) => todo!("{:?}", err),
                 }
@@ -95,14 +89,7 @@ pub(crate) async fn present_set_staging_menu(
                 let result = on_deck_query().await;
                 match result {
                     Ok(staging) => staging,
-                    Err(InquireError::OperationCanceled) => {
-                        return present_set_staging_menu(
-                            selected,
-                            send_to_data_storage_layer,
-                            default_selection,
-                        )
-                        .await
-                    }
+                    Err(InquireError::OperationCanceled) => continue,
                     Err(InquireError::OperationInterrupted) => return Err(()),
                     Err(err) => todo!("{:?}", err),
                 }
@@ -121,10 +108,7 @@ pub(crate) async fn present_set_staging_menu(
                 return Ok(());
             }
             Err(InquireError::OperationInterrupted) => return Err(()),
-            Err(InquireError::OperationCanceled) => {
-                // Just continue because we don't know exactly what to go back to
-                return Ok(());
-            }
+            Err(InquireError::OperationCanceled) => return Ok(()),
             Err(err) => todo!("{:?}", err),
         };
         break staging;
@@ -1,11 +1,12 @@
-use async_recursion::async_recursion;
 use chrono::Utc;
-use inquire::{InquireError, Select};
+use inquire::InquireError;
 use tokio::sync::mpsc::Sender;
 
 use crate::{
     base_data::{item::Item, BaseData},
     display::display_item::DisplayItem,
+    menu::prompt::Select,
+    menu::LeaveOutCycles,
     surrealdb_layer::DataLayerCommands,
 };
 
@@ -20,24 +21,28 @@ pub(crate) async fn something_else_should_be_done_first(
         .unwrap();
     let now = Utc::now();
     let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+    let mut cycles = LeaveOutCycles::new(base_data.get_surreal_tables());
     let list = base_data
         .get_active_items()
         .iter()
         .copied()
+        .filter(|x| cycles.keep(x, unable_to_do))
         .map(DisplayItem::new)
         .collect::<Vec<_>>();
     let selection = Select::new("Select from the below list|", list).prompt();
+    cycles.print_summary();
     match selection {
         Ok(should_be_done_first) => {
-            send_to_data_storage_layer
-                .send(DataLayerCommands::CoverItemWithAnExistingItem {
-                    item_to_be_covered: unable_to_do.get_surreal_record_id().clone(),
-                    item_that_should_do_the_covering: should_be_done_first
-                        .get_surreal_record_id()
-                        .clone(),
-                })
-                .await
-                .unwrap();
+            if let Err(would_create_a_cycle) = DataLayerCommands::cover_item_with_an_existing_item(
+                send_to_data_storage_layer,
+                unable_to_do.get_surreal_record_id().clone(),
+                should_be_done_first.get_surreal_record_id().clone(),
+            )
+            .await
+            .unwrap()
+            {
+                println!("{}", would_create_a_cycle);
+            }
             Ok(())
         }
         Err(InquireError::OperationCanceled | InquireError::InvalidConfiguration(_)) => {
@@ -51,35 +56,36 @@ pub(crate) async fn something_else_should_be_done_first(
     }
 }
 
-#[async_recursion]
 pub(crate) async fn something_else_should_be_done_first_new_item(
     unable_to_do: &Item<'_>,
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
 ) -> Result<(), ()> {
-    let list = ItemTypeSelection::create_list();
-    let selection = Select::new("Select from the below list|", list).prompt();
-    match selection {
-        Ok(ItemTypeSelection::NormalHelp) => {
-            ItemTypeSelection::print_normal_help();
-            something_else_should_be_done_first_new_item(unable_to_do, send_to_data_storage_layer)
-                .await
-        }
-        Ok(ItemTypeSelection::ResponsiveHelp) => {
-            ItemTypeSelection::print_responsive_help();
-            something_else_should_be_done_first_new_item(unable_to_do, send_to_data_storage_layer)
-                .await
-        }
-        Ok(selection) => {
-            let new_item = selection.create_new_item_prompt_user_for_summary();
-            send_to_data_storage_layer
-                .send(DataLayerCommands::CoverItemWithANewItem {
-                    cover_this: unable_to_do.get_surreal_record_id().clone(),
-                    cover_with: new_item,
-                })
-                .await
-                .unwrap();
-            Ok(())
-        }
-        Err(_) => todo!(),
+    loop {
+        let list = ItemTypeSelection::create_list();
+        let selection = Select::new("Select from the below list|", list).prompt();
+        return match selection {
+            Ok(ItemTypeSelection::NormalHelp) => {
+                ItemTypeSelection::print_normal_help();
+                continue;
+            }
+            Ok(ItemTypeSelection::ResponsiveHelp) => {
+                ItemTypeSelection::print_responsive_help();
+                continue;
+            }
+            Ok(selection) => {
+                let new_item = selection.create_new_item_prompt_user_for_summary();
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::CoverItemWithANewItem {
+                        cover_this: unable_to_do.get_surreal_record_id().clone(),
+                        cover_with: new_item,
+                    })
+                    .await
+                    .unwrap();
+                Ok(())
+            }
+            Err(InquireError::OperationCanceled) => Ok(()),
+            Err(InquireError::OperationInterrupted) => Err(()),
+            Err(err) => todo!("Unexpected {}", err),
+        };
     }
 }
@@ -1,20 +1,20 @@
 use std::fmt::Display;
 
 use chrono::{DateTime, Utc};
-use inquire::{InquireError, Select};
+use inquire::InquireError;
 use tokio::sync::mpsc::Sender;
 
 use crate::{
-    base_data::{covering::Covering, covering_until_date_time::CoveringUntilDateTime, item::Item},
-    menu::{
-        bullet_list_menu::bullet_list_single_item::present_bullet_list_item_selected,
-        top_menu::capture,
-    },
+    base_data::item::Item,
+    display::display_focus_suggestion::DisplayFocusSuggestion,
+    git::{check_out_branch, CheckedOut},
+    menu::{prompt::Select, top_menu::capture, waiting_for::start_waiting_for},
     node::{item_node::ItemNode, item_status::ItemStatus},
     surrealdb_layer::DataLayerCommands,
+    systems::busy_blocks::{find_busy_blocks, suggest_focus_session},
 };
 
-use super::finish_bullet_item;
+use super::{finish_bullet_item, ItemMenuDone};
 
 enum WorkingOnNow {
     CaptureAnUnrelatedItem,
@@ -62,18 +62,33 @@ impl WorkingOnNow {
 
 pub(crate) async fn starting_to_work_on_this_now(
     currently_working_on: &ItemStatus<'_>,
-    all_item_status: &[ItemStatus<'_>],
     current_date_time: &DateTime<Utc>,
-    all_coverings: &[Covering<'_>],
-    all_snoozed: &[&CoveringUntilDateTime<'_>],
     all_items: &[&Item<'_>],
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
-) -> Result<(), ()> {
+) -> Result<ItemMenuDone, ()> {
+    let busy_blocks = find_busy_blocks(all_items, *current_date_time);
+    let focus_suggestion = suggest_focus_session(
+        &busy_blocks,
+        currently_working_on.get_item(),
+        *current_date_time,
+    );
+    println!("{}", DisplayFocusSuggestion::new(&focus_suggestion));
+    if let Some(git_branch) = currently_working_on.get_item().get_git_branch() {
+        match check_out_branch(git_branch) {
+            Ok(CheckedOut::Existing) => println!("Checked out {}", git_branch.branch),
+            Ok(CheckedOut::Created) => println!("Created and checked out {}", git_branch.branch),
+            Err(err) => println!("Unable to check out {}: {}", git_branch.branch, err),
+        }
+    }
+
     let list = WorkingOnNow::make_list(currently_working_on.get_item_node());
 
     let selection = Select::new("Select from the below list|", list).prompt();
     match selection {
-        Ok(WorkingOnNow::CaptureAnUnrelatedItem) => capture(send_to_data_storage_layer).await,
+        Ok(WorkingOnNow::CaptureAnUnrelatedItem) => {
+            capture(send_to_data_storage_layer).await?;
+            Ok(ItemMenuDone::Back)
+        }
         Ok(WorkingOnNow::DefineFutureItemOntoParent) => {
             todo!("Define future item onto parent")
         }
@@ -84,32 +99,15 @@ pub(crate) async fn starting_to_work_on_this_now(
             todo!("Worked on this but more to do before it is finished")
         }
         Ok(WorkingOnNow::DidSomethingAndNowIAmWaitingForAResponseOrForACommandToFinish) => {
-            todo!("I did something and now I am waiting for a response or for a command to finish")
+            start_waiting_for(currently_working_on.get_item(), send_to_data_storage_layer).await?;
+            Ok(ItemMenuDone::Back)
         }
         Ok(WorkingOnNow::IFinished) => {
-            finish_bullet_item(
-                currently_working_on,
-                all_item_status,
-                all_coverings,
-                all_snoozed,
-                all_items,
-                current_date_time,
-                send_to_data_storage_layer,
-            )
-            .await
-        }
-        Err(InquireError::OperationCanceled) => {
-            present_bullet_list_item_selected(
-                currently_working_on,
-                all_item_status,
-                current_date_time,
-                all_coverings,
-                all_snoozed,
-                all_items,
-                send_to_data_storage_layer,
-            )
-            .await
+            finish_bullet_item(currently_working_on, send_to_data_storage_layer).await
         }
+        Err(InquireError::OperationCanceled) => Ok(ItemMenuDone::ShowItem(
+            currently_working_on.get_surreal_record_id().clone(),
+        )),
         Err(InquireError::OperationInterrupted) => Err(()),
         Err(err) => {
             todo!("Error: {:?}", err);
@@ -1,6 +1,5 @@
-use async_recursion::async_recursion;
 use chrono::Utc;
-use inquire::{InquireError, Select};
+use inquire::InquireError;
 use tokio::sync::mpsc::Sender;
 
 use crate::{
@@ -13,8 +12,10 @@ use crate::{
         bullet_list_menu::bullet_list_single_item::set_staging::{
             present_set_staging_menu, StagingMenuSelection,
         },
+        prompt::Select,
         select_higher_priority_than_this::select_higher_priority_than_this,
         staging_query::{mentally_resident_query, on_deck_query},
+        LeaveOutCycles,
     },
     node::item_node::ItemNode,
     surrealdb_layer::{surreal_item::Staging, surreal_tables::SurrealTables, DataLayerCommands},
@@ -31,12 +32,15 @@ pub(crate) async fn state_a_smaller_next_step(
         .unwrap();
     let now = Utc::now();
     let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+    let mut cycles = LeaveOutCycles::new(base_data.get_surreal_tables());
     let active_items = base_data
         .get_active_items()
         .iter()
         .filter(|x| **x != selected_item.get_item())
+        .filter(|x| cycles.keep(x, selected_item.get_item()))
         .copied()
         .collect::<Vec<_>>();
+    cycles.print_summary();
     let mut list = Vec::default();
     if selected_item.is_type_motivation() {
         list.extend(active_items.filter_just_motivations().map(DisplayItem::new));
@@ -63,14 +67,17 @@ pub(crate) async fn state_a_smaller_next_step(
             } else {
                 None
             };
-            send_to_data_storage_layer
-                .send(DataLayerCommands::ParentItemWithExistingItem {
-                    child: child.get_surreal_record_id().clone(),
-                    parent: parent.get_surreal_record_id().clone(),
-                    higher_priority_than_this,
-                })
-                .await
-                .unwrap();
+            if let Err(would_create_a_cycle) = DataLayerCommands::parent_item_with_existing_item(
+                send_to_data_storage_layer,
+                child.get_surreal_record_id().clone(),
+                parent.get_surreal_record_id().clone(),
+                higher_priority_than_this,
+            )
+            .await
+            .unwrap()
+            {
+                println!("{}", would_create_a_cycle);
+            }
 
             println!(
                 "Please update Staging for {}",
@@ -100,88 +107,87 @@ pub(crate) async fn state_a_smaller_next_step(
     }
 }
 
-#[async_recursion]
 pub(crate) async fn state_a_smaller_next_step_new_item(
     selected_item: &ItemNode<'_>,
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
 ) -> Result<(), ()> {
-    let list = ItemTypeSelection::create_list();
+    loop {
+        let list = ItemTypeSelection::create_list();
 
-    let selection = Select::new("Select from the below list|", list).prompt();
-    match selection {
-        Ok(ItemTypeSelection::NormalHelp) => {
-            ItemTypeSelection::print_normal_help();
-            state_a_smaller_next_step_new_item(selected_item, send_to_data_storage_layer).await
-        }
-        Ok(ItemTypeSelection::ResponsiveHelp) => {
-            ItemTypeSelection::print_responsive_help();
-            state_a_smaller_next_step_new_item(selected_item, send_to_data_storage_layer).await
-        }
-        Ok(item_type_selection) => {
-            let mut new_item = item_type_selection.create_new_item_prompt_user_for_summary();
-            let higher_priority_than_this = if selected_item.has_active_children() {
-                let items = selected_item
-                    .get_smaller()
-                    .iter()
-                    .map(|x| x.get_item())
-                    .collect::<Vec<_>>();
-                select_higher_priority_than_this(&items)
-            } else {
-                None
-            };
-            let parent = selected_item;
+        let selection = Select::new("Select from the below list|", list).prompt();
+        return match selection {
+            Ok(ItemTypeSelection::NormalHelp) => {
+                ItemTypeSelection::print_normal_help();
+                continue;
+            }
+            Ok(ItemTypeSelection::ResponsiveHelp) => {
+                ItemTypeSelection::print_responsive_help();
+                continue;
+            }
+            Ok(item_type_selection) => {
+                let mut new_item = item_type_selection.create_new_item_prompt_user_for_summary();
+                let higher_priority_than_this = if selected_item.has_active_children() {
+                    let items = selected_item
+                        .get_smaller()
+                        .iter()
+                        .map(|x| x.get_item())
+                        .collect::<Vec<_>>();
+                    select_higher_priority_than_this(&items)
+                } else {
+                    None
+                };
+                let parent = selected_item;
 
-            let (list, starting_cursor) =
-                StagingMenuSelection::make_list(Some(StagingMenuSelection::NotSet));
+                let (list, starting_cursor) =
+                    StagingMenuSelection::make_list(Some(StagingMenuSelection::NotSet));
 
-            let selection = Select::new("Select from the below list|", list)
-                .with_starting_cursor(starting_cursor)
-                .prompt()
-                .unwrap();
-            new_item.staging = match selection {
-                StagingMenuSelection::NotSet => Staging::NotSet,
-                StagingMenuSelection::MentallyResident => {
-                    let result = mentally_resident_query().await;
-                    match result {
-                        Ok(mentally_resident) => mentally_resident,
-                        Err(InquireError::OperationCanceled) => {
-                            todo!("I probably need to refactor this into a function so I can use recursion here")
+                let selection = Select::new("Select from the below list|", list)
+                    .with_starting_cursor(starting_cursor)
+                    .prompt()
+                    .unwrap();
+                new_item.staging = match selection {
+                    StagingMenuSelection::NotSet => Staging::NotSet,
+                    StagingMenuSelection::MentallyResident => {
+                        let result = mentally_resident_query().await;
+                        match result {
+                            Ok(mentally_resident) => mentally_resident,
+                            //Start over from picking the type of the new item
+                            Err(InquireError::OperationCanceled) => continue,
+                            Err(InquireError::OperationInterrupted) => return Err(()),
+                            Err(err) => todo!("{:?}", err),
                         }
-                        Err(InquireError::OperationInterrupted) => return Err(()),
-                        Err(err) => todo!("{:?}", err),
                     }
-                }
-                StagingMenuSelection::OnDeck => {
-                    let result = on_deck_query().await;
-                    match result {
-                        Ok(staging) => staging,
-                        Err(InquireError::OperationCanceled) => {
-                            todo!("I probably need to refactor this into a function so I can use recursion here")
+                    StagingMenuSelection::OnDeck => {
+                        let result = on_deck_query().await;
+                        match result {
+                            Ok(staging) => staging,
+                            //Start over from picking the type of the new item
+                            Err(InquireError::OperationCanceled) => continue,
+                            Err(InquireError::OperationInterrupted) => return Err(()),
+                            Err(err) => todo!("{:?}", err),
                         }
-                        Err(InquireError::OperationInterrupted) => return Err(()),
-                        Err(err) => todo!("{:?}", err),
                     }
-                }
-                StagingMenuSelection::Planned => Staging::Planned,
-                StagingMenuSelection::ThinkingAbout => Staging::ThinkingAbout,
-                StagingMenuSelection::Released => Staging::Released,
-                StagingMenuSelection::MakeItemReactive => {
-                    todo!("I need to modify the return type to account for this different choice and pass up that information")
-                }
-            };
+                    StagingMenuSelection::Planned => Staging::Planned,
+                    StagingMenuSelection::ThinkingAbout => Staging::ThinkingAbout,
+                    StagingMenuSelection::Released => Staging::Released,
+                    StagingMenuSelection::MakeItemReactive => {
+                        todo!("I need to modify the return type to account for this different choice and pass up that information")
+                    }
+                };
 
-            send_to_data_storage_layer
-                .send(DataLayerCommands::ParentItemWithANewChildItem {
-                    child: new_item,
-                    parent: parent.get_surreal_record_id().clone(),
-                    higher_priority_than_this,
-                })
-                .await
-                .unwrap();
-            Ok(())
-        }
-        Err(InquireError::OperationCanceled) => todo!(),
-        Err(InquireError::OperationInterrupted) => Err(()),
-        Err(err) => todo!("Unexpected {}", err),
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::ParentItemWithANewChildItem {
+                        child: new_item,
+                        parent: parent.get_surreal_record_id().clone(),
+                        higher_priority_than_this,
+                    })
+                    .await
+                    .unwrap();
+                Ok(())
+            }
+            Err(InquireError::OperationCanceled) => Ok(()),
+            Err(InquireError::OperationInterrupted) => Err(()),
+            Err(err) => todo!("Unexpected {}", err),
+        };
     }
 }
@@ -1,10 +1,10 @@
 pub(crate) mod define_facing;
+pub(crate) mod expectations_report;
 
 use std::fmt::Display;
 
-use async_recursion::async_recursion;
 use chrono::Utc;
-use inquire::{Editor, InquireError, Select};
+use inquire::InquireError;
 use tokio::sync::mpsc::Sender;
 
 use crate::{
@@ -14,10 +14,14 @@ use crate::{
         item::{Item, ItemVecExtensions},
         BaseData,
     },
-    menu::top_menu::present_top_menu,
     menu::{
         bullet_list_menu::bullet_list_single_item::cover_with_item,
-        expectations::define_facing::define_facing, staging_query::on_deck_query,
+        expectations::{
+            define_facing::define_facing, expectations_report::view_expectations_report,
+        },
+        navigation::Navigation,
+        prompt::{Editor, Select},
+        staging_query::on_deck_query,
         update_item_summary::update_item_summary,
     },
     node::item_node::ItemNode,
@@ -101,6 +105,7 @@ pub(crate) fn create_hope_nodes<'a>(
 
 enum ExpectationsMenuItem {
     DefineFacing,
+    ExpectationsReport,
     MentallyResidentProjects,
     OnDeckProjects,
     IntensionProjects,
@@ -112,6 +117,7 @@ impl Display for ExpectationsMenuItem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::DefineFacing => write!(f, "👀 Define Facing              👀"),
+            Self::ExpectationsReport => write!(f, "📋 Expectations Report        📋"),
             Self::MentallyResidentProjects => write!(f, "🧠 Mentally Resident Projects 🏗️"),
             Self::OnDeckProjects => write!(f, "🚧 On Deck Projects           🏗️"),
             Self::IntensionProjects => write!(f, "🌠 Intension Projects         🏗️"),
@@ -125,6 +131,7 @@ impl ExpectationsMenuItem {
     fn make_list() -> Vec<Self> {
         vec![
             Self::DefineFacing,
+            Self::ExpectationsReport,
             Self::MentallyResidentProjects,
             Self::OnDeckProjects,
             Self::IntensionProjects,
@@ -134,16 +141,19 @@ impl ExpectationsMenuItem {
     }
 }
 
-#[async_recursion]
+/// After one of the views the expectations menu is shown again, Esc goes back
 pub(crate) async fn view_expectations(
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
-) -> Result<(), ()> {
+) -> Result<Navigation, ()> {
     let list = ExpectationsMenuItem::make_list();
 
     let selection = Select::new("Select from the below list|", list).prompt();
 
-    match selection {
+    let result = match selection {
         Ok(ExpectationsMenuItem::DefineFacing) => define_facing(send_to_data_storage_layer).await,
+        Ok(ExpectationsMenuItem::ExpectationsReport) => {
+            view_expectations_report(send_to_data_storage_layer).await
+        }
         Ok(ExpectationsMenuItem::MentallyResidentProjects) => {
             view_mentally_resident_project_goals(send_to_data_storage_layer).await
         }
@@ -153,13 +163,13 @@ pub(crate) async fn view_expectations(
         Ok(ExpectationsMenuItem::MaintenanceItems) => {
             view_maintenance_hopes(send_to_data_storage_layer).await
         }
-        Err(InquireError::OperationCanceled) => present_top_menu(send_to_data_storage_layer).await,
+        Err(InquireError::OperationCanceled) => return Ok(Navigation::Back),
         Err(InquireError::OperationInterrupted) => Err(()),
         Err(err) => todo!("{}", err),
-    }
+    };
+    result.map(|()| Navigation::Stay)
 }
 
-#[async_recursion]
 pub(crate) async fn view_mentally_resident_project_goals(
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
 ) -> Result<(), ()> {
@@ -199,15 +209,13 @@ pub(crate) async fn view_mentally_resident_project_goals(
                 .await
             }
             Err(err) => match err {
-                InquireError::OperationCanceled => {
-                    present_top_menu(send_to_data_storage_layer).await
-                }
+                InquireError::OperationCanceled => Ok(()),
                 _ => panic!("Unexpected InquireError of {}", err),
             },
         }
     } else {
-        println!("Hope List is Empty, falling back to main menu.");
-        present_top_menu(send_to_data_storage_layer).await
+        println!("Hope List is Empty, falling back to the expectations menu.");
+        Ok(())
     }
 }
 
@@ -241,7 +249,6 @@ impl<'a> MaintenanceHopeItem<'a> {
     }
 }
 
-#[async_recursion]
 pub(crate) async fn view_maintenance_hopes(
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
 ) -> Result<(), ()> {
@@ -270,15 +277,13 @@ pub(crate) async fn view_maintenance_hopes(
         let selected = Select::new("Select from the below list|", list).prompt();
         match selected {
             Ok(MaintenanceHopeItem::MaintenanceHope(_hope_node)) => todo!(),
-            Err(InquireError::OperationCanceled) => {
-                present_top_menu(send_to_data_storage_layer).await
-            }
+            Err(InquireError::OperationCanceled) => Ok(()),
             Err(InquireError::OperationInterrupted) => Err(()),
             Err(err) => todo!("{}", err),
         }
     } else {
-        println!("Maintenance List is empty, falling back to main menu.");
-        present_top_menu(send_to_data_storage_layer).await
+        println!("Maintenance List is empty, falling back to the expectations menu.");
+        Ok(())
     }
 }
 
@@ -323,70 +328,67 @@ impl MentallyResidentGoalSelectedMenuItem {
     }
 }
 
-#[async_recursion]
 pub(crate) async fn present_mentally_resident_goal_selected_menu(
     goal_selected: &Item<'_>,
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
 ) -> Result<(), ()> {
-    let list = MentallyResidentGoalSelectedMenuItem::create_list();
-
-    let selection = Select::new("Select from the below list|", list)
-        .with_page_size(15)
-        .prompt();
-    match selection {
-        Ok(MentallyResidentGoalSelectedMenuItem::CoverWithNextStep) => {
-            cover_with_item(goal_selected, send_to_data_storage_layer).await
-        }
-        Ok(MentallyResidentGoalSelectedMenuItem::ProcessAndFinish) => {
-            process_and_finish_goal(goal_selected, send_to_data_storage_layer).await
-        }
-        Ok(MentallyResidentGoalSelectedMenuItem::SwitchToMaintenanceGoal) => {
-            switch_to_maintenance_item(goal_selected, send_to_data_storage_layer).await
-        }
-        Ok(MentallyResidentGoalSelectedMenuItem::SwitchToOnDeckGoal) => {
-            let result = on_deck_query().await;
-            match result {
-                Ok(staging) => {
-                    send_to_data_storage_layer
-                        .send(DataLayerCommands::UpdateItemStaging(
-                            goal_selected.get_surreal_record_id().clone(),
-                            staging,
-                        ))
-                        .await
-                        .unwrap();
-                    Ok(())
+    loop {
+        let list = MentallyResidentGoalSelectedMenuItem::create_list();
+
+        let selection = Select::new("Select from the below list|", list)
+            .with_page_size(15)
+            .prompt();
+        return match selection {
+            Ok(MentallyResidentGoalSelectedMenuItem::CoverWithNextStep) => {
+                cover_with_item(goal_selected, send_to_data_storage_layer).await
+            }
+            Ok(MentallyResidentGoalSelectedMenuItem::ProcessAndFinish) => {
+                process_and_finish_goal(goal_selected, send_to_data_storage_layer).await
+            }
+            Ok(MentallyResidentGoalSelectedMenuItem::SwitchToMaintenanceGoal) => {
+                switch_to_maintenance_item(goal_selected, send_to_data_storage_layer).await
+            }
+            Ok(MentallyResidentGoalSelectedMenuItem::SwitchToOnDeckGoal) => {
+                let result = on_deck_query().await;
+                match result {
+                    Ok(staging) => {
+                        send_to_data_storage_layer
+                            .send(DataLayerCommands::UpdateItemStaging(
+                                goal_selected.get_surreal_record_id().clone(),
+                                staging,
+                            ))
+                            .await
+                            .unwrap();
+                        Ok(())
+                    }
+                    Err(InquireError::OperationCanceled) => continue,
+                    Err(InquireError::OperationInterrupted) => Err(()),
+                    Err(err) => todo!("Unexpected InquireError of {}", err),
                 }
-                Err(InquireError::OperationCanceled) => {
-                    present_mentally_resident_goal_selected_menu(
-                        goal_selected,
-                        send_to_data_storage_layer,
-                    )
+            }
+            Ok(MentallyResidentGoalSelectedMenuItem::SwitchToPlannedGoal) => {
+                update_item_staging(goal_selected, send_to_data_storage_layer, Staging::Planned)
                     .await
-                }
-                Err(InquireError::OperationInterrupted) => Err(()),
-                Err(err) => todo!("Unexpected InquireError of {}", err),
             }
-        }
-        Ok(MentallyResidentGoalSelectedMenuItem::SwitchToPlannedGoal) => {
-            update_item_staging(goal_selected, send_to_data_storage_layer, Staging::Planned).await
-        }
-        Ok(MentallyResidentGoalSelectedMenuItem::SwitchToThinkingAboutGoal) => {
-            update_item_staging(
-                goal_selected,
-                send_to_data_storage_layer,
-                Staging::ThinkingAbout,
-            )
-            .await
-        }
-        Ok(MentallyResidentGoalSelectedMenuItem::ReleaseGoal) => {
-            update_item_staging(goal_selected, send_to_data_storage_layer, Staging::Released).await
-        }
-        Ok(MentallyResidentGoalSelectedMenuItem::UpdateSummary) => {
-            update_item_summary(goal_selected, send_to_data_storage_layer).await
-        }
-        Err(InquireError::OperationCanceled) => view_expectations(send_to_data_storage_layer).await,
-        Err(InquireError::OperationInterrupted) => Err(()),
-        Err(err) => todo!("{}", err),
+            Ok(MentallyResidentGoalSelectedMenuItem::SwitchToThinkingAboutGoal) => {
+                update_item_staging(
+                    goal_selected,
+                    send_to_data_storage_layer,
+                    Staging::ThinkingAbout,
+                )
+                .await
+            }
+            Ok(MentallyResidentGoalSelectedMenuItem::ReleaseGoal) => {
+                update_item_staging(goal_selected, send_to_data_storage_layer, Staging::Released)
+                    .await
+            }
+            Ok(MentallyResidentGoalSelectedMenuItem::UpdateSummary) => {
+                update_item_summary(goal_selected, send_to_data_storage_layer).await
+            }
+            Err(InquireError::OperationCanceled) => Ok(()),
+            Err(InquireError::OperationInterrupted) => Err(()),
+            Err(err) => todo!("{}", err),
+        };
     }
 }
 
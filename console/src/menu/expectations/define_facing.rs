@@ -1,8 +1,7 @@
 use std::fmt::Display;
 
-use async_recursion::async_recursion;
 use chrono::Utc;
-use inquire::{InquireError, Select};
+use inquire::InquireError;
 use tokio::sync::mpsc::Sender;
 
 use crate::{
@@ -10,7 +9,7 @@ use crate::{
     display::display_item_node::DisplayItemNode,
     menu::{
         bullet_list_menu::bullet_list_single_item::parent_to_a_goal_or_motivation::parent_to_a_goal_or_motivation,
-        select_person_or_group::select_person_or_group,
+        prompt::Select, select_person_or_group::select_person_or_group,
     },
     node::item_node::ItemNode,
     surrealdb_layer::{
@@ -19,9 +18,6 @@ use crate::{
     },
 };
 
-use super::view_expectations;
-
-#[async_recursion]
 pub(crate) async fn define_facing(
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
 ) -> Result<(), ()> {
@@ -62,9 +58,7 @@ pub(crate) async fn define_facing(
                 let item_node = selection.get_item_node();
                 single_item_define_facing(item_node, send_to_data_storage_layer).await?
             }
-            Err(InquireError::OperationCanceled) => {
-                view_expectations(send_to_data_storage_layer).await?
-            }
+            Err(InquireError::OperationCanceled) => return Ok(()),
             Err(InquireError::OperationInterrupted) => return Err(()),
             Err(err) => todo!("{:?}", err),
         }
@@ -141,99 +135,99 @@ impl From<HowWellDefinedSelection> for HowWellDefined {
     }
 }
 
-#[async_recursion]
 async fn single_item_define_facing(
     item_node: &ItemNode<'_>,
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
 ) -> Result<(), ()> {
-    let list = FacingOptions::get_list();
-    let selection = Select::new("Select a facing |", list).prompt();
+    loop {
+        let list = FacingOptions::get_list();
+        let selection = Select::new("Select a facing |", list).prompt();
 
-    match selection {
-        Ok(FacingOptions::PickParent) => {
-            parent_to_a_goal_or_motivation(item_node.get_item(), send_to_data_storage_layer).await
-        }
-        Ok(FacingOptions::ForMyself) => {
-            let list = HowWellDefinedSelection::get_list();
-            let selection = Select::new("Select How Well Defined |", list).prompt();
-            match selection {
-                Ok(selection) => {
-                    let facing = Facing::Myself(selection.into());
-                    send_to_data_storage_layer
-                        .send(DataLayerCommands::UpdateFacing(
-                            item_node.get_surreal_record_id().clone(),
-                            vec![facing],
-                        ))
-                        .await
-                        .unwrap();
-                    Ok(())
-                }
-                Err(InquireError::OperationCanceled) => {
-                    single_item_define_facing(item_node, send_to_data_storage_layer).await
-                }
-                Err(InquireError::OperationInterrupted) => Err(()),
-                Err(err) => todo!("{:?}", err),
+        return match selection {
+            Ok(FacingOptions::PickParent) => {
+                parent_to_a_goal_or_motivation(item_node.get_item(), send_to_data_storage_layer)
+                    .await
             }
-        }
-        Ok(FacingOptions::ForAnother) => {
-            let person_or_group = select_person_or_group(send_to_data_storage_layer)
-                .await
-                .unwrap();
-            let list = HowWellDefinedSelection::get_list();
-            let selection = Select::new("Select How Well Defined |", list).prompt();
-            match selection {
-                Ok(selection) => {
-                    let facing = Facing::Others {
-                        how_well_defined: selection.into(),
-                        who: person_or_group,
-                    };
-                    send_to_data_storage_layer
-                        .send(DataLayerCommands::UpdateFacing(
-                            item_node.get_surreal_record_id().clone(),
-                            vec![facing],
-                        ))
-                        .await
-                        .unwrap();
-                    Ok(())
-                }
-                Err(InquireError::OperationCanceled) => {
-                    single_item_define_facing(item_node, send_to_data_storage_layer).await
+            Ok(FacingOptions::ForMyself) => {
+                let list = HowWellDefinedSelection::get_list();
+                let selection = Select::new("Select How Well Defined |", list).prompt();
+                match selection {
+                    Ok(selection) => {
+                        let facing = Facing::Myself(selection.into());
+                        send_to_data_storage_layer
+                            .send(DataLayerCommands::UpdateFacing(
+                                item_node.get_surreal_record_id().clone(),
+                                vec![facing],
+                            ))
+                            .await
+                            .unwrap();
+                        Ok(())
+                    }
+                    //Back to picking a facing
+                    Err(InquireError::OperationCanceled) => continue,
+                    Err(InquireError::OperationInterrupted) => Err(()),
+                    Err(err) => todo!("{:?}", err),
                 }
-                Err(InquireError::OperationInterrupted) => Err(()),
-                Err(err) => todo!("{:?}", err),
             }
-        }
-        Ok(FacingOptions::ForMyselfAndAnother) => {
-            let person_or_group = select_person_or_group(send_to_data_storage_layer)
-                .await
-                .unwrap();
-            let list = HowWellDefinedSelection::get_list();
-            let selection = Select::new("Select How Well Defined |", list).prompt();
-            match selection {
-                Ok(selection) => {
-                    let myself_facing = Facing::Myself(selection.into());
-                    let others_facing = Facing::Others {
-                        how_well_defined: selection.into(),
-                        who: person_or_group,
-                    };
-                    send_to_data_storage_layer
-                        .send(DataLayerCommands::UpdateFacing(
-                            item_node.get_surreal_record_id().clone(),
-                            vec![myself_facing, others_facing],
-                        ))
-                        .await
-                        .unwrap();
-                    Ok(())
+            Ok(FacingOptions::ForAnother) => {
+                let person_or_group = select_person_or_group(send_to_data_storage_layer)
+                    .await
+                    .unwrap();
+                let list = HowWellDefinedSelection::get_list();
+                let selection = Select::new("Select How Well Defined |", list).prompt();
+                match selection {
+                    Ok(selection) => {
+                        let facing = Facing::Others {
+                            how_well_defined: selection.into(),
+                            who: person_or_group,
+                        };
+                        send_to_data_storage_layer
+                            .send(DataLayerCommands::UpdateFacing(
+                                item_node.get_surreal_record_id().clone(),
+                                vec![facing],
+                            ))
+                            .await
+                            .unwrap();
+                        Ok(())
+                    }
+                    //Back to picking a facing
+                    Err(InquireError::OperationCanceled) => continue,
+                    Err(InquireError::OperationInterrupted) => Err(()),
+                    Err(err) => todo!("{:?}", err),
                 }
-                Err(InquireError::OperationCanceled) => {
-                    single_item_define_facing(item_node, send_to_data_storage_layer).await
+            }
+            Ok(FacingOptions::ForMyselfAndAnother) => {
+                let person_or_group = select_person_or_group(send_to_data_storage_layer)
+                    .await
+                    .unwrap();
+                let list = HowWellDefinedSelection::get_list();
+                let selection = Select::new("Select How Well Defined |", list).prompt();
+                match selection {
+                    Ok(selection) => {
+                        let myself_facing = Facing::Myself(selection.into());
+                        let others_facing = Facing::Others {
+                            how_well_defined: selection.into(),
+                            who: person_or_group,
+                        };
+                        send_to_data_storage_layer
+                            .send(DataLayerCommands::UpdateFacing(
+                                item_node.get_surreal_record_id().clone(),
+                                vec![myself_facing, others_facing],
+                            ))
+                            .await
+                            .unwrap();
+                        Ok(())
+                    }
+                    //Back to picking a facing
+                    Err(InquireError::OperationCanceled) => continue,
+                    Err(InquireError::OperationInterrupted) => Err(()),
+                    Err(err) => todo!("{:?}", err),
                 }
-                Err(InquireError::OperationInterrupted) => Err(()),
-                Err(err) => todo!("{:?}", err),
             }
-        }
-        Err(InquireError::OperationCanceled) => define_facing(send_to_data_storage_layer).await,
-        Err(InquireError::OperationInterrupted) => Err(()),
-        Err(err) => todo!("{:?}", err),
+            //Back to the list of items that still need a facing
+            Err(InquireError::OperationCanceled) => Ok(()),
+            Err(InquireError::OperationInterrupted) => Err(()),
+            Err(err) => todo!("{:?}", err),
+        };
     }
 }
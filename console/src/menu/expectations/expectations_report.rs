@@ -0,0 +1,167 @@
+use std::{fmt::Display, path::Path};
+
+use chrono::Utc;
+use inquire::InquireError;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::BaseData,
+    display::display_expectations_report::DisplayExpectationsReport,
+    menu::prompt::{Select, Text},
+    surrealdb_layer::{surreal_tables::SurrealTables, DataLayerCommands},
+    systems::expectations_report::ExpectationsReport,
+};
+
+/// Finished items are included so that finishing a smaller item counts as activity
+pub(crate) async fn make_expectations_report_markdown(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> String {
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    let now = Utc::now();
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+    let all_items = base_data.get_items().iter().collect::<Vec<_>>();
+    let report = ExpectationsReport::new(&all_items, base_data.get_coverings());
+    DisplayExpectationsReport::new(&report, now).to_string()
+}
+
+/// Prints the error rather than failing so the menus keep going
+pub(crate) fn save_expectations_report(path: &Path, markdown: &str) {
+    match std::fs::write(path, markdown) {
+        Ok(()) => println!("Saved the expectations report to {}", path.display()),
+        Err(err) => eprintln!("Unable to save to {}: {}", path.display(), err),
+    }
+}
+
+enum ExpectationsReportSelection {
+    ExportAsMarkdown,
+    ReturnToExpectations,
+}
+
+impl Display for ExpectationsReportSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExpectationsReportSelection::ExportAsMarkdown => write!(f, "Export as Markdown"),
+            ExpectationsReportSelection::ReturnToExpectations => {
+                write!(f, "Return to Expectations")
+            }
+        }
+    }
+}
+
+pub(crate) async fn view_expectations_report(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let markdown = make_expectations_report_markdown(send_to_data_storage_layer).await;
+    print!("{}", markdown);
+    let list = vec![
+        ExpectationsReportSelection::ExportAsMarkdown,
+        ExpectationsReportSelection::ReturnToExpectations,
+    ];
+
+    let selection = Select::new("Select from the below list|", list).prompt();
+    match selection {
+        Ok(ExpectationsReportSelection::ExportAsMarkdown) => {
+            let path = Text::new("Save to which file?")
+                .with_default("expectations.md")
+                .prompt();
+            match path {
+                Ok(path) => {
+                    save_expectations_report(Path::new(&path), &markdown);
+                    Ok(())
+                }
+                Err(InquireError::OperationCanceled) => Ok(()),
+                Err(InquireError::OperationInterrupted) => Err(()),
+                Err(err) => todo!("Unexpected {}", err),
+            }
+        }
+        Ok(ExpectationsReportSelection::ReturnToExpectations)
+        | Err(InquireError::OperationCanceled) => Ok(()),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => todo!("Unexpected {}", err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use tokio::sync::mpsc;
+
+    use crate::{
+        menu::prompt::scripted::{run_scripted, Answer},
+        new_item::NewItem,
+        surrealdb_layer::{
+            data_storage_start_and_run,
+            surreal_item::{Facing, HowWellDefined},
+            surreal_tables::SurrealTables,
+            DataLayerCommands,
+        },
+    };
+
+    use super::view_expectations_report;
+
+    #[tokio::test]
+    async fn the_report_is_exported_as_a_markdown_table_for_each_person() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new_person_or_group(
+                "Alex".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Budget | forecast".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let alex = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "Alex")
+            .unwrap();
+        let budget = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "Budget | forecast")
+            .unwrap();
+        sender
+            .send(DataLayerCommands::UpdateFacing(
+                budget.id.clone().unwrap(),
+                vec![Facing::Others {
+                    who: alex.id.clone().unwrap(),
+                    how_well_defined: HowWellDefined::RoughlyDefined,
+                }],
+            ))
+            .await
+            .unwrap();
+        let path =
+            std::env::temp_dir().join(format!("on_purpose_expectations_{}.md", std::process::id()));
+
+        run_scripted(
+            [
+                Answer::Select("Export as Markdown".into()),
+                Answer::Text(path.to_str().unwrap().to_owned()),
+            ],
+            view_expectations_report(&sender),
+        )
+        .await
+        .unwrap();
+
+        let markdown = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(markdown.starts_with("# Expectations\n"));
+        assert!(markdown.contains("## Alex\n"));
+        assert!(markdown.contains("| Budget \\| forecast | Roughly defined | Not set | "));
+        assert!(!markdown.contains("## Myself"));
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+}
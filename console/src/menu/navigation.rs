@@ -0,0 +1,238 @@
+//! Which of the top level menus is showing. These menus say where to go next rather than calling each other so a long
+//! session does not keep growing the call stack and Esc always goes back one screen. The menus below them return
+//! Ok(()) to go back to whatever called them and Err(()) to exit.
+
+use tokio::{sync::mpsc::Sender, task::JoinHandle};
+
+use crate::surrealdb_layer::DataLayerCommands;
+
+use super::{
+    bullet_list_menu::present_normal_bullet_list_menu, expectations::view_expectations,
+    people::view_people, top_menu::present_top_menu, waiting_for::view_waiting_for,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Screen {
+    TopMenu,
+    BulletList,
+    Expectations,
+    WaitingFor,
+    People,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Navigation {
+    /// Show the same screen again, for example after an action so it shows the change
+    Stay,
+    Push(Screen),
+    /// Esc, go back to the screen that was showing before this one
+    Back,
+}
+
+#[derive(Debug)]
+pub(crate) struct NavigationStack {
+    screens: Vec<Screen>,
+}
+
+impl NavigationStack {
+    /// Starts on the bullet list with the top menu behind it, so Esc from the bullet list shows the top menu and Esc
+    /// from the top menu exits
+    pub(crate) fn new() -> Self {
+        NavigationStack {
+            screens: vec![Screen::TopMenu, Screen::BulletList],
+        }
+    }
+
+    /// None once everything has been backed out of, time to exit
+    pub(crate) fn get_current(&self) -> Option<Screen> {
+        self.screens.last().copied()
+    }
+
+    pub(crate) fn navigate(&mut self, navigation: Navigation) {
+        match navigation {
+            Navigation::Stay => {}
+            Navigation::Push(screen) => {
+                //Going to a screen that is already open goes back to it rather than opening it a second time
+                match self.screens.iter().position(|x| *x == screen) {
+                    Some(index) => self.screens.truncate(index + 1),
+                    None => self.screens.push(screen),
+                }
+            }
+            Navigation::Back => {
+                self.screens.pop();
+            }
+        }
+    }
+}
+
+/// Shows the menus until everything has been backed out of or Ctrl+C is pressed
+pub(crate) async fn present_menus(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+    data_storage_join_handle: &JoinHandle<()>,
+) {
+    let mut navigation_stack = NavigationStack::new();
+    while let Some(screen) = navigation_stack.get_current() {
+        match present_screen(screen, send_to_data_storage_layer).await {
+            Result::Ok(navigation) => navigation_stack.navigate(navigation),
+            Result::Err(..) => break,
+        };
+
+        if data_storage_join_handle.is_finished() {
+            println!("Data Storage Layer closed early, unexpectedly");
+        }
+    }
+}
+
+async fn present_screen(
+    screen: Screen,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<Navigation, ()> {
+    match screen {
+        Screen::TopMenu => present_top_menu(send_to_data_storage_layer).await,
+        Screen::BulletList => present_normal_bullet_list_menu(send_to_data_storage_layer).await,
+        Screen::Expectations => view_expectations(send_to_data_storage_layer).await,
+        Screen::WaitingFor => view_waiting_for(send_to_data_storage_layer).await,
+        Screen::People => view_people(send_to_data_storage_layer).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use surrealdb::sql::Duration as SurrealDuration;
+    use tokio::sync::mpsc;
+
+    use crate::{
+        menu::prompt::scripted::{run_scripted, Answer},
+        new_item::NewItemBuilder,
+        surrealdb_layer::{
+            data_storage_start_and_run,
+            surreal_item::{EnterListReason, ItemType, Staging},
+            surreal_tables::SurrealTables,
+            DataLayerCommands,
+        },
+    };
+
+    use super::{present_menus, Navigation, NavigationStack, Screen};
+
+    #[test]
+    fn back_from_the_bullet_list_shows_the_top_menu_and_back_again_exits() {
+        let mut navigation_stack = NavigationStack::new();
+        assert_eq!(navigation_stack.get_current(), Some(Screen::BulletList));
+
+        navigation_stack.navigate(Navigation::Stay);
+        assert_eq!(navigation_stack.get_current(), Some(Screen::BulletList));
+
+        navigation_stack.navigate(Navigation::Back);
+        assert_eq!(navigation_stack.get_current(), Some(Screen::TopMenu));
+
+        navigation_stack.navigate(Navigation::Push(Screen::Expectations));
+        assert_eq!(navigation_stack.get_current(), Some(Screen::Expectations));
+
+        navigation_stack.navigate(Navigation::Back);
+        assert_eq!(navigation_stack.get_current(), Some(Screen::TopMenu));
+
+        navigation_stack.navigate(Navigation::Back);
+        assert_eq!(navigation_stack.get_current(), None);
+    }
+
+    #[test]
+    fn pushing_a_screen_that_is_already_open_goes_back_to_it() {
+        let mut navigation_stack = NavigationStack::new();
+        navigation_stack.navigate(Navigation::Push(Screen::Expectations));
+        navigation_stack.navigate(Navigation::Push(Screen::TopMenu));
+        assert_eq!(navigation_stack.get_current(), Some(Screen::TopMenu));
+
+        navigation_stack.navigate(Navigation::Back);
+        assert_eq!(navigation_stack.get_current(), None);
+    }
+
+    #[tokio::test]
+    async fn capture_set_staging_declare_type_and_finish_through_the_menus() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        run_scripted(
+            [
+                Answer::Select("Capture New Item".into()),
+                Answer::Text("Write the report".into()),
+                Answer::Select("Write the report".into()),
+                Answer::Select("Declare Item Type".into()),
+                Answer::Select("Action".into()),
+                Answer::Select("Write the report".into()),
+                Answer::Select("Mentally Resident".into()),
+                Answer::Select("Immediately".into()),
+                Answer::Text("1h".into()),
+                Answer::Select("Yes".into()),
+                Answer::Select("Write the report".into()),
+                Answer::Select("I finished".into()),
+                Answer::Select("Return to Bullet List".into()),
+                //Esc from the bullet list shows the top menu and Esc from there exits
+                Answer::Cancel,
+                Answer::Cancel,
+            ],
+            present_menus(&sender, &data_storage_join_handle),
+        )
+        .await;
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(surreal_tables.surreal_items.len(), 1);
+        let item = &surreal_tables.surreal_items[0];
+        assert_eq!(item.summary, "Write the report");
+        assert_eq!(item.item_type, ItemType::Action);
+        assert!(matches!(item.staging, Staging::MentallyResident { .. }));
+        assert!(item.finished.is_some());
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn esc_from_starting_to_work_shows_the_item_menu_again_and_then_the_bullet_list() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+        sender
+            .send(DataLayerCommands::NewItem(
+                NewItemBuilder::default()
+                    .summary("Write the report")
+                    .item_type(ItemType::Action)
+                    .staging(Staging::OnDeck {
+                        enter_list: EnterListReason::DateTime(
+                            (Utc::now() - Duration::hours(1)).into(),
+                        ),
+                        lap: SurrealDuration::from_days(1),
+                    })
+                    .build()
+                    .unwrap(),
+            ))
+            .await
+            .unwrap();
+
+        run_scripted(
+            [
+                Answer::Select("Write the report".into()),
+                Answer::Select("I'm starting to work on this now".into()),
+                Answer::Cancel,
+                //The menu for the same item again
+                Answer::Select("I'm starting to work on this now".into()),
+                Answer::Cancel,
+                Answer::Cancel,
+                //Back on the bullet list, then the top menu, then exit
+                Answer::Select("Write the report".into()),
+                Answer::Cancel,
+                Answer::Cancel,
+                Answer::Cancel,
+            ],
+            present_menus(&sender, &data_storage_join_handle),
+        )
+        .await;
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert!(surreal_tables.surreal_items[0].finished.is_none());
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+}
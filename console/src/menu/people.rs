@@ -0,0 +1,238 @@
+//! The people and groups that items wait on, are covered by, or are done for, and what to discuss with each of them
+
+use std::fmt::Display;
+
+use chrono::{DateTime, Utc};
+use inquire::InquireError;
+use surrealdb::opt::RecordId;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::BaseData,
+    display::{display_agenda::DisplayAgenda, display_item::DisplayItem},
+    menu::{
+        navigation::Navigation,
+        prompt::{Select, Text},
+    },
+    surrealdb_layer::{surreal_tables::SurrealTables, DataLayerCommands},
+    systems::agenda::Agenda,
+};
+
+struct PersonOrGroupItem<'e> {
+    agenda: &'e Agenda<'e>,
+}
+
+impl Display for PersonOrGroupItem<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", DisplayItem::new(self.agenda.get_person_or_group()))?;
+        if !self.agenda.is_empty() {
+            write!(f, " ({} to discuss)", self.agenda.len())?;
+        }
+        Ok(())
+    }
+}
+
+/// Everyone with something to discuss first and then everyone else, each by name
+pub(crate) async fn view_people(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<Navigation, ()> {
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, Utc::now());
+    let mut agendas =
+        Agenda::new_for_everyone(base_data.get_coverings(), base_data.get_active_items());
+    agendas.sort_by_key(|x| {
+        (
+            x.is_empty(),
+            x.get_person_or_group().get_summary().to_lowercase(),
+        )
+    });
+
+    if agendas.is_empty() {
+        println!("No people or groups yet");
+        return Ok(Navigation::Back);
+    }
+
+    let list = agendas
+        .iter()
+        .map(|agenda| PersonOrGroupItem { agenda })
+        .collect::<Vec<_>>();
+    let selection = Select::new("People and groups|", list)
+        .with_page_size(30)
+        .prompt();
+    let result = match selection {
+        Ok(selected) => {
+            present_person_or_group_selected(selected.agenda, send_to_data_storage_layer).await
+        }
+        Err(InquireError::OperationCanceled) => return Ok(Navigation::Back),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => todo!("Unexpected {}", err),
+    };
+    result.map(|()| Navigation::Stay)
+}
+
+enum PersonOrGroupSelection {
+    TheyAreHereNow,
+    TheyAreNotAround,
+    ReturnToPeople,
+}
+
+impl Display for PersonOrGroupSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PersonOrGroupSelection::TheyAreHereNow => write!(f, "They're here now"),
+            PersonOrGroupSelection::TheyAreNotAround => {
+                write!(f, "They're not around, ask again later")
+            }
+            PersonOrGroupSelection::ReturnToPeople => write!(f, "Return to People"),
+        }
+    }
+}
+
+async fn present_person_or_group_selected(
+    agenda: &Agenda<'_>,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    print!("{}", DisplayAgenda::new(agenda));
+    let list = vec![
+        PersonOrGroupSelection::TheyAreHereNow,
+        PersonOrGroupSelection::TheyAreNotAround,
+        PersonOrGroupSelection::ReturnToPeople,
+    ];
+
+    let selection = Select::new("Select from the below list|", list).prompt();
+    let person_or_group = agenda.get_person_or_group().get_surreal_record_id();
+    match selection {
+        Ok(PersonOrGroupSelection::TheyAreHereNow) => {
+            they_are_here_now(person_or_group, send_to_data_storage_layer).await
+        }
+        Ok(PersonOrGroupSelection::TheyAreNotAround) => {
+            snooze_agenda(person_or_group, send_to_data_storage_layer).await
+        }
+        Ok(PersonOrGroupSelection::ReturnToPeople) | Err(InquireError::OperationCanceled) => Ok(()),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => todo!("Unexpected {}", err),
+    }
+}
+
+/// Shows everything to discuss with them and puts what was waiting for them to be around back on the bullet list
+pub(crate) async fn they_are_here_now(
+    person_or_group: &RecordId,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, Utc::now());
+    let active_items = base_data.get_active_items();
+    let person_or_group = active_items
+        .iter()
+        .find(|x| x.get_surreal_record_id() == person_or_group)
+        .expect("Person or group is active");
+    let agenda = Agenda::new(person_or_group, base_data.get_coverings(), active_items);
+
+    print!("{}", DisplayAgenda::new(&agenda));
+    for covering in agenda.get_coverings() {
+        send_to_data_storage_layer
+            .send(DataLayerCommands::RemoveCoveringItem(
+                covering._surreal_covering.clone(),
+            ))
+            .await
+            .unwrap();
+    }
+    Ok(())
+}
+
+/// Hides their agenda from the bullet list until they might be around again
+pub(crate) async fn snooze_agenda(
+    person_or_group: &RecordId,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let until: DateTime<Utc> = loop {
+        let until = Text::new("When might they be around next?")
+            .with_default("1d")
+            .prompt();
+        let until = match until {
+            Ok(until) => until,
+            Err(InquireError::OperationCanceled) => return Ok(()),
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => todo!("Unexpected {}", err),
+        };
+        match duration_str::parse(&until) {
+            Ok(duration) => break Utc::now() + duration,
+            Err(_) => match dateparser::parse(&until) {
+                Ok(until) => break until,
+                Err(_) => println!("Invalid input. Please try again."),
+            },
+        }
+    };
+    send_to_data_storage_layer
+        .send(DataLayerCommands::CoverItemUntilAnExactDateTime(
+            person_or_group.clone(),
+            until,
+        ))
+        .await
+        .unwrap();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use tokio::sync::mpsc;
+
+    use crate::{
+        menu::prompt::scripted::{run_scripted, Answer},
+        new_item::NewItem,
+        surrealdb_layer::{
+            data_storage_start_and_run, surreal_tables::SurrealTables, DataLayerCommands,
+        },
+    };
+
+    use super::view_people;
+
+    #[tokio::test]
+    async fn when_they_are_here_what_needed_them_is_no_longer_covered_by_them() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Ask about the budget".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        sender
+            .send(DataLayerCommands::CoverItemWithANewItem {
+                cover_this: surreal_tables.surreal_items[0].id.clone().unwrap(),
+                cover_with: NewItem::new_person_or_group("Alex".into(), Utc::now()),
+            })
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(surreal_tables.surreal_coverings.len(), 1);
+
+        run_scripted(
+            [
+                Answer::Select("Alex (1 to discuss)".into()),
+                Answer::Select("They're here now".into()),
+            ],
+            view_people(&sender),
+        )
+        .await
+        .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert!(surreal_tables.surreal_coverings.is_empty());
+        assert!(surreal_tables
+            .surreal_items
+            .iter()
+            .all(|x| x.finished.is_none()));
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+}
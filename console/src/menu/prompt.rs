@@ -0,0 +1,223 @@
+//! The menus ask their questions through these rather than through inquire directly so a test can answer them. They
+//! have the same builder methods as the inquire prompts of the same name. Normally inquire shows them in the terminal,
+//! inside of run_scripted the answers come from a list instead.
+
+use std::{cell::RefCell, fmt::Display};
+
+use inquire::InquireError;
+
+#[cfg(test)]
+pub(crate) mod scripted;
+
+/// Everything is passed as text so this can be used as a trait object, the selected options are returned as indexes
+pub(crate) trait Prompter {
+    fn select(
+        &mut self,
+        message: &str,
+        options: Vec<String>,
+        starting_cursor: usize,
+        page_size: Option<usize>,
+    ) -> Result<usize, InquireError>;
+
+    fn multi_select(
+        &mut self,
+        message: &str,
+        options: Vec<String>,
+        default: &[usize],
+    ) -> Result<Vec<usize>, InquireError>;
+
+    fn text(
+        &mut self,
+        message: &str,
+        default: Option<&str>,
+        initial_value: Option<&str>,
+    ) -> Result<String, InquireError>;
+
+    fn editor(&mut self, message: &str) -> Result<String, InquireError>;
+}
+
+pub(crate) struct InquirePrompter;
+
+impl Prompter for InquirePrompter {
+    fn select(
+        &mut self,
+        message: &str,
+        options: Vec<String>,
+        starting_cursor: usize,
+        page_size: Option<usize>,
+    ) -> Result<usize, InquireError> {
+        let select = inquire::Select::new(message, options).with_starting_cursor(starting_cursor);
+        let select = match page_size {
+            Some(page_size) => select.with_page_size(page_size),
+            None => select,
+        };
+        select.raw_prompt().map(|x| x.index)
+    }
+
+    fn multi_select(
+        &mut self,
+        message: &str,
+        options: Vec<String>,
+        default: &[usize],
+    ) -> Result<Vec<usize>, InquireError> {
+        inquire::MultiSelect::new(message, options)
+            .with_default(default)
+            .raw_prompt()
+            .map(|x| x.into_iter().map(|x| x.index).collect())
+    }
+
+    fn text(
+        &mut self,
+        message: &str,
+        default: Option<&str>,
+        initial_value: Option<&str>,
+    ) -> Result<String, InquireError> {
+        let mut text = inquire::Text::new(message);
+        if let Some(default) = default {
+            text = text.with_default(default);
+        }
+        if let Some(initial_value) = initial_value {
+            text = text.with_initial_value(initial_value);
+        }
+        text.prompt()
+    }
+
+    fn editor(&mut self, message: &str) -> Result<String, InquireError> {
+        inquire::Editor::new(message).prompt()
+    }
+}
+
+tokio::task_local! {
+    static PROMPTER: RefCell<Box<dyn Prompter>>;
+}
+
+fn with_prompter<R>(f: impl FnOnce(&mut dyn Prompter) -> R) -> R {
+    if PROMPTER.try_with(|_| ()).is_ok() {
+        PROMPTER.with(|prompter| f(prompter.borrow_mut().as_mut()))
+    } else {
+        f(&mut InquirePrompter)
+    }
+}
+
+pub(crate) struct Select<'a, T> {
+    message: &'a str,
+    options: Vec<T>,
+    starting_cursor: usize,
+    page_size: Option<usize>,
+}
+
+impl<'a, T: Display> Select<'a, T> {
+    pub(crate) fn new(message: &'a str, options: Vec<T>) -> Self {
+        Select {
+            message,
+            options,
+            starting_cursor: 0,
+            page_size: None,
+        }
+    }
+
+    pub(crate) fn with_starting_cursor(mut self, starting_cursor: usize) -> Self {
+        self.starting_cursor = starting_cursor;
+        self
+    }
+
+    pub(crate) fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    pub(crate) fn prompt(self) -> Result<T, InquireError> {
+        let displayed = self.options.iter().map(|x| x.to_string()).collect();
+        let index = with_prompter(|prompter| {
+            prompter.select(
+                self.message,
+                displayed,
+                self.starting_cursor,
+                self.page_size,
+            )
+        })?;
+        Ok(self
+            .options
+            .into_iter()
+            .nth(index)
+            .expect("Index is one of the options"))
+    }
+}
+
+pub(crate) struct MultiSelect<'a, T> {
+    message: &'a str,
+    options: Vec<T>,
+    default: &'a [usize],
+}
+
+impl<'a, T: Display> MultiSelect<'a, T> {
+    pub(crate) fn new(message: &'a str, options: Vec<T>) -> Self {
+        MultiSelect {
+            message,
+            options,
+            default: &[],
+        }
+    }
+
+    pub(crate) fn with_default(mut self, default: &'a [usize]) -> Self {
+        self.default = default;
+        self
+    }
+
+    pub(crate) fn prompt(self) -> Result<Vec<T>, InquireError> {
+        let displayed = self.options.iter().map(|x| x.to_string()).collect();
+        let indexes =
+            with_prompter(|prompter| prompter.multi_select(self.message, displayed, self.default))?;
+        Ok(self
+            .options
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| indexes.contains(index))
+            .map(|(_, x)| x)
+            .collect())
+    }
+}
+
+pub(crate) struct Text<'a> {
+    message: &'a str,
+    default: Option<&'a str>,
+    initial_value: Option<&'a str>,
+}
+
+impl<'a> Text<'a> {
+    pub(crate) fn new(message: &'a str) -> Self {
+        Text {
+            message,
+            default: None,
+            initial_value: None,
+        }
+    }
+
+    pub(crate) fn with_default(mut self, default: &'a str) -> Self {
+        self.default = Some(default);
+        self
+    }
+
+    pub(crate) fn with_initial_value(mut self, initial_value: &'a str) -> Self {
+        self.initial_value = Some(initial_value);
+        self
+    }
+
+    pub(crate) fn prompt(self) -> Result<String, InquireError> {
+        with_prompter(|prompter| prompter.text(self.message, self.default, self.initial_value))
+    }
+}
+
+pub(crate) struct Editor<'a> {
+    message: &'a str,
+}
+
+impl<'a> Editor<'a> {
+    pub(crate) fn new(message: &'a str) -> Self {
+        Editor { message }
+    }
+
+    pub(crate) fn prompt(self) -> Result<String, InquireError> {
+        with_prompter(|prompter| prompter.editor(self.message))
+    }
+}
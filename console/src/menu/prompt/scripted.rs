@@ -0,0 +1,144 @@
+//! Answers the prompts from a list so tests can go through the menus the way someone would at the terminal
+
+use std::{cell::RefCell, collections::VecDeque, future::Future, rc::Rc};
+
+use inquire::InquireError;
+
+use super::{Prompter, PROMPTER};
+
+/// What a test would have typed
+#[derive(Debug, Clone)]
+pub(crate) enum Answer {
+    /// Picks the first option that contains this text
+    Select(String),
+    /// Picks every option that contains one of these
+    MultiSelect(Vec<String>),
+    /// For Text and Editor prompts, empty takes the initial value and then the default
+    Text(String),
+    /// Esc
+    Cancel,
+}
+
+/// Answers in order and then acts like Ctrl+C was pressed so the menus exit
+pub(crate) struct ScriptedPrompter {
+    answers: Rc<RefCell<VecDeque<Answer>>>,
+}
+
+impl ScriptedPrompter {
+    fn next_answer(&mut self, message: &str) -> Result<Answer, InquireError> {
+        let next = self.answers.borrow_mut().pop_front();
+        match next {
+            Some(Answer::Cancel) => {
+                println!("{} <Esc>", message);
+                Err(InquireError::OperationCanceled)
+            }
+            Some(answer) => Ok(answer),
+            None => {
+                println!("{} <Out of scripted answers>", message);
+                Err(InquireError::OperationInterrupted)
+            }
+        }
+    }
+}
+
+fn find_option(message: &str, options: &[String], answer: &str) -> usize {
+    options
+        .iter()
+        .position(|x| x.contains(answer))
+        .unwrap_or_else(|| {
+            panic!(
+                "\"{}\" is not one of the options for \"{}\", the options are {:#?}",
+                answer, message, options
+            )
+        })
+}
+
+impl Prompter for ScriptedPrompter {
+    fn select(
+        &mut self,
+        message: &str,
+        options: Vec<String>,
+        _starting_cursor: usize,
+        _page_size: Option<usize>,
+    ) -> Result<usize, InquireError> {
+        if options.is_empty() {
+            //The same as inquire
+            return Err(InquireError::InvalidConfiguration(
+                "Available options can not be empty".into(),
+            ));
+        }
+        match self.next_answer(message)? {
+            Answer::Select(answer) => {
+                let index = find_option(message, &options, &answer);
+                println!("{} {}", message, options[index]);
+                Ok(index)
+            }
+            answer => panic!("Expected to select for \"{}\" not {:?}", message, answer),
+        }
+    }
+
+    fn multi_select(
+        &mut self,
+        message: &str,
+        options: Vec<String>,
+        _default: &[usize],
+    ) -> Result<Vec<usize>, InquireError> {
+        match self.next_answer(message)? {
+            Answer::MultiSelect(answers) => {
+                let indexes = answers
+                    .iter()
+                    .map(|x| find_option(message, &options, x))
+                    .collect();
+                println!("{} {:?}", message, answers);
+                Ok(indexes)
+            }
+            answer => panic!(
+                "Expected to multi select for \"{}\" not {:?}",
+                message, answer
+            ),
+        }
+    }
+
+    fn text(
+        &mut self,
+        message: &str,
+        default: Option<&str>,
+        initial_value: Option<&str>,
+    ) -> Result<String, InquireError> {
+        match self.next_answer(message)? {
+            Answer::Text(answer) => {
+                println!("{} {}", message, answer);
+                match initial_value.or(default) {
+                    Some(initial_value_or_default) if answer.is_empty() => {
+                        Ok(initial_value_or_default.to_string())
+                    }
+                    _ => Ok(answer),
+                }
+            }
+            answer => panic!("Expected to type for \"{}\" not {:?}", message, answer),
+        }
+    }
+
+    fn editor(&mut self, message: &str) -> Result<String, InquireError> {
+        self.text(message, None, None)
+    }
+}
+
+/// Every prompt shown while running the future is answered from the list, panics if some of the answers are not used
+pub(crate) async fn run_scripted<F: Future>(
+    answers: impl IntoIterator<Item = Answer>,
+    future: F,
+) -> F::Output {
+    let answers = Rc::new(RefCell::new(answers.into_iter().collect()));
+    let prompter: Box<dyn Prompter> = Box::new(ScriptedPrompter {
+        answers: answers.clone(),
+    });
+    let output = PROMPTER.scope(RefCell::new(prompter), future).await;
+    //Answers that were not used mean the menus went somewhere other than where the test expected
+    assert!(
+        answers.borrow().is_empty(),
+        "Not every scripted answer was used, these were left {:?}",
+        answers.borrow()
+    );
+    output
+}
@@ -0,0 +1,209 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Duration, Local, Utc};
+use inquire::InquireError;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::{covering::Covering, item::Item},
+    display::display_item::DisplayItem,
+    menu::prompt::{MultiSelect, Select},
+    surrealdb_layer::{surreal_tables::SurrealTables, DataLayerCommands},
+};
+
+/// How far back to look for items that were finished
+const RECENTLY_FINISHED_DAYS: i64 = 14;
+
+/// Children finished within this amount of time from their parent are considered to be finished as part of the same cascade
+const SAME_CASCADE_MINUTES: i64 = 10;
+
+struct RecentlyFinishedItem<'e> {
+    item: &'e Item<'e>,
+}
+
+impl Display for RecentlyFinishedItem<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let finished: DateTime<Local> = self
+            .item
+            .when_finished()
+            .expect("Only finished items are in this list")
+            .into();
+        write!(
+            f,
+            "✔ {} {}",
+            finished.format("%a %d %b %H:%M"),
+            DisplayItem::new(self.item)
+        )
+    }
+}
+
+impl<'e> RecentlyFinishedItem<'e> {
+    fn make_list(items: &[&'e Item<'e>], now: &DateTime<Utc>) -> Vec<Self> {
+        let mut list = items
+            .iter()
+            .copied()
+            .filter(|x| {
+                x.when_finished().is_some_and(|finished| {
+                    *now - finished < Duration::days(RECENTLY_FINISHED_DAYS)
+                })
+            })
+            .map(|item| RecentlyFinishedItem { item })
+            .collect::<Vec<_>>();
+        //Most recently finished first
+        list.sort_by_key(|x| std::cmp::Reverse(x.item.when_finished()));
+        list
+    }
+}
+
+pub(crate) async fn present_recently_finished_menu(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    let now = Utc::now();
+    //Finished items are not included in BaseData's active items or coverings so build these directly
+    let items = surreal_tables.make_items();
+    let all_items = items.iter().collect::<Vec<_>>();
+    let coverings = surreal_tables.make_coverings(&all_items);
+
+    let list = RecentlyFinishedItem::make_list(&all_items, &now);
+    if list.is_empty() {
+        println!(
+            "Nothing has been finished in the last {} days",
+            RECENTLY_FINISHED_DAYS
+        );
+        return Ok(());
+    }
+
+    let selection = Select::new("Select an item to reopen|", list)
+        .with_page_size(10)
+        .prompt();
+    match selection {
+        Ok(RecentlyFinishedItem { item }) => {
+            reopen_item(item, &coverings, &all_items, send_to_data_storage_layer).await
+        }
+        Err(InquireError::OperationCanceled) => Ok(()),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => todo!("Unexpected {}", err),
+    }
+}
+
+async fn reopen_item(
+    reopen_this: &Item<'_>,
+    coverings: &[Covering<'_>],
+    all_items: &[&Item<'_>],
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let finished_in_same_cascade = reopen_this.find_children_finished_in_the_same_cascade(
+        coverings,
+        all_items,
+        Duration::minutes(SAME_CASCADE_MINUTES),
+    );
+
+    let also_reopen = if finished_in_same_cascade.is_empty() {
+        Vec::default()
+    } else {
+        let list = DisplayItem::make_list(&finished_in_same_cascade);
+        let all_selected = (0..list.len()).collect::<Vec<_>>();
+        let selection = MultiSelect::new(
+            "These children were finished at the same time, select the ones to also reopen|",
+            list,
+        )
+        .with_default(&all_selected)
+        .prompt();
+        match selection {
+            Ok(selected) => selected,
+            Err(InquireError::OperationCanceled) => return Ok(()),
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => todo!("Unexpected {}", err),
+        }
+    };
+
+    //Coverings and the parent's ordering of smaller items are left in place when an item is finished, they are
+    //just filtered out, so clearing finished is enough to restore them.
+    send_to_data_storage_layer
+        .send(DataLayerCommands::UnfinishItem(
+            reopen_this.get_surreal_record_id().clone(),
+        ))
+        .await
+        .unwrap();
+    for child in also_reopen.into_iter() {
+        send_to_data_storage_layer
+            .send(DataLayerCommands::UnfinishItem(child.into()))
+            .await
+            .unwrap();
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use tokio::sync::mpsc;
+
+    use crate::{
+        menu::prompt::scripted::{run_scripted, Answer},
+        new_item::NewItem,
+        surrealdb_layer::{
+            data_storage_start_and_run, surreal_tables::SurrealTables, DataLayerCommands,
+        },
+    };
+
+    use super::present_recently_finished_menu;
+
+    #[tokio::test]
+    async fn reopening_a_parent_also_reopens_the_selected_children_finished_with_it() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Parent".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let parent = surreal_tables.surreal_items[0].id.clone().unwrap();
+        sender
+            .send(DataLayerCommands::ParentItemWithANewChildItem {
+                child: NewItem::new("Child".into(), Utc::now()),
+                parent: parent.clone(),
+                higher_priority_than_this: None,
+            })
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        for surreal_item in surreal_tables.surreal_items.iter() {
+            sender
+                .send(DataLayerCommands::FinishItem(
+                    surreal_item.id.clone().unwrap(),
+                ))
+                .await
+                .unwrap();
+        }
+
+        run_scripted(
+            [
+                Answer::Select("Parent".into()),
+                Answer::MultiSelect(vec!["Child".into()]),
+            ],
+            present_recently_finished_menu(&sender),
+        )
+        .await
+        .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(surreal_tables.surreal_items.len(), 2);
+        assert!(surreal_tables
+            .surreal_items
+            .iter()
+            .all(|x| x.finished.is_none()));
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+}
@@ -1,8 +1,8 @@
 use std::fmt::Display;
 
-use inquire::Select;
 use surrealdb::opt::RecordId;
 
+use crate::menu::prompt::Select;
 use crate::{base_data::item::Item, display::display_item::DisplayItem};
 
 #[derive(Debug)]
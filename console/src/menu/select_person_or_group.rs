@@ -1,11 +1,12 @@
 use chrono::Utc;
-use inquire::{InquireError, Select};
+use inquire::InquireError;
 use surrealdb::opt::RecordId;
 use tokio::sync::mpsc::Sender;
 
 use crate::{
     base_data::BaseData,
     display::display_item::DisplayItem,
+    menu::prompt::{Select, Text},
     new_item::NewItemBuilder,
     surrealdb_layer::{surreal_item::ItemType, DataLayerCommands},
 };
@@ -40,7 +41,7 @@ pub(crate) async fn select_person_or_group(
 async fn select_person_or_group_new_person_or_group(
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
 ) -> Option<RecordId> {
-    let summary = inquire::Text::new("Enter a summary for the new person or group |")
+    let summary = Text::new("Enter a summary for the new person or group |")
         .prompt()
         .unwrap();
 
@@ -0,0 +1,146 @@
+use std::fmt::Display;
+
+use chrono::Utc;
+use duration_str::parse;
+use inquire::InquireError;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::item::Item,
+    menu::prompt::{Select, Text},
+    surrealdb_layer::{
+        surreal_item::{DueDate, DueDateStrictness},
+        DataLayerCommands,
+    },
+};
+
+enum DueDateSelection {
+    Hard,
+    Soft,
+    Remove,
+}
+
+impl Display for DueDateSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DueDateSelection::Hard => {
+                write!(f, "Hard, for example something promised to someone else")
+            }
+            DueDateSelection::Soft => write!(f, "Soft, a target that can slip if it has to"),
+            DueDateSelection::Remove => write!(f, "Remove the due date"),
+        }
+    }
+}
+
+pub(crate) async fn set_due_date(
+    item: &Item<'_>,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let mut list = vec![DueDateSelection::Hard, DueDateSelection::Soft];
+    if item.get_due_date().is_some() {
+        list.push(DueDateSelection::Remove);
+    }
+    let selection = Select::new("How firm is the due date?", list).prompt();
+    let strictness = match selection {
+        Ok(DueDateSelection::Hard) => DueDateStrictness::Hard,
+        Ok(DueDateSelection::Soft) => DueDateStrictness::Soft,
+        Ok(DueDateSelection::Remove) => {
+            send_to_data_storage_layer
+                .send(DataLayerCommands::UpdateItemDueDate(
+                    item.get_surreal_record_id().clone(),
+                    None,
+                ))
+                .await
+                .unwrap();
+            return Ok(());
+        }
+        Err(InquireError::OperationCanceled) => return Ok(()),
+        Err(InquireError::OperationInterrupted) => return Err(()),
+        Err(err) => todo!("Unexpected {}", err),
+    };
+
+    loop {
+        let due = Text::new("When is it due? For example \"3d\" or \"2024-06-01 17:00\"").prompt();
+        let due = match due {
+            Ok(due) => due,
+            Err(InquireError::OperationCanceled) => return Ok(()),
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => todo!("Unexpected {}", err),
+        };
+        let due = match parse(&due) {
+            Ok(duration) => Utc::now() + duration,
+            Err(_) => match dateparser::parse(&due) {
+                Ok(due) => due,
+                Err(_) => {
+                    println!("Invalid input. Please try again.");
+                    continue;
+                }
+            },
+        };
+        send_to_data_storage_layer
+            .send(DataLayerCommands::UpdateItemDueDate(
+                item.get_surreal_record_id().clone(),
+                Some(DueDate {
+                    due: due.into(),
+                    strictness,
+                }),
+            ))
+            .await
+            .unwrap();
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, Utc};
+    use tokio::sync::mpsc;
+
+    use crate::{
+        menu::prompt::scripted::{run_scripted, Answer},
+        new_item::NewItem,
+        surrealdb_layer::{
+            data_storage_start_and_run, surreal_item::DueDateStrictness,
+            surreal_tables::SurrealTables, DataLayerCommands,
+        },
+    };
+
+    use super::set_due_date;
+
+    #[tokio::test]
+    async fn an_invalid_date_is_asked_again_and_a_hard_due_date_is_saved() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Send the proposal".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let items = surreal_tables.make_items();
+
+        run_scripted(
+            [
+                Answer::Select("Hard".into()),
+                Answer::Text("sometime".into()),
+                Answer::Text("2d".into()),
+            ],
+            set_due_date(&items[0], &sender),
+        )
+        .await
+        .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let due_date = surreal_tables.surreal_items[0].due_date.clone().unwrap();
+        assert_eq!(due_date.strictness, DueDateStrictness::Hard);
+        let due: DateTime<Utc> = due_date.due.into();
+        assert!(due - Utc::now() > Duration::days(1));
+        assert!(due - Utc::now() <= Duration::days(2));
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+}
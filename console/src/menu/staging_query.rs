@@ -2,12 +2,13 @@ use std::{fmt::Display, time::Duration};
 
 use chrono::Utc;
 use duration_str::parse;
-use inquire::{InquireError, Select, Text};
+use inquire::InquireError;
 
 use crate::{
     display::{
         display_duration::DisplayDuration, display_enter_list_reason::DisplayEnterListReason,
     },
+    menu::prompt::{Select, Text},
     menu::YesOrNo,
     surrealdb_layer::surreal_item::{EnterListReason, Staging},
 };
@@ -1,29 +1,34 @@
 use std::fmt::Display;
 
-use async_recursion::async_recursion;
 use chrono::{Local, Utc};
-use inquire::{InquireError, Select, Text};
+use inquire::InquireError;
 use tokio::sync::mpsc::Sender;
 
 use crate::{
     base_data::BaseData,
     change_routine::change_routine,
     display::display_item_node::DisplayItemNode,
-    menu::expectations::view_expectations,
+    menu::{
+        navigation::{Navigation, Screen},
+        prompt::{Select, Text},
+        recently_finished::present_recently_finished_menu,
+    },
     new_item::NewItem,
     node::item_node::ItemNode,
     surrealdb_layer::{surreal_tables::SurrealTables, DataLayerCommands},
 };
 
-use super::bullet_list_menu::present_normal_bullet_list_menu;
-
 enum TopMenuSelection {
     Capture,
     ChangeRoutine,
     Reflection,
     ViewBulletList,
     ViewExpectations,
+    ViewWaitingFor,
+    ViewPeople,
     ViewMotivations,
+    RecentlyFinished,
+    ArchiveFinishedItems,
     DebugViewAllItems,
 }
 
@@ -37,9 +42,21 @@ impl Display for TopMenuSelection {
             TopMenuSelection::ViewExpectations => {
                 write!(f, "👁 🙏 View Expectations         👁")
             }
+            TopMenuSelection::ViewWaitingFor => {
+                write!(f, "👁 ⏳ View Waiting For          👁")
+            }
+            TopMenuSelection::ViewPeople => {
+                write!(f, "👁 👤 View People & Agendas     👁")
+            }
             TopMenuSelection::ViewMotivations => {
                 write!(f, "👁 🎯 View Motivations          👁")
             }
+            TopMenuSelection::RecentlyFinished => {
+                write!(f, "✔  Recently Finished (Reopen)  ✔")
+            }
+            TopMenuSelection::ArchiveFinishedItems => {
+                write!(f, "🗄  Archive Finished Items      🗄")
+            }
             TopMenuSelection::DebugViewAllItems => {
                 write!(f, "👁 🗒️ Debug View All Items      👁")
             }
@@ -55,37 +72,48 @@ impl TopMenuSelection {
             Self::Reflection,
             Self::ViewBulletList,
             Self::ViewExpectations,
+            Self::ViewWaitingFor,
+            Self::ViewPeople,
             Self::ViewMotivations,
+            Self::RecentlyFinished,
+            Self::ArchiveFinishedItems,
             Self::DebugViewAllItems,
         ]
     }
 }
 
-#[async_recursion]
+/// After an action the top menu is shown again, Esc goes back
 pub(crate) async fn present_top_menu(
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
-) -> Result<(), ()> {
+) -> Result<Navigation, ()> {
     let top_menu = TopMenuSelection::make_list();
 
     let selection = Select::new("Select from the below list|", top_menu).prompt();
-    match selection {
+    let result = match selection {
         Ok(TopMenuSelection::Capture) => capture(send_to_data_storage_layer).await,
         Ok(TopMenuSelection::ChangeRoutine) => change_routine(send_to_data_storage_layer).await,
         Ok(TopMenuSelection::Reflection) => todo!("Implement Reflection"),
         Ok(TopMenuSelection::ViewExpectations) => {
-            view_expectations(send_to_data_storage_layer).await
-        }
-        Ok(TopMenuSelection::ViewBulletList) => {
-            present_normal_bullet_list_menu(send_to_data_storage_layer).await
+            return Ok(Navigation::Push(Screen::Expectations))
         }
+        Ok(TopMenuSelection::ViewWaitingFor) => return Ok(Navigation::Push(Screen::WaitingFor)),
+        Ok(TopMenuSelection::ViewPeople) => return Ok(Navigation::Push(Screen::People)),
+        Ok(TopMenuSelection::ViewBulletList) => return Ok(Navigation::Push(Screen::BulletList)),
         Ok(TopMenuSelection::ViewMotivations) => view_motivations().await,
+        Ok(TopMenuSelection::RecentlyFinished) => {
+            present_recently_finished_menu(send_to_data_storage_layer).await
+        }
+        Ok(TopMenuSelection::ArchiveFinishedItems) => {
+            archive_finished_items(send_to_data_storage_layer).await
+        }
         Ok(TopMenuSelection::DebugViewAllItems) => {
             debug_view_all_items(send_to_data_storage_layer).await
         }
-        Err(InquireError::OperationCanceled) => Err(()),
+        Err(InquireError::OperationCanceled) => return Ok(Navigation::Back),
         Err(InquireError::OperationInterrupted) => Err(()),
         Err(err) => todo!("Unexpected InquireError of {}", err),
-    }
+    };
+    result.map(|()| Navigation::Stay)
 }
 
 pub(crate) async fn capture(
@@ -108,6 +136,33 @@ pub(crate) async fn capture(
     }
 }
 
+async fn archive_finished_items(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let days = loop {
+        let days = Text::new("Archive items finished more than how many days ago?")
+            .with_default("30")
+            .prompt();
+        match days {
+            Ok(days) => match days.trim().parse::<u32>() {
+                Ok(days) => break days,
+                Err(_) => println!("Please enter a whole number of days"),
+            },
+            Err(InquireError::OperationCanceled) => return Ok(()),
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => todo!("Unexpected InquireError of {}", err),
+        }
+    };
+
+    send_to_data_storage_layer
+        .send(DataLayerCommands::ArchiveFinishedItems {
+            finished_before: Utc::now() - chrono::Duration::days(days.into()),
+        })
+        .await
+        .unwrap();
+    Ok(())
+}
+
 async fn view_motivations() -> Result<(), ()> {
     todo!()
 }
@@ -177,7 +232,7 @@ async fn debug_view_all_items(
 
             Ok(())
         }
-        Err(InquireError::OperationCanceled) => present_top_menu(send_to_data_storage_layer).await,
+        Err(InquireError::OperationCanceled) => Ok(()),
         Err(InquireError::OperationInterrupted) => Err(()),
         Err(err) => todo!("Unexpected InquireError of {}", err),
     }
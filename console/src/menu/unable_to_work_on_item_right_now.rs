@@ -1,9 +1,8 @@
 use core::iter::once;
 use std::fmt::Display;
 
-use async_recursion::async_recursion;
 use chrono::{DateTime, Local, Utc};
-use inquire::{InquireError, Select, Text};
+use inquire::InquireError;
 use itertools::chain;
 use parse_datetime::{parse_datetime_at_date, ParseDateTimeError};
 use tokio::sync::mpsc::Sender;
@@ -14,11 +13,12 @@ use crate::{
         BaseData,
     },
     display::display_item::DisplayItem,
+    menu::prompt::{Select, Text},
     new_item::NewItem,
     surrealdb_layer::{surreal_tables::SurrealTables, DataLayerCommands},
 };
 
-use super::YesOrNo;
+use super::{LeaveOutCycles, YesOrNo};
 
 enum UnableReason {
     SomeoneOrGroupIsNotAvailable,
@@ -57,32 +57,43 @@ impl UnableReason {
     }
 }
 
-#[async_recursion]
+/// What happened in the menu for a reason
+enum ReasonHandled {
+    Done,
+    /// Esc, show the reasons again
+    PickAnotherReason,
+}
+
 pub(crate) async fn unable_to_work_on_item_right_now(
     unable_to_do: &Item<'_>,
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
 ) -> Result<(), ()> {
-    let list = UnableReason::make_list();
-    let selection = inquire::Select::new("Select from the below list|", list).prompt();
+    loop {
+        let list = UnableReason::make_list();
+        let selection = Select::new("Select from the below list|", list).prompt();
 
-    match selection {
-        Ok(UnableReason::SomeoneOrGroupIsNotAvailable) => {
-            person_or_group_is_not_available(unable_to_do, send_to_data_storage_layer).await
-        }
-        Ok(UnableReason::PlaceToContactIsNotOpen) => {
-            place_to_contact_is_not_open(unable_to_do, send_to_data_storage_layer).await
-        }
-        Ok(UnableReason::NeedToWaitBeforeWorkingOnThis) => {
-            need_to_wait_before_working_on_this(unable_to_do, send_to_data_storage_layer).await
-        }
-        Ok(UnableReason::NotEnoughTime) => {
-            todo!()
-        }
-        Err(InquireError::OperationCanceled) => {
-            todo!("I put in this todo because back at the time I would need to adjust some calling parameters to make this work")
+        let handled = match selection {
+            Ok(UnableReason::SomeoneOrGroupIsNotAvailable) => {
+                person_or_group_is_not_available(unable_to_do, send_to_data_storage_layer).await?
+            }
+            Ok(UnableReason::PlaceToContactIsNotOpen) => {
+                place_to_contact_is_not_open(unable_to_do, send_to_data_storage_layer).await?
+            }
+            Ok(UnableReason::NeedToWaitBeforeWorkingOnThis) => {
+                need_to_wait_before_working_on_this(unable_to_do, send_to_data_storage_layer)
+                    .await?
+            }
+            Ok(UnableReason::NotEnoughTime) => {
+                todo!()
+            }
+            Err(InquireError::OperationCanceled) => return Ok(()),
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => todo!("{:?}", err),
+        };
+        match handled {
+            ReasonHandled::Done => return Ok(()),
+            ReasonHandled::PickAnotherReason => continue,
         }
-        Err(InquireError::OperationInterrupted) => Err(()),
-        Err(err) => todo!("{:?}", err),
     }
 }
 
@@ -114,13 +125,12 @@ impl WhatLibraryToUse {
     }
 }
 
-#[async_recursion]
-pub(crate) async fn place_to_contact_is_not_open(
+async fn place_to_contact_is_not_open(
     unable_to_do: &Item<'_>,
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
-) -> Result<(), ()> {
+) -> Result<ReasonHandled, ()> {
     let list = WhatLibraryToUse::make_list();
-    let selection = inquire::Select::new(
+    let selection = Select::new(
         "What library should be used to state when they will be open",
         list,
     )
@@ -128,9 +138,7 @@ pub(crate) async fn place_to_contact_is_not_open(
     match selection {
         Ok(WhatLibraryToUse::DateParser) => {
             let when_they_will_be_open = loop {
-                let when_they_will_be_open = inquire::Text::new("When will they be open?")
-                    .prompt()
-                    .unwrap();
+                let when_they_will_be_open = Text::new("When will they be open?").prompt().unwrap();
                 match dateparser::parse(&when_they_will_be_open) {
                     Ok(when_they_will_be_open) => break when_they_will_be_open,
                     Err(err) => {
@@ -148,13 +156,11 @@ pub(crate) async fn place_to_contact_is_not_open(
                 ))
                 .await
                 .unwrap();
-            Ok(())
+            Ok(ReasonHandled::Done)
         }
         Ok(WhatLibraryToUse::ParseDateTime) => {
             let when_they_will_be_open = loop {
-                let when_they_will_be_open = inquire::Text::new("When will they be open?")
-                    .prompt()
-                    .unwrap();
+                let when_they_will_be_open = Text::new("When will they be open?").prompt().unwrap();
                 let now = Local::now();
                 match parse_datetime_at_date(now, when_they_will_be_open) {
                     Ok(when_they_will_be_open) => break when_they_will_be_open,
@@ -171,14 +177,12 @@ pub(crate) async fn place_to_contact_is_not_open(
                 ))
                 .await
                 .unwrap();
-            Ok(())
+            Ok(ReasonHandled::Done)
         }
         Ok(WhatLibraryToUse::DurationStr) => {
             todo!()
         }
-        Err(InquireError::OperationCanceled) => {
-            unable_to_work_on_item_right_now(unable_to_do, send_to_data_storage_layer).await
-        }
+        Err(InquireError::OperationCanceled) => Ok(ReasonHandled::PickAnotherReason),
         Err(InquireError::OperationInterrupted) => Err(()),
         Err(err) => todo!("{:?}", err),
     }
@@ -212,32 +216,39 @@ impl<'e> PersonOrGroupSelection<'e> {
     }
 }
 
-pub(crate) async fn person_or_group_is_not_available(
+async fn person_or_group_is_not_available(
     unable_to_do: &Item<'_>,
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
-) -> Result<(), ()> {
+) -> Result<ReasonHandled, ()> {
     let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
         .await
         .unwrap();
     let now = Utc::now();
     let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+    let mut cycles = LeaveOutCycles::new(base_data.get_surreal_tables());
     let items = base_data.get_items();
-    let list = PersonOrGroupSelection::make_list(items.filter_just_persons_or_groups());
+    let list = PersonOrGroupSelection::make_list(
+        items
+            .filter_just_persons_or_groups()
+            .filter(|x| cycles.keep(x, unable_to_do)),
+    );
+    cycles.print_summary();
 
     let selection = Select::new("Select from the below list|", list).prompt();
     match selection {
         Ok(PersonOrGroupSelection::ExistingPersonOrGroup(person_or_group)) => {
             let person_or_group: &Item = person_or_group.into();
-            send_to_data_storage_layer
-                .send(DataLayerCommands::CoverItemWithAnExistingItem {
-                    item_to_be_covered: unable_to_do.get_surreal_record_id().clone(),
-                    item_that_should_do_the_covering: person_or_group
-                        .get_surreal_record_id()
-                        .clone(),
-                })
-                .await
-                .unwrap();
-            Ok(())
+            if let Err(would_create_a_cycle) = DataLayerCommands::cover_item_with_an_existing_item(
+                send_to_data_storage_layer,
+                unable_to_do.get_surreal_record_id().clone(),
+                person_or_group.get_surreal_record_id().clone(),
+            )
+            .await
+            .unwrap()
+            {
+                println!("{}", would_create_a_cycle);
+            }
+            Ok(ReasonHandled::Done)
         }
         Ok(PersonOrGroupSelection::NewPersonOrGroup) => {
             let summary = Text::new("Enter the name of the person or group ⍠")
@@ -251,29 +262,24 @@ pub(crate) async fn person_or_group_is_not_available(
                 })
                 .await
                 .unwrap();
-            Ok(())
-        }
-        Err(InquireError::OperationCanceled) => {
-            unable_to_work_on_item_right_now(unable_to_do, send_to_data_storage_layer).await
+            Ok(ReasonHandled::Done)
         }
+        Err(InquireError::OperationCanceled) => Ok(ReasonHandled::PickAnotherReason),
         Err(InquireError::OperationInterrupted) => Err(()),
         Err(err) => todo!("{:?}", err),
     }
 }
 
-pub(crate) async fn need_to_wait_before_working_on_this(
+async fn need_to_wait_before_working_on_this(
     unable_to_do: &Item<'_>,
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
-) -> Result<(), ()> {
+) -> Result<ReasonHandled, ()> {
     let now = Local::now();
     let wait_until: DateTime<Utc> = loop {
         let wait_for_how_long = Text::new("Wait for how long?").prompt();
         let wait_for_how_long = match wait_for_how_long {
             Ok(wait_for_how_long) => wait_for_how_long,
-            Err(InquireError::OperationCanceled) => {
-                unable_to_work_on_item_right_now(unable_to_do, send_to_data_storage_layer).await?;
-                return Ok(());
-            }
+            Err(InquireError::OperationCanceled) => return Ok(ReasonHandled::PickAnotherReason),
             Err(InquireError::OperationInterrupted) => return Err(()),
             Err(err) => todo!("{:?}", err),
         };
@@ -305,5 +311,57 @@ pub(crate) async fn need_to_wait_before_working_on_this(
         .await
         .unwrap();
 
-    Ok(())
+    Ok(ReasonHandled::Done)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use tokio::sync::mpsc;
+
+    use crate::{
+        menu::prompt::scripted::{run_scripted, Answer},
+        new_item::NewItem,
+        surrealdb_layer::{
+            data_storage_start_and_run, surreal_tables::SurrealTables, DataLayerCommands,
+        },
+    };
+
+    use super::unable_to_work_on_item_right_now;
+
+    #[tokio::test]
+    async fn esc_goes_back_to_the_reasons_and_then_back_to_the_caller() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Call the bank".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let items = surreal_tables.make_items();
+
+        let result = run_scripted(
+            [
+                Answer::Select("Someone is not available".into()),
+                Answer::Cancel,
+                Answer::Select("Need to wait".into()),
+                Answer::Cancel,
+                Answer::Cancel,
+            ],
+            unable_to_work_on_item_right_now(&items[0], &sender),
+        )
+        .await;
+
+        assert_eq!(result, Ok(()));
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert!(surreal_tables.surreal_coverings.is_empty());
+        assert!(surreal_tables.surreal_coverings_until_date_time.is_empty());
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
 }
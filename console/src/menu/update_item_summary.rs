@@ -1,5 +1,6 @@
 use crate::base_data::item::Item;
-use inquire::{InquireError, Text};
+use crate::menu::prompt::Text;
+use inquire::InquireError;
 use tokio::sync::mpsc::Sender;
 
 use crate::surrealdb_layer::DataLayerCommands;
@@ -22,8 +23,56 @@ pub(crate) async fn update_item_summary(
                 .unwrap();
             Ok(())
         }
-        Err(InquireError::OperationCanceled) => todo!("Handle return to caller"),
+        Err(InquireError::OperationCanceled) => Ok(()),
         Err(InquireError::OperationInterrupted) => Err(()),
         Err(err) => panic!("Unexpected error: {:?}", err),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use tokio::sync::mpsc;
+
+    use crate::{
+        menu::prompt::scripted::{run_scripted, Answer},
+        new_item::NewItem,
+        surrealdb_layer::{
+            data_storage_start_and_run, surreal_tables::SurrealTables, DataLayerCommands,
+        },
+    };
+
+    use super::update_item_summary;
+
+    #[tokio::test]
+    async fn pressing_enter_keeps_the_summary() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Fix the login page".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let items = surreal_tables.make_items();
+
+        run_scripted(
+            [Answer::Text("".into())],
+            update_item_summary(&items[0], &sender),
+        )
+        .await
+        .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(
+            surreal_tables.surreal_items[0].summary,
+            "Fix the login page"
+        );
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+}
@@ -0,0 +1,339 @@
+//! Keeping track of what is handed off to someone else. The item is snoozed until it is time to follow up and then it
+//! comes back onto the bullet list as "Follow up with ... about ...".
+
+use std::{fmt::Display, iter::once};
+
+use chrono::{DateTime, Utc};
+use inquire::InquireError;
+use itertools::chain;
+use surrealdb::opt::RecordId;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::{
+        item::{Item, ItemVecExtensions},
+        BaseData,
+    },
+    calculated_data::CalculatedData,
+    display::{display_item::DisplayItem, display_waiting_for::DisplayWaitingFor},
+    menu::{
+        navigation::Navigation,
+        prompt::{Select, Text},
+    },
+    new_item::NewItem,
+    node::item_node::ItemNode,
+    surrealdb_layer::{surreal_item::WaitingFor, surreal_tables::SurrealTables, DataLayerCommands},
+};
+
+enum WhoSelection<'e> {
+    ExistingPersonOrGroup(DisplayItem<'e>),
+    NewPersonOrGroup,
+}
+
+impl Display for WhoSelection<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WhoSelection::ExistingPersonOrGroup(person_or_group) => {
+                write!(f, "{}", person_or_group)
+            }
+            WhoSelection::NewPersonOrGroup => write!(f, "New Person or Group"),
+        }
+    }
+}
+
+impl<'e> WhoSelection<'e> {
+    fn make_list(persons_or_groups: impl Iterator<Item = &'e Item<'e>>) -> Vec<Self> {
+        chain!(
+            persons_or_groups.map(|x| Self::ExistingPersonOrGroup(DisplayItem::new(x))),
+            once(Self::NewPersonOrGroup)
+        )
+        .collect()
+    }
+}
+
+/// Asks who this is waiting on and when to follow up with them
+pub(crate) async fn start_waiting_for(
+    waiting_on_this: &Item<'_>,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let who = match select_who(send_to_data_storage_layer).await? {
+        Some(who) => who,
+        None => return Ok(()),
+    };
+    let follow_up_after = match ask_when_to_follow_up()? {
+        Some(follow_up_after) => follow_up_after,
+        None => return Ok(()),
+    };
+    send_to_data_storage_layer
+        .send(DataLayerCommands::UpdateItemWaitingFor(
+            waiting_on_this.get_surreal_record_id().clone(),
+            Some(WaitingFor {
+                who,
+                since: Utc::now().into(),
+                follow_up_after: follow_up_after.into(),
+            }),
+        ))
+        .await
+        .unwrap();
+    Ok(())
+}
+
+/// None if Esc was pressed
+async fn select_who(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<Option<RecordId>, ()> {
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, Utc::now());
+    let list = WhoSelection::make_list(base_data.get_items().filter_just_persons_or_groups());
+
+    let selection = Select::new("Who are you waiting on?", list).prompt();
+    match selection {
+        Ok(WhoSelection::ExistingPersonOrGroup(person_or_group)) => {
+            let person_or_group: &Item = person_or_group.into();
+            Ok(Some(person_or_group.get_surreal_record_id().clone()))
+        }
+        Ok(WhoSelection::NewPersonOrGroup) => {
+            let name = Text::new("Enter the name of the person or group ⍠").prompt();
+            let name = match name {
+                Ok(name) => name,
+                Err(InquireError::OperationCanceled) => return Ok(None),
+                Err(InquireError::OperationInterrupted) => return Err(()),
+                Err(err) => todo!("Unexpected {}", err),
+            };
+            send_to_data_storage_layer
+                .send(DataLayerCommands::NewItem(NewItem::new_person_or_group(
+                    name.clone(),
+                    Utc::now(),
+                )))
+                .await
+                .unwrap();
+
+            let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+                .await
+                .unwrap();
+            let base_data = BaseData::new_from_surreal_tables(surreal_tables, Utc::now());
+            let new_person_or_group = base_data
+                .get_items()
+                .filter_just_persons_or_groups()
+                .find(|x| x.get_summary() == name)
+                .expect("Just created");
+            Ok(Some(new_person_or_group.get_surreal_record_id().clone()))
+        }
+        Err(InquireError::OperationCanceled) => Ok(None),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => todo!("Unexpected {}", err),
+    }
+}
+
+/// None if Esc was pressed
+fn ask_when_to_follow_up() -> Result<Option<DateTime<Utc>>, ()> {
+    loop {
+        let follow_up = Text::new("Follow up if there is no response after how long?")
+            .with_default("3d")
+            .prompt();
+        let follow_up = match follow_up {
+            Ok(follow_up) => follow_up,
+            Err(InquireError::OperationCanceled) => return Ok(None),
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => todo!("Unexpected {}", err),
+        };
+        match duration_str::parse(&follow_up) {
+            Ok(duration) => return Ok(Some(Utc::now() + duration)),
+            Err(_) => match dateparser::parse(&follow_up) {
+                Ok(follow_up) => return Ok(Some(follow_up)),
+                Err(_) => println!("Invalid input. Please try again."),
+            },
+        }
+    }
+}
+
+enum WaitingForSelection {
+    TheyResponded,
+    IFollowedUp,
+    ReturnToPreviousMenu,
+}
+
+impl Display for WaitingForSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WaitingForSelection::TheyResponded => {
+                write!(f, "They responded, this is back to me")
+            }
+            WaitingForSelection::IFollowedUp => write!(f, "I followed up, keep waiting"),
+            WaitingForSelection::ReturnToPreviousMenu => write!(f, "Return to the previous menu"),
+        }
+    }
+}
+
+/// For an item that is already waiting on someone
+pub(crate) async fn present_waiting_for_selected(
+    item_node: &ItemNode<'_>,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let waiting_for = item_node
+        .get_waiting_for()
+        .expect("Only called for items that are waiting");
+    println!("{}", DisplayWaitingFor::new(item_node, Utc::now()));
+    let list = vec![
+        WaitingForSelection::TheyResponded,
+        WaitingForSelection::IFollowedUp,
+        WaitingForSelection::ReturnToPreviousMenu,
+    ];
+
+    let selection = Select::new("Select from the below list|", list).prompt();
+    match selection {
+        Ok(WaitingForSelection::TheyResponded) => {
+            send_to_data_storage_layer
+                .send(DataLayerCommands::UpdateItemWaitingFor(
+                    item_node.get_surreal_record_id().clone(),
+                    None,
+                ))
+                .await
+                .unwrap();
+            Ok(())
+        }
+        Ok(WaitingForSelection::IFollowedUp) => {
+            let follow_up_after = match ask_when_to_follow_up()? {
+                Some(follow_up_after) => follow_up_after,
+                None => return Ok(()),
+            };
+            send_to_data_storage_layer
+                .send(DataLayerCommands::UpdateItemWaitingFor(
+                    item_node.get_surreal_record_id().clone(),
+                    Some(WaitingFor {
+                        follow_up_after: follow_up_after.into(),
+                        ..waiting_for.clone()
+                    }),
+                ))
+                .await
+                .unwrap();
+            Ok(())
+        }
+        Ok(WaitingForSelection::ReturnToPreviousMenu) | Err(InquireError::OperationCanceled) => {
+            Ok(())
+        }
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => todo!("Unexpected {}", err),
+    }
+}
+
+/// Everything that is waiting on someone, the ones to follow up on first
+pub(crate) async fn view_waiting_for(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<Navigation, ()> {
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    let now = Utc::now();
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+    let calculated_data = CalculatedData::new_from_base_data(base_data, &now);
+    let mut waiting = calculated_data
+        .get_item_status()
+        .iter()
+        .map(|x| x.get_item_node())
+        .filter(|x| x.get_waiting_for().is_some())
+        .collect::<Vec<_>>();
+    waiting.sort_by_key(|x| {
+        x.get_waiting_for()
+            .expect("Filtered above")
+            .follow_up_after
+            .clone()
+    });
+
+    if waiting.is_empty() {
+        println!("Not waiting on anyone");
+        return Ok(Navigation::Back);
+    }
+
+    let list = waiting
+        .iter()
+        .map(|x| WaitingForItem { item_node: x, now })
+        .collect::<Vec<_>>();
+    let selection = Select::new("Waiting for|", list)
+        .with_page_size(30)
+        .prompt();
+    let result = match selection {
+        Ok(selected) => {
+            present_waiting_for_selected(selected.item_node, send_to_data_storage_layer).await
+        }
+        Err(InquireError::OperationCanceled) => return Ok(Navigation::Back),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => todo!("Unexpected {}", err),
+    };
+    result.map(|()| Navigation::Stay)
+}
+
+struct WaitingForItem<'e> {
+    item_node: &'e ItemNode<'e>,
+    now: DateTime<Utc>,
+}
+
+impl Display for WaitingForItem<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", DisplayWaitingFor::new(self.item_node, self.now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, Utc};
+    use tokio::sync::mpsc;
+
+    use crate::{
+        menu::prompt::scripted::{run_scripted, Answer},
+        new_item::NewItem,
+        surrealdb_layer::{
+            data_storage_start_and_run, surreal_tables::SurrealTables, DataLayerCommands,
+        },
+    };
+
+    use super::start_waiting_for;
+
+    #[tokio::test]
+    async fn waiting_on_a_new_person_saves_who_and_when_to_follow_up() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Get the contract signed".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let items = surreal_tables.make_items();
+
+        run_scripted(
+            [
+                Answer::Select("New Person or Group".into()),
+                Answer::Text("Alex".into()),
+                Answer::Text("2d".into()),
+            ],
+            start_waiting_for(&items[0], &sender),
+        )
+        .await
+        .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let alex = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "Alex")
+            .unwrap();
+        let contract = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "Get the contract signed")
+            .unwrap();
+        let waiting_for = contract.waiting_for.clone().unwrap();
+        assert_eq!(Some(waiting_for.who), alex.id);
+        let follow_up_after: DateTime<Utc> = waiting_for.follow_up_after.into();
+        assert!(follow_up_after - Utc::now() > Duration::days(1));
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+}
@@ -1,2 +0,0 @@
-pub(crate) mod item_node;
-pub(crate) mod item_status;
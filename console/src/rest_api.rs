@@ -0,0 +1,717 @@
+//! The serve command. Exposes the data on localhost as JSON so small browser dashboards and editor integrations can be
+//! built without linking this crate. GET /openapi.json describes the endpoints.
+
+use axum::{
+    extract::{Path, Request, State},
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::{self, Next},
+    response::Response,
+    routing::{get, post, put},
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use surrealdb::opt::RecordId;
+use tokio::{
+    net::TcpListener,
+    sync::{mpsc::Sender, oneshot},
+};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use crate::{
+    base_data::BaseData,
+    calculated_data::CalculatedData,
+    config::Config,
+    new_item::NewItem,
+    surrealdb_layer::{
+        surreal_covering::SurrealCovering,
+        surreal_covering_until_date_time::SurrealCoveringUntilDatetime,
+        surreal_item::{
+            ItemType, Permanence, Responsibility, Staging, SurrealItem, SurrealOrderedSubItem,
+        },
+        surreal_tables::SurrealTables,
+        DataLayerCommands,
+    },
+    systems::bullet_list::{BulletList, BulletListReason},
+};
+
+/// Only listens on localhost and only answers requests made to localhost, there is no authentication
+pub(crate) async fn run_rest_api(
+    port: u16,
+    send_to_data_storage_layer: Sender<DataLayerCommands>,
+) -> std::io::Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+    println!(
+        "Serving at http://{}, see /openapi.json for the endpoints, press Ctrl+C to stop",
+        listener.local_addr()?
+    );
+    let port = listener.local_addr()?.port();
+    axum::serve(listener, router(send_to_data_storage_layer, port))
+        .with_graceful_shutdown(async {
+            tokio::signal::ctrl_c().await.unwrap();
+        })
+        .await
+}
+
+/// Pages from other localhost ports can read the responses, pages from anywhere else cannot
+fn router(send_to_data_storage_layer: Sender<DataLayerCommands>, port: u16) -> Router {
+    let cors = CorsLayer::new()
+        .allow_origin(AllowOrigin::predicate(|origin, _| {
+            is_localhost_origin(origin)
+        }))
+        .allow_methods([Method::GET, Method::POST, Method::PUT])
+        .allow_headers([header::CONTENT_TYPE]);
+    Router::new()
+        .route("/openapi.json", get(get_openapi))
+        .route("/items", get(get_items).post(post_item))
+        .route("/items/:id/staging", put(put_staging))
+        .route("/items/:id/snooze", post(post_snooze))
+        .route("/coverings", get(get_coverings).post(post_covering))
+        .route("/snoozes", get(get_snoozes))
+        .route("/bullet-list", get(get_bullet_list))
+        .with_state(send_to_data_storage_layer)
+        .layer(cors)
+        .layer(middleware::from_fn_with_state(port, check_host))
+}
+
+/// A website can point its own name at 127.0.0.1 (DNS rebinding), the browser still sends that name as the Host so
+/// those requests are refused
+async fn check_host(
+    State(port): State<u16>,
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let is_localhost = request
+        .headers()
+        .get(header::HOST)
+        .and_then(|x| x.to_str().ok())
+        .is_some_and(|host| {
+            host == format!("127.0.0.1:{}", port) || host == format!("localhost:{}", port)
+        });
+    if is_localhost {
+        Ok(next.run(request).await)
+    } else {
+        Err(StatusCode::FORBIDDEN)
+    }
+}
+
+/// For example "http://localhost:3000" or "http://127.0.0.1:8080"
+fn is_localhost_origin(origin: &HeaderValue) -> bool {
+    let Some(host) = origin.to_str().ok().and_then(|x| {
+        x.strip_prefix("http://")
+            .or_else(|| x.strip_prefix("https://"))
+    }) else {
+        return false;
+    };
+    //The port does not matter, a dashboard can be served from anywhere on this computer
+    let host = host.rsplit_once(':').map_or(host, |(host, _)| host);
+    host == "localhost" || host == "127.0.0.1"
+}
+
+/// Ids are the raw SurrealDB record ids, for example "item:fh29uai3538k3jd38qxm"
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct ItemJson {
+    id: String,
+    summary: String,
+    finished: Option<DateTime<Utc>>,
+    item_type: ItemType,
+    responsibility: Responsibility,
+    permanence: Permanence,
+    staging: Staging,
+    smaller_items_in_priority_order: Vec<String>,
+}
+
+impl From<&SurrealItem> for ItemJson {
+    fn from(value: &SurrealItem) -> Self {
+        ItemJson {
+            id: value.id.as_ref().expect("Already in the database").to_raw(),
+            summary: value.summary.clone(),
+            finished: value.finished.clone().map(|x| x.0),
+            item_type: value.item_type.clone(),
+            responsibility: value.responsibility.clone(),
+            permanence: value.permanence.clone(),
+            staging: value.staging.clone(),
+            smaller_items_in_priority_order: value
+                .smaller_items_in_priority_order
+                .iter()
+                .filter_map(|x| match x {
+                    SurrealOrderedSubItem::SubItem { surreal_item_id } => {
+                        Some(surreal_item_id.to_raw())
+                    }
+                    SurrealOrderedSubItem::Split { .. } => None,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct CoveringJson {
+    /// The smaller item that is doing the covering
+    smaller: String,
+    /// The larger item that is being covered
+    larger: String,
+}
+
+impl From<&SurrealCovering> for CoveringJson {
+    fn from(value: &SurrealCovering) -> Self {
+        CoveringJson {
+            smaller: value.smaller.to_raw(),
+            larger: value.parent.to_raw(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct SnoozeJson {
+    item: String,
+    until: DateTime<Utc>,
+}
+
+impl From<&SurrealCoveringUntilDatetime> for SnoozeJson {
+    fn from(value: &SurrealCoveringUntilDatetime) -> Self {
+        SnoozeJson {
+            item: value.cover_this.to_raw(),
+            until: value.until.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum BulletListReasonJson {
+    WorkOn,
+    SetStaging,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct BulletListItemJson {
+    id: String,
+    summary: String,
+    reason: BulletListReasonJson,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct NewItemJson {
+    summary: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct SnoozeUntilJson {
+    until: DateTime<Utc>,
+}
+
+fn find_item(surreal_tables: &SurrealTables, id: &str) -> Result<RecordId, StatusCode> {
+    surreal_tables
+        .surreal_items
+        .iter()
+        .filter_map(|x| x.id.as_ref())
+        .find(|x| x.to_raw() == id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+async fn get_surreal_tables(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<SurrealTables, StatusCode> {
+    SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .map_err(|_| StatusCode::SERVICE_UNAVAILABLE)
+}
+
+async fn send(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+    command: DataLayerCommands,
+) -> StatusCode {
+    //Commands are handled in order so the change is seen by any request that comes after this
+    match send_to_data_storage_layer.send(command).await {
+        Ok(()) => StatusCode::NO_CONTENT,
+        Err(_) => StatusCode::SERVICE_UNAVAILABLE,
+    }
+}
+
+async fn get_items(
+    State(send_to_data_storage_layer): State<Sender<DataLayerCommands>>,
+) -> Result<Json<Vec<ItemJson>>, StatusCode> {
+    let surreal_tables = get_surreal_tables(&send_to_data_storage_layer).await?;
+    Ok(Json(
+        surreal_tables
+            .surreal_items
+            .iter()
+            .map(ItemJson::from)
+            .collect(),
+    ))
+}
+
+async fn post_item(
+    State(send_to_data_storage_layer): State<Sender<DataLayerCommands>>,
+    Json(new_item): Json<NewItemJson>,
+) -> StatusCode {
+    send(
+        &send_to_data_storage_layer,
+        DataLayerCommands::NewItem(NewItem::new(new_item.summary, Utc::now())),
+    )
+    .await
+}
+
+async fn put_staging(
+    State(send_to_data_storage_layer): State<Sender<DataLayerCommands>>,
+    Path(id): Path<String>,
+    Json(staging): Json<Staging>,
+) -> Result<StatusCode, StatusCode> {
+    let surreal_tables = get_surreal_tables(&send_to_data_storage_layer).await?;
+    let item = find_item(&surreal_tables, &id)?;
+    Ok(send(
+        &send_to_data_storage_layer,
+        DataLayerCommands::UpdateItemStaging(item, staging),
+    )
+    .await)
+}
+
+async fn post_snooze(
+    State(send_to_data_storage_layer): State<Sender<DataLayerCommands>>,
+    Path(id): Path<String>,
+    Json(snooze): Json<SnoozeUntilJson>,
+) -> Result<StatusCode, StatusCode> {
+    let surreal_tables = get_surreal_tables(&send_to_data_storage_layer).await?;
+    let item = find_item(&surreal_tables, &id)?;
+    Ok(send(
+        &send_to_data_storage_layer,
+        DataLayerCommands::CoverItemUntilAnExactDateTime(item, snooze.until),
+    )
+    .await)
+}
+
+async fn get_coverings(
+    State(send_to_data_storage_layer): State<Sender<DataLayerCommands>>,
+) -> Result<Json<Vec<CoveringJson>>, StatusCode> {
+    let surreal_tables = get_surreal_tables(&send_to_data_storage_layer).await?;
+    Ok(Json(
+        surreal_tables
+            .surreal_coverings
+            .iter()
+            .map(CoveringJson::from)
+            .collect(),
+    ))
+}
+
+async fn post_covering(
+    State(send_to_data_storage_layer): State<Sender<DataLayerCommands>>,
+    Json(covering): Json<CoveringJson>,
+) -> Result<StatusCode, StatusCode> {
+    let surreal_tables = get_surreal_tables(&send_to_data_storage_layer).await?;
+    let item_that_should_do_the_covering = find_item(&surreal_tables, &covering.smaller)?;
+    let item_to_be_covered = find_item(&surreal_tables, &covering.larger)?;
+    let (send_response_here, response) = oneshot::channel();
+    let status = send(
+        &send_to_data_storage_layer,
+        DataLayerCommands::CoverItemWithAnExistingItem {
+            item_to_be_covered,
+            item_that_should_do_the_covering,
+            send_response_here,
+        },
+    )
+    .await;
+    match response.await {
+        Ok(Ok(())) => Ok(status),
+        //Refused because it would create a cycle
+        Ok(Err(_)) => Err(StatusCode::CONFLICT),
+        Err(_) => Err(StatusCode::SERVICE_UNAVAILABLE),
+    }
+}
+
+async fn get_snoozes(
+    State(send_to_data_storage_layer): State<Sender<DataLayerCommands>>,
+) -> Result<Json<Vec<SnoozeJson>>, StatusCode> {
+    let surreal_tables = get_surreal_tables(&send_to_data_storage_layer).await?;
+    Ok(Json(
+        surreal_tables
+            .surreal_coverings_until_date_time
+            .iter()
+            .map(SnoozeJson::from)
+            .collect(),
+    ))
+}
+
+async fn get_bullet_list(
+    State(send_to_data_storage_layer): State<Sender<DataLayerCommands>>,
+) -> Result<Json<Vec<BulletListItemJson>>, StatusCode> {
+    let surreal_tables = get_surreal_tables(&send_to_data_storage_layer).await?;
+    Ok(Json(make_bullet_list(surreal_tables, Utc::now())))
+}
+
+fn make_bullet_list(surreal_tables: SurrealTables, now: DateTime<Utc>) -> Vec<BulletListItemJson> {
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+    let calculated_data = CalculatedData::new_from_base_data(base_data, &now);
+    let ranking_strategy = Config::load().ranking_strategy.create();
+    let bullet_list =
+        BulletList::new_bullet_list_ranked_by(calculated_data, ranking_strategy.as_ref());
+    bullet_list
+        .get_bullet_list()
+        .iter()
+        .map(|x| {
+            let (item_status, reason) = match x {
+                BulletListReason::WorkOn(item_status) => {
+                    (item_status, BulletListReasonJson::WorkOn)
+                }
+                BulletListReason::SetStaging(item_status) => {
+                    (item_status, BulletListReasonJson::SetStaging)
+                }
+            };
+            BulletListItemJson {
+                id: item_status.get_surreal_record_id().to_raw(),
+                summary: item_status.get_item().get_summary().to_string(),
+                reason,
+            }
+        })
+        .collect()
+}
+
+async fn get_openapi() -> Json<Value> {
+    Json(openapi())
+}
+
+fn openapi() -> Value {
+    let id_parameter = json!({
+        "name": "id", "in": "path", "required": true,
+        "description": "The item's id as returned by GET /items, for example item:fh29uai3538k3jd38qxm",
+        "schema": { "type": "string" }
+    });
+    let changed = json!({ "description": "Changed, later requests will see the change" });
+    let not_found = json!({ "description": "No item has that id" });
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "On-Purpose",
+            "version": option_env!("CARGO_PKG_VERSION").unwrap_or("UNKNOWN"),
+            "description": "Items, coverings, snoozes, staging and the bullet list order. Listens on localhost only."
+        },
+        "paths": {
+            "/items": {
+                "get": {
+                    "summary": "Every item including finished items",
+                    "responses": { "200": { "description": "Items", "content": { "application/json": {
+                        "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Item" } } } } } }
+                },
+                "post": {
+                    "summary": "Capture a new item",
+                    "requestBody": { "required": true, "content": { "application/json": {
+                        "schema": { "$ref": "#/components/schemas/NewItem" } } } },
+                    "responses": { "204": changed }
+                }
+            },
+            "/items/{id}/staging": {
+                "put": {
+                    "summary": "Set the staging of an item",
+                    "parameters": [id_parameter],
+                    "requestBody": { "required": true, "content": { "application/json": {
+                        "schema": { "$ref": "#/components/schemas/Staging" } } } },
+                    "responses": { "204": changed, "404": not_found }
+                }
+            },
+            "/items/{id}/snooze": {
+                "post": {
+                    "summary": "Cover an item until an exact date and time",
+                    "parameters": [id_parameter],
+                    "requestBody": { "required": true, "content": { "application/json": {
+                        "schema": { "$ref": "#/components/schemas/SnoozeUntil" } } } },
+                    "responses": { "204": changed, "404": not_found }
+                }
+            },
+            "/coverings": {
+                "get": {
+                    "summary": "Items that are covered by a smaller item that should be done first",
+                    "responses": { "200": { "description": "Coverings", "content": { "application/json": {
+                        "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Covering" } } } } } }
+                },
+                "post": {
+                    "summary": "Cover the larger item with the smaller item",
+                    "requestBody": { "required": true, "content": { "application/json": {
+                        "schema": { "$ref": "#/components/schemas/Covering" } } } },
+                    "responses": {
+                        "204": changed,
+                        "404": not_found,
+                        "409": { "description": "Not changed because it would create a cycle" }
+                    }
+                }
+            },
+            "/snoozes": {
+                "get": {
+                    "summary": "Items that are covered until an exact date and time",
+                    "responses": { "200": { "description": "Snoozes", "content": { "application/json": {
+                        "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Snooze" } } } } } }
+                }
+            },
+            "/bullet-list": {
+                "get": {
+                    "summary": "The items to work on in the same order as the bullet list menu",
+                    "responses": { "200": { "description": "Bullet list", "content": { "application/json": {
+                        "schema": { "type": "array", "items": { "$ref": "#/components/schemas/BulletListItem" } } } } } }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "Item": {
+                    "type": "object",
+                    "required": ["id", "summary", "item_type", "responsibility", "permanence", "staging", "smaller_items_in_priority_order"],
+                    "properties": {
+                        "id": { "type": "string" },
+                        "summary": { "type": "string" },
+                        "finished": { "type": "string", "format": "date-time", "nullable": true },
+                        "item_type": { "description": "Undeclared, Action, { \"Goal\": HowMuchIsInMyControl }, IdeaOrThought, Motivation or PersonOrGroup" },
+                        "responsibility": { "type": "string", "enum": ["ProactiveActionToTake", "ReactiveBeAvailableToAct", "WaitingFor", "TrackingToBeAwareOf"] },
+                        "permanence": { "type": "string", "enum": ["Maintenance", "Project", "NotSet"] },
+                        "staging": { "$ref": "#/components/schemas/Staging" },
+                        "smaller_items_in_priority_order": { "type": "array", "items": { "type": "string" } }
+                    }
+                },
+                "NewItem": {
+                    "type": "object",
+                    "required": ["summary"],
+                    "properties": { "summary": { "type": "string" } }
+                },
+                "Staging": {
+                    "description": "NotSet, Planned, ThinkingAbout, Released, or { \"MentallyResident\": { \"enter_list\", \"lap\" } } or { \"OnDeck\": { \"enter_list\", \"lap\" } }"
+                },
+                "SnoozeUntil": {
+                    "type": "object",
+                    "required": ["until"],
+                    "properties": { "until": { "type": "string", "format": "date-time" } }
+                },
+                "Snooze": {
+                    "type": "object",
+                    "required": ["item", "until"],
+                    "properties": {
+                        "item": { "type": "string" },
+                        "until": { "type": "string", "format": "date-time" }
+                    }
+                },
+                "Covering": {
+                    "type": "object",
+                    "required": ["smaller", "larger"],
+                    "properties": {
+                        "smaller": { "type": "string", "description": "The item that is doing the covering" },
+                        "larger": { "type": "string", "description": "The item that is being covered" }
+                    }
+                },
+                "BulletListItem": {
+                    "type": "object",
+                    "required": ["id", "summary", "reason"],
+                    "properties": {
+                        "id": { "type": "string" },
+                        "summary": { "type": "string" },
+                        "reason": { "type": "string", "enum": ["WorkOn", "SetStaging"] }
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::{to_bytes, Body},
+        http::Request,
+    };
+    use serde::de::DeserializeOwned;
+    use tokio::sync::mpsc;
+    use tower::ServiceExt;
+
+    use crate::surrealdb_layer::data_storage_start_and_run;
+
+    use super::*;
+
+    const PORT: u16 = 7878;
+
+    async fn call(
+        router: &Router,
+        method: &str,
+        uri: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Vec<u8>) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("host", format!("127.0.0.1:{}", PORT))
+            .header("content-type", "application/json")
+            .body(match body {
+                Some(body) => Body::from(body.to_string()),
+                None => Body::empty(),
+            })
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, body.to_vec())
+    }
+
+    async fn get<T: DeserializeOwned>(router: &Router, uri: &str) -> T {
+        let (status, body) = call(router, "GET", uri, None).await;
+        assert_eq!(status, StatusCode::OK);
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn items_coverings_snoozes_and_staging_can_be_changed_and_read_back() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+        let router = router(sender, PORT);
+
+        for summary in ["Larger item", "Smaller item"] {
+            let (status, _) = call(
+                &router,
+                "POST",
+                "/items",
+                Some(json!({ "summary": summary })),
+            )
+            .await;
+            assert_eq!(status, StatusCode::NO_CONTENT);
+        }
+        let items: Vec<ItemJson> = get(&router, "/items").await;
+        assert_eq!(items.len(), 2);
+        let larger = items.iter().find(|x| x.summary == "Larger item").unwrap();
+        let smaller = items.iter().find(|x| x.summary == "Smaller item").unwrap();
+
+        let (status, _) = call(
+            &router,
+            "PUT",
+            &format!("/items/{}/staging", smaller.id),
+            Some(json!("Planned")),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = call(
+            &router,
+            "POST",
+            "/coverings",
+            Some(json!({ "smaller": smaller.id, "larger": larger.id })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let until = Utc::now() + chrono::Duration::days(1);
+        let (status, _) = call(
+            &router,
+            "POST",
+            &format!("/items/{}/snooze", larger.id),
+            Some(json!({ "until": until })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        let items: Vec<ItemJson> = get(&router, "/items").await;
+        let smaller = items.iter().find(|x| x.summary == "Smaller item").unwrap();
+        assert_eq!(smaller.staging, Staging::Planned);
+        let coverings: Vec<CoveringJson> = get(&router, "/coverings").await;
+        assert_eq!(
+            coverings,
+            vec![CoveringJson {
+                smaller: smaller.id.clone(),
+                larger: larger.id.clone()
+            }]
+        );
+        let snoozes: Vec<SnoozeJson> = get(&router, "/snoozes").await;
+        assert_eq!(snoozes.len(), 1);
+        assert_eq!(snoozes[0].item, larger.id);
+
+        let (status, _) = call(
+            &router,
+            "POST",
+            "/coverings",
+            Some(json!({ "smaller": larger.id, "larger": smaller.id })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT, "Would create a cycle");
+        let (status, _) = call(
+            &router,
+            "PUT",
+            "/items/item:does_not_exist/staging",
+            Some(json!("Planned")),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let bullet_list: Vec<BulletListItemJson> = get(&router, "/bullet-list").await;
+        assert_eq!(
+            bullet_list.iter().map(|x| &x.summary).collect::<Vec<_>>(),
+            vec!["Smaller item"],
+            "The larger item is covered so only the smaller item should be worked on"
+        );
+
+        drop(router);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn other_hosts_are_refused_and_only_localhost_pages_get_cors_headers() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+        let router = router(sender, PORT);
+
+        let send = |host: &str, origin: &str| {
+            router.clone().oneshot(
+                Request::builder()
+                    .uri("/items")
+                    .header("host", host)
+                    .header("origin", origin)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+        };
+        let allowed_origin = |response: &Response| {
+            response
+                .headers()
+                .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+                .map(|x| x.to_str().unwrap().to_string())
+        };
+
+        let response = send("localhost:7878", "http://localhost:3000")
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            allowed_origin(&response),
+            Some("http://localhost:3000".to_string())
+        );
+
+        let response = send("127.0.0.1:7878", "https://example.com").await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(allowed_origin(&response), None);
+
+        //DNS rebinding, the page's own name resolves to 127.0.0.1
+        let response = send("example.com:7878", "http://example.com:7878")
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = send("127.0.0.1:8080", "http://localhost:3000")
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        drop(router);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn openapi_documents_every_route() {
+        let (sender, _receiver) = mpsc::channel(1);
+        let router = router(sender, PORT);
+
+        let openapi: Value = get(&router, "/openapi.json").await;
+
+        let paths = openapi["paths"].as_object().unwrap();
+        for path in [
+            "/items",
+            "/items/{id}/staging",
+            "/items/{id}/snooze",
+            "/coverings",
+            "/snoozes",
+            "/bullet-list",
+        ] {
+            assert!(paths.contains_key(path), "{} is not documented", path);
+        }
+    }
+}
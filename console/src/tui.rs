@@ -0,0 +1,617 @@
+//! The tui command. A full-screen bullet list that stays open with single key shortcuts for the common actions rather
+//! than a chain of prompts. The state and the drawing do not touch the terminal so they can be tested with ratatui's
+//! TestBackend.
+
+use std::{io::Stdout, time::Duration};
+
+use chrono::{DateTime, Local, Utc};
+use crossterm::{
+    cursor::Show,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use duration_str::parse;
+use ratatui::{
+    backend::{Backend, CrosstermBackend},
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    Frame, Terminal,
+};
+use surrealdb::opt::RecordId;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::BaseData,
+    calculated_data::CalculatedData,
+    config::Config,
+    display::{display_item::DisplayItem, display_staging::DisplayStaging},
+    new_item::NewItem,
+    surrealdb_layer::{
+        surreal_item::{EnterListReason, Staging},
+        surreal_tables::SurrealTables,
+        DataLayerCommands,
+    },
+    systems::bullet_list::{ranking_strategy::RankingStrategy, BulletList, BulletListReason},
+};
+
+/// How often the list is reloaded when no key is pressed, so snoozes that end and changes from other processes show up
+const REFRESH_EVERY: Duration = Duration::from_secs(5);
+
+/// What is needed to draw one line of the bullet list and its detail pane, copied out of the BulletList because that
+/// borrows from the data it was calculated from
+#[derive(Debug, Clone)]
+struct BulletListRow {
+    record_id: RecordId,
+    summary: String,
+    parent_chain: Vec<String>,
+    staging: Staging,
+    lap_count: f32,
+    is_snoozed: bool,
+    snoozed_until: Vec<DateTime<Local>>,
+    staging_needs_to_be_set: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Mode {
+    Browse,
+    Capture(String),
+    Snooze(String),
+    WorkedOn(String),
+}
+
+enum Action {
+    Send(Box<DataLayerCommands>),
+    Quit,
+}
+
+struct App {
+    rows: Vec<BulletListRow>,
+    list_state: ListState,
+    mode: Mode,
+    message: Option<String>,
+    ranking_strategy: Box<dyn RankingStrategy + Send + Sync>,
+}
+
+impl App {
+    fn new() -> Self {
+        App {
+            rows: Vec::default(),
+            list_state: ListState::default(),
+            mode: Mode::Browse,
+            message: None,
+            ranking_strategy: Config::load().ranking_strategy.create(),
+        }
+    }
+
+    /// Keeps the same item selected if it is still on the list
+    fn refresh(&mut self, surreal_tables: SurrealTables, now: DateTime<Utc>) {
+        let selected = self.get_selected().map(|x| x.record_id.clone());
+
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+        let calculated_data = CalculatedData::new_from_base_data(base_data, &now);
+        let bullet_list =
+            BulletList::new_bullet_list_ranked_by(calculated_data, self.ranking_strategy.as_ref());
+        self.rows = bullet_list
+            .get_bullet_list()
+            .iter()
+            .map(|x| {
+                let item_status = x.get_item_status();
+                let item_node = item_status.get_item_node();
+                BulletListRow {
+                    record_id: item_status.get_surreal_record_id().clone(),
+                    summary: DisplayItem::new(item_status.get_item()).to_string(),
+                    parent_chain: item_node
+                        .create_parent_chain()
+                        .iter()
+                        .map(|x| DisplayItem::new(x).to_string())
+                        .collect(),
+                    staging: item_status.get_staging().clone(),
+                    lap_count: item_status.get_lap_count(),
+                    is_snoozed: item_status.is_snoozed(),
+                    snoozed_until: item_node.get_snoozed_until().iter().map(|x| **x).collect(),
+                    staging_needs_to_be_set: matches!(x, BulletListReason::SetStaging(..)),
+                }
+            })
+            .collect();
+
+        let index = selected
+            .and_then(|selected| self.rows.iter().position(|x| x.record_id == selected))
+            .or(self.list_state.selected())
+            .map(|x| x.min(self.rows.len().saturating_sub(1)));
+        self.list_state.select(if self.rows.is_empty() {
+            None
+        } else {
+            index.or(Some(0))
+        });
+    }
+
+    fn get_selected(&self) -> Option<&BulletListRow> {
+        self.list_state.selected().and_then(|x| self.rows.get(x))
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, now: DateTime<Utc>) -> Option<Action> {
+        if key.kind != KeyEventKind::Press {
+            return None;
+        }
+        //Raw mode turns Ctrl+C into a key press rather than a signal
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            if let KeyCode::Char(c) = key.code {
+                return (c == 'c').then_some(Action::Quit);
+            }
+        }
+        match self.mode.clone() {
+            Mode::Browse => self.handle_browse_key(key),
+            Mode::Capture(text) | Mode::Snooze(text) | Mode::WorkedOn(text) => match key.code {
+                KeyCode::Esc => {
+                    self.mode = Mode::Browse;
+                    None
+                }
+                KeyCode::Enter => {
+                    let mode = std::mem::replace(&mut self.mode, Mode::Browse);
+                    self.submit(mode, text, now)
+                }
+                KeyCode::Backspace => {
+                    let mut text = text;
+                    text.pop();
+                    self.set_input(text);
+                    None
+                }
+                KeyCode::Char(c) => {
+                    let mut text = text;
+                    text.push(c);
+                    self.set_input(text);
+                    None
+                }
+                _ => None,
+            },
+        }
+    }
+
+    fn handle_browse_key(&mut self, key: KeyEvent) -> Option<Action> {
+        self.message = None;
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => Some(Action::Quit),
+            KeyCode::Down | KeyCode::Char('j') => {
+                if let Some(selected) = self.list_state.selected() {
+                    if selected + 1 < self.rows.len() {
+                        self.list_state.select(Some(selected + 1));
+                    }
+                }
+                None
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                if let Some(selected) = self.list_state.selected() {
+                    self.list_state.select(Some(selected.saturating_sub(1)));
+                }
+                None
+            }
+            KeyCode::Char('c') => {
+                self.mode = Mode::Capture(String::default());
+                None
+            }
+            KeyCode::Char('f') => self.get_selected().map(|x| {
+                Action::Send(Box::new(DataLayerCommands::FinishItem(x.record_id.clone())))
+            }),
+            KeyCode::Char('s') => {
+                if self.get_selected().is_some() {
+                    self.mode = Mode::Snooze(String::default());
+                }
+                None
+            }
+            KeyCode::Char('w') => {
+                if self.get_selected().is_some() {
+                    self.mode = Mode::WorkedOn(String::default());
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn set_input(&mut self, text: String) {
+        self.mode = match self.mode {
+            Mode::Browse => Mode::Browse,
+            Mode::Capture(_) => Mode::Capture(text),
+            Mode::Snooze(_) => Mode::Snooze(text),
+            Mode::WorkedOn(_) => Mode::WorkedOn(text),
+        };
+    }
+
+    fn submit(&mut self, mode: Mode, text: String, now: DateTime<Utc>) -> Option<Action> {
+        match mode {
+            Mode::Browse => None,
+            Mode::Capture(_) => {
+                if text.trim().is_empty() {
+                    return None;
+                }
+                Some(Action::Send(Box::new(DataLayerCommands::NewItem(
+                    NewItem::new(text, now),
+                ))))
+            }
+            Mode::Snooze(_) => {
+                let selected = self.get_selected()?.record_id.clone();
+                let until = match parse(&text) {
+                    Ok(duration) => now + duration,
+                    Err(_) => match dateparser::parse(&text) {
+                        Ok(until) => until,
+                        Err(_) => {
+                            self.message = Some(format!("Unable to understand \"{}\"", text));
+                            return None;
+                        }
+                    },
+                };
+                Some(Action::Send(Box::new(
+                    DataLayerCommands::CoverItemUntilAnExactDateTime(selected, until),
+                )))
+            }
+            Mode::WorkedOn(_) => {
+                let selected = self.get_selected()?;
+                //The lap starts over from now, an empty answer keeps the current lap length if there is one
+                let lap = if text.trim().is_empty() {
+                    match &selected.staging {
+                        Staging::MentallyResident { lap, .. } | Staging::OnDeck { lap, .. } => *lap,
+                        _ => {
+                            self.message = Some("How long is a lap? For example 1h".into());
+                            return None;
+                        }
+                    }
+                } else {
+                    match parse(&text) {
+                        Ok(lap) => lap.into(),
+                        Err(_) => {
+                            self.message = Some(format!("Unable to understand \"{}\"", text));
+                            return None;
+                        }
+                    }
+                };
+                Some(Action::Send(Box::new(
+                    DataLayerCommands::UpdateItemStaging(
+                        selected.record_id.clone(),
+                        Staging::MentallyResident {
+                            enter_list: EnterListReason::DateTime(now.into()),
+                            lap,
+                        },
+                    ),
+                )))
+            }
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, app: &mut App) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(frame.size());
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(rows[0]);
+
+    let list = List::new(
+        app.rows
+            .iter()
+            .map(|x| {
+                if x.is_snoozed {
+                    ListItem::new(format!("💤 {}", x.summary))
+                } else if x.lap_count > 1.0 {
+                    ListItem::new(format!("⏰ {}", x.summary))
+                } else {
+                    ListItem::new(x.summary.clone())
+                }
+            })
+            .collect::<Vec<_>>(),
+    )
+    .block(Block::default().borders(Borders::ALL).title("Bullet List"))
+    .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(list, panes[0], &mut app.list_state);
+
+    let detail = match app.get_selected() {
+        Some(selected) => make_detail(selected),
+        None => vec![Line::from("Nothing to do, press c to capture")],
+    };
+    let detail = Paragraph::new(detail)
+        .block(Block::default().borders(Borders::ALL).title("Detail"))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(detail, panes[1]);
+
+    let bottom = match (&app.mode, &app.message) {
+        (Mode::Capture(text), _) => format!("New item: {}▏", text),
+        (Mode::Snooze(text), _) => format!("Snooze for how long or until when: {}▏", text),
+        (Mode::WorkedOn(text), _) => format!("Lap length, empty keeps the current one: {}▏", text),
+        (Mode::Browse, Some(message)) => message.clone(),
+        (Mode::Browse, None) => {
+            "f finish  w worked on  s snooze  c capture  ↑↓ select  q quit".to_string()
+        }
+    };
+    frame.render_widget(Paragraph::new(bottom), rows[1]);
+}
+
+fn make_detail(selected: &BulletListRow) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(selected.summary.clone()), Line::from("")];
+    if selected.parent_chain.is_empty() {
+        lines.push(Line::from("Parents: None"));
+    } else {
+        lines.push(Line::from("Parents:"));
+        lines.extend(
+            selected
+                .parent_chain
+                .iter()
+                .map(|x| Line::from(format!("  ⬅ {}", x))),
+        );
+    }
+    lines.push(Line::from(format!(
+        "Staging: {}",
+        DisplayStaging::new(&selected.staging)
+    )));
+    if selected.staging_needs_to_be_set {
+        lines.push(Line::from("  The staging needs to be set"));
+    }
+    lines.push(Line::from(format!("Lap count: {:.1}", selected.lap_count)));
+    if selected.snoozed_until.is_empty() {
+        //Staging can also snooze an item until it is time for it to enter the list
+        let is_snoozed = if selected.is_snoozed { "Yes" } else { "No" };
+        lines.push(Line::from(format!("Snoozed: {}", is_snoozed)));
+    } else {
+        for until in selected.snoozed_until.iter() {
+            lines.push(Line::from(format!(
+                "Snoozed until: {}",
+                until.format("%a %d %b %Y %I:%M%p")
+            )));
+        }
+    }
+    lines
+}
+
+async fn run_app<B: Backend>(
+    terminal: &mut Terminal<B>,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> std::io::Result<()> {
+    let mut app = App::new();
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    app.refresh(surreal_tables, Utc::now());
+
+    loop {
+        terminal.draw(|frame| draw(frame, &mut app))?;
+
+        //Reading the terminal blocks so it is kept off of the async worker threads
+        let event = tokio::task::spawn_blocking(|| -> std::io::Result<Option<Event>> {
+            if event::poll(REFRESH_EVERY)? {
+                Ok(Some(event::read()?))
+            } else {
+                Ok(None)
+            }
+        })
+        .await
+        .unwrap()?;
+
+        //Typing into capture or moving the selection does not change the data so there is nothing to reload
+        let is_refresh_needed = match event {
+            None => true,
+            Some(Event::Key(key)) => match app.handle_key(key, Utc::now()) {
+                Some(Action::Quit) => return Ok(()),
+                Some(Action::Send(command)) => {
+                    send_to_data_storage_layer.send(*command).await.unwrap();
+                    true
+                }
+                None => false,
+            },
+            Some(_) => false,
+        };
+
+        if is_refresh_needed {
+            let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+                .await
+                .unwrap();
+            app.refresh(surreal_tables, Utc::now());
+        }
+    }
+}
+
+/// Puts the terminal back when dropped, so it is also put back when run_app returns early or panics
+struct RestoreTerminal;
+
+impl Drop for RestoreTerminal {
+    fn drop(&mut self) {
+        //Nothing more can be done if this fails
+        let _ = disable_raw_mode();
+        let _ = execute!(std::io::stdout(), LeaveAlternateScreen, Show);
+    }
+}
+
+/// Takes over the terminal until q is pressed
+pub(crate) async fn run_tui(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> std::io::Result<()> {
+    enable_raw_mode()?;
+    let restore_terminal = RestoreTerminal;
+    let mut stdout: Stdout = std::io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let result = run_app(&mut terminal, send_to_data_storage_layer).await;
+
+    //Put the terminal back even if the app failed so the error can be read
+    drop(restore_terminal);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{backend::TestBackend, Terminal};
+    use tokio::sync::mpsc;
+
+    use crate::{
+        new_item::NewItemBuilder,
+        surrealdb_layer::{
+            data_storage_start_and_run,
+            surreal_item::{Responsibility, Staging},
+            surreal_tables::SurrealTables,
+            DataLayerCommands,
+        },
+    };
+
+    use super::{draw, Action, App, Mode};
+
+    fn press(app: &mut App, code: KeyCode) -> Option<Action> {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE), Utc::now())
+    }
+
+    fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            assert!(press(app, KeyCode::Char(c)).is_none());
+        }
+    }
+
+    fn screen(app: &mut App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 12)).unwrap();
+        terminal.draw(|frame| draw(frame, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer.get(x, y).symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[tokio::test]
+    async fn keys_finish_snooze_and_capture_the_selected_item() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        for summary in ["Plan the trip", "Book the flights"] {
+            sender
+                .send(DataLayerCommands::NewItem(
+                    NewItemBuilder::default()
+                        .summary(summary)
+                        .responsibility(Responsibility::ProactiveActionToTake)
+                        .build()
+                        .unwrap(),
+                ))
+                .await
+                .unwrap();
+        }
+
+        let mut app = App::new();
+        app.refresh(SurrealTables::new(&sender).await.unwrap(), Utc::now());
+        assert_eq!(app.rows.len(), 2);
+
+        let screen_text = screen(&mut app);
+        assert!(screen_text.contains("Bullet List"));
+        assert!(screen_text.contains("Plan the trip"));
+        assert!(screen_text.contains("Book the flights"));
+        assert!(screen_text.contains("Lap count"));
+        assert!(screen_text.contains("f finish"));
+
+        let first = app.rows[0].record_id.clone();
+        let second = app.rows[1].record_id.clone();
+
+        //Snooze the second item
+        assert!(press(&mut app, KeyCode::Down).is_none());
+        assert!(press(&mut app, KeyCode::Char('s')).is_none());
+        type_text(&mut app, "1h");
+        assert!(screen(&mut app).contains("Snooze for how long or until when: 1h"));
+        match press(&mut app, KeyCode::Enter) {
+            Some(Action::Send(command)) => {
+                assert!(matches!(
+                    command.as_ref(),
+                    DataLayerCommands::CoverItemUntilAnExactDateTime(item, until)
+                        if item == &second && until > &Utc::now()
+                ));
+                sender.send(*command).await.unwrap();
+            }
+            _ => panic!("Expected a snooze"),
+        }
+        assert_eq!(app.mode, Mode::Browse);
+        app.refresh(SurrealTables::new(&sender).await.unwrap(), Utc::now());
+        //Snoozed items move to the bottom of the list and stay selected
+        assert_eq!(app.rows.len(), 2);
+        assert_eq!(app.rows[1].record_id, second);
+        assert!(app.rows[1].is_snoozed);
+        assert!(screen(&mut app).contains("Snoozed until"));
+        assert!(press(&mut app, KeyCode::Up).is_none());
+
+        //Worked on needs a lap length because the first item does not have one yet
+        assert!(press(&mut app, KeyCode::Char('w')).is_none());
+        assert!(press(&mut app, KeyCode::Enter).is_none());
+        assert!(screen(&mut app).contains("How long is a lap?"));
+        assert!(press(&mut app, KeyCode::Char('w')).is_none());
+        type_text(&mut app, "30m");
+        match press(&mut app, KeyCode::Enter) {
+            Some(Action::Send(command)) => {
+                assert!(matches!(
+                    command.as_ref(),
+                    DataLayerCommands::UpdateItemStaging(item, Staging::MentallyResident { .. })
+                        if item == &first
+                ));
+                sender.send(*command).await.unwrap();
+            }
+            _ => panic!("Expected worked on"),
+        }
+        app.refresh(SurrealTables::new(&sender).await.unwrap(), Utc::now());
+        assert!(matches!(
+            app.rows[0].staging,
+            Staging::MentallyResident { .. }
+        ));
+
+        //Finish the first item
+        match press(&mut app, KeyCode::Char('f')) {
+            Some(Action::Send(command)) => {
+                assert!(
+                    matches!(command.as_ref(), DataLayerCommands::FinishItem(item) if item == &first)
+                );
+                sender.send(*command).await.unwrap();
+            }
+            _ => panic!("Expected finish"),
+        }
+        app.refresh(SurrealTables::new(&sender).await.unwrap(), Utc::now());
+        assert_eq!(app.rows.len(), 1);
+        assert_eq!(app.rows[0].record_id, second);
+
+        //Capture a new item, escape cancels without sending anything
+        assert!(press(&mut app, KeyCode::Char('c')).is_none());
+        type_text(&mut app, "Pack");
+        assert!(press(&mut app, KeyCode::Esc).is_none());
+        assert_eq!(app.mode, Mode::Browse);
+        assert!(press(&mut app, KeyCode::Char('c')).is_none());
+        type_text(&mut app, "Packs");
+        assert!(press(&mut app, KeyCode::Backspace).is_none());
+        match press(&mut app, KeyCode::Enter) {
+            Some(Action::Send(command)) => {
+                assert!(
+                    matches!(command.as_ref(), DataLayerCommands::NewItem(new_item) if new_item.summary == "Pack")
+                );
+            }
+            _ => panic!("Expected capture"),
+        }
+
+        assert!(matches!(
+            press(&mut app, KeyCode::Char('q')),
+            Some(Action::Quit)
+        ));
+
+        //Ctrl+C quits from any mode rather than typing a c
+        assert!(press(&mut app, KeyCode::Char('c')).is_none());
+        let control = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+        assert!(app.handle_key(control('a'), Utc::now()).is_none());
+        assert_eq!(app.mode, Mode::Capture(String::default()));
+        assert!(matches!(
+            app.handle_key(control('c'), Utc::now()),
+            Some(Action::Quit)
+        ));
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+}
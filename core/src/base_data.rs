@@ -1,9 +1,12 @@
-pub(crate) mod circumstance;
-pub(crate) mod covering;
-pub(crate) mod covering_until_date_time;
-pub(crate) mod item;
-pub(crate) mod life_area;
-pub(crate) mod routine;
+//! The database records with references between them, so for example a covering refers to its two items rather than
+//! to their ids
+
+pub mod circumstance;
+pub mod covering;
+pub mod covering_until_date_time;
+pub mod item;
+pub mod life_area;
+pub mod routine;
 
 use chrono::{DateTime, Utc};
 use ouroboros::self_referencing;
@@ -18,8 +21,9 @@ use self::{
     routine::Routine,
 };
 
+/// The database records with references between them, for example each covering refers to its two items
 #[self_referencing]
-pub(crate) struct BaseData {
+pub struct BaseData {
     surreal_tables: SurrealTables,
 
     #[borrows(surreal_tables)]
@@ -54,10 +58,8 @@ pub(crate) struct BaseData {
 }
 
 impl BaseData {
-    pub(crate) fn new_from_surreal_tables(
-        surreal_tables: SurrealTables,
-        now: DateTime<Utc>,
-    ) -> Self {
+    /// now is used for anything that depends on the time, like whether an item is still snoozed
+    pub fn new_from_surreal_tables(surreal_tables: SurrealTables, now: DateTime<Utc>) -> Self {
         BaseDataBuilder {
             surreal_tables,
             items_builder: |surreal_tables| surreal_tables.make_items(),
@@ -81,31 +83,43 @@ impl BaseData {
         .build()
     }
 
-    pub(crate) fn get_items(&self) -> &[Item] {
+    /// The records as they were loaded
+    pub fn get_surreal_tables(&self) -> &SurrealTables {
+        self.borrow_surreal_tables()
+    }
+
+    /// Every item, including finished items
+    pub fn get_items(&self) -> &[Item] {
         self.borrow_items()
     }
 
-    pub(crate) fn get_active_items(&self) -> &[&Item] {
+    /// Items that are not finished
+    pub fn get_active_items(&self) -> &[&Item] {
         self.borrow_active_items()
     }
 
-    pub(crate) fn get_coverings(&self) -> &[Covering] {
+    /// Coverings between active items
+    pub fn get_coverings(&self) -> &[Covering] {
         self.borrow_coverings()
     }
 
-    pub(crate) fn get_coverings_until_date_time(&self) -> &[CoveringUntilDateTime] {
+    /// Every snooze of an active item, including ones that have ended
+    pub fn get_coverings_until_date_time(&self) -> &[CoveringUntilDateTime] {
         self.borrow_coverings_until_date_time()
     }
 
-    pub(crate) fn get_active_snoozed(&self) -> &[&CoveringUntilDateTime] {
+    /// Snoozes of active items that have not ended yet
+    pub fn get_active_snoozed(&self) -> &[&CoveringUntilDateTime] {
         self.borrow_active_coverings_until_date_time()
     }
 
-    pub(crate) fn get_life_areas(&self) -> &[LifeArea] {
+    /// Every life area
+    pub fn get_life_areas(&self) -> &[LifeArea] {
         self.borrow_life_areas()
     }
 
-    pub(crate) fn get_routines(&self) -> &[Routine] {
+    /// Every routine
+    pub fn get_routines(&self) -> &[Routine] {
         self.borrow_routines()
     }
 }
@@ -1,12 +1,17 @@
+//! What has to be true before an item can be worked on, like being in focus time
+
 use crate::surrealdb_layer::{
     surreal_item::SurrealItem,
     surreal_required_circumstance::{CircumstanceType, SurrealRequiredCircumstance},
 };
 
+/// A required circumstance with the item that requires it
 #[derive(PartialEq, Eq, Clone, Debug)]
-pub(crate) struct Circumstance<'a> {
-    pub(crate) circumstance_for: &'a SurrealItem,
-    pub(crate) circumstance_type: &'a CircumstanceType,
+pub struct Circumstance<'a> {
+    /// The item that can only be worked on in this circumstance
+    pub circumstance_for: &'a SurrealItem,
+    /// What is required
+    pub circumstance_type: &'a CircumstanceType,
     surreal_required_circumstance: &'a SurrealRequiredCircumstance,
 }
 
@@ -0,0 +1,15 @@
+//! An item that needs to be done before another item can be worked on
+
+use crate::surrealdb_layer::surreal_covering::SurrealCovering;
+
+use super::item::Item;
+
+/// smaller needs to be done before parent can be worked on
+pub struct Covering<'a> {
+    /// The item to do first
+    pub smaller: &'a Item<'a>,
+    /// The item that is waiting on smaller
+    pub parent: &'a Item<'a>,
+    /// The record this was made from
+    pub _surreal_covering: &'a SurrealCovering,
+}
@@ -0,0 +1,14 @@
+//! An item that is snoozed until a date and time
+
+use chrono::{DateTime, Local};
+
+use super::item::Item;
+
+/// cover_this is snoozed until the date and time
+#[derive(Debug)]
+pub struct CoveringUntilDateTime<'a> {
+    /// The item that is snoozed
+    pub cover_this: &'a Item<'a>,
+    /// When the snooze ends
+    pub until: DateTime<Local>,
+}
@@ -1,19 +1,22 @@
+//! An item, which is anything to do or to keep in mind, with accessors for its record
+
 use chrono::{DateTime, Local, Utc};
 use itertools::chain;
 use surrealdb::{opt::RecordId, sql::Thing};
 
 use crate::surrealdb_layer::{
     surreal_item::{
-        Facing, ItemType, NotesLocation, Permanence, Responsibility, Staging, SurrealItem,
-        SurrealOrderedSubItem,
+        CalendarEvent, DueDate, Facing, GitBranch, ItemType, NotesLocation, Permanence,
+        Responsibility, Staging, SurrealItem, SurrealOrderedSubItem, WaitingFor,
     },
     surreal_required_circumstance::SurrealRequiredCircumstance,
 };
 
 use super::{covering::Covering, covering_until_date_time::CoveringUntilDateTime};
 
+/// A SurrealItem with the required circumstances that refer to it
 #[derive(PartialEq, Eq, Clone, Debug)]
-pub(crate) struct Item<'s> {
+pub struct Item<'s> {
     id: &'s RecordId,
     required_circumstances: Vec<&'s SurrealRequiredCircumstance>,
     surreal_item: &'s SurrealItem,
@@ -42,15 +45,24 @@ impl From<Item<'_>> for RecordId {
     }
 }
 
-pub(crate) trait ItemVecExtensions<'t> {
+/// Lookups and filters for a list of items
+pub trait ItemVecExtensions<'t> {
+    /// The filters return this so they can be chained without collecting
     type ItemIterator: Iterator<Item = &'t Item<'t>>;
 
+    /// The item with record_id, if it is in the list
     fn lookup_from_record_id<'a>(&'a self, record_id: &RecordId) -> Option<&'a Item>;
+    /// Just items of type Action
     fn filter_just_actions(&'t self) -> Self::ItemIterator;
+    /// Just items of type Goal
     fn filter_just_goals(&'t self) -> Self::ItemIterator;
+    /// Just items of type Motivation
     fn filter_just_motivations(&'t self) -> Self::ItemIterator;
+    /// Just items of type PersonOrGroup
     fn filter_just_persons_or_groups(&'t self) -> Self::ItemIterator;
+    /// Just items that do not have a type yet
     fn filter_just_undeclared_items(&'t self) -> Self::ItemIterator;
+    /// Items that are not finished
     fn filter_active_items(&self) -> Vec<&Item>;
 }
 
@@ -186,7 +198,8 @@ impl<'s> ItemVecExtensions<'s> for [&Item<'s>] {
 }
 
 impl<'b> Item<'b> {
-    pub(crate) fn new(
+    /// required_circumstances are the ones for this item
+    pub fn new(
         surreal_item: &'b SurrealItem,
         required_circumstances: Vec<&'b SurrealRequiredCircumstance>,
     ) -> Self {
@@ -197,19 +210,23 @@ impl<'b> Item<'b> {
         }
     }
 
-    pub(crate) fn get_item_type(&self) -> &'b ItemType {
+    /// For example an action, goal or motivation
+    pub fn get_item_type(&self) -> &'b ItemType {
         &self.surreal_item.item_type
     }
 
-    pub(crate) fn is_person_or_group(&self) -> bool {
+    /// A person or group that items can be waiting on
+    pub fn is_person_or_group(&self) -> bool {
         self.get_item_type() == &ItemType::PersonOrGroup
     }
 
-    pub(crate) fn is_finished(&self) -> bool {
+    /// True once the item has been marked as finished
+    pub fn is_finished(&self) -> bool {
         self.surreal_item.finished.is_some()
     }
 
-    pub(crate) fn when_finished(&self) -> Option<DateTime<Utc>> {
+    /// None until the item is finished
+    pub fn when_finished(&self) -> Option<DateTime<Utc>> {
         match self.surreal_item.finished {
             Some(ref finished) => {
                 let finished = finished.clone().into();
@@ -219,7 +236,13 @@ impl<'b> Item<'b> {
         }
     }
 
-    pub(crate) fn get_covered_by_another_item(&self, coverings: &[Covering<'b>]) -> Vec<&Self> {
+    /// When the item was captured
+    pub fn get_created(&self) -> DateTime<Utc> {
+        self.surreal_item.created.clone().into()
+    }
+
+    /// Unfinished items that need to be done before this item can be worked on
+    pub fn get_covered_by_another_item(&self, coverings: &[Covering<'b>]) -> Vec<&Self> {
         let covered_by = coverings.iter().filter(|x| self == x.parent);
         //Now see if the items that are covering are finished or active
         covered_by
@@ -233,7 +256,8 @@ impl<'b> Item<'b> {
             .collect()
     }
 
-    pub(crate) fn get_covering_another_item(&self, coverings: &[Covering<'b>]) -> Vec<&Self> {
+    /// Unfinished items that are waiting on this item
+    pub fn get_covering_another_item(&self, coverings: &[Covering<'b>]) -> Vec<&Self> {
         let cover_others = coverings.iter().filter(|x| self == x.smaller);
         //Now see if the items that are covering are finished or active
         cover_others
@@ -247,7 +271,8 @@ impl<'b> Item<'b> {
             .collect()
     }
 
-    pub(crate) fn get_covered_by_date_time_filter_out_the_past<'a>(
+    /// When the snoozes of this item that have not ended yet will end
+    pub fn get_covered_by_date_time_filter_out_the_past<'a>(
         &self,
         coverings_until_date_time: &'a [CoveringUntilDateTime<'a>],
         now: &DateTime<Local>,
@@ -260,7 +285,8 @@ impl<'b> Item<'b> {
             .collect()
     }
 
-    pub(crate) fn get_covered_by_date_time<'a>(
+    /// When each snooze of this item will end, pass in just the snoozes that should count
+    pub fn get_covered_by_date_time<'a>(
         &self,
         coverings_until_date_time: &'a [&'a CoveringUntilDateTime<'a>],
     ) -> Vec<&'a DateTime<Local>> {
@@ -270,7 +296,8 @@ impl<'b> Item<'b> {
         covered_by_date_time.map(|x| &x.until).collect()
     }
 
-    pub(crate) fn covered_by(
+    /// Unfinished items that cover this item or that are smaller items of it
+    pub fn covered_by(
         &'b self,
         coverings: &'b [Covering<'b>],
         all_items: &'b [&Item<'b>],
@@ -296,79 +323,118 @@ impl<'b> Item<'b> {
         )
     }
 
-    pub(crate) fn get_id(&self) -> &'b Thing {
+    /// The id of the record this item was made from
+    pub fn get_id(&self) -> &'b Thing {
         self.id
     }
 
-    pub(crate) fn get_surreal_record_id(&self) -> &'b RecordId {
+    /// The same as get_id, as a RecordId
+    pub fn get_surreal_record_id(&self) -> &'b RecordId {
         self.id
     }
 
-    pub(crate) fn get_summary(&self) -> &'b str {
+    /// What the item is, shown in every list
+    pub fn get_summary(&self) -> &'b str {
         &self.surreal_item.summary
     }
 
-    pub(crate) fn get_type(&self) -> &'b ItemType {
+    /// The same as get_item_type
+    pub fn get_type(&self) -> &'b ItemType {
         self.get_item_type()
     }
 
-    pub(crate) fn is_type_undeclared(&self) -> bool {
+    /// True if the item does not have a type yet
+    pub fn is_type_undeclared(&self) -> bool {
         self.get_item_type() == &ItemType::Undeclared
     }
 
-    pub(crate) fn is_type_action(&self) -> bool {
+    /// True if the item is an Action
+    pub fn is_type_action(&self) -> bool {
         self.get_item_type() == &ItemType::Action
     }
 
-    pub(crate) fn is_type_goal(&self) -> bool {
+    /// True if the item is a Goal
+    pub fn is_type_goal(&self) -> bool {
         matches!(self.get_item_type(), &ItemType::Goal(..))
     }
 
-    pub(crate) fn is_type_motivation(&self) -> bool {
+    /// True if the item is a Motivation
+    pub fn is_type_motivation(&self) -> bool {
         self.get_item_type() == &ItemType::Motivation
     }
 
-    pub(crate) fn has_children(&self) -> bool {
+    /// True if the item has smaller items, finished or not
+    pub fn has_children(&self) -> bool {
         !self.surreal_item.smaller_items_in_priority_order.is_empty()
     }
 
-    pub(crate) fn is_there_notes(&self) -> bool {
+    /// True if there are notes about the item somewhere
+    pub fn is_there_notes(&self) -> bool {
         self.surreal_item.notes_location != NotesLocation::None
     }
 
-    pub(crate) fn get_staging(&self) -> &Staging {
+    /// How often to think about the item
+    pub fn get_staging(&self) -> &Staging {
         &self.surreal_item.staging
     }
 
-    pub(crate) fn is_mentally_resident(&self) -> bool {
+    /// Just this item's due date, ItemNode::get_due_date also includes the due dates of the items this is for
+    pub fn get_due_date(&self) -> Option<&'b DueDate> {
+        self.surreal_item.due_date.as_ref()
+    }
+
+    /// Some if the item is waiting on someone or something
+    pub fn get_waiting_for(&self) -> Option<&'b WaitingFor> {
+        self.surreal_item.waiting_for.as_ref()
+    }
+
+    /// Some if this was imported from a calendar
+    pub fn get_calendar_event(&self) -> Option<&'b CalendarEvent> {
+        self.surreal_item.calendar_event.as_deref()
+    }
+
+    /// Some if the item is linked to a git branch
+    pub fn get_git_branch(&self) -> Option<&'b GitBranch> {
+        self.surreal_item.git_branch.as_deref()
+    }
+
+    /// True if the staging is MentallyResident
+    pub fn is_mentally_resident(&self) -> bool {
         matches!(self.get_staging(), Staging::MentallyResident { .. })
     }
 
-    pub(crate) fn is_staging_not_set(&self) -> bool {
+    /// True if the staging has not been set yet
+    pub fn is_staging_not_set(&self) -> bool {
         self.get_staging() == &Staging::NotSet
     }
 
-    pub(crate) fn get_permanence(&self) -> &Permanence {
+    /// Whether the item is done once or kept
+    pub fn get_permanence(&self) -> &Permanence {
         &self.surreal_item.permanence
     }
 
-    pub(crate) fn is_project(&self) -> bool {
+    /// True if the permanence is Project
+    pub fn is_project(&self) -> bool {
         self.get_permanence() == &Permanence::Project
     }
 
-    pub(crate) fn is_permanence_not_set(&self) -> bool {
+    /// True if the permanence has not been set yet
+    pub fn is_permanence_not_set(&self) -> bool {
         self.get_permanence() == &Permanence::NotSet
     }
 
-    pub(crate) fn is_maintenance(&self) -> bool {
+    /// True if the permanence is Maintenance
+    pub fn is_maintenance(&self) -> bool {
         self.get_permanence() == &Permanence::Maintenance
     }
 
-    pub(crate) fn is_goal(&self) -> bool {
+    /// The same as is_type_goal
+    pub fn is_goal(&self) -> bool {
         matches!(self.get_item_type(), &ItemType::Goal(..))
     }
 
-    pub(crate) fn is_covered_by_a_goal(
+    /// True if an unfinished Goal covers this item or is one of its smaller items
+    pub fn is_covered_by_a_goal(
         &self,
         coverings: &[Covering<'_>],
         all_items: &[&Item<'_>],
@@ -377,17 +443,20 @@ impl<'b> Item<'b> {
             .any(|x| x.is_type_goal() && !x.is_finished())
     }
 
-    pub(crate) fn get_thing(&self) -> &Thing {
+    /// The same as get_id
+    pub fn get_thing(&self) -> &Thing {
         self.surreal_item.id.as_ref().expect("Already in DB")
     }
 
-    pub(crate) fn get_facing(&self) -> &Vec<Facing> {
+    /// Who or what this item is for
+    pub fn get_facing(&self) -> &Vec<Facing> {
         &self.surreal_item.facing
     }
 }
 
 impl Item<'_> {
-    pub(crate) fn find_parents<'a>(
+    /// Items that this item covers or that have this item as a smaller item, leaving out the items in visited
+    pub fn find_parents<'a>(
         &self,
         linkage: &'a [Covering<'a>],
         other_items: &'a [&'a Item<'a>],
@@ -412,7 +481,8 @@ impl Item<'_> {
         .collect()
     }
 
-    pub(crate) fn find_children<'a>(
+    /// Items that cover this item or that are smaller items of it, leaving out the items in visited
+    pub fn find_children<'a>(
         &self,
         linkage: &'a [Covering<'a>],
         other_items: &'a [&'a Item<'a>],
@@ -440,7 +510,39 @@ impl Item<'_> {
         .collect()
     }
 
-    pub(crate) fn is_this_a_smaller_item(&self, other_item: &Item) -> bool {
+    /// When an item is finished the children are often finished right before or after as part of the same cascade
+    /// of finishing. This finds those children, and their children, so they can be reopened together.
+    pub fn find_children_finished_in_the_same_cascade<'a>(
+        &self,
+        linkage: &'a [Covering<'a>],
+        all_items: &'a [&'a Item<'a>],
+        within: chrono::Duration,
+    ) -> Vec<&'a Item<'a>> {
+        let finished = match self.when_finished() {
+            Some(finished) => finished,
+            None => return Vec::default(),
+        };
+        let mut result: Vec<&'a Item<'a>> = Vec::default();
+        let mut to_search = self.find_children(linkage, all_items, &result);
+        while let Some(child) = to_search.pop() {
+            if child.get_surreal_record_id() == self.get_surreal_record_id()
+                || result.contains(&child)
+            {
+                continue;
+            }
+            match child.when_finished() {
+                Some(child_finished) if (finished - child_finished).abs() <= within => {
+                    result.push(child);
+                    to_search.extend(child.find_children(linkage, all_items, &result));
+                }
+                _ => (),
+            }
+        }
+        result
+    }
+
+    /// True if other_item is one of this item's smaller items
+    pub fn is_this_a_smaller_item(&self, other_item: &Item) -> bool {
         self.surreal_item
             .smaller_items_in_priority_order
             .iter()
@@ -459,11 +561,13 @@ impl Item<'_> {
             })
     }
 
-    pub(crate) fn is_responsibility_reactive(&self) -> bool {
+    /// True if the item is something to respond to rather than something to do
+    pub fn is_responsibility_reactive(&self) -> bool {
         self.get_responsibility() == &Responsibility::ReactiveBeAvailableToAct
     }
 
-    pub(crate) fn get_responsibility(&self) -> &Responsibility {
+    /// Whether this is something to do or something to respond to
+    pub fn get_responsibility(&self) -> &Responsibility {
         &self.surreal_item.responsibility
     }
 }
@@ -478,7 +582,8 @@ mod tests {
     use super::*;
 
     impl Item<'_> {
-        pub(crate) fn has_active_children(&self, all_items: &[&Item<'_>]) -> bool {
+        /// True if any item in smaller_items_in_priority_order is in all_items and not finished
+        pub fn has_active_children(&self, all_items: &[&Item<'_>]) -> bool {
             self.surreal_item
                 .smaller_items_in_priority_order
                 .iter()
@@ -604,4 +709,76 @@ mod tests {
             smaller_item.id.as_ref().expect("set above")
         );
     }
+
+    #[test]
+    fn find_children_finished_in_the_same_cascade_only_returns_children_finished_close_to_the_parent(
+    ) {
+        let parent_finished = Utc::now();
+        let finished_with_parent = SurrealItemBuilder::default()
+            .id(Some(("surreal_item", "1").into()))
+            .summary("Finished with the parent")
+            .item_type(ItemType::Action)
+            .finished(Some(
+                (parent_finished - chrono::Duration::seconds(30)).into(),
+            ))
+            .build()
+            .unwrap();
+        let finished_long_before = SurrealItemBuilder::default()
+            .id(Some(("surreal_item", "2").into()))
+            .summary("Finished long before the parent")
+            .item_type(ItemType::Action)
+            .finished(Some((parent_finished - chrono::Duration::days(3)).into()))
+            .build()
+            .unwrap();
+        let still_active = SurrealItemBuilder::default()
+            .id(Some(("surreal_item", "3").into()))
+            .summary("Still active")
+            .item_type(ItemType::Action)
+            .build()
+            .unwrap();
+        let parent_item = SurrealItemBuilder::default()
+            .id(Some(("surreal_item", "4").into()))
+            .summary("Parent item")
+            .item_type(ItemType::Goal(Default::default()))
+            .finished(Some(parent_finished.into()))
+            .smaller_items_in_priority_order(
+                [&finished_with_parent, &finished_long_before, &still_active]
+                    .iter()
+                    .map(|x| SurrealOrderedSubItem::SubItem {
+                        surreal_item_id: x.id.as_ref().expect("set above").clone(),
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .build()
+            .unwrap();
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                finished_with_parent.clone(),
+                finished_long_before,
+                still_active,
+                parent_item.clone(),
+            ])
+            .build()
+            .unwrap();
+        let items: Vec<Item> = surreal_tables.make_items();
+        let all_items = items.iter().collect::<Vec<_>>();
+        let coverings = surreal_tables.make_coverings(&all_items);
+
+        let under_test_parent_item = items
+            .iter()
+            .find(|x| parent_item.id.as_ref().unwrap() == x.id)
+            .unwrap();
+
+        let same_cascade = under_test_parent_item.find_children_finished_in_the_same_cascade(
+            &coverings,
+            &all_items,
+            chrono::Duration::minutes(10),
+        );
+
+        assert_eq!(same_cascade.len(), 1);
+        assert_eq!(
+            same_cascade.first().expect("checked in assert above").id,
+            finished_with_parent.id.as_ref().expect("set above")
+        );
+    }
 }
@@ -0,0 +1,21 @@
+//! An area of life, like health or work
+
+use crate::surrealdb_layer::surreal_life_area::SurrealLifeArea;
+
+/// A life area with the record it was made from
+pub struct LifeArea<'s> {
+    /// The record this was made from
+    pub surreal_life_area: &'s SurrealLifeArea,
+}
+
+impl<'s> LifeArea<'s> {
+    /// Wraps the record
+    pub fn new(surreal_life_area: &'s SurrealLifeArea) -> Self {
+        Self { surreal_life_area }
+    }
+
+    /// The name of the life area
+    pub fn summary(&self) -> &str {
+        &self.surreal_life_area.summary
+    }
+}
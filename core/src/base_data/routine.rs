@@ -0,0 +1,21 @@
+//! A routine that items can be part of
+
+use crate::surrealdb_layer::surreal_routine::SurrealRoutine;
+
+/// A routine with the record it was made from
+pub struct Routine<'s> {
+    /// The record this was made from
+    pub surreal_routine: &'s SurrealRoutine,
+}
+
+impl<'s> Routine<'s> {
+    /// Wraps the record
+    pub fn new(surreal_routine: &'s SurrealRoutine) -> Self {
+        Self { surreal_routine }
+    }
+
+    /// The name of the routine
+    pub fn summary(&self) -> &str {
+        &self.surreal_routine.summary
+    }
+}
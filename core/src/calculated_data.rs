@@ -1,3 +1,5 @@
+//! What can be worked out from BaseData without the user, like how each item relates to the larger and smaller items
+
 use crate::{
     base_data::{
         covering::Covering, covering_until_date_time::CoveringUntilDateTime, item::Item, BaseData,
@@ -7,8 +9,9 @@ use crate::{
 use chrono::{DateTime, Utc};
 use ouroboros::self_referencing;
 
+/// Each item with how it relates to the items that are larger and smaller than it
 #[self_referencing]
-pub(crate) struct CalculatedData {
+pub struct CalculatedData {
     base_data: BaseData,
 
     #[borrows(base_data)]
@@ -17,10 +20,8 @@ pub(crate) struct CalculatedData {
 }
 
 impl CalculatedData {
-    pub(crate) fn new_from_base_data(
-        base_data: BaseData,
-        current_date_time: &DateTime<Utc>,
-    ) -> Self {
+    /// Works out the ItemStatus of every active item, current_date_time is used for anything that depends on the time
+    pub fn new_from_base_data(base_data: BaseData, current_date_time: &DateTime<Utc>) -> Self {
         CalculatedDataBuilder {
             base_data,
             item_status_builder: |base_data| {
@@ -42,19 +43,23 @@ impl CalculatedData {
         .build()
     }
 
-    pub(crate) fn get_item_status(&self) -> &[ItemStatus] {
+    /// The status of every active item
+    pub fn get_item_status(&self) -> &[ItemStatus] {
         self.borrow_item_status()
     }
 
-    pub(crate) fn get_active_items(&self) -> &[&Item] {
+    /// Items that are not finished
+    pub fn get_active_items(&self) -> &[&Item] {
         self.borrow_base_data().get_active_items()
     }
 
-    pub(crate) fn get_coverings(&self) -> &[Covering] {
+    /// Coverings between active items
+    pub fn get_coverings(&self) -> &[Covering] {
         self.borrow_base_data().get_coverings()
     }
 
-    pub(crate) fn get_active_snoozed(&self) -> &[&CoveringUntilDateTime] {
+    /// Snoozes of active items that have not ended yet
+    pub fn get_active_snoozed(&self) -> &[&CoveringUntilDateTime] {
         self.borrow_base_data().get_active_snoozed()
     }
 }
@@ -0,0 +1,389 @@
+//! Where the data is kept. The commands are handled the same way for every backend, see data_storage_run, and each
+//! backend says which records it changed so the cached SurrealTables can be kept up to date.
+//!
+//! * SurrealDB, see surrealdb_layer::data_storage_start_and_run
+//! * JSON files that can be kept in a git repository, see file_storage::FileStorage
+
+pub mod file_storage;
+
+use chrono::{DateTime, Utc};
+use surrealdb::opt::RecordId;
+use tokio::sync::{broadcast, mpsc::Receiver, oneshot};
+
+use crate::{
+    new_item::NewItem,
+    surrealdb_layer::{
+        surreal_covering::SurrealCovering,
+        surreal_covering_until_date_time::SurrealCoveringUntilDatetime,
+        surreal_item::{
+            CalendarEvent, DueDate, Facing, GitBranch, ItemType, Permanence, Responsibility,
+            Staging, SurrealOrderedSubItem, WaitingFor,
+        },
+        surreal_item_archive::SurrealItemArchive,
+        surreal_processed_text::SurrealProcessedText,
+        surreal_required_circumstance::SurrealRequiredCircumstance,
+        surreal_tables::{SurrealTables, SurrealTablesChange, WouldCreateACycle},
+        DataChanged, DataLayerCommands,
+    },
+};
+
+/// One method for each of the DataLayerCommands. Methods that change data return the records that they saved or removed.
+//Only used with concrete types so the futures do not need a Send bound to be spawned
+#[allow(async_fn_in_trait)]
+pub trait DataStorage {
+    /// Every table except processed_text and item_archive
+    async fn load_tables(&mut self) -> SurrealTables;
+    /// The processed text written about for_item
+    async fn get_processed_text(&mut self, for_item: RecordId) -> Vec<SurrealProcessedText>;
+    /// The processed text written about every item
+    async fn get_all_processed_text(&mut self) -> Vec<SurrealProcessedText>;
+    /// The items that were moved out of the item table by archive_finished_items
+    async fn get_item_archive(&mut self) -> Vec<SurrealItemArchive>;
+    /// Saves text written about for_item
+    async fn add_processed_text(&mut self, processed_text: String, for_item: RecordId);
+    /// Removes the processed text
+    async fn remove_processed_text(&mut self, processed_text: SurrealProcessedText);
+    /// Marks the item as finished now
+    async fn finish_item(&mut self, finish_this: RecordId) -> Vec<SurrealTablesChange>;
+    /// Marks the item as not finished
+    async fn unfinish_item(&mut self, unfinish_this: RecordId) -> Vec<SurrealTablesChange>;
+    /// Removes the item from the item table
+    async fn delete_item(&mut self, delete_this: RecordId) -> Vec<SurrealTablesChange>;
+    /// Moves the items that were finished before finished_before to the item archive
+    async fn archive_finished_items(
+        &mut self,
+        finished_before: DateTime<Utc>,
+    ) -> Vec<SurrealTablesChange>;
+    /// Saves the new item
+    async fn new_item(&mut self, new_item: NewItem) -> Vec<SurrealTablesChange>;
+    /// Saves cover_with as a new item and covers cover_this with it
+    async fn cover_with_a_new_item(
+        &mut self,
+        cover_this: RecordId,
+        cover_with: NewItem,
+    ) -> Vec<SurrealTablesChange>;
+    /// Refuses, and changes nothing, if it would create a cycle
+    async fn cover_item_with_an_existing_item(
+        &mut self,
+        item_to_be_covered: RecordId,
+        item_that_should_do_the_covering: RecordId,
+    ) -> Result<Vec<SurrealTablesChange>, WouldCreateACycle>;
+    /// Removes the covering
+    async fn remove_covering(&mut self, covering: SurrealCovering) -> Vec<SurrealTablesChange>;
+    /// Removes the covering until date time
+    async fn remove_covering_until_date_time(
+        &mut self,
+        covering_until_date_time: SurrealCoveringUntilDatetime,
+    ) -> Vec<SurrealTablesChange>;
+    /// Removes the required circumstance
+    async fn remove_required_circumstance(
+        &mut self,
+        required_circumstance: SurrealRequiredCircumstance,
+    ) -> Vec<SurrealTablesChange>;
+    /// Covers the item until cover_until
+    async fn cover_item_until_an_exact_date_time(
+        &mut self,
+        item_to_cover: RecordId,
+        cover_until: DateTime<Utc>,
+    ) -> Vec<SurrealTablesChange>;
+    /// Refuses, and changes nothing, if it would create a cycle
+    async fn parent_item_with_existing_item(
+        &mut self,
+        child: RecordId,
+        parent: RecordId,
+        higher_priority_than_this: Option<RecordId>,
+    ) -> Result<Vec<SurrealTablesChange>, WouldCreateACycle>;
+    /// Saves child as a new item and makes it smaller than parent, higher priority than higher_priority_than_this or the lowest priority
+    async fn parent_item_with_a_new_child(
+        &mut self,
+        child: NewItem,
+        parent: RecordId,
+        higher_priority_than_this: Option<RecordId>,
+    ) -> Vec<SurrealTablesChange>;
+    /// Saves parent_new_item as a new item and makes child smaller than it
+    async fn parent_new_item_with_an_existing_child_item(
+        &mut self,
+        child: RecordId,
+        parent_new_item: NewItem,
+    ) -> Vec<SurrealTablesChange>;
+    /// Sets both the responsibility and the item type
+    async fn update_responsibility_and_item_type(
+        &mut self,
+        item: RecordId,
+        new_responsibility: Responsibility,
+        new_item_type: ItemType,
+    ) -> Vec<SurrealTablesChange>;
+    /// Sets the responsibility
+    async fn update_item_responsibility(
+        &mut self,
+        item: RecordId,
+        new_responsibility: Responsibility,
+    ) -> Vec<SurrealTablesChange>;
+    /// Sets the permanence
+    async fn update_item_permanence(
+        &mut self,
+        item: RecordId,
+        new_permanence: Permanence,
+    ) -> Vec<SurrealTablesChange>;
+    /// Sets the staging
+    async fn update_item_staging(
+        &mut self,
+        item: RecordId,
+        new_staging: Staging,
+    ) -> Vec<SurrealTablesChange>;
+    /// Sets the due date, None removes it
+    async fn update_item_due_date(
+        &mut self,
+        item: RecordId,
+        new_due_date: Option<DueDate>,
+    ) -> Vec<SurrealTablesChange>;
+    /// Sets what the item is waiting for, None removes it
+    async fn update_item_waiting_for(
+        &mut self,
+        item: RecordId,
+        new_waiting_for: Option<WaitingFor>,
+    ) -> Vec<SurrealTablesChange>;
+    /// Sets the calendar event, None removes it
+    async fn update_item_calendar_event(
+        &mut self,
+        item: RecordId,
+        new_calendar_event: Option<Box<CalendarEvent>>,
+    ) -> Vec<SurrealTablesChange>;
+    /// Sets the git branch, None removes it
+    async fn update_item_git_branch(
+        &mut self,
+        item: RecordId,
+        new_git_branch: Option<Box<GitBranch>>,
+    ) -> Vec<SurrealTablesChange>;
+    /// Sets the summary
+    async fn update_item_summary(
+        &mut self,
+        item: RecordId,
+        new_summary: String,
+    ) -> Vec<SurrealTablesChange>;
+    /// Sets the facing
+    async fn update_facing(
+        &mut self,
+        item: RecordId,
+        new_facing: Vec<Facing>,
+    ) -> Vec<SurrealTablesChange>;
+    /// Replaces the smaller items, in priority order
+    async fn update_smaller_items_in_priority_order(
+        &mut self,
+        item: RecordId,
+        new_smaller_items_in_priority_order: Vec<SurrealOrderedSubItem>,
+    ) -> Vec<SurrealTablesChange>;
+}
+
+/// Handles commands in the order they are received until every sender is dropped
+pub async fn data_storage_run(
+    mut data_storage_layer_receive_rx: Receiver<DataLayerCommands>,
+    mut storage: impl DataStorage,
+) {
+    //Loaded the first time the data is requested and then kept up to date with the changes that each command makes so
+    //the tables do not need to be reloaded for every menu
+    let mut cached: Option<SurrealTables> = None;
+    let (data_changed_tx, _) = broadcast::channel(16);
+
+    loop {
+        let received = data_storage_layer_receive_rx.recv().await;
+        let changes = match received {
+            Some(DataLayerCommands::SendRawData(oneshot)) => {
+                let surreal_tables = match cached.as_ref() {
+                    Some(cached) => cached.clone(),
+                    None => {
+                        let loaded = storage.load_tables().await;
+                        cached = Some(loaded.clone());
+                        loaded
+                    }
+                };
+                oneshot.send(surreal_tables).unwrap();
+                Vec::default()
+            }
+            Some(DataLayerCommands::SubscribeToChanges(oneshot)) => {
+                oneshot.send(data_changed_tx.subscribe()).unwrap();
+                Vec::default()
+            }
+            Some(DataLayerCommands::AddProcessedText(processed_text, for_item)) => {
+                storage.add_processed_text(processed_text, for_item).await;
+                Vec::default()
+            }
+            Some(DataLayerCommands::SendProcessedText(for_item, send_response_here)) => {
+                let processed_text = storage.get_processed_text(for_item).await;
+                send_response_here.send(processed_text).unwrap();
+                Vec::default()
+            }
+            Some(DataLayerCommands::SendAllProcessedText(send_response_here)) => {
+                let all_processed_text = storage.get_all_processed_text().await;
+                send_response_here.send(all_processed_text).unwrap();
+                Vec::default()
+            }
+            Some(DataLayerCommands::SendItemArchive(send_response_here)) => {
+                let item_archive = storage.get_item_archive().await;
+                send_response_here.send(item_archive).unwrap();
+                Vec::default()
+            }
+            Some(DataLayerCommands::RemoveProcessedText(processed_text)) => {
+                storage.remove_processed_text(processed_text).await;
+                Vec::default()
+            }
+            Some(DataLayerCommands::FinishItem(item)) => storage.finish_item(item).await,
+            Some(DataLayerCommands::UnfinishItem(item)) => storage.unfinish_item(item).await,
+            Some(DataLayerCommands::DeleteItem(item)) => storage.delete_item(item).await,
+            Some(DataLayerCommands::ArchiveFinishedItems { finished_before }) => {
+                storage.archive_finished_items(finished_before).await
+            }
+            Some(DataLayerCommands::NewItem(new_item)) => storage.new_item(new_item).await,
+            Some(DataLayerCommands::NewItemAndSendId(new_item, send_response_here)) => {
+                let changes = storage.new_item(new_item).await;
+                let id = changes
+                    .iter()
+                    .find_map(|change| match change {
+                        SurrealTablesChange::SavedItem(item) => item.id.clone(),
+                        _ => None,
+                    })
+                    .expect("new_item always saves the item");
+                //An error just means that the caller did not wait for the response
+                let _ = send_response_here.send(id);
+                changes
+            }
+            Some(DataLayerCommands::CoverItemWithANewItem {
+                cover_this,
+                cover_with,
+            }) => storage.cover_with_a_new_item(cover_this, cover_with).await,
+            Some(DataLayerCommands::CoverItemWithAnExistingItem {
+                item_to_be_covered,
+                item_that_should_do_the_covering,
+                send_response_here,
+            }) => {
+                let result = storage
+                    .cover_item_with_an_existing_item(
+                        item_to_be_covered,
+                        item_that_should_do_the_covering,
+                    )
+                    .await;
+                respond_with_refusal(result, send_response_here)
+            }
+            Some(DataLayerCommands::RemoveCoveringItem(surreal_covering)) => {
+                storage.remove_covering(surreal_covering).await
+            }
+            Some(DataLayerCommands::RemoveCoveringUntilDatetime(covering_until_date_time)) => {
+                storage
+                    .remove_covering_until_date_time(covering_until_date_time)
+                    .await
+            }
+            Some(DataLayerCommands::RemoveRequiredCircumstance(required_circumstance)) => {
+                storage
+                    .remove_required_circumstance(required_circumstance)
+                    .await
+            }
+            Some(DataLayerCommands::CoverItemUntilAnExactDateTime(item_to_cover, cover_until)) => {
+                storage
+                    .cover_item_until_an_exact_date_time(item_to_cover, cover_until)
+                    .await
+            }
+            Some(DataLayerCommands::ParentItemWithExistingItem {
+                child,
+                parent,
+                higher_priority_than_this,
+                send_response_here,
+            }) => {
+                let result = storage
+                    .parent_item_with_existing_item(child, parent, higher_priority_than_this)
+                    .await;
+                respond_with_refusal(result, send_response_here)
+            }
+            Some(DataLayerCommands::ParentItemWithANewChildItem {
+                child,
+                parent,
+                higher_priority_than_this,
+            }) => {
+                storage
+                    .parent_item_with_a_new_child(child, parent, higher_priority_than_this)
+                    .await
+            }
+            Some(DataLayerCommands::ParentNewItemWithAnExistingChildItem {
+                child,
+                parent_new_item,
+            }) => {
+                storage
+                    .parent_new_item_with_an_existing_child_item(child, parent_new_item)
+                    .await
+            }
+            Some(DataLayerCommands::UpdateItemPermanence(item, new_permanence)) => {
+                storage.update_item_permanence(item, new_permanence).await
+            }
+            Some(DataLayerCommands::UpdateItemStaging(record_id, new_staging)) => {
+                storage.update_item_staging(record_id, new_staging).await
+            }
+            Some(DataLayerCommands::UpdateItemDueDate(item, new_due_date)) => {
+                storage.update_item_due_date(item, new_due_date).await
+            }
+            Some(DataLayerCommands::UpdateItemWaitingFor(item, new_waiting_for)) => {
+                storage.update_item_waiting_for(item, new_waiting_for).await
+            }
+            Some(DataLayerCommands::UpdateItemCalendarEvent(item, new_calendar_event)) => {
+                storage
+                    .update_item_calendar_event(item, new_calendar_event)
+                    .await
+            }
+            Some(DataLayerCommands::UpdateItemGitBranch(item, new_git_branch)) => {
+                storage.update_item_git_branch(item, new_git_branch).await
+            }
+            Some(DataLayerCommands::UpdateItemSummary(item, new_summary)) => {
+                storage.update_item_summary(item, new_summary).await
+            }
+            Some(DataLayerCommands::UpdateResponsibilityAndItemType(
+                item,
+                new_responsibility,
+                new_item_type,
+            )) => {
+                storage
+                    .update_responsibility_and_item_type(item, new_responsibility, new_item_type)
+                    .await
+            }
+            Some(DataLayerCommands::UpdateItemResponsibility(record_id, new_responsibility)) => {
+                storage
+                    .update_item_responsibility(record_id, new_responsibility)
+                    .await
+            }
+            Some(DataLayerCommands::UpdateFacing(record_id, new_facing)) => {
+                storage.update_facing(record_id, new_facing).await
+            }
+            Some(DataLayerCommands::UpdateSmallerItemsInPriorityOrder(
+                record_id,
+                new_smaller_items_in_priority_order,
+            )) => {
+                storage
+                    .update_smaller_items_in_priority_order(
+                        record_id,
+                        new_smaller_items_in_priority_order,
+                    )
+                    .await
+            }
+            None => return, //Channel closed, time to shutdown down, exit
+        };
+
+        if !changes.is_empty() {
+            if let Some(cached) = cached.as_mut() {
+                cached.apply_changes(changes);
+            }
+            //An error just means that nobody is subscribed right now
+            let _ = data_changed_tx.send(DataChanged);
+        }
+    }
+}
+
+/// Tells the caller whether the change was made and returns the changes to apply, which is nothing if it was refused
+fn respond_with_refusal(
+    result: Result<Vec<SurrealTablesChange>, WouldCreateACycle>,
+    send_response_here: oneshot::Sender<Result<(), WouldCreateACycle>>,
+) -> Vec<SurrealTablesChange> {
+    let (changes, response) = match result {
+        Ok(changes) => (changes, Ok(())),
+        Err(would_create_a_cycle) => (Vec::default(), Err(would_create_a_cycle)),
+    };
+    //An error just means that the caller did not wait for the response
+    let _ = send_response_here.send(response);
+    changes
+}
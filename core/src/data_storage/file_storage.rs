@@ -0,0 +1,640 @@
+//! Keeps each table in its own pretty printed JSON file, named after the table, so the data can be kept in a git
+//! repository and the changes read in a diff. Records are kept in id order so a change only touches the lines of the
+//! records that changed.
+
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{de::DeserializeOwned, Serialize};
+use surrealdb::{
+    opt::RecordId,
+    sql::{Id, Thing},
+};
+use surrealdb_extra::table::Table;
+
+use crate::{
+    new_item::NewItem,
+    surrealdb_layer::{
+        surreal_covering::SurrealCovering,
+        surreal_covering_until_date_time::SurrealCoveringUntilDatetime,
+        surreal_item::{
+            CalendarEvent, DueDate, Facing, GitBranch, ItemType, Permanence, Responsibility,
+            Staging, SurrealItem, SurrealOrderedSubItem, WaitingFor,
+        },
+        surreal_item_archive::SurrealItemArchive,
+        surreal_life_area::SurrealLifeArea,
+        surreal_processed_text::SurrealProcessedText,
+        surreal_required_circumstance::SurrealRequiredCircumstance,
+        surreal_routine::SurrealRoutine,
+        surreal_tables::{SurrealTables, SurrealTablesChange, WouldCreateACycle},
+    },
+};
+
+use super::DataStorage;
+
+/// Keeps each table in its own JSON file in directory and all of the tables in memory
+pub struct FileStorage {
+    directory: PathBuf,
+    surreal_tables: SurrealTables,
+    processed_text: Vec<SurrealProcessedText>,
+    item_archive: Vec<SurrealItemArchive>,
+}
+
+impl FileStorage {
+    /// Reads every table in directory, the directory is created if it does not exist yet and a missing file is an
+    /// empty table
+    pub fn open(directory: impl Into<PathBuf>) -> io::Result<Self> {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)?;
+        Ok(FileStorage {
+            surreal_tables: SurrealTables {
+                surreal_items: read_table(&directory)?,
+                surreal_coverings: read_table(&directory)?,
+                surreal_required_circumstances: read_table(&directory)?,
+                surreal_coverings_until_date_time: read_table(&directory)?,
+                surreal_life_areas: read_table(&directory)?,
+                surreal_routines: read_table(&directory)?,
+            },
+            processed_text: read_table(&directory)?,
+            item_archive: read_table(&directory)?,
+            directory,
+        })
+    }
+
+    fn get_item(&self, record_id: &RecordId) -> SurrealItem {
+        self.surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.id.as_ref() == Some(record_id))
+            .expect("Item should exist")
+            .clone()
+    }
+
+    fn update_item(
+        &mut self,
+        record_id: &RecordId,
+        update: impl FnOnce(&mut SurrealItem),
+    ) -> Vec<SurrealTablesChange> {
+        let mut item = self.get_item(record_id);
+        update(&mut item);
+        self.save(vec![SurrealTablesChange::SavedItem(item)])
+    }
+
+    fn apply(&mut self, changes: Vec<SurrealTablesChange>) -> Vec<SurrealTablesChange> {
+        self.surreal_tables.apply_changes(changes.clone());
+        changes
+    }
+
+    fn save(&mut self, changes: Vec<SurrealTablesChange>) -> Vec<SurrealTablesChange> {
+        let changes = self.apply(changes);
+        self.write_surreal_tables();
+        changes
+    }
+
+    fn write_surreal_tables(&self) {
+        let surreal_tables = &self.surreal_tables;
+        write_table(&self.directory, &surreal_tables.surreal_items).unwrap();
+        write_table(&self.directory, &surreal_tables.surreal_coverings).unwrap();
+        write_table(
+            &self.directory,
+            &surreal_tables.surreal_required_circumstances,
+        )
+        .unwrap();
+        write_table(
+            &self.directory,
+            &surreal_tables.surreal_coverings_until_date_time,
+        )
+        .unwrap();
+        write_table::<SurrealLifeArea>(&self.directory, &surreal_tables.surreal_life_areas)
+            .unwrap();
+        write_table::<SurrealRoutine>(&self.directory, &surreal_tables.surreal_routines).unwrap();
+    }
+
+    fn new_item_record(new_item: NewItem, smaller: Vec<SurrealOrderedSubItem>) -> SurrealItem {
+        let mut item = SurrealItem::new(new_item, smaller);
+        item.id = Some(new_id(SurrealItem::TABLE_NAME));
+        item
+    }
+
+    /// The changes to remove an item along with everything that references it, the items that had it as a smaller
+    /// item come first
+    fn remove_item_changes(&self, remove_this: &RecordId) -> Vec<SurrealTablesChange> {
+        let surreal_tables = &self.surreal_tables;
+        let removed_id = |id: &Option<Thing>| id.clone().expect("Saved records have an id");
+        surreal_tables
+            .surreal_items
+            .iter()
+            .filter_map(|x| {
+                let mut item = x.clone();
+                item.remove_smaller_item(remove_this)
+                    .then_some(SurrealTablesChange::SavedItem(item))
+            })
+            .chain(
+                surreal_tables
+                    .surreal_coverings
+                    .iter()
+                    .filter(|x| &x.smaller == remove_this || &x.parent == remove_this)
+                    .map(|x| SurrealTablesChange::RemovedCovering(removed_id(&x.id))),
+            )
+            .chain(
+                surreal_tables
+                    .surreal_coverings_until_date_time
+                    .iter()
+                    .filter(|x| &x.cover_this == remove_this)
+                    .map(|x| SurrealTablesChange::RemovedCoveringUntilDatetime(removed_id(&x.id))),
+            )
+            .chain(
+                surreal_tables
+                    .surreal_required_circumstances
+                    .iter()
+                    .filter(|x| &x.required_for == remove_this)
+                    .map(|x| SurrealTablesChange::RemovedRequiredCircumstance(removed_id(&x.id))),
+            )
+            .chain(std::iter::once(SurrealTablesChange::RemovedItem(
+                remove_this.clone(),
+            )))
+            .collect()
+    }
+}
+
+fn new_id(table_name: &str) -> Thing {
+    (table_name, Id::rand()).into()
+}
+
+fn table_path<T: Table>(directory: &Path) -> PathBuf {
+    directory.join(format!("{}.json", T::TABLE_NAME))
+}
+
+fn read_table<T: Table + DeserializeOwned>(directory: &Path) -> io::Result<Vec<T>> {
+    match fs::read_to_string(table_path::<T>(directory)) {
+        Ok(json) => Ok(serde_json::from_str(&json)?),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(Vec::default()),
+        Err(err) => Err(err),
+    }
+}
+
+fn write_table<T: Table + Serialize>(directory: &Path, records: &[T]) -> io::Result<()> {
+    let path = table_path::<T>(directory);
+    let mut json = serde_json::to_string_pretty(records)?;
+    json.push('\n');
+    //Written next to the file and then renamed so the file is never left half written
+    let temporary = path.with_extension("json.tmp");
+    fs::write(&temporary, json)?;
+    fs::rename(temporary, path)
+}
+
+impl DataStorage for FileStorage {
+    async fn load_tables(&mut self) -> SurrealTables {
+        self.surreal_tables.clone()
+    }
+
+    async fn get_processed_text(&mut self, for_item: RecordId) -> Vec<SurrealProcessedText> {
+        self.processed_text
+            .iter()
+            .filter(|x| x.for_item == for_item)
+            .cloned()
+            .collect()
+    }
+
+    async fn get_all_processed_text(&mut self) -> Vec<SurrealProcessedText> {
+        self.processed_text.clone()
+    }
+
+    async fn get_item_archive(&mut self) -> Vec<SurrealItemArchive> {
+        self.item_archive.clone()
+    }
+
+    async fn add_processed_text(&mut self, processed_text: String, for_item: RecordId) {
+        self.processed_text.push(SurrealProcessedText {
+            id: Some(new_id(SurrealProcessedText::TABLE_NAME)),
+            text: processed_text,
+            when_written: Utc::now().into(),
+            for_item,
+        });
+        write_table(&self.directory, &self.processed_text).unwrap();
+    }
+
+    async fn remove_processed_text(&mut self, processed_text: SurrealProcessedText) {
+        self.processed_text.retain(|x| x.id != processed_text.id);
+        write_table(&self.directory, &self.processed_text).unwrap();
+    }
+
+    async fn finish_item(&mut self, finish_this: RecordId) -> Vec<SurrealTablesChange> {
+        self.update_item(&finish_this, |x| x.finished = Some(Utc::now().into()))
+    }
+
+    async fn unfinish_item(&mut self, unfinish_this: RecordId) -> Vec<SurrealTablesChange> {
+        self.update_item(&unfinish_this, |x| x.finished = None)
+    }
+
+    async fn delete_item(&mut self, delete_this: RecordId) -> Vec<SurrealTablesChange> {
+        let changes = self.remove_item_changes(&delete_this);
+        self.processed_text.retain(|x| x.for_item != delete_this);
+        write_table(&self.directory, &self.processed_text).unwrap();
+        self.save(changes)
+    }
+
+    async fn archive_finished_items(
+        &mut self,
+        finished_before: DateTime<Utc>,
+    ) -> Vec<SurrealTablesChange> {
+        let to_archive = self
+            .surreal_tables
+            .surreal_items
+            .iter()
+            .filter(|x| x.finished.as_ref().is_some_and(|x| x.0 < finished_before))
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut changes = Vec::default();
+        for item in to_archive {
+            let item_id = item.id.clone().expect("Saved records have an id");
+            let surreal_tables = &self.surreal_tables;
+            let item_changes = self.remove_item_changes(&item_id);
+            self.item_archive.push(SurrealItemArchive {
+                //Use the same key as the item so the archive is easy to find from the item's RecordId
+                id: Some((SurrealItemArchive::TABLE_NAME, item_id.id.clone()).into()),
+                coverings: surreal_tables
+                    .surreal_coverings
+                    .iter()
+                    .filter(|x| x.smaller == item_id || x.parent == item_id)
+                    .cloned()
+                    .collect(),
+                coverings_until_date_time: surreal_tables
+                    .surreal_coverings_until_date_time
+                    .iter()
+                    .filter(|x| x.cover_this == item_id)
+                    .cloned()
+                    .collect(),
+                required_circumstances: surreal_tables
+                    .surreal_required_circumstances
+                    .iter()
+                    .filter(|x| x.required_for == item_id)
+                    .cloned()
+                    .collect(),
+                smaller_item_of: item_changes
+                    .iter()
+                    .filter_map(|x| match x {
+                        SurrealTablesChange::SavedItem(larger) => larger.id.clone(),
+                        _ => None,
+                    })
+                    .collect(),
+                item,
+                archived: Utc::now().into(),
+            });
+            //Applied right away so the next item sees the smaller item lists without this item
+            changes.extend(self.apply(item_changes));
+        }
+        write_table(&self.directory, &self.item_archive).unwrap();
+        self.write_surreal_tables();
+        changes
+    }
+
+    async fn new_item(&mut self, new_item: NewItem) -> Vec<SurrealTablesChange> {
+        let item = Self::new_item_record(new_item, vec![]);
+        self.save(vec![SurrealTablesChange::SavedItem(item)])
+    }
+
+    async fn cover_with_a_new_item(
+        &mut self,
+        cover_this: RecordId,
+        cover_with: NewItem,
+    ) -> Vec<SurrealTablesChange> {
+        let cover_with = Self::new_item_record(cover_with, vec![]);
+        let covering = SurrealCovering {
+            id: Some(new_id(SurrealCovering::TABLE_NAME)),
+            smaller: cover_with.id.clone().expect("Just set"),
+            parent: cover_this,
+        };
+        self.save(vec![
+            SurrealTablesChange::SavedItem(cover_with),
+            SurrealTablesChange::SavedCovering(covering),
+        ])
+    }
+
+    async fn cover_item_with_an_existing_item(
+        &mut self,
+        item_to_be_covered: RecordId,
+        item_that_should_do_the_covering: RecordId,
+    ) -> Result<Vec<SurrealTablesChange>, WouldCreateACycle> {
+        self.surreal_tables
+            .check_for_a_cycle(&item_that_should_do_the_covering, &item_to_be_covered)?;
+        let covering = SurrealCovering {
+            id: Some(new_id(SurrealCovering::TABLE_NAME)),
+            smaller: item_that_should_do_the_covering,
+            parent: item_to_be_covered,
+        };
+        Ok(self.save(vec![SurrealTablesChange::SavedCovering(covering)]))
+    }
+
+    async fn remove_covering(&mut self, covering: SurrealCovering) -> Vec<SurrealTablesChange> {
+        self.save(vec![SurrealTablesChange::RemovedCovering(
+            covering.id.expect("Came from storage"),
+        )])
+    }
+
+    async fn remove_covering_until_date_time(
+        &mut self,
+        covering_until_date_time: SurrealCoveringUntilDatetime,
+    ) -> Vec<SurrealTablesChange> {
+        self.save(vec![SurrealTablesChange::RemovedCoveringUntilDatetime(
+            covering_until_date_time.id.expect("Came from storage"),
+        )])
+    }
+
+    async fn remove_required_circumstance(
+        &mut self,
+        required_circumstance: SurrealRequiredCircumstance,
+    ) -> Vec<SurrealTablesChange> {
+        self.save(vec![SurrealTablesChange::RemovedRequiredCircumstance(
+            required_circumstance.id.expect("Came from storage"),
+        )])
+    }
+
+    async fn cover_item_until_an_exact_date_time(
+        &mut self,
+        item_to_cover: RecordId,
+        cover_until: DateTime<Utc>,
+    ) -> Vec<SurrealTablesChange> {
+        let covering_until_date_time = SurrealCoveringUntilDatetime {
+            id: Some(new_id(SurrealCoveringUntilDatetime::TABLE_NAME)),
+            cover_this: item_to_cover,
+            until: cover_until.into(),
+        };
+        self.save(vec![SurrealTablesChange::SavedCoveringUntilDatetime(
+            covering_until_date_time,
+        )])
+    }
+
+    async fn parent_item_with_existing_item(
+        &mut self,
+        child: RecordId,
+        parent: RecordId,
+        higher_priority_than_this: Option<RecordId>,
+    ) -> Result<Vec<SurrealTablesChange>, WouldCreateACycle> {
+        self.surreal_tables.check_for_a_cycle(&child, &parent)?;
+        Ok(self.update_item(&parent, |x| {
+            x.add_smaller_item(child, higher_priority_than_this)
+        }))
+    }
+
+    async fn parent_item_with_a_new_child(
+        &mut self,
+        child: NewItem,
+        parent: RecordId,
+        higher_priority_than_this: Option<RecordId>,
+    ) -> Vec<SurrealTablesChange> {
+        let child = Self::new_item_record(child, vec![]);
+        let child_id = child.id.clone().expect("Just set");
+        let mut changes = self.save(vec![SurrealTablesChange::SavedItem(child)]);
+        changes.extend(
+            self.parent_item_with_existing_item(child_id, parent, higher_priority_than_this)
+                .await
+                .expect("A new item cannot be part of a cycle"),
+        );
+        changes
+    }
+
+    async fn parent_new_item_with_an_existing_child_item(
+        &mut self,
+        child: RecordId,
+        parent_new_item: NewItem,
+    ) -> Vec<SurrealTablesChange> {
+        let parent = Self::new_item_record(
+            parent_new_item,
+            vec![SurrealOrderedSubItem::SubItem {
+                surreal_item_id: child,
+            }],
+        );
+        self.save(vec![SurrealTablesChange::SavedItem(parent)])
+    }
+
+    async fn update_responsibility_and_item_type(
+        &mut self,
+        item: RecordId,
+        new_responsibility: Responsibility,
+        new_item_type: ItemType,
+    ) -> Vec<SurrealTablesChange> {
+        self.update_item(&item, |x| {
+            x.responsibility = new_responsibility;
+            x.item_type = new_item_type;
+        })
+    }
+
+    async fn update_item_responsibility(
+        &mut self,
+        item: RecordId,
+        new_responsibility: Responsibility,
+    ) -> Vec<SurrealTablesChange> {
+        self.update_item(&item, |x| x.responsibility = new_responsibility)
+    }
+
+    async fn update_item_permanence(
+        &mut self,
+        item: RecordId,
+        new_permanence: Permanence,
+    ) -> Vec<SurrealTablesChange> {
+        self.update_item(&item, |x| x.permanence = new_permanence)
+    }
+
+    async fn update_item_staging(
+        &mut self,
+        item: RecordId,
+        new_staging: Staging,
+    ) -> Vec<SurrealTablesChange> {
+        self.update_item(&item, |x| x.staging = new_staging)
+    }
+
+    async fn update_item_due_date(
+        &mut self,
+        item: RecordId,
+        new_due_date: Option<DueDate>,
+    ) -> Vec<SurrealTablesChange> {
+        self.update_item(&item, |x| x.due_date = new_due_date)
+    }
+
+    async fn update_item_waiting_for(
+        &mut self,
+        item: RecordId,
+        new_waiting_for: Option<WaitingFor>,
+    ) -> Vec<SurrealTablesChange> {
+        self.update_item(&item, |x| x.waiting_for = new_waiting_for)
+    }
+
+    async fn update_item_calendar_event(
+        &mut self,
+        item: RecordId,
+        new_calendar_event: Option<Box<CalendarEvent>>,
+    ) -> Vec<SurrealTablesChange> {
+        self.update_item(&item, |x| x.calendar_event = new_calendar_event)
+    }
+
+    async fn update_item_git_branch(
+        &mut self,
+        item: RecordId,
+        new_git_branch: Option<Box<GitBranch>>,
+    ) -> Vec<SurrealTablesChange> {
+        self.update_item(&item, |x| x.git_branch = new_git_branch)
+    }
+
+    async fn update_item_summary(
+        &mut self,
+        item: RecordId,
+        new_summary: String,
+    ) -> Vec<SurrealTablesChange> {
+        self.update_item(&item, |x| x.summary = new_summary)
+    }
+
+    async fn update_facing(
+        &mut self,
+        item: RecordId,
+        new_facing: Vec<Facing>,
+    ) -> Vec<SurrealTablesChange> {
+        self.update_item(&item, |x| x.facing = new_facing)
+    }
+
+    async fn update_smaller_items_in_priority_order(
+        &mut self,
+        item: RecordId,
+        new_smaller_items_in_priority_order: Vec<SurrealOrderedSubItem>,
+    ) -> Vec<SurrealTablesChange> {
+        self.update_item(&item, |x| {
+            x.smaller_items_in_priority_order = new_smaller_items_in_priority_order
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+
+    use crate::{data_storage::data_storage_run, surrealdb_layer::DataLayerCommands};
+
+    use super::*;
+
+    fn empty_directory(name: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("on_purpose_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        directory
+    }
+
+    #[tokio::test]
+    async fn changes_are_written_to_the_files_and_read_back() {
+        let directory = empty_directory("changes_are_written_to_the_files_and_read_back");
+        let (sender, receiver) = mpsc::channel(1);
+        let file_storage = FileStorage::open(&directory).unwrap();
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_run(receiver, file_storage).await });
+
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Parent".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let parent = surreal_tables.surreal_items[0].id.clone().expect("Saved");
+        sender
+            .send(DataLayerCommands::ParentItemWithANewChildItem {
+                child: NewItem::new("Child".into(), Utc::now()),
+                parent: parent.clone(),
+                higher_priority_than_this: None,
+            })
+            .await
+            .unwrap();
+        sender
+            .send(DataLayerCommands::CoverItemUntilAnExactDateTime(
+                parent.clone(),
+                Utc::now() + chrono::Duration::days(1),
+            ))
+            .await
+            .unwrap();
+        sender
+            .send(DataLayerCommands::AddProcessedText(
+                "Some processed text".into(),
+                parent.clone(),
+            ))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+
+        assert_eq!(surreal_tables.surreal_items.len(), 2);
+        let parent_item = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.id.as_ref() == Some(&parent))
+            .unwrap();
+        assert_eq!(parent_item.smaller_items_in_priority_order.len(), 1);
+        assert_eq!(surreal_tables.surreal_coverings_until_date_time.len(), 1);
+
+        let mut reopened = FileStorage::open(&directory).unwrap();
+        assert_eq!(reopened.load_tables().await, surreal_tables);
+        assert_eq!(reopened.get_processed_text(parent).await.len(), 1);
+        let items_file = fs::read_to_string(directory.join("item.json")).unwrap();
+        assert!(
+            items_file.lines().count() > surreal_tables.surreal_items.len(),
+            "Should be pretty printed so a diff shows just the lines that changed"
+        );
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn delete_item_also_removes_everything_that_references_it() {
+        let directory = empty_directory("delete_item_also_removes_everything_that_references_it");
+        let mut file_storage = FileStorage::open(&directory).unwrap();
+
+        file_storage
+            .new_item(NewItem::new("Parent".into(), Utc::now()))
+            .await;
+        let parent = file_storage.surreal_tables.surreal_items[0]
+            .id
+            .clone()
+            .expect("Saved");
+        file_storage
+            .parent_item_with_a_new_child(
+                NewItem::new("Item to delete".into(), Utc::now()),
+                parent.clone(),
+                None,
+            )
+            .await;
+        let delete_this = file_storage
+            .surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "Item to delete")
+            .unwrap()
+            .id
+            .clone()
+            .expect("Saved");
+        file_storage
+            .cover_with_a_new_item(
+                delete_this.clone(),
+                NewItem::new("Covering item".into(), Utc::now()),
+            )
+            .await;
+        file_storage
+            .add_processed_text("Some processed text".into(), delete_this.clone())
+            .await;
+
+        file_storage.delete_item(delete_this.clone()).await;
+
+        let surreal_tables = FileStorage::open(&directory).unwrap().load_tables().await;
+        assert_eq!(surreal_tables.surreal_items.len(), 2);
+        assert!(surreal_tables
+            .surreal_items
+            .iter()
+            .all(|x| x.smaller_items_in_priority_order.is_empty()));
+        assert!(surreal_tables.surreal_coverings.is_empty());
+        assert!(file_storage.get_all_processed_text().await.is_empty());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,58 @@
+//! The prioritization engine behind On-Purpose, without any of the menus, so other tools can load the data, compute
+//! the bullet list, and make changes.
+//!
+//! * [`surrealdb_layer`] owns the database. Start [`surrealdb_layer::data_storage_start_and_run`] on its own task
+//!   and talk to it by sending [`surrealdb_layer::DataLayerCommands`].
+//! * [`data_storage`] has the same commands for other ways of storing the data, like JSON files in a git repository,
+//!   see [`data_storage::data_storage_run`].
+//! * [`base_data`] wraps the raw [`surrealdb_layer::surreal_tables::SurrealTables`] with references between the
+//!   records.
+//! * [`calculated_data`] and [`node`] work out how each item relates to the items that are larger and smaller.
+//! * [`systems::bullet_list`] orders the items into the list of what to work on.
+//!
+//! ```
+//! use chrono::Utc;
+//! use on_purpose_core::{
+//!     base_data::BaseData,
+//!     calculated_data::CalculatedData,
+//!     new_item::NewItem,
+//!     surrealdb_layer::{data_storage_start_and_run, surreal_tables::SurrealTables, DataLayerCommands},
+//!     systems::bullet_list::BulletList,
+//! };
+//! use tokio::sync::mpsc;
+//!
+//! # tokio::runtime::Runtime::new().unwrap().block_on(async {
+//! let (sender, receiver) = mpsc::channel(20);
+//! let data_storage = tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+//!
+//! sender
+//!     .send(DataLayerCommands::NewItem(NewItem::new("Call the dentist".into(), Utc::now())))
+//!     .await
+//!     .unwrap();
+//!
+//! let now = Utc::now();
+//! let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+//! let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+//! let calculated_data = CalculatedData::new_from_base_data(base_data, &now);
+//! let bullet_list = BulletList::new_bullet_list(calculated_data);
+//! let summaries = bullet_list
+//!     .get_bullet_list()
+//!     .iter()
+//!     .map(|x| x.get_item_status().get_item().get_summary())
+//!     .collect::<Vec<_>>();
+//! assert_eq!(summaries, vec!["Call the dentist"]);
+//!
+//! drop(sender);
+//! data_storage.await.unwrap();
+//! # });
+//! ```
+
+#![warn(missing_docs)]
+
+pub mod base_data;
+pub mod calculated_data;
+pub mod data_storage;
+pub mod new_item;
+pub mod node;
+pub mod surrealdb_layer;
+pub mod systems;
@@ -0,0 +1,95 @@
+//! The fields for creating an item, everything except the summary has a default
+
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Datetime;
+
+use crate::surrealdb_layer::surreal_item::{
+    CalendarEvent, DueDate, Facing, ItemType, NotesLocation, Permanence, Responsibility, Staging,
+};
+
+/// An item that is not in the database yet, send it with DataLayerCommands::NewItem or one of the commands that
+/// makes a new item and links it to another
+#[derive(Builder, Serialize, Deserialize, Debug)]
+#[builder(setter(into))]
+pub struct NewItem {
+    /// What the item is, shown in every list
+    pub summary: String,
+
+    /// Set when importing items that are already finished
+    #[builder(default)]
+    pub finished: Option<Datetime>,
+
+    /// Whether this is something to do or something to respond to
+    #[builder(default)]
+    pub responsibility: Responsibility,
+
+    /// Who or what this item is for
+    #[builder(default)]
+    pub facing: Vec<Facing>,
+
+    /// For example an action, goal or motivation
+    #[builder(default)]
+    pub item_type: ItemType,
+
+    /// Whether the item is done once or kept
+    #[builder(default)]
+    pub permanence: Permanence,
+
+    /// How often to think about the item
+    #[builder(default)]
+    pub staging: Staging,
+
+    /// When the item needs to be done by
+    #[builder(default)]
+    pub due_date: Option<DueDate>,
+
+    /// For meetings and other things that happen at a set time
+    #[builder(default)]
+    pub calendar_event: Option<Box<CalendarEvent>>,
+
+    /// Where to find any notes about the item
+    #[builder(default)]
+    pub notes_location: NotesLocation,
+
+    /// When the item was captured
+    #[builder(default = "Utc::now()")]
+    pub created: DateTime<Utc>,
+}
+
+impl NewItem {
+    /// Everything other than summary is left at the default
+    pub fn new(summary: String, now: DateTime<Utc>) -> Self {
+        NewItem {
+            summary,
+            finished: None,
+            responsibility: Responsibility::default(),
+            facing: Default::default(),
+            item_type: ItemType::Undeclared,
+            permanence: Permanence::default(),
+            staging: Staging::default(),
+            due_date: None,
+            calendar_event: None,
+            notes_location: NotesLocation::default(),
+            created: now,
+        }
+    }
+
+    /// A person or group that items can be waiting on
+    pub fn new_person_or_group(summary: String, now: DateTime<Utc>) -> Self {
+        NewItem {
+            summary,
+            finished: None,
+            responsibility: Responsibility::ReactiveBeAvailableToAct,
+            facing: Default::default(),
+            item_type: ItemType::PersonOrGroup,
+            permanence: Permanence::default(),
+            staging: Staging::default(),
+            due_date: None,
+            calendar_event: None,
+            notes_location: NotesLocation::default(),
+            created: now,
+        }
+    }
+}
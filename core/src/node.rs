@@ -0,0 +1,4 @@
+//! An item together with the items that are larger and smaller than it
+
+pub mod item_node;
+pub mod item_status;
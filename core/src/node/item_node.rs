@@ -1,18 +1,25 @@
+//! An item with the chains of larger and smaller items that it is part of
+
+use std::iter::once;
+
 use chrono::{DateTime, Local, Utc};
 use surrealdb::sql::Thing;
 
 use crate::{
     base_data::{covering::Covering, covering_until_date_time::CoveringUntilDateTime, item::Item},
-    surrealdb_layer::surreal_item::{Facing, ItemType, Staging, SurrealItem},
+    surrealdb_layer::surreal_item::{DueDate, Facing, ItemType, Staging, SurrealItem, WaitingFor},
 };
 
+/// An item with the chains of items that it is smaller than and the chains of items that are smaller than it
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub(crate) struct ItemNode<'s> {
+pub struct ItemNode<'s> {
     item: &'s Item<'s>,
     larger: Vec<GrowingItemNode<'s>>,
     smaller: Vec<ShrinkingItemNode<'s>>,
     snoozed_until: Vec<&'s DateTime<Local>>,
     facing: Vec<Facing>,
+    due_date: Option<&'s DueDate>,
+    waiting_for_who: Option<&'s Item<'s>>,
 }
 
 impl<'a> From<&'a ItemNode<'a>> for &'a Item<'a> {
@@ -28,7 +35,8 @@ impl<'a> From<&'a ItemNode<'a>> for &'a SurrealItem {
 }
 
 impl<'s> ItemNode<'s> {
-    pub(crate) fn new(
+    /// Follows the coverings and smaller items of all_items to build the chains, snoozed are the snoozes that count
+    pub fn new(
         item: &'s Item<'s>,
         coverings: &'s [Covering<'s>],
         snoozed: &'s [&'s CoveringUntilDateTime<'s>],
@@ -54,16 +62,34 @@ impl<'s> ItemNode<'s> {
             //Value is set so use it
             item_facing.to_vec()
         };
+        //A smaller item has to be done by the time the item it is for is due so the earliest due date is the one used
+        let due_date = once(item)
+            .chain(
+                larger
+                    .iter()
+                    .flat_map(|x| once(x.item).chain(x.create_growing_parents())),
+            )
+            .filter_map(|x| x.get_due_date())
+            .min();
+        let waiting_for_who = item.get_waiting_for().and_then(|waiting_for| {
+            all_items
+                .iter()
+                .copied()
+                .find(|x| x.get_surreal_record_id() == &waiting_for.who)
+        });
         ItemNode {
             item,
             larger,
             smaller,
             snoozed_until,
             facing,
+            due_date,
+            waiting_for_who,
         }
     }
 
-    pub(crate) fn create_parent_chain(&'s self) -> Vec<&'s Item<'s>> {
+    /// Every item that this item is smaller than, all the way up
+    pub fn create_parent_chain(&'s self) -> Vec<&'s Item<'s>> {
         let mut result = Vec::default();
         for i in self.larger.iter() {
             result.push(i.item);
@@ -73,43 +99,53 @@ impl<'s> ItemNode<'s> {
         result
     }
 
-    pub(crate) fn get_smaller(&'s self) -> &'s [ShrinkingItemNode<'s>] {
+    /// The chains of items that are smaller than this item
+    pub fn get_smaller(&'s self) -> &'s [ShrinkingItemNode<'s>] {
         &self.smaller
     }
 
-    pub(crate) fn get_item(&self) -> &'s Item<'s> {
+    /// The item this node is for
+    pub fn get_item(&self) -> &'s Item<'s> {
         self.item
     }
 
-    pub(crate) fn get_surreal_record_id(&self) -> &Thing {
+    /// The id of the item
+    pub fn get_surreal_record_id(&self) -> &Thing {
         self.item.get_surreal_record_id()
     }
 
-    pub(crate) fn is_person_or_group(&self) -> bool {
+    /// A person or group that items can be waiting on
+    pub fn is_person_or_group(&self) -> bool {
         self.item.is_person_or_group()
     }
 
-    pub(crate) fn is_maintenance(&self) -> bool {
+    /// True if the permanence is Maintenance
+    pub fn is_maintenance(&self) -> bool {
         self.item.is_maintenance()
     }
 
-    pub(crate) fn is_goal(&self) -> bool {
+    /// True if the item is a Goal
+    pub fn is_goal(&self) -> bool {
         self.item.is_goal()
     }
 
-    pub(crate) fn has_larger(&self) -> bool {
+    /// True if this item is smaller than any other item
+    pub fn has_larger(&self) -> bool {
         !self.larger.is_empty()
     }
 
-    pub(crate) fn get_larger(&self) -> &[GrowingItemNode] {
+    /// The chains of items that this item is smaller than
+    pub fn get_larger(&self) -> &[GrowingItemNode] {
         &self.larger
     }
 
-    pub(crate) fn get_type(&self) -> &ItemType {
+    /// For example an action, goal or motivation
+    pub fn get_type(&self) -> &ItemType {
         self.item.get_type()
     }
 
-    pub(crate) fn is_type_action(&self) -> bool {
+    /// True if the item is an Action, or does not have a type and is smaller than an Action
+    pub fn is_type_action(&self) -> bool {
         if self.item.get_type() == &ItemType::Undeclared {
             //Look to parents for a setting
             self.get_larger().iter().any(|x| x.is_type_action())
@@ -119,7 +155,8 @@ impl<'s> ItemNode<'s> {
         }
     }
 
-    pub(crate) fn is_type_undeclared(&self) -> bool {
+    /// True if the item does not have a type and the type cannot be taken from a larger item
+    pub fn is_type_undeclared(&self) -> bool {
         let is_type_undeclared = self.item.is_type_undeclared();
         if is_type_undeclared && self.is_type_action() {
             //This type can be inferred from the parent so check that first
@@ -129,24 +166,29 @@ impl<'s> ItemNode<'s> {
         }
     }
 
-    pub(crate) fn is_type_goal(&self) -> bool {
+    /// True if the item is a Goal
+    pub fn is_type_goal(&self) -> bool {
         self.item.is_type_goal()
     }
 
-    pub(crate) fn is_type_motivation(&self) -> bool {
+    /// True if the item is a Motivation
+    pub fn is_type_motivation(&self) -> bool {
         self.item.is_type_motivation()
     }
 
-    pub(crate) fn has_active_children(&self) -> bool {
+    /// True if any smaller item is not finished
+    pub fn has_active_children(&self) -> bool {
         self.smaller.iter().any(|x| !x.get_item().is_finished())
     }
 
-    pub(crate) fn is_there_notes(&self) -> bool {
+    /// True if there are notes about the item somewhere
+    pub fn is_there_notes(&self) -> bool {
         //I should probably change this to search through the parents as well, but going with this for now to maintain backwards compatibility with the code already written before I switched over to this ItemNode type
         self.item.is_there_notes()
     }
 
-    pub(crate) fn is_staging_not_set(&self) -> bool {
+    /// True if neither the item nor any larger item has the staging set
+    pub fn is_staging_not_set(&self) -> bool {
         let is_staging_not_set = self.item.is_staging_not_set();
         if is_staging_not_set {
             //This type can be inferred from the parent so check that first
@@ -156,7 +198,8 @@ impl<'s> ItemNode<'s> {
         }
     }
 
-    pub(crate) fn get_staging(&'s self) -> &'s Staging {
+    /// The staging of the item, or of the first larger item that has it set
+    pub fn get_staging(&'s self) -> &'s Staging {
         let staging = self.item.get_staging();
         if staging == &Staging::NotSet {
             //This type can be inferred from the parent so check that first
@@ -172,41 +215,66 @@ impl<'s> ItemNode<'s> {
         }
     }
 
-    pub(crate) fn get_thing(&self) -> &'s Thing {
+    /// The id of the item
+    pub fn get_thing(&self) -> &'s Thing {
         self.item.get_thing()
     }
 
-    pub(crate) fn is_responsibility_reactive(&self) -> bool {
+    /// True if the item is something to respond to rather than something to do
+    pub fn is_responsibility_reactive(&self) -> bool {
         self.item.is_responsibility_reactive()
     }
 
-    pub(crate) fn is_staging_mentally_resident(&self) -> bool {
+    /// True if the staging, see get_staging, is MentallyResident
+    pub fn is_staging_mentally_resident(&self) -> bool {
         matches!(self.get_staging(), Staging::MentallyResident { .. })
     }
 
-    pub(crate) fn get_snoozed_until(&'s self) -> &'s [&'s DateTime<Local>] {
+    /// The earliest due date of this item and the items that it is for
+    pub fn get_due_date(&self) -> Option<&'s DueDate> {
+        self.due_date
+    }
+
+    /// Some if the item is waiting on someone or something
+    pub fn get_waiting_for(&self) -> Option<&'s WaitingFor> {
+        self.item.get_waiting_for()
+    }
+
+    /// The person or group this is waiting on, None if it is not waiting or they have been deleted
+    pub fn get_waiting_for_who(&self) -> Option<&'s Item<'s>> {
+        self.waiting_for_who
+    }
+
+    /// When the snoozes of this item end
+    pub fn get_snoozed_until(&'s self) -> &'s [&'s DateTime<Local>] {
         //TODO: snoozed_until should be DateTime<Utc> not local
         &self.snoozed_until
     }
 
-    pub(crate) fn get_facing(&'s self) -> &'s Vec<Facing> {
+    /// The facing of the item, or of the larger items if it is not set on the item
+    pub fn get_facing(&'s self) -> &'s Vec<Facing> {
         &self.facing
     }
 
-    pub(crate) fn is_facing_undefined(&self) -> bool {
+    /// True if get_facing is empty
+    pub fn is_facing_undefined(&self) -> bool {
         self.get_facing().is_empty()
     }
 }
 
+/// An item with the chains of items that it is smaller than
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub(crate) struct GrowingItemNode<'s> {
-    pub(crate) item: &'s Item<'s>,
-    pub(crate) larger: Vec<GrowingItemNode<'s>>,
+pub struct GrowingItemNode<'s> {
+    /// The item this node is for
+    pub item: &'s Item<'s>,
+    /// The items that this item is smaller than
+    pub larger: Vec<GrowingItemNode<'s>>,
     facing: Vec<Facing>,
 }
 
 impl<'s> GrowingItemNode<'s> {
-    pub(crate) fn create_growing_parents(&self) -> Vec<&'s Item<'s>> {
+    /// Every item that this item is smaller than, all the way up
+    pub fn create_growing_parents(&self) -> Vec<&'s Item<'s>> {
         let mut result = Vec::default();
         for i in self.larger.iter() {
             result.push(i.item);
@@ -216,7 +284,8 @@ impl<'s> GrowingItemNode<'s> {
         result
     }
 
-    pub(crate) fn is_type_action(&self) -> bool {
+    /// True if the item is an Action, or does not have a type and is smaller than an Action
+    pub fn is_type_action(&self) -> bool {
         if self.item.get_type() == &ItemType::Undeclared {
             //Look to parents for a setting
             self.larger.iter().any(|x| x.is_type_action())
@@ -226,7 +295,8 @@ impl<'s> GrowingItemNode<'s> {
         }
     }
 
-    pub(crate) fn is_staging_not_set(&self) -> bool {
+    /// True if neither the item nor any larger item has the staging set
+    pub fn is_staging_not_set(&self) -> bool {
         let is_staging_not_set = self.item.is_staging_not_set();
         if is_staging_not_set {
             //This type can be inferred from the parent so check that first
@@ -236,7 +306,8 @@ impl<'s> GrowingItemNode<'s> {
         }
     }
 
-    pub(crate) fn get_staging(&'s self) -> &'s Staging {
+    /// The staging of the item, or of the first larger item that has it set
+    pub fn get_staging(&'s self) -> &'s Staging {
         let staging = self.item.get_staging();
         if staging == &Staging::NotSet {
             //This type can be inferred from the parent so check that first
@@ -252,19 +323,23 @@ impl<'s> GrowingItemNode<'s> {
         }
     }
 
-    pub(crate) fn get_item(&self) -> &'s Item<'s> {
+    /// The item this node is for
+    pub fn get_item(&self) -> &'s Item<'s> {
         self.item
     }
 
-    pub(crate) fn get_larger(&self) -> &[GrowingItemNode] {
+    /// The items that this item is smaller than
+    pub fn get_larger(&self) -> &[GrowingItemNode] {
         &self.larger
     }
 
-    pub(crate) fn get_facing(&'s self) -> &'s Vec<Facing> {
+    /// The facing of the item, or of the larger items if it is not set on the item
+    pub fn get_facing(&'s self) -> &'s Vec<Facing> {
         &self.facing
     }
 
-    pub(crate) fn get_node<'a>(&self, all_nodes: &'a [ItemNode<'a>]) -> &'a ItemNode<'a> {
+    /// The ItemNode for the same item
+    pub fn get_node<'a>(&self, all_nodes: &'a [ItemNode<'a>]) -> &'a ItemNode<'a> {
         all_nodes
             .iter()
             .find(|x| x.get_item() == self.item)
@@ -272,7 +347,8 @@ impl<'s> GrowingItemNode<'s> {
     }
 }
 
-pub(crate) fn create_growing_nodes<'a>(
+/// A GrowingItemNode for each item, an item already in visited is not followed again so a cycle ends there
+pub fn create_growing_nodes<'a>(
     items: Vec<&'a Item<'a>>,
     coverings: &'a [Covering<'a>],
     possible_parents: &'a [&'a Item<'a>],
@@ -299,7 +375,8 @@ pub(crate) fn create_growing_nodes<'a>(
         .collect()
 }
 
-pub(crate) fn create_growing_node<'a>(
+/// The chains of items that item is smaller than, leaving out the items in visited
+pub fn create_growing_node<'a>(
     item: &'a Item<'a>,
     coverings: &'a [Covering<'a>],
     all_items: &'a [&'a Item<'a>],
@@ -328,31 +405,37 @@ pub(crate) fn create_growing_node<'a>(
     }
 }
 
+/// An item with the chains of items that are smaller than it
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub(crate) struct ShrinkingItemNode<'s> {
+pub struct ShrinkingItemNode<'s> {
     item: &'s Item<'s>,
     _smaller: Vec<ShrinkingItemNode<'s>>,
 }
 
 impl<'s> ShrinkingItemNode<'s> {
-    pub(crate) fn get_item(&self) -> &'s Item<'s> {
+    /// The item this node is for
+    pub fn get_item(&self) -> &'s Item<'s> {
         self.item
     }
 
-    pub(crate) fn is_finished(&self) -> bool {
+    /// True once the item has been marked as finished
+    pub fn is_finished(&self) -> bool {
         self.item.is_finished()
     }
 
-    pub(crate) fn when_finished(&self) -> Option<DateTime<Utc>> {
+    /// None until the item is finished
+    pub fn when_finished(&self) -> Option<DateTime<Utc>> {
         self.item.when_finished()
     }
 
-    pub(crate) fn get_staging(&self) -> &Staging {
+    /// How often to think about the item
+    pub fn get_staging(&self) -> &Staging {
         self.item.get_staging()
     }
 }
 
-pub(crate) fn create_shrinking_nodes<'a>(
+/// A ShrinkingItemNode for each item, an item already in visited is not followed again so a cycle ends there
+pub fn create_shrinking_nodes<'a>(
     items: Vec<&'a Item<'a>>,
     coverings: &'a [Covering<'a>],
     possible_children: &'a [&'a Item<'a>],
@@ -376,7 +459,8 @@ pub(crate) fn create_shrinking_nodes<'a>(
         .collect()
 }
 
-pub(crate) fn create_shrinking_node<'a>(
+/// The chains of items that are smaller than item, leaving out the items in visited
+pub fn create_shrinking_node<'a>(
     item: &'a Item<'a>,
     coverings: &'a [Covering<'a>],
     all_items: &'a [&'a Item<'a>],
@@ -1,6 +1,8 @@
+//! An ItemNode with whether it is ready to work on and, if not, why not
+
 use std::{ops::Sub, time::Duration};
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Local, Utc};
 use surrealdb::{
     opt::RecordId,
     sql::{Datetime, Thing},
@@ -8,98 +10,203 @@ use surrealdb::{
 
 use crate::{
     base_data::item::Item,
-    surrealdb_layer::surreal_item::{EnterListReason, ItemType, Staging},
+    surrealdb_layer::surreal_item::{DueDateStrictness, EnterListReason, ItemType, Staging},
 };
 
 use super::item_node::{GrowingItemNode, ItemNode, ShrinkingItemNode};
 
+/// An ItemNode with how far along it is in its lap and whether it is snoozed
 #[derive(Clone, Debug)]
-pub(crate) struct ItemStatus<'s> {
+pub struct ItemStatus<'s> {
     item_node: ItemNode<'s>,
     lap_count: f32,
-    is_snoozed: bool,
+    snooze_reasons: Vec<SnoozeReason>,
+    time_until_due: Option<chrono::Duration>,
+}
+
+/// How long before a hard due date the item starts to move up the bullet list
+const HARD_DUE_DATE_ESCALATES_WITHIN_DAYS: i64 = 3;
+
+/// How long before a soft due date the item starts to move up the bullet list
+const SOFT_DUE_DATE_ESCALATES_WITHIN_DAYS: i64 = 1;
+
+/// How pressing the due date is, later variants are more pressing
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DueDateUrgency {
+    /// No due date or it is still far away
+    NotPressing,
+    /// A soft due date is less than a day away
+    SoftApproaching,
+    /// A hard due date is less than three days away
+    HardApproaching,
+    /// Past a soft due date
+    SoftOverdue,
+    /// Past a hard due date
+    HardOverdue,
+}
+
+/// Why an item is snoozed, an item can be snoozed for more than one reason
+#[derive(Clone, Debug, PartialEq)]
+pub enum SnoozeReason {
+    /// Snoozed until this time
+    CoveredUntil(DateTime<Local>),
+    /// The staging says the item enters the list at this time
+    EntersListAt(DateTime<Utc>),
+    /// Waiting to be the highest uncovered item but not before this time
+    EarliestNotReached(DateTime<Utc>),
+    /// Another item is the highest uncovered item of the parent
+    NotHighestUncovered,
+    /// Waiting on someone else until it is time to follow up with them
+    FollowUpAt(DateTime<Utc>),
 }
 
 impl<'s> ItemStatus<'s> {
-    pub(crate) fn new(
+    /// Works out the lap count and snooze reasons at current_date_time, all_nodes are needed to find the highest uncovered item
+    pub fn new(
         item_node: ItemNode<'s>,
         all_nodes: &[ItemNode<'_>],
         current_date_time: &DateTime<Utc>,
     ) -> Self {
         let lap_count = calculate_lap_count(&item_node, all_nodes, current_date_time);
-        let is_snoozed = calculate_is_snoozed(&item_node, all_nodes, current_date_time);
+        let snooze_reasons = calculate_snooze_reasons(&item_node, all_nodes, current_date_time);
+        let time_until_due = item_node.get_due_date().map(|due_date| {
+            let due: DateTime<Utc> = due_date.due.clone().into();
+            due - *current_date_time
+        });
         Self {
             item_node,
             lap_count,
-            is_snoozed,
+            snooze_reasons,
+            time_until_due,
         }
     }
 
-    pub(crate) fn get_lap_count(&self) -> f32 {
+    /// How far along the item is in its lap, 1 is a full lap and 0 if the staging is not set
+    pub fn get_lap_count(&self) -> f32 {
         self.lap_count
     }
 
-    pub(crate) fn is_snoozed(&self) -> bool {
-        self.is_snoozed
+    /// True if there is at least one SnoozeReason
+    pub fn is_snoozed(&self) -> bool {
+        !self.snooze_reasons.is_empty()
+    }
+
+    /// Empty if the item is not snoozed
+    pub fn get_snooze_reasons(&self) -> &[SnoozeReason] {
+        &self.snooze_reasons
+    }
+
+    /// Negative once it is overdue, None if neither it nor anything it is for has a due date
+    pub fn get_time_until_due(&self) -> Option<chrono::Duration> {
+        self.time_until_due
+    }
+
+    /// NotPressing unless the due date of the item, or of anything it is for, is close or past
+    pub fn get_due_date_urgency(&self) -> DueDateUrgency {
+        let (due_date, time_until_due) = match (self.item_node.get_due_date(), self.time_until_due)
+        {
+            (Some(due_date), Some(time_until_due)) => (due_date, time_until_due),
+            _ => return DueDateUrgency::NotPressing,
+        };
+        let is_overdue = time_until_due < chrono::Duration::zero();
+        match due_date.strictness {
+            DueDateStrictness::Hard if is_overdue => DueDateUrgency::HardOverdue,
+            DueDateStrictness::Soft if is_overdue => DueDateUrgency::SoftOverdue,
+            DueDateStrictness::Hard
+                if time_until_due < chrono::Duration::days(HARD_DUE_DATE_ESCALATES_WITHIN_DAYS) =>
+            {
+                DueDateUrgency::HardApproaching
+            }
+            DueDateStrictness::Soft
+                if time_until_due < chrono::Duration::days(SOFT_DUE_DATE_ESCALATES_WITHIN_DAYS) =>
+            {
+                DueDateUrgency::SoftApproaching
+            }
+            DueDateStrictness::Hard | DueDateStrictness::Soft => DueDateUrgency::NotPressing,
+        }
+    }
+
+    /// Waiting on someone and they have not responded by the follow up time
+    pub fn is_follow_up_due(&self) -> bool {
+        self.item_node.get_waiting_for().is_some()
+            && !self
+                .snooze_reasons
+                .iter()
+                .any(|x| matches!(x, SnoozeReason::FollowUpAt(..)))
     }
 
-    pub(crate) fn is_first_lap_finished(&self) -> bool {
+    /// The lap count is over 1
+    pub fn is_first_lap_finished(&self) -> bool {
         self.get_lap_count() > 1.0
     }
 
-    pub(crate) fn get_item_node(&'s self) -> &'s ItemNode<'s> {
+    /// The ItemNode this status is for
+    pub fn get_item_node(&'s self) -> &'s ItemNode<'s> {
         &self.item_node
     }
 
-    pub(crate) fn get_staging(&self) -> &Staging {
+    /// How often to think about the item
+    pub fn get_staging(&self) -> &Staging {
         self.item_node.get_staging()
     }
 
-    pub(crate) fn is_staging_not_set(&self) -> bool {
+    /// True if the staging has not been set yet
+    pub fn is_staging_not_set(&self) -> bool {
         self.item_node.is_staging_not_set()
     }
 
-    pub(crate) fn get_thing(&self) -> &Thing {
+    /// The id of the item
+    pub fn get_thing(&self) -> &Thing {
         self.item_node.get_thing()
     }
 
-    pub(crate) fn is_responsibility_reactive(&self) -> bool {
+    /// True if the item is something to respond to rather than something to do
+    pub fn is_responsibility_reactive(&self) -> bool {
         self.item_node.is_responsibility_reactive()
     }
 
-    pub(crate) fn is_type_undeclared(&self) -> bool {
+    /// True if the item does not have a type yet
+    pub fn is_type_undeclared(&self) -> bool {
         self.item_node.is_type_undeclared()
     }
 
-    pub(crate) fn is_staging_mentally_resident(&self) -> bool {
+    /// True if the staging is MentallyResident
+    pub fn is_staging_mentally_resident(&self) -> bool {
         self.item_node.is_staging_mentally_resident()
     }
 
-    pub(crate) fn is_person_or_group(&self) -> bool {
+    /// A person or group that items can be waiting on
+    pub fn is_person_or_group(&self) -> bool {
         self.item_node.is_person_or_group()
     }
 
-    pub(crate) fn get_item(&self) -> &Item<'s> {
+    /// The item this status is for
+    pub fn get_item(&self) -> &Item<'s> {
         self.item_node.get_item()
     }
 
-    pub(crate) fn has_active_children(&self) -> bool {
+    /// True if any smaller item is not finished
+    pub fn has_active_children(&self) -> bool {
         self.item_node.has_active_children()
     }
 
-    pub(crate) fn get_smaller(&'s self) -> &[ShrinkingItemNode<'s>] {
+    /// The chains of items that are smaller than this item
+    pub fn get_smaller(&'s self) -> &[ShrinkingItemNode<'s>] {
         self.item_node.get_smaller()
     }
 
-    pub(crate) fn get_type(&self) -> &ItemType {
+    /// For example an action, goal or motivation
+    pub fn get_type(&self) -> &ItemType {
         self.item_node.get_type()
     }
 
-    pub(crate) fn get_surreal_record_id(&self) -> &RecordId {
+    /// The id of the item, as a RecordId
+    pub fn get_surreal_record_id(&self) -> &RecordId {
         self.item_node.get_surreal_record_id()
     }
 
-    pub(crate) fn get_larger(&'s self) -> &[GrowingItemNode<'s>] {
+    /// The chains of items that this item is smaller than
+    pub fn get_larger(&'s self) -> &[GrowingItemNode<'s>] {
         self.item_node.get_larger()
     }
 }
@@ -177,69 +284,89 @@ fn calculate_lap_count(
 }
 
 /// You can be snoozed if you are covered or if you just haven't reached the starting on staging yet
-fn calculate_is_snoozed(
+fn calculate_snooze_reasons(
     item_node: &ItemNode<'_>,
     all_nodes: &[ItemNode<'_>],
     now: &DateTime<Utc>,
-) -> bool {
+) -> Vec<SnoozeReason> {
     let staging = item_node.get_staging();
     let snoozed_from_staging = match staging {
-        Staging::NotSet => false,
+        Staging::NotSet => None,
         Staging::OnDeck { enter_list, .. } | Staging::MentallyResident { enter_list, .. } => {
             match enter_list {
                 EnterListReason::DateTime(enter_list) => {
                     let enter_list: DateTime<Utc> = enter_list.clone().into();
-                    &enter_list > now
+                    if &enter_list > now {
+                        Some(SnoozeReason::EntersListAt(enter_list))
+                    } else {
+                        None
+                    }
                 }
                 EnterListReason::HighestUncovered {
                     earliest,
                     review_after,
                 } => {
                     if now < earliest {
-                        return true;
-                    }
-                    let all_larger = item_node.get_larger();
-                    let all_larger = all_larger
-                        .iter()
-                        .map(|x| x.get_node(all_nodes))
-                        .collect::<Vec<_>>();
-                    let mut all_larger_iter = all_larger.iter();
-                    let (highest_uncovered, uncovered_when) = loop {
-                        let larger = all_larger_iter.next();
-                        match larger {
-                            Some(larger) => {
-                                let (highest_uncovered, uncovered_when) =
-                                    find_highest_uncovered_child_with_when_uncovered(larger, now);
-                                if let Some(highest_uncovered) = highest_uncovered {
-                                    break (Some(highest_uncovered), uncovered_when);
+                        Some(SnoozeReason::EarliestNotReached(earliest.clone().into()))
+                    } else {
+                        let all_larger = item_node.get_larger();
+                        let all_larger = all_larger
+                            .iter()
+                            .map(|x| x.get_node(all_nodes))
+                            .collect::<Vec<_>>();
+                        let mut all_larger_iter = all_larger.iter();
+                        let (highest_uncovered, uncovered_when) = loop {
+                            let larger = all_larger_iter.next();
+                            match larger {
+                                Some(larger) => {
+                                    let (highest_uncovered, uncovered_when) =
+                                        find_highest_uncovered_child_with_when_uncovered(
+                                            larger, now,
+                                        );
+                                    if let Some(highest_uncovered) = highest_uncovered {
+                                        break (Some(highest_uncovered), uncovered_when);
+                                    }
                                 }
+                                None => break (None, None),
                             }
-                            None => break (None, None),
-                        }
-                    };
-                    match highest_uncovered {
-                        Some(highest_uncovered) => {
-                            if highest_uncovered == item_node.get_item() {
-                                let uncovered_when: DateTime<Utc> = match uncovered_when {
-                                    Some(_) => todo!(),
-                                    None => earliest.clone().into(),
-                                };
-                                false
-                            } else {
-                                true
+                        };
+                        match highest_uncovered {
+                            Some(highest_uncovered) => {
+                                if highest_uncovered == item_node.get_item() {
+                                    let uncovered_when: DateTime<Utc> = match uncovered_when {
+                                        Some(_) => todo!(),
+                                        None => earliest.clone().into(),
+                                    };
+                                    None
+                                } else {
+                                    Some(SnoozeReason::NotHighestUncovered)
+                                }
                             }
+                            None => Some(SnoozeReason::NotHighestUncovered),
                         }
-                        None => true,
                     }
                 }
             }
         }
-        Staging::Planned => false,
-        Staging::ThinkingAbout => false,
-        Staging::Released => false,
+        Staging::Planned => None,
+        Staging::ThinkingAbout => None,
+        Staging::Released => None,
     };
 
-    item_node.get_snoozed_until().iter().any(|x| x > &now) || snoozed_from_staging
+    let waiting_for_follow_up = item_node
+        .get_waiting_for()
+        .map(|x| -> DateTime<Utc> { x.follow_up_after.clone().into() })
+        .filter(|follow_up_after| follow_up_after > now)
+        .map(SnoozeReason::FollowUpAt);
+
+    item_node
+        .get_snoozed_until()
+        .iter()
+        .filter(|x| **x > now)
+        .map(|x| SnoozeReason::CoveredUntil(**x))
+        .chain(snoozed_from_staging)
+        .chain(waiting_for_follow_up)
+        .collect()
 }
 
 fn find_highest_uncovered_child_with_when_uncovered<'a>(
@@ -333,12 +460,14 @@ mod tests {
         new_item::{NewItem, NewItemBuilder},
         surrealdb_layer::{
             data_storage_start_and_run,
-            surreal_item::{EnterListReason, Staging},
+            surreal_item::{EnterListReason, Staging, WaitingFor},
             surreal_tables::SurrealTables,
             DataLayerCommands,
         },
     };
 
+    use super::SnoozeReason;
+
     #[tokio::test]
     async fn parent_node_1_child_configured_for_highest_uncovered_if_before_earliest_time_it_should_remain_snoozed(
     ) {
@@ -401,6 +530,10 @@ mod tests {
 
         // Assert
         assert_eq!(child.is_snoozed(), true);
+        assert!(matches!(
+            child.get_snooze_reasons(),
+            [SnoozeReason::EarliestNotReached(_)]
+        ));
         assert_eq!(child.get_lap_count(), 0.0);
 
         drop(sender);
@@ -712,4 +845,79 @@ mod tests {
     ) {
         todo!("test case todo")
     }
+
+    #[tokio::test]
+    async fn waiting_for_someone_is_snoozed_until_the_follow_up_time_and_then_is_due_for_follow_up()
+    {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Get the contract signed".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new_person_or_group(
+                "Alex".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let contract = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "Get the contract signed")
+            .unwrap();
+        let alex = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "Alex")
+            .unwrap();
+        let follow_up_after = Utc::now() + chrono::Duration::days(2);
+        sender
+            .send(DataLayerCommands::UpdateItemWaitingFor(
+                contract.id.clone().unwrap(),
+                Some(WaitingFor {
+                    who: alex.id.clone().unwrap(),
+                    since: Utc::now().into(),
+                    follow_up_after: follow_up_after.into(),
+                }),
+            ))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        for (now, should_follow_up) in [
+            (Utc::now(), false),
+            (follow_up_after + chrono::Duration::hours(1), true),
+        ] {
+            let base_data = BaseData::new_from_surreal_tables(surreal_tables.clone(), now);
+            let calculated_data = CalculatedData::new_from_base_data(base_data, &now);
+            let contract = calculated_data
+                .get_item_status()
+                .iter()
+                .find(|x| x.get_item().get_summary() == "Get the contract signed")
+                .unwrap();
+
+            assert_eq!(contract.is_follow_up_due(), should_follow_up);
+            assert_eq!(contract.is_snoozed(), !should_follow_up);
+            assert_eq!(
+                contract
+                    .get_item_node()
+                    .get_waiting_for_who()
+                    .unwrap()
+                    .get_summary(),
+                "Alex"
+            );
+        }
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
 }
@@ -0,0 +1,2529 @@
+//! The database, see data_storage_start_and_run, and the records that are stored in it
+
+pub mod surreal_covering;
+pub mod surreal_covering_until_date_time;
+pub mod surreal_item;
+pub mod surreal_item_archive;
+pub mod surreal_life_area;
+pub mod surreal_processed_text;
+pub mod surreal_required_circumstance;
+pub mod surreal_routine;
+pub mod surreal_tables;
+
+use std::iter::once;
+
+use chrono::{DateTime, Local, Utc};
+use itertools::chain;
+use surrealdb::{
+    engine::any::{connect, Any, IntoEndpoint},
+    opt::RecordId,
+    sql::{Datetime, Thing},
+    Surreal,
+};
+use surrealdb_extra::table::{Table, TableError};
+use tokio::sync::{
+    broadcast::{self, error::TryRecvError},
+    mpsc::{Receiver, Sender},
+    oneshot::{self, error::RecvError},
+};
+
+use crate::{
+    data_storage::{data_storage_run, DataStorage},
+    new_item::NewItem,
+};
+
+use self::{
+    surreal_covering::SurrealCovering,
+    surreal_covering_until_date_time::SurrealCoveringUntilDatetime,
+    surreal_item::{
+        CalendarEvent, DueDate, Facing, GitBranch, ItemType, Permanence, Responsibility, Staging,
+        SurrealItem, SurrealItemOldVersion, SurrealOrderedSubItem, WaitingFor,
+    },
+    surreal_item_archive::SurrealItemArchive,
+    surreal_life_area::SurrealLifeArea,
+    surreal_processed_text::SurrealProcessedText,
+    surreal_required_circumstance::SurrealRequiredCircumstance,
+    surreal_routine::SurrealRoutine,
+    surreal_tables::{SurrealTables, SurrealTablesChange, WouldCreateACycle},
+};
+
+/// Sent to subscribers every time a command changes the data that SendRawData returns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DataChanged;
+
+/// Requests for data_storage_start_and_run. Commands that return something include a oneshot to send it back on,
+/// the helper functions like get_raw_data make and await that oneshot.
+pub enum DataLayerCommands {
+    /// Sends back every table except processed_text and item_archive, see get_raw_data
+    SendRawData(oneshot::Sender<SurrealTables>),
+    /// Sends back a receiver for DataChanged, see subscribe_to_changes
+    SubscribeToChanges(oneshot::Sender<broadcast::Receiver<DataChanged>>),
+    /// Sends back the processed text written about the item, see get_processed_text
+    SendProcessedText(RecordId, oneshot::Sender<Vec<SurrealProcessedText>>),
+    /// Sends back the processed text written about every item, see get_all_processed_text
+    SendAllProcessedText(oneshot::Sender<Vec<SurrealProcessedText>>),
+    /// Sends back the archived items, see get_item_archive
+    SendItemArchive(oneshot::Sender<Vec<SurrealItemArchive>>),
+    /// Removes the processed text
+    RemoveProcessedText(SurrealProcessedText),
+    /// Saves the text as processed text written about the item
+    AddProcessedText(String, RecordId),
+    /// Marks the item as finished now
+    FinishItem(RecordId),
+    /// Marks the item as not finished
+    UnfinishItem(RecordId),
+    /// Removes the item and everything that references it, this cannot be undone
+    DeleteItem(RecordId),
+    /// Moves finished items out of the item table so they are no longer loaded
+    ArchiveFinishedItems {
+        /// Items finished before this are archived
+        finished_before: DateTime<Utc>,
+    },
+    /// Saves the new item
+    NewItem(NewItem),
+    /// Saves the new item and sends back its id, see new_item_and_get_id
+    NewItemAndSendId(NewItem, oneshot::Sender<RecordId>),
+    /// Saves cover_with as a new item and covers cover_this with it
+    CoverItemWithANewItem {
+        /// The item to be covered
+        cover_this: RecordId,
+        /// The new item that does the covering
+        cover_with: NewItem,
+    },
+    /// Refused with the conflicting chain if it would create a cycle
+    CoverItemWithAnExistingItem {
+        /// The item to be covered
+        item_to_be_covered: RecordId,
+        /// The item that does the covering
+        item_that_should_do_the_covering: RecordId,
+        /// Ok once the covering is saved
+        send_response_here: oneshot::Sender<Result<(), WouldCreateACycle>>,
+    },
+    /// Removes the covering
+    RemoveCoveringItem(SurrealCovering),
+    /// Removes the covering until date time
+    RemoveCoveringUntilDatetime(SurrealCoveringUntilDatetime),
+    /// Removes the required circumstance
+    RemoveRequiredCircumstance(SurrealRequiredCircumstance),
+    /// Covers the item until the date time
+    CoverItemUntilAnExactDateTime(RecordId, DateTime<Utc>),
+    /// Refused with the conflicting chain if it would create a cycle
+    ParentItemWithExistingItem {
+        /// The item that becomes smaller
+        child: RecordId,
+        /// The item that becomes larger
+        parent: RecordId,
+        /// None puts the child at the lowest priority
+        higher_priority_than_this: Option<RecordId>,
+        /// Ok once the change is saved
+        send_response_here: oneshot::Sender<Result<(), WouldCreateACycle>>,
+    },
+    /// Saves child as a new item and makes it smaller than parent
+    ParentItemWithANewChildItem {
+        /// The new item that becomes smaller
+        child: NewItem,
+        /// The item that becomes larger
+        parent: RecordId,
+        /// None puts the child at the lowest priority
+        higher_priority_than_this: Option<RecordId>,
+    },
+    /// Saves parent_new_item as a new item and makes child smaller than it
+    ParentNewItemWithAnExistingChildItem {
+        /// The item that becomes smaller
+        child: RecordId,
+        /// The new item that becomes larger
+        parent_new_item: NewItem,
+    },
+    /// Sets both the responsibility and the item type
+    UpdateResponsibilityAndItemType(RecordId, Responsibility, ItemType),
+    /// Sets the responsibility
+    UpdateItemResponsibility(RecordId, Responsibility),
+    /// Sets the permanence
+    UpdateItemPermanence(RecordId, Permanence),
+    /// Sets the staging
+    UpdateItemStaging(RecordId, Staging),
+    /// Sets the due date, None removes it
+    UpdateItemDueDate(RecordId, Option<DueDate>),
+    /// Sets what the item is waiting for, None removes it
+    UpdateItemWaitingFor(RecordId, Option<WaitingFor>),
+    /// Sets the calendar event, None removes it
+    UpdateItemCalendarEvent(RecordId, Option<Box<CalendarEvent>>),
+    /// Sets the git branch, None removes it
+    UpdateItemGitBranch(RecordId, Option<Box<GitBranch>>),
+    /// Sets the summary
+    UpdateItemSummary(RecordId, String),
+    /// Sets the facing
+    UpdateFacing(RecordId, Vec<Facing>),
+    /// Replaces the smaller items, in priority order
+    UpdateSmallerItemsInPriorityOrder(RecordId, Vec<SurrealOrderedSubItem>),
+}
+
+impl DataLayerCommands {
+    /// Every table except processed_text and item_archive
+    pub async fn get_raw_data(
+        sender: &Sender<DataLayerCommands>,
+    ) -> Result<SurrealTables, RecvError> {
+        let (raw_data_sender, raw_data_receiver) = oneshot::channel();
+        sender
+            .send(DataLayerCommands::SendRawData(raw_data_sender))
+            .await
+            .unwrap();
+        raw_data_receiver.await
+    }
+
+    /// The processed text written about for_item
+    #[allow(dead_code)] //Remove after this is used beyond the unit tests
+    pub async fn get_processed_text(
+        sender: &Sender<DataLayerCommands>,
+        for_item: RecordId,
+    ) -> Result<Vec<SurrealProcessedText>, RecvError> {
+        let (processed_text_tx, processed_text_rx) = oneshot::channel();
+        sender
+            .send(DataLayerCommands::SendProcessedText(
+                for_item,
+                processed_text_tx,
+            ))
+            .await
+            .unwrap();
+        processed_text_rx.await
+    }
+
+    /// Subscribe before getting the raw data so no change is missed
+    pub async fn subscribe_to_changes(
+        sender: &Sender<DataLayerCommands>,
+    ) -> Result<broadcast::Receiver<DataChanged>, RecvError> {
+        let (subscription_tx, subscription_rx) = oneshot::channel();
+        sender
+            .send(DataLayerCommands::SubscribeToChanges(subscription_tx))
+            .await
+            .unwrap();
+        subscription_rx.await
+    }
+
+    /// The processed text written about every item
+    pub async fn get_all_processed_text(
+        sender: &Sender<DataLayerCommands>,
+    ) -> Result<Vec<SurrealProcessedText>, RecvError> {
+        let (processed_text_tx, processed_text_rx) = oneshot::channel();
+        sender
+            .send(DataLayerCommands::SendAllProcessedText(processed_text_tx))
+            .await
+            .unwrap();
+        processed_text_rx.await
+    }
+
+    /// The items that were moved out of the item table by ArchiveFinishedItems
+    pub async fn get_item_archive(
+        sender: &Sender<DataLayerCommands>,
+    ) -> Result<Vec<SurrealItemArchive>, RecvError> {
+        let (item_archive_tx, item_archive_rx) = oneshot::channel();
+        sender
+            .send(DataLayerCommands::SendItemArchive(item_archive_tx))
+            .await
+            .unwrap();
+        item_archive_rx.await
+    }
+
+    /// Covers item_to_be_covered with item_that_should_do_the_covering unless that would create a cycle
+    pub async fn cover_item_with_an_existing_item(
+        sender: &Sender<DataLayerCommands>,
+        item_to_be_covered: RecordId,
+        item_that_should_do_the_covering: RecordId,
+    ) -> Result<Result<(), WouldCreateACycle>, RecvError> {
+        let (response_tx, response_rx) = oneshot::channel();
+        sender
+            .send(DataLayerCommands::CoverItemWithAnExistingItem {
+                item_to_be_covered,
+                item_that_should_do_the_covering,
+                send_response_here: response_tx,
+            })
+            .await
+            .unwrap();
+        response_rx.await
+    }
+
+    /// Saves the new item and returns the id it was given
+    pub async fn new_item_and_get_id(
+        sender: &Sender<DataLayerCommands>,
+        new_item: NewItem,
+    ) -> Result<RecordId, RecvError> {
+        let (id_tx, id_rx) = oneshot::channel();
+        sender
+            .send(DataLayerCommands::NewItemAndSendId(new_item, id_tx))
+            .await
+            .unwrap();
+        id_rx.await
+    }
+
+    /// Makes child smaller than parent unless that would create a cycle
+    pub async fn parent_item_with_existing_item(
+        sender: &Sender<DataLayerCommands>,
+        child: RecordId,
+        parent: RecordId,
+        higher_priority_than_this: Option<RecordId>,
+    ) -> Result<Result<(), WouldCreateACycle>, RecvError> {
+        let (response_tx, response_rx) = oneshot::channel();
+        sender
+            .send(DataLayerCommands::ParentItemWithExistingItem {
+                child,
+                parent,
+                higher_priority_than_this,
+                send_response_here: response_tx,
+            })
+            .await
+            .unwrap();
+        response_rx.await
+    }
+}
+
+/// Opens the database at endpoint, for example "mem://" or "file://...", and handles commands in the order they are
+/// received until every sender is dropped
+pub async fn data_storage_start_and_run(
+    data_storage_layer_receive_rx: Receiver<DataLayerCommands>,
+    endpoint: impl IntoEndpoint,
+) {
+    let db = connect(endpoint).await.unwrap();
+    db.use_ns("OnPurpose").use_db("Russ").await.unwrap();
+
+    data_storage_run(data_storage_layer_receive_rx, db).await
+}
+
+impl DataStorage for Surreal<Any> {
+    async fn load_tables(&mut self) -> SurrealTables {
+        load_from_surrealdb_upgrade_if_needed(self).await
+    }
+
+    async fn get_processed_text(&mut self, for_item: RecordId) -> Vec<SurrealProcessedText> {
+        get_processed_text(for_item, self).await
+    }
+
+    async fn get_all_processed_text(&mut self) -> Vec<SurrealProcessedText> {
+        SurrealProcessedText::get_all(self).await.unwrap()
+    }
+
+    async fn get_item_archive(&mut self) -> Vec<SurrealItemArchive> {
+        SurrealItemArchive::get_all(self).await.unwrap()
+    }
+
+    async fn add_processed_text(&mut self, processed_text: String, for_item: RecordId) {
+        add_processed_text(processed_text, for_item, self).await
+    }
+
+    async fn remove_processed_text(&mut self, processed_text: SurrealProcessedText) {
+        SurrealProcessedText::delete(self, processed_text.id.unwrap().id.to_raw())
+            .await
+            .unwrap()
+            .unwrap(); //2nd unwrap ensures the delete actually happened
+    }
+
+    async fn finish_item(&mut self, finish_this: RecordId) -> Vec<SurrealTablesChange> {
+        finish_item(finish_this, self).await
+    }
+
+    async fn unfinish_item(&mut self, unfinish_this: RecordId) -> Vec<SurrealTablesChange> {
+        unfinish_item(unfinish_this, self).await
+    }
+
+    async fn delete_item(&mut self, delete_this: RecordId) -> Vec<SurrealTablesChange> {
+        delete_item(delete_this, self).await
+    }
+
+    async fn archive_finished_items(
+        &mut self,
+        finished_before: DateTime<Utc>,
+    ) -> Vec<SurrealTablesChange> {
+        archive_finished_items(finished_before, self).await
+    }
+
+    async fn new_item(&mut self, new_item: NewItem) -> Vec<SurrealTablesChange> {
+        let new_item = self::new_item(new_item, self).await;
+        vec![SurrealTablesChange::SavedItem(new_item)]
+    }
+
+    async fn cover_with_a_new_item(
+        &mut self,
+        cover_this: RecordId,
+        cover_with: NewItem,
+    ) -> Vec<SurrealTablesChange> {
+        cover_with_a_new_item(cover_this, cover_with, self).await
+    }
+
+    async fn cover_item_with_an_existing_item(
+        &mut self,
+        item_to_be_covered: RecordId,
+        item_that_should_do_the_covering: RecordId,
+    ) -> Result<Vec<SurrealTablesChange>, WouldCreateACycle> {
+        cover_item_with_an_existing_item(item_to_be_covered, item_that_should_do_the_covering, self)
+            .await
+    }
+
+    async fn remove_covering(&mut self, covering: SurrealCovering) -> Vec<SurrealTablesChange> {
+        let removed = SurrealCovering::delete(self, covering.id.unwrap().id.to_raw())
+            .await
+            .unwrap()
+            .unwrap(); //2nd unwrap ensures the delete actually happened
+        vec![SurrealTablesChange::RemovedCovering(
+            removed.id.expect("Came from the DB"),
+        )]
+    }
+
+    async fn remove_covering_until_date_time(
+        &mut self,
+        covering_until_date_time: SurrealCoveringUntilDatetime,
+    ) -> Vec<SurrealTablesChange> {
+        let removed = SurrealCoveringUntilDatetime::delete(
+            self,
+            covering_until_date_time.id.unwrap().id.to_raw(),
+        )
+        .await
+        .unwrap()
+        .unwrap(); //2nd unwrap ensures the delete actually happened
+        vec![SurrealTablesChange::RemovedCoveringUntilDatetime(
+            removed.id.expect("Came from the DB"),
+        )]
+    }
+
+    async fn remove_required_circumstance(
+        &mut self,
+        required_circumstance: SurrealRequiredCircumstance,
+    ) -> Vec<SurrealTablesChange> {
+        let removed = SurrealRequiredCircumstance::delete(
+            self,
+            required_circumstance.id.unwrap().id.to_raw(),
+        )
+        .await
+        .unwrap()
+        .unwrap(); //2nd unwrap ensures the delete actually happened
+        vec![SurrealTablesChange::RemovedRequiredCircumstance(
+            removed.id.expect("Came from the DB"),
+        )]
+    }
+
+    async fn cover_item_until_an_exact_date_time(
+        &mut self,
+        item_to_cover: RecordId,
+        cover_until: DateTime<Utc>,
+    ) -> Vec<SurrealTablesChange> {
+        cover_item_until_an_exact_date_time(item_to_cover, cover_until, self).await
+    }
+
+    async fn parent_item_with_existing_item(
+        &mut self,
+        child: RecordId,
+        parent: RecordId,
+        higher_priority_than_this: Option<RecordId>,
+    ) -> Result<Vec<SurrealTablesChange>, WouldCreateACycle> {
+        parent_item_with_existing_item(child, parent, higher_priority_than_this, self).await
+    }
+
+    async fn parent_item_with_a_new_child(
+        &mut self,
+        child: NewItem,
+        parent: RecordId,
+        higher_priority_than_this: Option<RecordId>,
+    ) -> Vec<SurrealTablesChange> {
+        parent_item_with_a_new_child(child, parent, higher_priority_than_this, self).await
+    }
+
+    async fn parent_new_item_with_an_existing_child_item(
+        &mut self,
+        child: RecordId,
+        parent_new_item: NewItem,
+    ) -> Vec<SurrealTablesChange> {
+        parent_new_item_with_an_existing_child_item(child, parent_new_item, self).await
+    }
+
+    async fn update_responsibility_and_item_type(
+        &mut self,
+        item: RecordId,
+        new_responsibility: Responsibility,
+        new_item_type: ItemType,
+    ) -> Vec<SurrealTablesChange> {
+        let mut item = SurrealItem::get_by_id(self, item.id.to_raw())
+            .await
+            .unwrap()
+            .unwrap();
+        item.responsibility = new_responsibility;
+        item.item_type = new_item_type;
+        let new = self
+            .update((
+                SurrealItem::TABLE_NAME,
+                item.get_id()
+                    .clone()
+                    .expect("Came from the DB")
+                    .id
+                    .clone()
+                    .to_raw(),
+            ))
+            .content(&item)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(item, new);
+        vec![SurrealTablesChange::SavedItem(new)]
+    }
+
+    async fn update_item_responsibility(
+        &mut self,
+        item: RecordId,
+        new_responsibility: Responsibility,
+    ) -> Vec<SurrealTablesChange> {
+        let mut item = SurrealItem::get_by_id(self, item.id.to_raw())
+            .await
+            .unwrap()
+            .unwrap();
+        item.responsibility = new_responsibility;
+        let updated = item.update(self).await.unwrap().unwrap();
+        vec![SurrealTablesChange::SavedItem(updated)]
+    }
+
+    async fn update_item_permanence(
+        &mut self,
+        item: RecordId,
+        new_permanence: Permanence,
+    ) -> Vec<SurrealTablesChange> {
+        update_hope_permanence(item, new_permanence, self).await
+    }
+
+    async fn update_item_staging(
+        &mut self,
+        item: RecordId,
+        new_staging: Staging,
+    ) -> Vec<SurrealTablesChange> {
+        update_hope_staging(item, new_staging, self).await
+    }
+
+    async fn update_item_due_date(
+        &mut self,
+        item: RecordId,
+        new_due_date: Option<DueDate>,
+    ) -> Vec<SurrealTablesChange> {
+        update_item_with(item, |x| x.due_date = new_due_date, self).await
+    }
+
+    async fn update_item_waiting_for(
+        &mut self,
+        item: RecordId,
+        new_waiting_for: Option<WaitingFor>,
+    ) -> Vec<SurrealTablesChange> {
+        update_item_with(item, |x| x.waiting_for = new_waiting_for, self).await
+    }
+
+    async fn update_item_calendar_event(
+        &mut self,
+        item: RecordId,
+        new_calendar_event: Option<Box<CalendarEvent>>,
+    ) -> Vec<SurrealTablesChange> {
+        update_item_with(item, |x| x.calendar_event = new_calendar_event, self).await
+    }
+
+    async fn update_item_git_branch(
+        &mut self,
+        item: RecordId,
+        new_git_branch: Option<Box<GitBranch>>,
+    ) -> Vec<SurrealTablesChange> {
+        update_item_with(item, |x| x.git_branch = new_git_branch, self).await
+    }
+
+    async fn update_item_summary(
+        &mut self,
+        item: RecordId,
+        new_summary: String,
+    ) -> Vec<SurrealTablesChange> {
+        update_item_summary(item, new_summary, self).await
+    }
+
+    async fn update_facing(
+        &mut self,
+        item: RecordId,
+        new_facing: Vec<Facing>,
+    ) -> Vec<SurrealTablesChange> {
+        let mut item = SurrealItem::get_by_id(self, item.id.to_raw())
+            .await
+            .unwrap()
+            .unwrap();
+        item.facing = new_facing;
+        let updated = item.clone().update(self).await.unwrap().unwrap();
+        assert_eq!(item, updated);
+        vec![SurrealTablesChange::SavedItem(updated)]
+    }
+
+    async fn update_smaller_items_in_priority_order(
+        &mut self,
+        item: RecordId,
+        new_smaller_items_in_priority_order: Vec<SurrealOrderedSubItem>,
+    ) -> Vec<SurrealTablesChange> {
+        let mut item = SurrealItem::get_by_id(self, item.id.to_raw())
+            .await
+            .unwrap()
+            .unwrap();
+        item.smaller_items_in_priority_order = new_smaller_items_in_priority_order;
+        let updated = item.clone().update(self).await.unwrap().unwrap();
+        assert_eq!(item, updated);
+        vec![SurrealTablesChange::SavedItem(updated)]
+    }
+}
+
+/// True when a change happened after subscribing, also true if so many changes happened that some were missed
+pub fn has_data_changed(data_changed: &mut broadcast::Receiver<DataChanged>) -> bool {
+    let mut changed = false;
+    loop {
+        match data_changed.try_recv() {
+            Ok(DataChanged) | Err(TryRecvError::Lagged(_)) => changed = true,
+            Err(TryRecvError::Empty | TryRecvError::Closed) => return changed,
+        }
+    }
+}
+
+pub(crate) async fn load_from_surrealdb_upgrade_if_needed(db: &Surreal<Any>) -> SurrealTables {
+    //TODO: I should do some timings to see if starting all of these get_all requests and then doing awaits on them later really is faster in Rust. Or if they just for sure don't start until the await. For example I could call this function as many times as possible in 10 sec and time that and then see how many times I can call that function written like this and then again with the get_all being right with the await to make sure that code like this is worth it perf wise.
+    let all_items = SurrealItem::get_all(db);
+    let all_coverings = SurrealCovering::get_all(db);
+    let all_required_circumstances = SurrealRequiredCircumstance::get_all(db);
+    let all_coverings_until_date_time = SurrealCoveringUntilDatetime::get_all(db);
+    let all_life_areas = SurrealLifeArea::get_all(db);
+    let all_routines = SurrealRoutine::get_all(db);
+
+    let all_items = match all_items.await {
+        Ok(all_items) => all_items,
+        Err(err) => {
+            println!("Upgrading items table because of issue: {}", err);
+            upgrade_items_table(db).await;
+            SurrealItem::get_all(db).await.unwrap()
+        }
+    };
+
+    SurrealTables {
+        surreal_items: all_items,
+        surreal_coverings: all_coverings.await.unwrap(),
+        surreal_required_circumstances: all_required_circumstances.await.unwrap(),
+        surreal_coverings_until_date_time: all_coverings_until_date_time.await.unwrap(),
+        surreal_life_areas: all_life_areas.await.unwrap(),
+        surreal_routines: all_routines.await.unwrap(),
+    }
+}
+
+async fn upgrade_items_table(db: &Surreal<Any>) {
+    for item_old_version in SurrealItemOldVersion::get_all(db)
+        .await
+        .unwrap()
+        .into_iter()
+    {
+        let item: SurrealItem = item_old_version.into();
+        let _: SurrealItem = db
+            .update((
+                SurrealItem::TABLE_NAME,
+                item.get_id()
+                    .clone()
+                    .ok_or(TableError::IdEmpty)
+                    .unwrap()
+                    .id
+                    .clone()
+                    .to_raw(),
+            ))
+            .content(item)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+}
+
+pub(crate) async fn add_processed_text(
+    processed_text: String,
+    for_item: RecordId,
+    db: &Surreal<Any>,
+) {
+    let for_item: Option<Thing> = for_item.into();
+    let data = SurrealProcessedText {
+        id: None,
+        text: processed_text,
+        when_written: Local::now().naive_utc().and_utc().into(),
+        for_item: for_item.unwrap(),
+    };
+    data.create(db).await.unwrap();
+}
+
+async fn get_processed_text(for_item: RecordId, db: &Surreal<Any>) -> Vec<SurrealProcessedText> {
+    let mut query_result = db
+        .query("SELECT * FROM processed_text WHERE for_item = $for_item")
+        .bind(("for_item", for_item))
+        .await
+        .unwrap();
+
+    query_result.take(0).unwrap()
+}
+
+pub(crate) async fn finish_item(
+    finish_this: RecordId,
+    db: &Surreal<Any>,
+) -> Vec<SurrealTablesChange> {
+    let mut finish_this = SurrealItem::get_by_id(db, finish_this.id.to_raw())
+        .await
+        .unwrap()
+        .unwrap();
+    finish_this.finished = Some(Local::now().naive_utc().and_utc().into());
+    let updated = finish_this.update(db).await.unwrap().unwrap();
+    vec![SurrealTablesChange::SavedItem(updated)]
+}
+
+pub(crate) async fn unfinish_item(
+    unfinish_this: RecordId,
+    db: &Surreal<Any>,
+) -> Vec<SurrealTablesChange> {
+    let mut unfinish_this = SurrealItem::get_by_id(db, unfinish_this.id.to_raw())
+        .await
+        .unwrap()
+        .unwrap();
+    unfinish_this.finished = None;
+    //Using content rather than update (merge) so the finished field is for sure cleared out
+    let updated: SurrealItem = db
+        .update((
+            SurrealItem::TABLE_NAME,
+            unfinish_this
+                .get_id()
+                .clone()
+                .expect("Came from the DB")
+                .id
+                .to_raw(),
+        ))
+        .content(&unfinish_this)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(unfinish_this, updated);
+    vec![SurrealTablesChange::SavedItem(updated)]
+}
+
+/// Returns the items that had remove_this in their smaller_items_in_priority_order list, as they are after the update
+async fn remove_from_smaller_items_in_priority_order(
+    remove_this: &RecordId,
+    db: &Surreal<Any>,
+) -> Vec<SurrealItem> {
+    let mut removed_from = Vec::default();
+    for mut item in SurrealItem::get_all(db).await.unwrap().into_iter() {
+        if item.remove_smaller_item(remove_this) {
+            let saved = item.clone().update(db).await.unwrap().unwrap();
+            assert_eq!(item, saved);
+            removed_from.push(saved);
+        }
+    }
+    removed_from
+}
+
+/// Deletes the coverings, coverings_until_datetime, and required_circumstances that reference the item
+async fn delete_references_to_item(item: &RecordId, db: &Surreal<Any>) -> Vec<SurrealTablesChange> {
+    let mut deleted = db
+        .query(format!(
+            "DELETE {} WHERE smaller = $item OR parent = $item RETURN BEFORE",
+            SurrealCovering::TABLE_NAME
+        ))
+        .query(format!(
+            "DELETE {} WHERE cover_this = $item RETURN BEFORE",
+            SurrealCoveringUntilDatetime::TABLE_NAME
+        ))
+        .query(format!(
+            "DELETE {} WHERE required_for = $item RETURN BEFORE",
+            SurrealRequiredCircumstance::TABLE_NAME
+        ))
+        .bind(("item", item))
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+    let coverings: Vec<SurrealCovering> = deleted.take(0).unwrap();
+    let coverings_until_date_time: Vec<SurrealCoveringUntilDatetime> = deleted.take(1).unwrap();
+    let required_circumstances: Vec<SurrealRequiredCircumstance> = deleted.take(2).unwrap();
+
+    let removed_id = |id: Option<Thing>| id.expect("Came from the DB");
+    coverings
+        .into_iter()
+        .map(|x| SurrealTablesChange::RemovedCovering(removed_id(x.id)))
+        .chain(
+            coverings_until_date_time
+                .into_iter()
+                .map(|x| SurrealTablesChange::RemovedCoveringUntilDatetime(removed_id(x.id))),
+        )
+        .chain(
+            required_circumstances
+                .into_iter()
+                .map(|x| SurrealTablesChange::RemovedRequiredCircumstance(removed_id(x.id))),
+        )
+        .collect()
+}
+
+async fn delete_item(delete_this: RecordId, db: &Surreal<Any>) -> Vec<SurrealTablesChange> {
+    let mut changes = remove_from_smaller_items_in_priority_order(&delete_this, db)
+        .await
+        .into_iter()
+        .map(SurrealTablesChange::SavedItem)
+        .collect::<Vec<_>>();
+    changes.extend(delete_references_to_item(&delete_this, db).await);
+    db.query(format!(
+        "DELETE {} WHERE for_item = $item",
+        SurrealProcessedText::TABLE_NAME
+    ))
+    .bind(("item", &delete_this))
+    .await
+    .unwrap()
+    .check()
+    .unwrap();
+
+    SurrealItem::delete(db, delete_this.id.to_raw())
+        .await
+        .unwrap()
+        .unwrap(); //2nd unwrap ensures the delete actually happened
+    changes.push(SurrealTablesChange::RemovedItem(delete_this));
+    changes
+}
+
+async fn archive_finished_items(
+    finished_before: DateTime<Utc>,
+    db: &Surreal<Any>,
+) -> Vec<SurrealTablesChange> {
+    let mut changes = Vec::default();
+    let surreal_tables = load_from_surrealdb_upgrade_if_needed(db).await;
+    let finished_before: Datetime = finished_before.into();
+    let to_archive = surreal_tables.surreal_items.iter().filter(|x| {
+        x.finished
+            .as_ref()
+            .is_some_and(|finished| finished < &finished_before)
+    });
+    for item in to_archive {
+        let item_id = item.id.clone().expect("Came from the DB");
+        let smaller_item_of = remove_from_smaller_items_in_priority_order(&item_id, db).await;
+        //Processed text is only loaded for one item at a time so it does not need to be moved
+        let archive = SurrealItemArchive {
+            //Use the same key as the item so the archive is easy to find from the item's RecordId
+            id: Some((SurrealItemArchive::TABLE_NAME, item_id.id.clone()).into()),
+            item: item.clone(),
+            coverings: surreal_tables
+                .surreal_coverings
+                .iter()
+                .filter(|x| x.smaller == item_id || x.parent == item_id)
+                .cloned()
+                .collect(),
+            coverings_until_date_time: surreal_tables
+                .surreal_coverings_until_date_time
+                .iter()
+                .filter(|x| x.cover_this == item_id)
+                .cloned()
+                .collect(),
+            required_circumstances: surreal_tables
+                .surreal_required_circumstances
+                .iter()
+                .filter(|x| x.required_for == item_id)
+                .cloned()
+                .collect(),
+            smaller_item_of: smaller_item_of
+                .iter()
+                .map(|x| x.id.clone().expect("Came from the DB"))
+                .collect(),
+            archived: Utc::now().into(),
+        };
+        archive.create(db).await.unwrap();
+
+        changes.extend(
+            smaller_item_of
+                .into_iter()
+                .map(SurrealTablesChange::SavedItem),
+        );
+        changes.extend(delete_references_to_item(&item_id, db).await);
+        SurrealItem::delete(db, item_id.id.to_raw())
+            .await
+            .unwrap()
+            .unwrap(); //2nd unwrap ensures the delete actually happened
+        changes.push(SurrealTablesChange::RemovedItem(item_id));
+    }
+    changes
+}
+
+async fn new_item(new_item: NewItem, db: &Surreal<Any>) -> SurrealItem {
+    let surreal_item: SurrealItem = SurrealItem::new(new_item, vec![]);
+    surreal_item
+        .create(db)
+        .await
+        .unwrap()
+        .into_iter()
+        .next()
+        .expect("I just created one item it should be there")
+}
+
+async fn cover_with_a_new_item(
+    cover_this: RecordId,
+    cover_with: NewItem,
+    db: &Surreal<Any>,
+) -> Vec<SurrealTablesChange> {
+    let cover_with = SurrealItem::new(cover_with, vec![]);
+    let cover_with = cover_with
+        .create(db)
+        .await
+        .unwrap()
+        .into_iter()
+        .next()
+        .unwrap();
+
+    let cover_with_id: Option<Thing> = cover_with.clone().into();
+    let cover_this: Option<Thing> = cover_this.into();
+    let covering = SurrealCovering {
+        id: None,
+        smaller: cover_with_id.expect("Should already be in the database"),
+        parent: cover_this.expect("Should already be in the database"),
+    }
+    .create(db)
+    .await
+    .unwrap();
+    chain!(
+        once(SurrealTablesChange::SavedItem(cover_with)),
+        covering.into_iter().map(SurrealTablesChange::SavedCovering)
+    )
+    .collect()
+}
+
+async fn check_for_a_cycle(
+    smaller: &RecordId,
+    larger: &RecordId,
+    db: &Surreal<Any>,
+) -> Result<(), WouldCreateACycle> {
+    let surreal_tables = load_from_surrealdb_upgrade_if_needed(db).await;
+    surreal_tables.check_for_a_cycle(smaller, larger)
+}
+
+async fn cover_item_with_an_existing_item(
+    existing_item_to_be_covered: RecordId,
+    existing_item_that_is_doing_the_covering: RecordId,
+    db: &Surreal<Any>,
+) -> Result<Vec<SurrealTablesChange>, WouldCreateACycle> {
+    check_for_a_cycle(
+        &existing_item_that_is_doing_the_covering,
+        &existing_item_to_be_covered,
+        db,
+    )
+    .await?;
+    let smaller_option: Option<Thing> = existing_item_that_is_doing_the_covering.into();
+    let parent_option: Option<Thing> = existing_item_to_be_covered.into();
+    let changes = SurrealCovering {
+        id: None,
+        smaller: smaller_option.expect("Should already be in the database"),
+        parent: parent_option.expect("Should already be in the database"),
+    }
+    .create(db)
+    .await
+    .unwrap()
+    .into_iter()
+    .map(SurrealTablesChange::SavedCovering)
+    .collect();
+    Ok(changes)
+}
+
+async fn cover_item_until_an_exact_date_time(
+    item_to_cover: RecordId,
+    cover_until: DateTime<Utc>,
+    db: &Surreal<Any>,
+) -> Vec<SurrealTablesChange> {
+    SurrealCoveringUntilDatetime {
+        id: None,
+        cover_this: item_to_cover,
+        until: cover_until.into(),
+    }
+    .create(db)
+    .await
+    .unwrap()
+    .into_iter()
+    .map(SurrealTablesChange::SavedCoveringUntilDatetime)
+    .collect()
+}
+
+async fn parent_item_with_existing_item(
+    child: RecordId,
+    parent: RecordId,
+    higher_priority_than_this: Option<RecordId>,
+    db: &Surreal<Any>,
+) -> Result<Vec<SurrealTablesChange>, WouldCreateACycle> {
+    check_for_a_cycle(&child, &parent, db).await?;
+    let mut parent = SurrealItem::get_by_id(db, parent.id.to_raw())
+        .await
+        .unwrap()
+        .unwrap();
+    parent.add_smaller_item(child, higher_priority_than_this);
+    let saved = parent.clone().update(db).await.unwrap().unwrap();
+    assert_eq!(parent, saved);
+    Ok(vec![SurrealTablesChange::SavedItem(saved)])
+}
+
+async fn parent_item_with_a_new_child(
+    child: NewItem,
+    parent: RecordId,
+    higher_priority_than_this: Option<RecordId>,
+    db: &Surreal<Any>,
+) -> Vec<SurrealTablesChange> {
+    let child = new_item(child, db).await;
+    let child_id = child.id.clone().expect("In DB");
+    let mut changes = vec![SurrealTablesChange::SavedItem(child)];
+    changes.extend(
+        parent_item_with_existing_item(child_id, parent, higher_priority_than_this, db)
+            .await
+            .expect("A new item cannot be part of a cycle"),
+    );
+    changes
+}
+
+async fn parent_new_item_with_an_existing_child_item(
+    child: RecordId,
+    parent_new_item: NewItem,
+    db: &Surreal<Any>,
+) -> Vec<SurrealTablesChange> {
+    //TODO: Write a Unit Test for this
+    let smaller_items_in_priority_order = vec![SurrealOrderedSubItem::SubItem {
+        surreal_item_id: child,
+    }];
+
+    let parent_surreal_item = SurrealItem::new(parent_new_item, smaller_items_in_priority_order);
+    parent_surreal_item
+        .create(db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(SurrealTablesChange::SavedItem)
+        .collect()
+}
+
+async fn update_hope_permanence(
+    surreal_item: RecordId,
+    new_permanence: Permanence,
+    db: &Surreal<Any>,
+) -> Vec<SurrealTablesChange> {
+    let mut surreal_item = SurrealItem::get_by_id(db, surreal_item.id.to_raw())
+        .await
+        .unwrap()
+        .unwrap();
+    surreal_item.permanence = new_permanence;
+
+    if surreal_item.id.is_some() {
+        //Update
+        let updated = surreal_item.update(db).await.unwrap().unwrap();
+        vec![SurrealTablesChange::SavedItem(updated)]
+    } else {
+        //Create record
+        surreal_item
+            .create(db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(SurrealTablesChange::SavedItem)
+            .collect()
+    }
+}
+
+async fn update_hope_staging(
+    record_id: RecordId,
+    new_staging: Staging,
+    db: &Surreal<Any>,
+) -> Vec<SurrealTablesChange> {
+    let mut surreal_item = SurrealItem::get_by_id(db, record_id.id.to_raw())
+        .await
+        .unwrap()
+        .unwrap();
+    surreal_item.staging = new_staging;
+
+    if surreal_item.id.is_some() {
+        let updated: SurrealItem = db
+            .update((
+                SurrealItem::TABLE_NAME,
+                surreal_item.get_id().clone().unwrap().id.clone().to_raw(),
+            ))
+            //I am doing this directly rather than using the update method on the surreal_item type because I need to call content rather than update
+            //because I changed the type of Staging::OnDeck to include two parameters and update will silently not update and content will properly
+            //do this update. Although in theory content is creating a new record so that might cause more churn if it is not required. I might consider
+            //just migrating all records all at once and one time to prevent this need to use content for ever more.
+            .content(surreal_item)
+            .await
+            .unwrap()
+            .unwrap();
+        vec![SurrealTablesChange::SavedItem(updated)]
+    } else {
+        //Create record
+        surreal_item
+            .create(db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(SurrealTablesChange::SavedItem)
+            .collect()
+    }
+}
+
+async fn update_item_summary(
+    item_to_update: RecordId,
+    new_summary: String,
+    db: &Surreal<Any>,
+) -> Vec<SurrealTablesChange> {
+    let mut item_to_update = SurrealItem::get_by_id(db, item_to_update.id.to_raw())
+        .await
+        .unwrap()
+        .unwrap();
+    item_to_update.summary = new_summary;
+
+    let updated = item_to_update.update(db).await.unwrap().unwrap();
+    vec![SurrealTablesChange::SavedItem(updated)]
+}
+
+/// Gets the item, lets update change it and saves the whole item back
+async fn update_item_with(
+    item: RecordId,
+    update: impl FnOnce(&mut SurrealItem),
+    db: &Surreal<Any>,
+) -> Vec<SurrealTablesChange> {
+    let mut item = SurrealItem::get_by_id(db, item.id.to_raw())
+        .await
+        .unwrap()
+        .unwrap();
+    update(&mut item);
+    //content rather than update so items saved before a field existed get the new field
+    let updated: SurrealItem = db
+        .update((
+            SurrealItem::TABLE_NAME,
+            item.get_id().clone().unwrap().id.clone().to_raw(),
+        ))
+        .content(item)
+        .await
+        .unwrap()
+        .unwrap();
+    vec![SurrealTablesChange::SavedItem(updated)]
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    use crate::{
+        base_data::item::ItemVecExtensions, new_item::NewItemBuilder,
+        surrealdb_layer::surreal_item::HowMuchIsInMyControl,
+    };
+
+    #[tokio::test]
+    async fn data_starts_empty() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+
+        assert!(surreal_tables.surreal_items.is_empty());
+        assert!(surreal_tables.surreal_coverings.is_empty());
+        assert!(surreal_tables.surreal_required_circumstances.is_empty());
+        assert!(surreal_tables.surreal_coverings_until_date_time.is_empty());
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn add_new_item() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        let new_item = NewItem::new("New item".into(), Utc::now());
+        sender
+            .send(DataLayerCommands::NewItem(new_item))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+
+        assert_eq!(surreal_tables.surreal_items.len(), 1);
+        assert_eq!(
+            ItemType::Undeclared,
+            surreal_tables.surreal_items.first().unwrap().item_type
+        );
+        assert_eq!(surreal_tables.surreal_coverings.len(), 0);
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn add_user_processed_text() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        let new_action = NewItemBuilder::default()
+            .summary("New next step")
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(new_action))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+
+        let item = surreal_tables.surreal_items.first().unwrap();
+        let processed_text = DataLayerCommands::get_processed_text(
+            &sender,
+            item.get_id().as_ref().expect("Item exists in DB").clone(),
+        )
+        .await
+        .unwrap();
+
+        assert!(processed_text.is_empty());
+
+        sender
+            .send(DataLayerCommands::AddProcessedText(
+                "Some user processed text".into(),
+                item.get_id().as_ref().expect("Already in DB").clone(),
+            ))
+            .await
+            .unwrap();
+
+        let (processed_text_tx, processed_text_rx) = oneshot::channel();
+        let next_step = surreal_tables.surreal_items.first().unwrap();
+        sender
+            .send(DataLayerCommands::SendProcessedText(
+                next_step
+                    .get_id()
+                    .as_ref()
+                    .expect("Item exists in DB")
+                    .clone(),
+                processed_text_tx,
+            ))
+            .await
+            .unwrap();
+
+        let processed_text = processed_text_rx.await.unwrap();
+        assert!(!processed_text.is_empty());
+        assert_eq!(
+            "Some user processed text",
+            processed_text.first().unwrap().text
+        );
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn finish_item() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        let new_next_step = NewItemBuilder::default()
+            .summary("New next step")
+            .item_type(ItemType::Action)
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(new_next_step))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let items = surreal_tables.make_items();
+
+        assert_eq!(items.len(), 1);
+        let next_step_item = items.first().unwrap();
+        assert_eq!(next_step_item.is_finished(), false);
+        assert_eq!(surreal_tables.surreal_coverings.len(), 0);
+
+        sender
+            .send(DataLayerCommands::FinishItem(
+                next_step_item.get_id().clone().into(),
+            ))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let items = surreal_tables.make_items();
+
+        assert_eq!(items.len(), 1);
+        let next_step_item = items.first().unwrap();
+        assert_eq!(next_step_item.is_finished(), true);
+        assert_eq!(surreal_tables.surreal_coverings.len(), 0);
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn unfinish_item_restores_the_item_and_its_coverings() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        let item_to_be_covered = NewItemBuilder::default()
+            .summary("Item to be covered")
+            .item_type(ItemType::Action)
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(item_to_be_covered))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let item_to_be_covered = surreal_tables.surreal_items.first().unwrap();
+        sender
+            .send(DataLayerCommands::CoverItemWithANewItem {
+                cover_this: item_to_be_covered.id.clone().expect("In DB"),
+                cover_with: NewItemBuilder::default()
+                    .summary("Covering item")
+                    .item_type(ItemType::Action)
+                    .build()
+                    .expect("Filled out required fields"),
+            })
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let covering_item = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "Covering item")
+            .unwrap();
+        sender
+            .send(DataLayerCommands::FinishItem(
+                covering_item.id.clone().expect("In DB"),
+            ))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let items = surreal_tables.make_items();
+        let active_items = items.filter_active_items();
+        assert_eq!(active_items.len(), 1);
+        assert!(surreal_tables.make_coverings(&active_items).is_empty());
+
+        sender
+            .send(DataLayerCommands::UnfinishItem(
+                covering_item.id.clone().expect("In DB"),
+            ))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let items = surreal_tables.make_items();
+        let active_items = items.filter_active_items();
+        assert_eq!(active_items.len(), 2);
+        let covering_item = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "Covering item")
+            .unwrap();
+        assert_eq!(covering_item.finished, None);
+        assert_eq!(surreal_tables.make_coverings(&active_items).len(), 1);
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn delete_item_also_removes_everything_that_references_it() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Parent".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let parent = surreal_tables.surreal_items.first().unwrap();
+        sender
+            .send(DataLayerCommands::ParentItemWithANewChildItem {
+                child: NewItem::new("Item to delete".into(), Utc::now()),
+                parent: parent.id.clone().expect("In DB"),
+                higher_priority_than_this: None,
+            })
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let delete_this = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "Item to delete")
+            .unwrap()
+            .id
+            .clone()
+            .expect("In DB");
+        sender
+            .send(DataLayerCommands::CoverItemWithANewItem {
+                cover_this: delete_this.clone(),
+                cover_with: NewItem::new("Covering item".into(), Utc::now()),
+            })
+            .await
+            .unwrap();
+        sender
+            .send(DataLayerCommands::CoverItemUntilAnExactDateTime(
+                delete_this.clone(),
+                Utc::now() + chrono::Duration::days(1),
+            ))
+            .await
+            .unwrap();
+        sender
+            .send(DataLayerCommands::AddProcessedText(
+                "Some processed text".into(),
+                delete_this.clone(),
+            ))
+            .await
+            .unwrap();
+
+        sender
+            .send(DataLayerCommands::DeleteItem(delete_this.clone()))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(surreal_tables.surreal_items.len(), 2);
+        assert!(surreal_tables
+            .surreal_items
+            .iter()
+            .all(|x| x.id.as_ref() != Some(&delete_this)));
+        let parent = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "Parent")
+            .unwrap();
+        assert!(parent.smaller_items_in_priority_order.is_empty());
+        assert!(surreal_tables.surreal_coverings.is_empty());
+        assert!(surreal_tables.surreal_coverings_until_date_time.is_empty());
+        let processed_text = DataLayerCommands::get_processed_text(&sender, delete_this)
+            .await
+            .unwrap();
+        assert!(processed_text.is_empty());
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn archive_finished_items_only_moves_items_finished_before_the_cutoff() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        for summary in ["Finished item", "Still active item"] {
+            sender
+                .send(DataLayerCommands::NewItem(NewItem::new(
+                    summary.into(),
+                    Utc::now(),
+                )))
+                .await
+                .unwrap();
+        }
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let finished_item = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "Finished item")
+            .unwrap();
+        sender
+            .send(DataLayerCommands::FinishItem(
+                finished_item.id.clone().expect("In DB"),
+            ))
+            .await
+            .unwrap();
+
+        sender
+            .send(DataLayerCommands::ArchiveFinishedItems {
+                finished_before: Utc::now() - chrono::Duration::days(1),
+            })
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(surreal_tables.surreal_items.len(), 2);
+
+        sender
+            .send(DataLayerCommands::ArchiveFinishedItems {
+                finished_before: Utc::now() + chrono::Duration::minutes(1),
+            })
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(surreal_tables.surreal_items.len(), 1);
+        assert_eq!(
+            surreal_tables.surreal_items.first().unwrap().summary,
+            "Still active item"
+        );
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn cover_item_with_a_new_proactive_next_step() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        let new_action = NewItemBuilder::default()
+            .summary("Item to be covered")
+            .item_type(ItemType::Action)
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(new_action))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+
+        assert_eq!(1, surreal_tables.surreal_items.len());
+        assert_eq!(0, surreal_tables.surreal_coverings.len()); //length of zero means nothing is covered
+        let item_to_cover = surreal_tables.surreal_items.first().unwrap();
+
+        let new_item = NewItemBuilder::default()
+            .summary("Covering item")
+            .responsibility(Responsibility::ProactiveActionToTake)
+            .item_type(ItemType::Action)
+            .build()
+            .unwrap();
+
+        sender
+            .send(DataLayerCommands::CoverItemWithANewItem {
+                cover_this: item_to_cover.get_id().as_ref().expect("In DB").clone(),
+                cover_with: new_item,
+            })
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+
+        assert_eq!(2, surreal_tables.surreal_items.len());
+        assert_eq!(1, surreal_tables.surreal_coverings.len()); //expect one item to be is covered
+        let covering = surreal_tables.surreal_coverings.first().unwrap();
+        let item_that_should_be_covered = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "Item to be covered")
+            .unwrap();
+        let item_that_should_cover = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "Covering item")
+            .unwrap();
+        assert_eq!(
+            item_that_should_be_covered.id.as_ref().unwrap(),
+            &covering.parent
+        );
+        assert_eq!(
+            item_that_should_cover.id.as_ref().unwrap(),
+            &covering.smaller
+        );
+        //TODO: Check Order & Responsibility that they are properly set
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn cover_item_until_an_exact_date_time() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        let new_action = NewItemBuilder::default()
+            .summary("Item to be covered")
+            .item_type(ItemType::Action)
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(new_action))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+
+        assert_eq!(1, surreal_tables.surreal_items.len());
+        assert!(surreal_tables.surreal_coverings_until_date_time.is_empty());
+
+        let cover_until: chrono::DateTime<Utc> = Utc::now();
+        sender
+            .send(DataLayerCommands::CoverItemUntilAnExactDateTime(
+                surreal_tables
+                    .surreal_items
+                    .into_iter()
+                    .next()
+                    .unwrap()
+                    .id
+                    .expect("In DB"),
+                cover_until.clone().into(),
+            ))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+
+        assert_eq!(1, surreal_tables.surreal_items.len());
+        assert_eq!(1, surreal_tables.surreal_coverings_until_date_time.len());
+        assert_eq!(
+            surreal_tables
+                .surreal_items
+                .first()
+                .unwrap()
+                .id
+                .as_ref()
+                .unwrap(),
+            &surreal_tables
+                .surreal_coverings_until_date_time
+                .first()
+                .unwrap()
+                .cover_this
+        );
+        assert_eq!(
+            cover_until,
+            surreal_tables
+                .surreal_coverings_until_date_time
+                .first()
+                .as_ref()
+                .unwrap()
+                .until
+                .clone()
+                .into()
+        );
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn parent_item_with_a_new_item() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        let new_action = NewItemBuilder::default()
+            .summary("Item that needs a parent")
+            .item_type(ItemType::Action)
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(new_action))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+
+        assert_eq!(1, surreal_tables.surreal_items.len());
+
+        sender
+            .send(DataLayerCommands::ParentNewItemWithAnExistingChildItem {
+                child: surreal_tables
+                    .surreal_items
+                    .into_iter()
+                    .next()
+                    .unwrap()
+                    .id
+                    .expect("In Db"),
+                parent_new_item: NewItemBuilder::default()
+                    .summary("Parent Item")
+                    .item_type(ItemType::Goal(HowMuchIsInMyControl::default()))
+                    .build()
+                    .unwrap(),
+            })
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+
+        assert_eq!(2, surreal_tables.surreal_items.len());
+        assert_eq!(
+            1,
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Parent Item")
+                .unwrap()
+                .smaller_items_in_priority_order
+                .len()
+        );
+        assert_eq!(
+            &SurrealOrderedSubItem::SubItem {
+                surreal_item_id: surreal_tables
+                    .surreal_items
+                    .iter()
+                    .find(|x| x.summary == "Item that needs a parent")
+                    .unwrap()
+                    .id
+                    .as_ref()
+                    .unwrap()
+                    .clone()
+            },
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Parent Item")
+                .unwrap()
+                .smaller_items_in_priority_order
+                .first()
+                .unwrap()
+        );
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn parent_item_with_an_existing_item_that_has_no_children() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        let item_that_needs_a_parent = NewItemBuilder::default()
+            .summary("Item that needs a parent")
+            .item_type(ItemType::Action)
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(item_that_needs_a_parent))
+            .await
+            .unwrap();
+
+        let parent_item = NewItemBuilder::default()
+            .summary("Parent Item")
+            .item_type(ItemType::Goal(HowMuchIsInMyControl::default()))
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(parent_item))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+
+        assert_eq!(2, surreal_tables.surreal_items.len());
+
+        DataLayerCommands::parent_item_with_existing_item(
+            &sender,
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Item that needs a parent")
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Parent Item")
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+
+        assert_eq!(2, surreal_tables.surreal_items.len());
+        assert_eq!(
+            1,
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Parent Item")
+                .unwrap()
+                .smaller_items_in_priority_order
+                .len()
+        );
+        assert_eq!(
+            &SurrealOrderedSubItem::SubItem {
+                surreal_item_id: surreal_tables
+                    .surreal_items
+                    .iter()
+                    .find(|x| x.summary == "Item that needs a parent")
+                    .unwrap()
+                    .id
+                    .as_ref()
+                    .unwrap()
+                    .clone()
+            },
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Parent Item")
+                .unwrap()
+                .smaller_items_in_priority_order
+                .first()
+                .unwrap()
+        );
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn parent_item_with_an_existing_item_that_has_children() {
+        // SETUP
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        let child_item = NewItemBuilder::default()
+            .summary("Child Item at the top of the list")
+            .item_type(ItemType::Action)
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(child_item))
+            .await
+            .unwrap();
+
+        let child_item = NewItemBuilder::default()
+            .summary("Child Item 2nd position")
+            .item_type(ItemType::Action)
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(child_item))
+            .await
+            .unwrap();
+
+        let child_item = NewItemBuilder::default()
+            .summary("Child Item 3rd position")
+            .item_type(ItemType::Action)
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(child_item))
+            .await
+            .unwrap();
+
+        let child_item = NewItemBuilder::default()
+            .summary("Child Item bottom position")
+            .item_type(ItemType::Action)
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(child_item))
+            .await
+            .unwrap();
+
+        let parent_item = NewItemBuilder::default()
+            .summary("Parent Item")
+            .item_type(ItemType::Goal(HowMuchIsInMyControl::default()))
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(parent_item))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+
+        assert_eq!(5, surreal_tables.surreal_items.len());
+
+        DataLayerCommands::parent_item_with_existing_item(
+            &sender,
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Child Item at the top of the list")
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Parent Item")
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        // TEST - The order of adding the items is meant to cause the higher_priority_than_this to be used
+
+        DataLayerCommands::parent_item_with_existing_item(
+            &sender,
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Child Item bottom position")
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Parent Item")
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        DataLayerCommands::parent_item_with_existing_item(
+            &sender,
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Child Item 2nd position")
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Parent Item")
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            Some(
+                surreal_tables
+                    .surreal_items
+                    .iter()
+                    .find(|x| x.summary == "Child Item bottom position")
+                    .unwrap()
+                    .get_id()
+                    .as_ref()
+                    .expect("In DB")
+                    .clone(),
+            ),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        DataLayerCommands::parent_item_with_existing_item(
+            &sender,
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Child Item 3rd position")
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Parent Item")
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            Some(
+                surreal_tables
+                    .surreal_items
+                    .iter()
+                    .find(|x| x.summary == "Child Item bottom position")
+                    .unwrap()
+                    .get_id()
+                    .as_ref()
+                    .expect("In DB")
+                    .clone(),
+            ),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+
+        assert_eq!(
+            vec![
+                SurrealOrderedSubItem::SubItem {
+                    surreal_item_id: surreal_tables
+                        .surreal_items
+                        .iter()
+                        .find(|x| x.summary == "Child Item at the top of the list")
+                        .unwrap()
+                        .id
+                        .as_ref()
+                        .unwrap()
+                        .clone()
+                },
+                SurrealOrderedSubItem::SubItem {
+                    surreal_item_id: surreal_tables
+                        .surreal_items
+                        .iter()
+                        .find(|x| x.summary == "Child Item 2nd position")
+                        .unwrap()
+                        .id
+                        .as_ref()
+                        .unwrap()
+                        .clone()
+                },
+                SurrealOrderedSubItem::SubItem {
+                    surreal_item_id: surreal_tables
+                        .surreal_items
+                        .iter()
+                        .find(|x| x.summary == "Child Item 3rd position")
+                        .unwrap()
+                        .id
+                        .as_ref()
+                        .unwrap()
+                        .clone()
+                },
+                SurrealOrderedSubItem::SubItem {
+                    surreal_item_id: surreal_tables
+                        .surreal_items
+                        .iter()
+                        .find(|x| x.summary == "Child Item bottom position")
+                        .unwrap()
+                        .id
+                        .as_ref()
+                        .unwrap()
+                        .clone()
+                },
+            ],
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Parent Item")
+                .unwrap()
+                .smaller_items_in_priority_order
+        );
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn change_order_of_children() {
+        // SETUP
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        let child_item = NewItemBuilder::default()
+            .summary("Child Item at the top of the list")
+            .item_type(ItemType::Action)
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(child_item))
+            .await
+            .unwrap();
+
+        let child_item = NewItemBuilder::default()
+            .summary("Child Item 2nd position")
+            .item_type(ItemType::Action)
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(child_item))
+            .await
+            .unwrap();
+
+        let child_item = NewItemBuilder::default()
+            .summary("Child Item 3rd position")
+            .item_type(ItemType::Action)
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(child_item))
+            .await
+            .unwrap();
+
+        let child_item = NewItemBuilder::default()
+            .summary("Child Item bottom position, then moved to above 2nd position")
+            .item_type(ItemType::Action)
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(child_item))
+            .await
+            .unwrap();
+
+        let parent_item = NewItemBuilder::default()
+            .summary("Parent Item")
+            .item_type(ItemType::Goal(HowMuchIsInMyControl::default()))
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(parent_item))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+
+        assert_eq!(5, surreal_tables.surreal_items.len());
+
+        DataLayerCommands::parent_item_with_existing_item(
+            &sender,
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Child Item at the top of the list")
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Parent Item")
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        DataLayerCommands::parent_item_with_existing_item(
+            &sender,
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| {
+                    x.summary == "Child Item bottom position, then moved to above 2nd position"
+                })
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Parent Item")
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        DataLayerCommands::parent_item_with_existing_item(
+            &sender,
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Child Item 2nd position")
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Parent Item")
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            Some(
+                surreal_tables
+                    .surreal_items
+                    .iter()
+                    .find(|x| {
+                        x.summary == "Child Item bottom position, then moved to above 2nd position"
+                    })
+                    .unwrap()
+                    .get_id()
+                    .as_ref()
+                    .expect("In DB")
+                    .clone(),
+            ),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        DataLayerCommands::parent_item_with_existing_item(
+            &sender,
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Child Item 3rd position")
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Parent Item")
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            Some(
+                surreal_tables
+                    .surreal_items
+                    .iter()
+                    .find(|x| {
+                        x.summary == "Child Item bottom position, then moved to above 2nd position"
+                    })
+                    .unwrap()
+                    .get_id()
+                    .as_ref()
+                    .expect("In DB")
+                    .clone(),
+            ),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        // TEST - Move the bottom item to the 2nd position
+        DataLayerCommands::parent_item_with_existing_item(
+            &sender,
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| {
+                    x.summary == "Child Item bottom position, then moved to above 2nd position"
+                })
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Parent Item")
+                .unwrap()
+                .get_id()
+                .as_ref()
+                .expect("In DB")
+                .clone(),
+            Some(
+                surreal_tables
+                    .surreal_items
+                    .iter()
+                    .find(|x| x.summary == "Child Item 2nd position")
+                    .unwrap()
+                    .get_id()
+                    .as_ref()
+                    .expect("In DB")
+                    .clone(),
+            ),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+
+        assert_eq!(
+            vec![
+                SurrealOrderedSubItem::SubItem {
+                    surreal_item_id: surreal_tables
+                        .surreal_items
+                        .iter()
+                        .find(|x| x.summary == "Child Item at the top of the list")
+                        .unwrap()
+                        .id
+                        .as_ref()
+                        .unwrap()
+                        .clone()
+                },
+                SurrealOrderedSubItem::SubItem {
+                    surreal_item_id: surreal_tables
+                        .surreal_items
+                        .iter()
+                        .find(|x| x.summary
+                            == "Child Item bottom position, then moved to above 2nd position")
+                        .unwrap()
+                        .id
+                        .as_ref()
+                        .unwrap()
+                        .clone()
+                },
+                SurrealOrderedSubItem::SubItem {
+                    surreal_item_id: surreal_tables
+                        .surreal_items
+                        .iter()
+                        .find(|x| x.summary == "Child Item 2nd position")
+                        .unwrap()
+                        .id
+                        .as_ref()
+                        .unwrap()
+                        .clone()
+                },
+                SurrealOrderedSubItem::SubItem {
+                    surreal_item_id: surreal_tables
+                        .surreal_items
+                        .iter()
+                        .find(|x| x.summary == "Child Item 3rd position")
+                        .unwrap()
+                        .id
+                        .as_ref()
+                        .unwrap()
+                        .clone()
+                },
+            ],
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == "Parent Item")
+                .unwrap()
+                .smaller_items_in_priority_order
+        );
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn parenting_or_covering_that_would_create_a_cycle_is_refused() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        let parent = NewItemBuilder::default()
+            .summary("Parent")
+            .item_type(ItemType::Goal(HowMuchIsInMyControl::default()))
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(parent))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let parent = surreal_tables.surreal_items.first().unwrap();
+        sender
+            .send(DataLayerCommands::ParentItemWithANewChildItem {
+                child: NewItemBuilder::default()
+                    .summary("Child")
+                    .item_type(ItemType::Action)
+                    .build()
+                    .expect("Filled out required fields"),
+                parent: parent.id.clone().expect("In DB"),
+                higher_priority_than_this: None,
+            })
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let parent = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "Parent")
+            .unwrap();
+        let child = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "Child")
+            .unwrap();
+        assert_eq!(
+            DataLayerCommands::parent_item_with_existing_item(
+                &sender,
+                parent.id.clone().expect("In DB"),
+                child.id.clone().expect("In DB"),
+                None
+            )
+            .await
+            .unwrap(),
+            Err(WouldCreateACycle {
+                cycle: "\"Parent\" ⟶ \"Child\" ⟶ \"Parent\"".into()
+            })
+        );
+        assert!(DataLayerCommands::cover_item_with_an_existing_item(
+            &sender,
+            child.id.clone().expect("In DB"),
+            parent.id.clone().expect("In DB"),
+        )
+        .await
+        .unwrap()
+        .is_err());
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let child = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "Child")
+            .unwrap();
+        assert!(child.smaller_items_in_priority_order.is_empty());
+        assert!(surreal_tables.surreal_coverings.is_empty());
+        assert!(surreal_tables.find_cycles().is_empty());
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn cached_tables_with_changes_applied_match_a_full_reload() {
+        let db = connect("mem://").await.unwrap();
+        db.use_ns("OnPurpose").use_db("Russ").await.unwrap();
+
+        let goal = super::new_item(
+            NewItemBuilder::default()
+                .summary("Goal")
+                .item_type(ItemType::Goal(HowMuchIsInMyControl::default()))
+                .build()
+                .expect("Filled out required fields"),
+            &db,
+        )
+        .await;
+        let goal_id = goal.id.clone().expect("In DB");
+        let mut cached = load_from_surrealdb_upgrade_if_needed(&db).await;
+
+        let mut changes = super::parent_item_with_a_new_child(
+            NewItem::new("Child".into(), Utc::now()),
+            goal_id.clone(),
+            None,
+            &db,
+        )
+        .await;
+        changes.extend(
+            super::cover_with_a_new_item(
+                goal_id.clone(),
+                NewItem::new("Covering".into(), Utc::now()),
+                &db,
+            )
+            .await,
+        );
+        changes.extend(
+            super::cover_item_until_an_exact_date_time(goal_id.clone(), Utc::now(), &db).await,
+        );
+        changes
+            .extend(super::update_item_summary(goal_id.clone(), "Renamed goal".into(), &db).await);
+        cached.apply_changes(changes);
+        assert_eq!(cached, load_from_surrealdb_upgrade_if_needed(&db).await);
+
+        let covering_item = cached
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "Covering")
+            .unwrap()
+            .id
+            .clone()
+            .expect("In DB");
+        let mut changes = super::finish_item(covering_item.clone(), &db).await;
+        changes.extend(super::delete_item(goal_id, &db).await);
+        cached.apply_changes(changes);
+        assert_eq!(cached, load_from_surrealdb_upgrade_if_needed(&db).await);
+        assert!(cached.surreal_coverings.is_empty());
+        assert!(cached.surreal_coverings_until_date_time.is_empty());
+    }
+
+    #[tokio::test]
+    async fn subscribers_are_told_about_changes_but_not_about_reads() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+
+        let mut data_changed = DataLayerCommands::subscribe_to_changes(&sender)
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert!(surreal_tables.surreal_items.is_empty());
+        assert!(!has_data_changed(&mut data_changed));
+
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "New item".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+        //Getting the raw data makes sure the previous command has been processed
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert!(has_data_changed(&mut data_changed));
+        assert!(!has_data_changed(&mut data_changed));
+
+        let item = surreal_tables.surreal_items.first().unwrap();
+        sender
+            .send(DataLayerCommands::AddProcessedText(
+                "Notes are not part of the raw data".into(),
+                item.id.clone().expect("In DB"),
+            ))
+            .await
+            .unwrap();
+        SurrealTables::new(&sender).await.unwrap();
+        assert!(!has_data_changed(&mut data_changed));
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    /// Run with `cargo test benchmark -- --ignored --nocapture` to compare the cached snapshot to a full reload
+    #[tokio::test]
+    #[ignore]
+    async fn benchmark_cached_snapshot_compared_to_full_reload() {
+        const ITEM_COUNT: usize = 1000;
+        const SNAPSHOT_COUNT: u32 = 100;
+
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, "mem://").await });
+        for i in 0..ITEM_COUNT {
+            sender
+                .send(DataLayerCommands::NewItem(NewItem::new(
+                    format!("Item {}", i),
+                    Utc::now(),
+                )))
+                .await
+                .unwrap();
+        }
+
+        let db = connect("mem://").await.unwrap();
+        db.use_ns("OnPurpose").use_db("Russ").await.unwrap();
+        for i in 0..ITEM_COUNT {
+            super::new_item(NewItem::new(format!("Item {}", i), Utc::now()), &db).await;
+        }
+
+        let start = std::time::Instant::now();
+        for _ in 0..SNAPSHOT_COUNT {
+            load_from_surrealdb_upgrade_if_needed(&db).await;
+        }
+        let full_reload = start.elapsed() / SNAPSHOT_COUNT;
+
+        //The first request loads the cache
+        SurrealTables::new(&sender).await.unwrap();
+        let start = std::time::Instant::now();
+        for _ in 0..SNAPSHOT_COUNT {
+            SurrealTables::new(&sender).await.unwrap();
+        }
+        let cached_snapshot = start.elapsed() / SNAPSHOT_COUNT;
+
+        println!(
+            "{} items, full reload: {:?}, cached snapshot: {:?}",
+            ITEM_COUNT, full_reload, cached_snapshot
+        );
+        assert!(cached_snapshot < full_reload);
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+}
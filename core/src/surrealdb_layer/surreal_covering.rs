@@ -1,17 +1,21 @@
+//! The coverings table, an item that needs to be done before another item can be worked on
+
 use serde::{Deserialize, Serialize};
 use surrealdb::{opt::RecordId, sql::Thing};
 use surrealdb_extra::table::Table;
 
 use crate::base_data::covering::Covering;
 
+/// smaller needs to be done before parent can be worked on
 #[derive(PartialEq, Eq, Table, Serialize, Deserialize, Clone, Debug)]
 #[table(name = "coverings")]
-pub(crate) struct SurrealCovering {
-    pub(crate) id: Option<Thing>,
+pub struct SurrealCovering {
+    /// None until it is saved
+    pub id: Option<Thing>,
     /// RecordId of the smaller item that is doing the covering
-    pub(crate) smaller: RecordId, //TODO: Rename to covering
+    pub smaller: RecordId, //TODO: Rename to covering
     /// RecordId of the larger item that is being covered
-    pub(crate) parent: RecordId, //TODO: Rename to being_covered
+    pub parent: RecordId, //TODO: Rename to being_covered
 }
 
 impl<'a> From<Covering<'a>> for SurrealCovering {
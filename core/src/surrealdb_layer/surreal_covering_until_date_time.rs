@@ -1,3 +1,5 @@
+//! The coverings_until_datetime table, items that are snoozed
+
 use serde::{Deserialize, Serialize};
 use surrealdb::{
     opt::RecordId,
@@ -9,8 +11,11 @@ use surrealdb_extra::table::Table;
 /// an exact date_time
 #[derive(PartialEq, Eq, Table, Serialize, Deserialize, Clone, Debug)]
 #[table(name = "coverings_until_datetime")]
-pub(crate) struct SurrealCoveringUntilDatetime {
-    pub(crate) id: Option<Thing>,
-    pub(crate) cover_this: RecordId,
-    pub(crate) until: Datetime,
+pub struct SurrealCoveringUntilDatetime {
+    /// None until it is saved
+    pub id: Option<Thing>,
+    /// The item that is snoozed
+    pub cover_this: RecordId,
+    /// When the snooze ends
+    pub until: Datetime,
 }
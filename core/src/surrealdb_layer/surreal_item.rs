@@ -0,0 +1,640 @@
+//! The item table, where everything to do or to keep in mind is stored
+
+use std::cmp::Ordering;
+
+use chrono::Utc;
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use surrealdb::{
+    opt::RecordId,
+    sql::{Datetime, Duration, Thing},
+};
+use surrealdb_extra::table::Table;
+
+use crate::{base_data::item::Item, new_item::NewItem};
+
+use super::surreal_required_circumstance::SurrealRequiredCircumstance;
+
+//derive Builder is only for tests, I tried adding it just for cfg_attr(test... but that
+//gave me false errors in the editor (rust-analyzer) so I am just going to try including
+//it always to see if that addresses these phantom errors. Nov2023.
+/// A record in the item table. Items that were saved before an optional field existed load it as None.
+#[derive(PartialEq, Eq, Table, Serialize, Deserialize, Clone, Debug, Builder)]
+#[builder(setter(into))]
+#[table(name = "item")] //TODO: This should be renamed items
+pub struct SurrealItem {
+    /// None until the item is saved
+    pub id: Option<Thing>,
+    /// A short description of the item
+    pub summary: String,
+
+    /// Some once the item is finished
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub finished: Option<Datetime>,
+
+    /// Whether this is something to do or something to respond to
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub responsibility: Responsibility,
+
+    /// Who the item is for, empty to take it from the larger items
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub facing: Vec<Facing>,
+
+    /// For example an action, goal or motivation
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub item_type: ItemType,
+
+    /// Where notes about the item are kept
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub notes_location: NotesLocation,
+
+    /// Whether this is ongoing or something that is finished
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub permanence: Permanence,
+
+    /// How often to think about the item
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub staging: Staging,
+
+    /// When this must be done by, None for most items
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub due_date: Option<DueDate>,
+
+    /// Set once this is handed off and the next step is someone else's, cleared when they respond
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub waiting_for: Option<WaitingFor>,
+
+    /// Set for meetings and other events that were imported from a calendar, boxed because most items are not events
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub calendar_event: Option<Box<CalendarEvent>>,
+
+    /// Set when the work happens on a branch of a local git repository
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub git_branch: Option<Box<GitBranch>>,
+
+    /// This is meant to be a list of the smaller or subitems of this item that further this item in an ordered list meaning that they should be done in order
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub smaller_items_in_priority_order: Vec<SurrealOrderedSubItem>,
+
+    /// When the item was first saved
+    #[cfg_attr(
+        any(test, feature = "test-builders"),
+        builder(default = "chrono::Utc::now().into()")
+    )]
+    pub created: Datetime,
+    //Touched and worked_on would be joined from separate tables so this does not need to be edited a lot for those purposes
+}
+
+impl From<SurrealItem> for Option<Thing> {
+    fn from(value: SurrealItem) -> Self {
+        value.id
+    }
+}
+
+impl SurrealItem {
+    /// The record to save for new_item, the id is None until it is saved
+    pub fn new(
+        new_item: NewItem,
+        smaller_items_in_priority_order: Vec<SurrealOrderedSubItem>,
+    ) -> Self {
+        SurrealItem {
+            id: None,
+            summary: new_item.summary,
+            finished: new_item.finished,
+            responsibility: new_item.responsibility,
+            facing: new_item.facing,
+            item_type: new_item.item_type,
+            smaller_items_in_priority_order,
+            notes_location: new_item.notes_location,
+            permanence: new_item.permanence,
+            staging: new_item.staging,
+            due_date: new_item.due_date,
+            waiting_for: None,
+            calendar_event: new_item.calendar_event,
+            git_branch: None,
+            created: new_item.created.into(),
+        }
+    }
+
+    /// Returns true if remove_this was in the list
+    pub fn remove_smaller_item(&mut self, remove_this: &RecordId) -> bool {
+        let before = self.smaller_items_in_priority_order.len();
+        self.smaller_items_in_priority_order.retain(|x| match x {
+            SurrealOrderedSubItem::SubItem { surreal_item_id } => surreal_item_id != remove_this,
+            SurrealOrderedSubItem::Split { .. } => todo!("I need to understand more about how split will be used before I can implement this"),
+        });
+        self.smaller_items_in_priority_order.len() != before
+    }
+
+    /// Adds child just before higher_priority_than_this, or at the end if that is None. If child is already in the
+    /// list it is moved.
+    pub fn add_smaller_item(
+        &mut self,
+        child: RecordId,
+        higher_priority_than_this: Option<RecordId>,
+    ) {
+        self.remove_smaller_item(&child);
+        if let Some(higher_priority_than_this) = higher_priority_than_this {
+            let index_of_higher_priority = self.smaller_items_in_priority_order
+                .iter()
+                .position(|x| match x {
+                    //Note that position() is short-circuiting. If there are multiple matches it could be argued that I should panic or assert but
+                    //I am just matching the first one and then I just keep going. Because I am still figuring out the design and this is 
+                    //more in the vein of hardening work I think this is fine but feel free to revisit this.
+                    SurrealOrderedSubItem::SubItem { surreal_item_id } => {
+                        surreal_item_id == &higher_priority_than_this
+                    }
+                    SurrealOrderedSubItem::Split { .. } => todo!("I need to understand more about how split will be used before I can implement this"),
+                })
+                .expect("Should already be in the list");
+            self.smaller_items_in_priority_order.insert(
+                index_of_higher_priority,
+                SurrealOrderedSubItem::SubItem {
+                    surreal_item_id: child,
+                },
+            );
+        } else {
+            self.smaller_items_in_priority_order
+                .push(SurrealOrderedSubItem::SubItem {
+                    surreal_item_id: child,
+                });
+        }
+    }
+
+    /// An Item with the required circumstances from requirements that refer to this item
+    pub fn make_item<'a>(&'a self, requirements: &'a [SurrealRequiredCircumstance]) -> Item<'a> {
+        let my_requirements = requirements
+            .iter()
+            .filter(|x| {
+                &x.required_for
+                    == self
+                        .id
+                        .as_ref()
+                        .expect("Item should already be in the database and have an id")
+            })
+            .collect();
+
+        Item::new(self, my_requirements)
+    }
+}
+
+/// Ordered by when it is due and then hard before soft so the earliest is the one that matters most
+#[derive(PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Clone, Debug)]
+pub struct DueDate {
+    /// When it is due
+    pub due: Datetime,
+    /// What happens if it is missed
+    pub strictness: DueDateStrictness,
+}
+
+/// How firm a due date is
+#[derive(PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum DueDateStrictness {
+    /// Missing it means letting someone down or a real consequence, for example a commitment to another person
+    Hard,
+    /// A target that can slip if it has to
+    Soft,
+}
+
+/// Waiting on a person or group to respond, the item itself is what they are being waited on for
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub struct WaitingFor {
+    /// A PersonOrGroup item
+    pub who: RecordId,
+    /// When they were asked
+    pub since: Datetime,
+    /// When to check in with them if they have not responded yet
+    pub follow_up_after: Datetime,
+}
+
+/// An event from a calendar, the item is staged to enter the list when the event starts
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub struct CalendarEvent {
+    /// The UID from the calendar so importing the same event again updates this item
+    pub uid: String,
+    /// When the event starts
+    pub start: Datetime,
+    /// When the event ends
+    pub end: Datetime,
+    /// Whether I am going
+    pub attendance: Attendance,
+}
+
+/// Whether I am going to an event
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub enum Attendance {
+    /// Not answered yet
+    #[default]
+    NotDecided,
+    /// Going
+    Attending,
+    /// Not going
+    NotAttending,
+}
+
+/// A branch in a git repository on this computer
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub struct GitBranch {
+    /// The directory of the repository
+    pub repository: String,
+    /// The name of the branch
+    pub branch: String,
+}
+
+/// Who an item is for
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub enum Facing {
+    /// For someone else
+    Others {
+        /// How well I know what they want
+        how_well_defined: HowWellDefined,
+        /// A PersonOrGroup item
+        who: RecordId,
+    },
+    /// For me, with how well I know what I want
+    Myself(HowWellDefined),
+    /// Only matters because of the larger items it is part of
+    InternalOrSmaller,
+}
+
+/// How well the outcome is understood
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug, Default)]
+pub enum HowWellDefined {
+    /// Not answered yet
+    #[default]
+    NotSet,
+    /// It is clear what done looks like
+    WellDefined,
+    /// Mostly clear
+    RoughlyDefined,
+    /// Only a vague idea
+    LooselyDefined,
+}
+
+/// What kind of item this is
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug, Default)]
+pub enum ItemType {
+    /// Not answered yet
+    #[default]
+    Undeclared,
+    /// Something to do
+    Action,
+    /// An outcome to reach, with how much it is in my control
+    Goal(HowMuchIsInMyControl),
+    /// Something to think about
+    IdeaOrThought,
+    /// A reason for doing larger things
+    Motivation,
+    /// Someone that items can be for or waiting on
+    PersonOrGroup,
+}
+
+/// How much reaching a goal is up to me
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug, Default)]
+pub enum HowMuchIsInMyControl {
+    /// Not answered yet
+    #[default]
+    NotSet,
+    /// Mostly up to me
+    MostlyInMyControl,
+    /// Partly up to me
+    PartiallyInMyControl,
+    /// Mostly up to others or to chance
+    LargelyOutOfMyControl,
+}
+
+/// ItemType as it was saved before Simple was removed
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug, Default)]
+pub enum ItemTypeOldVersion {
+    /// Not answered yet
+    #[default]
+    Undeclared,
+    /// Loaded as Action
+    Simple, //TODO: Remove this and just use Action
+    /// Something to do
+    Action,
+    /// An outcome to reach, with how much it is in my control
+    Goal(HowMuchIsInMyControl),
+    /// Something to think about
+    IdeaOrThought,
+    /// A reason for doing larger things
+    Motivation,
+    /// Someone that items can be for or waiting on
+    PersonOrGroup,
+}
+
+/// What kind of goal this is
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug, Default)]
+pub enum GoalType {
+    /// Not answered yet
+    #[default]
+    NotSpecified,
+    /// Something hoped for that may never be done
+    AspirationalHope,
+    /// Something that is clearly done or not
+    TangibleMilestone,
+}
+
+/// Whether this is something to do or something to respond to
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug, Default)]
+pub enum Responsibility {
+    /// Something to take action on
+    #[default]
+    ProactiveActionToTake,
+    /// Something to be ready to respond to
+    ReactiveBeAvailableToAct,
+    /// Waiting on someone, use SurrealItem::waiting_for instead
+    WaitingFor, //TODO: This should not exist, SurrealItem::waiting_for is now how to track who is being waited on and when to follow up
+    /// Something to know about without acting on it
+    TrackingToBeAwareOf,
+}
+
+/// Whether this is ongoing or something that is finished
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug, Default)]
+pub enum Permanence {
+    /// Ongoing, kept up rather than finished
+    Maintenance,
+    /// Finished once it is done
+    Project,
+    /// Not answered yet
+    #[default]
+    NotSet,
+}
+
+/// When an item enters the list
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub enum EnterListReason {
+    /// At this time
+    DateTime(Datetime),
+    /// When it is the highest priority item that is not covered, once earliest has passed
+    HighestUncovered {
+        /// Not before this
+        earliest: Datetime,
+        /// When to ask again whether this is still the right staging
+        review_after: Datetime,
+    },
+}
+
+/// How often to think about the item
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug, Default)]
+pub enum Staging {
+    /// Not answered yet
+    #[default]
+    NotSet,
+    /// Kept in mind all the time
+    MentallyResident {
+        /// When it enters the list
+        enter_list: EnterListReason,
+        /// How long to spend on it before moving on
+        lap: Duration,
+    },
+    /// Thought about often
+    OnDeck {
+        /// When it enters the list
+        enter_list: EnterListReason,
+        /// How long to spend on it before moving on
+        lap: Duration,
+    },
+    /// Will be thought about later
+    Planned,
+    /// Being considered whether to do at all
+    ThinkingAbout,
+    /// Let go of for now
+    Released,
+}
+
+impl PartialOrd for Staging {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Staging {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match self {
+            Staging::NotSet => match other {
+                Staging::NotSet => Ordering::Equal,
+                _ => Ordering::Less,
+            },
+            Staging::MentallyResident { .. } => match other {
+                Staging::NotSet => Ordering::Greater,
+                Staging::MentallyResident { .. } => Ordering::Equal,
+                _ => Ordering::Less,
+            },
+            Staging::OnDeck { .. } => match other {
+                Staging::NotSet | Staging::MentallyResident { .. } => Ordering::Greater,
+                Staging::OnDeck { .. } => Ordering::Equal,
+                _ => Ordering::Less,
+            },
+            Staging::Planned => match other {
+                Staging::Released | Staging::ThinkingAbout => Ordering::Less,
+                Staging::Planned => Ordering::Equal,
+                _ => Ordering::Greater,
+            },
+            Staging::ThinkingAbout => match other {
+                Staging::Released => Ordering::Less,
+                Staging::ThinkingAbout => Ordering::Equal,
+                _ => Ordering::Greater,
+            },
+            Staging::Released => match other {
+                Staging::Released => Ordering::Equal,
+                _ => Ordering::Greater,
+            },
+        }
+    }
+}
+
+/// An entry in SurrealItem::smaller_items_in_priority_order
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub enum SurrealOrderedSubItem {
+    /// A single smaller item
+    SubItem {
+        /// The id of the smaller item
+        surreal_item_id: Thing,
+    },
+    /// Several items that share the same priority, not used yet
+    Split {
+        /// How the priority is shared
+        shared_priority: Vec<SurrealPriorityGoal>,
+    },
+}
+
+//Each of these variants should be containing data but I don't want the data layer to get too far ahead of the prototype UI
+//so I want to wait until I can try it out before working out these details so just this for now.
+/// How a split priority is shared, not used yet
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub enum SurrealPriorityGoal {
+    /// By the number of times each is worked on
+    AbsoluteInvocationCount,
+    /// By the total amount of time each is worked on
+    AbsoluteAmountOfTime,
+    /// By the percentage of time each is worked on
+    RelativePercentageOfTime,
+}
+
+/// Where notes about an item are kept
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug, Default)]
+pub enum NotesLocation {
+    /// There are no notes
+    #[default]
+    None,
+    /// A link to a OneNote page
+    OneNoteLink(String),
+    /// A link to a web page
+    WebLink(String),
+}
+
+/// EnterListReason as it was saved before HighestUncovered had earliest
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub enum EnterListReasonOldVersion {
+    /// At this time
+    DateTime(Datetime),
+    /// When it is the highest priority item that is not covered
+    HighestUncovered {
+        /// When to ask again whether this is still the right staging
+        review_after: Datetime,
+    }, //TODO: add earliest DateTime
+}
+
+/// Staging as it was saved before HighestUncovered had earliest
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug, Default)]
+pub enum StagingOldVersion {
+    /// Not answered yet
+    #[default]
+    NotSet,
+    /// Kept in mind all the time
+    MentallyResident {
+        /// When it enters the list
+        enter_list: EnterListReasonOldVersion,
+        /// How long to spend on it before moving on
+        lap: Duration,
+    },
+    /// Thought about often
+    OnDeck {
+        /// When it enters the list
+        enter_list: EnterListReasonOldVersion,
+        /// How long to spend on it before moving on
+        lap: Duration,
+    },
+    /// Will be thought about later
+    Planned,
+    /// Being considered whether to do at all
+    ThinkingAbout,
+    /// Let go of for now
+    Released,
+}
+
+//derive Builder is only for tests, I tried adding it just for cfg_attr(test... but that
+//gave me false errors in the editor (rust-analyzer) so I am just going to try including
+//it always to see if that addresses these phantom errors. Nov2023.
+/// SurrealItem as it was saved before the staging had earliest, it is converted with into
+#[derive(PartialEq, Eq, Table, Serialize, Deserialize, Clone, Debug, Builder)]
+#[builder(setter(into))]
+#[table(name = "item")] //TODO: This should be renamed items
+pub struct SurrealItemOldVersion {
+    /// None until the item is saved
+    pub id: Option<Thing>,
+    /// A short description of the item
+    pub summary: String,
+
+    /// Some once the item is finished
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub finished: Option<Datetime>,
+
+    /// Whether this is something to do or something to respond to
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub responsibility: Responsibility,
+
+    /// Who the item is for, empty to take it from the larger items
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub facing: Vec<Facing>,
+
+    /// For example an action, goal or motivation
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub item_type: ItemType,
+
+    /// Where notes about the item are kept
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub notes_location: NotesLocation,
+
+    /// Whether this is ongoing or something that is finished
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub permanence: Permanence,
+
+    /// How often to think about the item
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub staging: StagingOldVersion,
+
+    /// This is meant to be a list of the smaller or subitems of this item that further this item in an ordered list meaning that they should be done in order
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub smaller_items_in_priority_order: Vec<SurrealOrderedSubItem>,
+
+    /// When the item was first saved
+    #[cfg_attr(
+        any(test, feature = "test-builders"),
+        builder(default = "chrono::Utc::now().into()")
+    )]
+    pub created: Datetime,
+    //Touched and worked_on would be joined from separate tables so this does not need to be edited a lot for those purposes
+}
+
+impl From<SurrealItemOldVersion> for SurrealItem {
+    fn from(value: SurrealItemOldVersion) -> Self {
+        SurrealItem {
+            id: value.id,
+            summary: value.summary,
+            finished: value.finished,
+            responsibility: value.responsibility,
+            item_type: value.item_type,
+            notes_location: value.notes_location,
+            permanence: value.permanence,
+            staging: value.staging.into(),
+            due_date: None,
+            waiting_for: None,
+            calendar_event: None,
+            git_branch: None,
+            smaller_items_in_priority_order: value.smaller_items_in_priority_order,
+            created: value.created,
+            facing: value.facing,
+        }
+    }
+}
+
+impl From<StagingOldVersion> for Staging {
+    fn from(value: StagingOldVersion) -> Self {
+        match value {
+            StagingOldVersion::NotSet => Staging::NotSet,
+            StagingOldVersion::MentallyResident { enter_list, lap } => Staging::MentallyResident {
+                enter_list: enter_list.into(),
+                lap,
+            },
+            StagingOldVersion::OnDeck { enter_list, lap } => Staging::OnDeck {
+                enter_list: enter_list.into(),
+                lap,
+            },
+            StagingOldVersion::Planned => Staging::Planned,
+            StagingOldVersion::ThinkingAbout => Staging::ThinkingAbout,
+            StagingOldVersion::Released => Staging::Released,
+        }
+    }
+}
+
+impl From<EnterListReasonOldVersion> for EnterListReason {
+    fn from(value: EnterListReasonOldVersion) -> Self {
+        match value {
+            EnterListReasonOldVersion::DateTime(datetime) => EnterListReason::DateTime(datetime),
+            EnterListReasonOldVersion::HighestUncovered { review_after } => {
+                EnterListReason::HighestUncovered {
+                    earliest: Utc::now().into(),
+                    review_after,
+                }
+            }
+        }
+    }
+}
+
+impl From<Datetime> for EnterListReason {
+    fn from(value: Datetime) -> Self {
+        EnterListReason::DateTime(value)
+    }
+}
@@ -0,0 +1,35 @@
+//! The item_archive table, finished items that are no longer loaded
+
+use serde::{Deserialize, Serialize};
+use surrealdb::{
+    opt::RecordId,
+    sql::{Datetime, Thing},
+};
+use surrealdb_extra::table::Table;
+
+use super::{
+    surreal_covering::SurrealCovering,
+    surreal_covering_until_date_time::SurrealCoveringUntilDatetime, surreal_item::SurrealItem,
+    surreal_required_circumstance::SurrealRequiredCircumstance,
+};
+
+/// Finished items are moved here, along with the records that reference them, so they are no longer loaded every time
+/// the item table is read. Everything needed to restore the item is kept together in one record.
+#[derive(PartialEq, Eq, Table, Serialize, Deserialize, Clone, Debug)]
+#[table(name = "item_archive")]
+pub struct SurrealItemArchive {
+    /// None until it is saved
+    pub id: Option<Thing>,
+    /// The finished item
+    pub item: SurrealItem,
+    /// Coverings that referenced the item
+    pub coverings: Vec<SurrealCovering>,
+    /// Snoozes of the item
+    pub coverings_until_date_time: Vec<SurrealCoveringUntilDatetime>,
+    /// Circumstances required by the item
+    pub required_circumstances: Vec<SurrealRequiredCircumstance>,
+    /// Items that had this item in their smaller_items_in_priority_order list
+    pub smaller_item_of: Vec<RecordId>,
+    /// When the item was archived
+    pub archived: Datetime,
+}
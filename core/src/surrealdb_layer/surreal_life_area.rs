@@ -1,10 +1,15 @@
+//! The life_areas table
+
 use serde::{Deserialize, Serialize};
 use surrealdb::sql::Thing;
 use surrealdb_extra::table::Table;
 
+/// An area of life, like health or work
 #[derive(PartialEq, Eq, Table, Serialize, Deserialize, Clone, Debug)]
 #[table(name = "life_areas")] //TODO: This should be adjusted to support change history tracking
-pub(crate) struct SurrealLifeArea {
-    pub(crate) id: Option<Thing>,
-    pub(crate) summary: String,
+pub struct SurrealLifeArea {
+    /// None until it is saved
+    pub id: Option<Thing>,
+    /// The name of the life area
+    pub summary: String,
 }
@@ -0,0 +1,22 @@
+//! The processed_text table, text written about an item
+
+use serde::{Deserialize, Serialize};
+use surrealdb::{
+    opt::RecordId,
+    sql::{Datetime, Thing},
+};
+use surrealdb_extra::table::Table;
+
+/// Text written about an item
+#[derive(PartialEq, Eq, Table, Serialize, Deserialize, Clone, Debug)]
+#[table(name = "processed_text")]
+pub struct SurrealProcessedText {
+    /// None until it is saved
+    pub id: Option<Thing>,
+    /// What was written
+    pub text: String,
+    /// When it was written
+    pub when_written: Datetime,
+    /// The item it was written about
+    pub for_item: RecordId,
+}
@@ -0,0 +1,27 @@
+//! The required_circumstances table, what has to be true before an item can be worked on
+
+use serde::{Deserialize, Serialize};
+use surrealdb::{opt::RecordId, sql::Thing};
+use surrealdb_extra::table::Table;
+
+/// circumstance_type has to be true before required_for can be worked on
+#[derive(PartialEq, Eq, Table, Serialize, Deserialize, Clone, Debug)]
+#[table(name = "required_circumstances")]
+pub struct SurrealRequiredCircumstance {
+    //TODO: This should be renamed to SurrealRequirement
+    /// None until it is saved
+    pub id: Option<Thing>,
+    /// The item that requires the circumstance
+    pub required_for: RecordId,
+    /// What is required
+    pub circumstance_type: CircumstanceType,
+}
+
+/// A circumstance that an item can require
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub enum CircumstanceType {
+    /// Any day except Sunday
+    NotSunday,
+    /// While focused on one thing without interruptions
+    DuringFocusTime, //TODO: I should add a new type for SurrealRequiredMentalState and this should be part of the PreferredOrRequiredMood
+}
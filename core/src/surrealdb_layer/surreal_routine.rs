@@ -0,0 +1,17 @@
+//! The routines table
+
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+use surrealdb_extra::table::Table;
+
+/// A routine that items can be part of
+#[derive(PartialEq, Eq, Table, Serialize, Deserialize, Clone, Debug)]
+#[table(name = "routines")]
+pub struct SurrealRoutine {
+    /// None until it is saved
+    pub id: Option<Thing>,
+    /// The name of the routine
+    pub summary: String,
+    /// What the routine is part of
+    pub parent: Thing,
+}
@@ -0,0 +1,521 @@
+//! Every table loaded into memory, along with the changes that commands make to them
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb::{opt::RecordId, sql::Thing};
+use tokio::sync::mpsc::Sender;
+
+#[cfg(any(test, feature = "test-builders"))]
+use derive_builder::Builder;
+
+use crate::base_data::{
+    covering::Covering,
+    covering_until_date_time::CoveringUntilDateTime,
+    item::{Item, ItemVecExtensions},
+    life_area::LifeArea,
+    routine::Routine,
+};
+
+use super::{
+    surreal_covering::SurrealCovering,
+    surreal_covering_until_date_time::SurrealCoveringUntilDatetime,
+    surreal_item::{SurrealItem, SurrealOrderedSubItem},
+    surreal_life_area::SurrealLifeArea,
+    surreal_required_circumstance::SurrealRequiredCircumstance,
+    surreal_routine::SurrealRoutine,
+    DataLayerCommands,
+};
+
+/// Every table in the database, as it is stored
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(
+    any(test, feature = "test-builders"),
+    derive(Builder),
+    builder(setter(into))
+)]
+pub struct SurrealTables {
+    /// The item table
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub surreal_items: Vec<SurrealItem>,
+
+    /// The coverings table
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub surreal_coverings: Vec<SurrealCovering>,
+
+    /// The required_circumstances table
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub surreal_required_circumstances: Vec<SurrealRequiredCircumstance>,
+
+    /// The coverings_until_datetime table
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub surreal_coverings_until_date_time: Vec<SurrealCoveringUntilDatetime>,
+
+    /// The life_areas table
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub surreal_life_areas: Vec<SurrealLifeArea>,
+
+    /// The routines table
+    #[cfg_attr(any(test, feature = "test-builders"), builder(default))]
+    pub surreal_routines: Vec<SurrealRoutine>,
+}
+
+impl SurrealTables {
+    /// Every record from the database, sent back by the data layer from its cache
+    pub async fn new(
+        sender: &Sender<DataLayerCommands>,
+    ) -> Result<Self, tokio::sync::oneshot::error::RecvError> {
+        DataLayerCommands::get_raw_data(sender).await
+    }
+
+    /// An Item for each record in the item table
+    pub fn make_items(&self) -> Vec<Item<'_>> {
+        self.surreal_items
+            .iter()
+            .map(|x| x.make_item(&self.surreal_required_circumstances))
+            .collect()
+    }
+
+    /// A Covering for each record in the coverings table, items must include both the smaller and the parent
+    pub fn make_coverings<'a>(&'a self, items: &'a [&'a Item<'a>]) -> Vec<Covering<'a>> {
+        self.surreal_coverings
+            .iter()
+            .filter_map(|x| {
+                //Items that are not found should just be filtered out. The main scenario for this is to filter out covering for items that are finished
+                let smaller = items.lookup_from_record_id(&x.smaller)?;
+                let parent = items.lookup_from_record_id(&x.parent)?;
+                Some(Covering {
+                    smaller,
+                    parent,
+                    _surreal_covering: x,
+                })
+            })
+            .collect()
+    }
+
+    /// A CoveringUntilDateTime for each record in the coverings_until_datetime table, items must include the covered item
+    pub fn make_coverings_until_date_time<'a>(
+        &'a self,
+        items: &'a [&'a Item<'a>],
+    ) -> Vec<CoveringUntilDateTime<'a>> {
+        self.surreal_coverings_until_date_time
+            .iter()
+            .filter_map(|x| {
+                let cover_this = items.lookup_from_record_id(&x.cover_this)?;
+                let until_utc: DateTime<Utc> = x.until.clone().into();
+                Some(CoveringUntilDateTime {
+                    cover_this,
+                    until: until_utc.into(),
+                })
+            })
+            .collect()
+    }
+
+    /// A LifeArea for each record in the life_areas table
+    pub fn make_life_areas(&self) -> Vec<LifeArea<'_>> {
+        self.surreal_life_areas.iter().map(LifeArea::new).collect()
+    }
+
+    /// A Routine for each record in the routines table
+    pub fn make_routines(&self) -> Vec<Routine<'_>> {
+        self.surreal_routines.iter().map(Routine::new).collect()
+    }
+}
+
+/// A record that a data layer command wrote or removed. This is used to keep a cached copy of SurrealTables up to date
+/// without reloading every table.
+#[derive(Debug, Clone)]
+pub enum SurrealTablesChange {
+    /// The item was added or updated
+    SavedItem(SurrealItem),
+    /// The item was removed
+    RemovedItem(RecordId),
+    /// The covering was added or updated
+    SavedCovering(SurrealCovering),
+    /// The covering was removed
+    RemovedCovering(RecordId),
+    /// The covering until date time was added or updated
+    SavedCoveringUntilDatetime(SurrealCoveringUntilDatetime),
+    /// The covering until date time was removed
+    RemovedCoveringUntilDatetime(RecordId),
+    /// The required circumstance was removed
+    RemovedRequiredCircumstance(RecordId),
+}
+
+impl SurrealTables {
+    /// Updates the tables with the changes, in order
+    pub fn apply_changes(&mut self, changes: Vec<SurrealTablesChange>) {
+        for change in changes.into_iter() {
+            match change {
+                SurrealTablesChange::SavedItem(item) => {
+                    save_record(&mut self.surreal_items, item, |x| &x.id)
+                }
+                SurrealTablesChange::RemovedItem(record_id) => {
+                    remove_record(&mut self.surreal_items, &record_id, |x| &x.id)
+                }
+                SurrealTablesChange::SavedCovering(covering) => {
+                    save_record(&mut self.surreal_coverings, covering, |x| &x.id)
+                }
+                SurrealTablesChange::RemovedCovering(record_id) => {
+                    remove_record(&mut self.surreal_coverings, &record_id, |x| &x.id)
+                }
+                SurrealTablesChange::SavedCoveringUntilDatetime(covering_until_date_time) => {
+                    save_record(
+                        &mut self.surreal_coverings_until_date_time,
+                        covering_until_date_time,
+                        |x| &x.id,
+                    )
+                }
+                SurrealTablesChange::RemovedCoveringUntilDatetime(record_id) => remove_record(
+                    &mut self.surreal_coverings_until_date_time,
+                    &record_id,
+                    |x| &x.id,
+                ),
+                SurrealTablesChange::RemovedRequiredCircumstance(record_id) => {
+                    remove_record(&mut self.surreal_required_circumstances, &record_id, |x| {
+                        &x.id
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// New records are inserted in id order because that is the order that get_all returns them in
+fn save_record<T>(records: &mut Vec<T>, record: T, get_id: impl Fn(&T) -> &Option<Thing>) {
+    match records.iter().position(|x| get_id(x) == get_id(&record)) {
+        Some(index) => records[index] = record,
+        None => {
+            let index = records.partition_point(|x| get_id(x) < get_id(&record));
+            records.insert(index, record);
+        }
+    }
+}
+
+fn remove_record<T>(
+    records: &mut Vec<T>,
+    record_id: &RecordId,
+    get_id: impl Fn(&T) -> &Option<Thing>,
+) {
+    records.retain(|x| get_id(x).as_ref() != Some(record_id));
+}
+
+/// A link from a smaller item to a larger item. Links come either from a covering or from the larger item's
+/// smaller_items_in_priority_order list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SurrealLink<'s> {
+    /// The smaller item is covered by the larger item
+    Covering(&'s SurrealCovering),
+    /// The smaller item is in the larger item's smaller_items_in_priority_order
+    SmallerItemInPriorityOrder {
+        /// The item with the list
+        larger: &'s SurrealItem,
+        /// The item in the list
+        smaller: &'s RecordId,
+    },
+}
+
+impl<'s> SurrealLink<'s> {
+    /// The item that is smaller
+    pub fn get_smaller(&self) -> &'s RecordId {
+        match self {
+            SurrealLink::Covering(covering) => &covering.smaller,
+            SurrealLink::SmallerItemInPriorityOrder { smaller, .. } => smaller,
+        }
+    }
+
+    /// The item that is larger
+    pub fn get_larger(&self) -> &'s RecordId {
+        match self {
+            SurrealLink::Covering(covering) => &covering.parent,
+            SurrealLink::SmallerItemInPriorityOrder { larger, .. } => {
+                larger.id.as_ref().expect("Came from the DB")
+            }
+        }
+    }
+}
+
+impl SurrealTables {
+    /// Only links where both the smaller and the larger item are in the item table are included
+    pub fn make_links(&self) -> Vec<SurrealLink<'_>> {
+        let item_ids = self
+            .surreal_items
+            .iter()
+            .filter_map(|x| x.id.as_ref())
+            .map(|x| x.to_raw())
+            .collect::<HashSet<_>>();
+        let coverings = self.surreal_coverings.iter().map(SurrealLink::Covering);
+        let smaller_items = self.surreal_items.iter().flat_map(|larger| {
+            larger
+                .smaller_items_in_priority_order
+                .iter()
+                .filter_map(move |x| match x {
+                    SurrealOrderedSubItem::SubItem { surreal_item_id } => {
+                        Some(SurrealLink::SmallerItemInPriorityOrder {
+                            larger,
+                            smaller: surreal_item_id,
+                        })
+                    }
+                    //Split does not reference any items directly
+                    SurrealOrderedSubItem::Split { .. } => None,
+                })
+        });
+        coverings
+            .chain(smaller_items)
+            .filter(|x| {
+                item_ids.contains(&x.get_smaller().to_raw())
+                    && item_ids.contains(&x.get_larger().to_raw())
+            })
+            .collect()
+    }
+
+    /// Each cycle is returned once as the chain of links that form it, starting from the smaller item and going larger
+    /// until it gets back to where it started.
+    pub fn find_cycles(&self) -> Vec<Vec<SurrealLink<'_>>> {
+        let links = self.make_links();
+        //Keyed by the raw record id because RecordId has interior mutability which makes it a poor key
+        let mut links_to_larger: HashMap<String, Vec<&SurrealLink<'_>>> = HashMap::new();
+        for link in links.iter() {
+            links_to_larger
+                .entry(link.get_smaller().to_raw())
+                .or_default()
+                .push(link);
+        }
+
+        let mut finished = HashSet::new();
+        let mut cycles = Vec::default();
+        for item in self.surreal_items.iter().filter_map(|x| x.id.as_ref()) {
+            let mut path = Vec::default();
+            find_cycles_from(
+                item,
+                &links_to_larger,
+                &mut path,
+                &mut finished,
+                &mut cycles,
+            );
+        }
+        cycles
+    }
+}
+
+/// Why a change was refused, the cycle is described like "A" ⟶ "B" ⟶ "A"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WouldCreateACycle {
+    /// The items in the cycle, starting and ending with the same item
+    pub cycle: String,
+}
+
+impl Display for WouldCreateACycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Change not made because it would create a cycle: {}",
+            self.cycle
+        )
+    }
+}
+
+impl SurrealTables {
+    /// When making smaller a smaller item of larger would create a cycle this returns the existing chain of links that
+    /// goes from larger back to smaller. An item being linked to itself is a cycle with no existing links.
+    pub fn find_cycle_if_linked(
+        &self,
+        smaller: &RecordId,
+        larger: &RecordId,
+    ) -> Option<Vec<SurrealLink<'_>>> {
+        Self::find_cycle_if_linked_in(&self.make_links(), smaller, larger)
+    }
+
+    /// find_cycle_if_linked with the links from make_links, so checking many candidates does not make them each time
+    pub fn find_cycle_if_linked_in<'s>(
+        links: &[SurrealLink<'s>],
+        smaller: &RecordId,
+        larger: &RecordId,
+    ) -> Option<Vec<SurrealLink<'s>>> {
+        if smaller == larger {
+            return Some(Vec::default());
+        }
+        let mut visited = vec![larger];
+        let mut path = Vec::default();
+        find_chain(larger, smaller, links, &mut visited, &mut path).then_some(path)
+    }
+
+    /// Describes the cycle that linking smaller to larger would create, for example "A" ⟶ "B" ⟶ "A"
+    pub fn describe_cycle_if_linked(
+        &self,
+        smaller: &RecordId,
+        larger: &RecordId,
+    ) -> Option<String> {
+        self.describe_cycle_if_linked_in(&self.make_links(), smaller, larger)
+    }
+
+    /// describe_cycle_if_linked with the links from make_links, so checking many candidates does not make them each
+    /// time
+    pub fn describe_cycle_if_linked_in(
+        &self,
+        links: &[SurrealLink<'_>],
+        smaller: &RecordId,
+        larger: &RecordId,
+    ) -> Option<String> {
+        Self::find_cycle_if_linked_in(links, smaller, larger).map(|chain| {
+            let back_to_smaller = if chain.is_empty() {
+                self.describe_record(smaller)
+            } else {
+                self.describe_chain(&chain)
+            };
+            format!("{} ⟶ {}", self.describe_record(smaller), back_to_smaller)
+        })
+    }
+
+    /// Err with the conflicting chain when linking smaller to larger would create a cycle so the change can be refused
+    pub fn check_for_a_cycle(
+        &self,
+        smaller: &RecordId,
+        larger: &RecordId,
+    ) -> Result<(), WouldCreateACycle> {
+        match self.describe_cycle_if_linked(smaller, larger) {
+            Some(cycle) => Err(WouldCreateACycle { cycle }),
+            None => Ok(()),
+        }
+    }
+
+    /// Shows a chain of links from smaller to larger, for example "A" ⟶ "B" ⟶ "A"
+    pub fn describe_chain(&self, links: &[SurrealLink<'_>]) -> String {
+        match links.first() {
+            Some(first) => links
+                .iter()
+                .fold(self.describe_record(first.get_smaller()), |chain, link| {
+                    format!("{} ⟶ {}", chain, self.describe_record(link.get_larger()))
+                }),
+            None => String::default(),
+        }
+    }
+
+    /// The summary in quotes, or the id if the item is not in the item table
+    pub fn describe_record(&self, record_id: &RecordId) -> String {
+        match self
+            .surreal_items
+            .iter()
+            .find(|x| x.id.as_ref() == Some(record_id))
+        {
+            Some(item) => Self::describe_item(item),
+            None => record_id.to_string(),
+        }
+    }
+
+    /// The summary in quotes
+    pub fn describe_item(item: &SurrealItem) -> String {
+        format!("\"{}\"", item.summary)
+    }
+}
+
+/// Depth first search going larger from item, path is left holding the chain of links when to is reached
+fn find_chain<'s: 'v, 'v>(
+    item: &RecordId,
+    to: &RecordId,
+    links: &[SurrealLink<'s>],
+    visited: &mut Vec<&'v RecordId>,
+    path: &mut Vec<SurrealLink<'s>>,
+) -> bool {
+    for link in links.iter().filter(|x| x.get_smaller() == item) {
+        let larger = link.get_larger();
+        if visited.contains(&larger) {
+            continue;
+        }
+        visited.push(larger);
+        path.push(link.clone());
+        if larger == to || find_chain(larger, to, links, visited, path) {
+            return true;
+        }
+        path.pop();
+    }
+    false
+}
+
+fn find_cycles_from<'s>(
+    item: &'s RecordId,
+    links_to_larger: &HashMap<String, Vec<&SurrealLink<'s>>>,
+    path: &mut Vec<SurrealLink<'s>>,
+    finished: &mut HashSet<String>,
+    cycles: &mut Vec<Vec<SurrealLink<'s>>>,
+) {
+    let raw_item = item.to_raw();
+    if finished.contains(&raw_item) {
+        return;
+    }
+    for link in links_to_larger.get(&raw_item).into_iter().flatten() {
+        let larger = link.get_larger();
+        if let Some(start) = path.iter().position(|x| x.get_smaller() == larger) {
+            let mut cycle = path[start..].to_vec();
+            cycle.push((*link).clone());
+            cycles.push(cycle);
+        } else if larger == item {
+            //An item that is linked to itself
+            cycles.push(vec![(*link).clone()]);
+        } else {
+            path.push((*link).clone());
+            find_cycles_from(larger, links_to_larger, path, finished, cycles);
+            path.pop();
+        }
+    }
+    finished.insert(raw_item);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::surrealdb_layer::surreal_item::SurrealItemBuilder;
+
+    #[test]
+    fn find_cycle_if_linked_returns_the_chain_that_would_be_closed() {
+        let surreal_items = vec![
+            SurrealItemBuilder::default()
+                .id(Some(("surreal_item", "1").into()))
+                .summary("Motivation")
+                .smaller_items_in_priority_order(vec![SurrealOrderedSubItem::SubItem {
+                    surreal_item_id: ("surreal_item", "2").into(),
+                }])
+                .build()
+                .unwrap(),
+            SurrealItemBuilder::default()
+                .id(Some(("surreal_item", "2").into()))
+                .summary("Goal")
+                .build()
+                .unwrap(),
+            SurrealItemBuilder::default()
+                .id(Some(("surreal_item", "3").into()))
+                .summary("Action")
+                .build()
+                .unwrap(),
+        ];
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(surreal_items)
+            .surreal_coverings(vec![SurrealCovering {
+                id: Some(("coverings", "1").into()),
+                smaller: ("surreal_item", "3").into(),
+                parent: ("surreal_item", "2").into(),
+            }])
+            .build()
+            .unwrap();
+        let motivation: RecordId = ("surreal_item", "1").into();
+        let action: RecordId = ("surreal_item", "3").into();
+
+        assert_eq!(
+            surreal_tables.describe_cycle_if_linked(&motivation, &action),
+            Some("\"Motivation\" ⟶ \"Action\" ⟶ \"Goal\" ⟶ \"Motivation\"".to_string())
+        );
+        assert_eq!(
+            surreal_tables.describe_cycle_if_linked(&action, &action),
+            Some("\"Action\" ⟶ \"Action\"".to_string())
+        );
+        assert_eq!(
+            surreal_tables.describe_cycle_if_linked(&action, &motivation),
+            None
+        );
+    }
+}
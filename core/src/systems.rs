@@ -6,4 +6,9 @@
 //! * Reflection
 //! * Recall
 
-pub(crate) mod bullet_list;
+pub mod agenda;
+pub mod bullet_list;
+pub mod busy_blocks;
+pub mod expectations_report;
+pub mod outline;
+pub mod upcoming_list;
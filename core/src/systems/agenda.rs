@@ -0,0 +1,189 @@
+//! Everything to bring up with a person or group the next time they are around
+
+use crate::{
+    base_data::{covering::Covering, item::Item},
+    surrealdb_layer::surreal_item::Facing,
+};
+
+/// Everything to bring up with one person or group
+pub struct Agenda<'s> {
+    person_or_group: &'s Item<'s>,
+    covered_by_them: Vec<&'s Covering<'s>>,
+    facing_them: Vec<&'s Item<'s>>,
+    waiting_for_them: Vec<&'s Item<'s>>,
+}
+
+impl<'s> Agenda<'s> {
+    /// Gathers the active items that involve person_or_group
+    pub fn new(
+        person_or_group: &'s Item<'s>,
+        coverings: &'s [Covering<'s>],
+        active_items: &'s [&'s Item<'s>],
+    ) -> Self {
+        let who = person_or_group.get_surreal_record_id();
+        let covered_by_them = coverings
+            .iter()
+            .filter(|x| x.smaller == person_or_group && !x.parent.is_finished())
+            .collect();
+        let facing_them = active_items
+            .iter()
+            .copied()
+            .filter(|x| {
+                x.get_facing().iter().any(|facing| match facing {
+                    Facing::Others {
+                        who: facing_who, ..
+                    } => facing_who == who,
+                    Facing::Myself(..) | Facing::InternalOrSmaller => false,
+                })
+            })
+            .collect();
+        let waiting_for_them = active_items
+            .iter()
+            .copied()
+            .filter(|x| match x.get_waiting_for() {
+                Some(waiting_for) => &waiting_for.who == who,
+                None => false,
+            })
+            .collect();
+        Agenda {
+            person_or_group,
+            covered_by_them,
+            facing_them,
+            waiting_for_them,
+        }
+    }
+
+    /// One agenda for each person or group that is not finished
+    pub fn new_for_everyone(
+        coverings: &'s [Covering<'s>],
+        active_items: &'s [&'s Item<'s>],
+    ) -> Vec<Self> {
+        active_items
+            .iter()
+            .filter(|x| x.is_person_or_group())
+            .map(|x| Agenda::new(x, coverings, active_items))
+            .collect()
+    }
+
+    /// Who the agenda is for
+    pub fn get_person_or_group(&self) -> &'s Item<'s> {
+        self.person_or_group
+    }
+
+    /// Items that can't be done until they are around
+    pub fn get_covered_by_them(&self) -> impl Iterator<Item = &'s Item<'s>> + '_ {
+        self.covered_by_them.iter().map(|x| x.parent)
+    }
+
+    /// The coverings to remove once they are around
+    pub fn get_coverings(&self) -> &[&'s Covering<'s>] {
+        &self.covered_by_them
+    }
+
+    /// Items that are done for them
+    pub fn get_facing_them(&self) -> &[&'s Item<'s>] {
+        &self.facing_them
+    }
+
+    /// Items that are waiting on a response from them
+    pub fn get_waiting_for_them(&self) -> &[&'s Item<'s>] {
+        &self.waiting_for_them
+    }
+
+    /// How many items are on the agenda
+    pub fn len(&self) -> usize {
+        self.covered_by_them.len() + self.facing_them.len() + self.waiting_for_them.len()
+    }
+
+    /// True if there is nothing to bring up
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use crate::{
+        base_data::BaseData,
+        surrealdb_layer::{
+            surreal_covering::SurrealCovering,
+            surreal_item::{
+                Facing, HowWellDefined, ItemType, SurrealItem, SurrealItemBuilder, WaitingFor,
+            },
+            surreal_tables::SurrealTablesBuilder,
+        },
+    };
+
+    use super::Agenda;
+
+    fn item(id: &str, item_type: ItemType) -> SurrealItem {
+        SurrealItemBuilder::default()
+            .id(Some(("surreal_item", id).into()))
+            .summary(id)
+            .item_type(item_type)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn an_agenda_has_what_they_cover_what_faces_them_and_what_is_waiting_on_them() {
+        let now = Utc::now();
+        let mut write_the_report = item("write the report", ItemType::Action);
+        write_the_report.facing = vec![Facing::Others {
+            how_well_defined: HowWellDefined::WellDefined,
+            who: ("surreal_item", "alex").into(),
+        }];
+        let mut get_the_contract_signed = item("get the contract signed", ItemType::Action);
+        get_the_contract_signed.waiting_for = Some(WaitingFor {
+            who: ("surreal_item", "alex").into(),
+            since: now.into(),
+            follow_up_after: now.into(),
+        });
+        let surreal_items = vec![
+            item("alex", ItemType::PersonOrGroup),
+            item("sam", ItemType::PersonOrGroup),
+            item("ask about the budget", ItemType::Action),
+            write_the_report,
+            get_the_contract_signed,
+            item("water the plants", ItemType::Action),
+        ];
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(surreal_items)
+            .surreal_coverings(vec![SurrealCovering {
+                id: Some(("coverings", "1").into()),
+                smaller: ("surreal_item", "alex").into(),
+                parent: ("surreal_item", "ask about the budget").into(),
+            }])
+            .build()
+            .unwrap();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+
+        let agendas =
+            Agenda::new_for_everyone(base_data.get_coverings(), base_data.get_active_items());
+
+        assert_eq!(agendas.len(), 2);
+        let alex = agendas
+            .iter()
+            .find(|x| x.get_person_or_group().get_summary() == "alex")
+            .unwrap();
+        assert_eq!(
+            alex.get_covered_by_them()
+                .map(|x| x.get_summary())
+                .collect::<Vec<_>>(),
+            ["ask about the budget"]
+        );
+        assert_eq!(alex.get_facing_them()[0].get_summary(), "write the report");
+        assert_eq!(
+            alex.get_waiting_for_them()[0].get_summary(),
+            "get the contract signed"
+        );
+        assert_eq!(alex.len(), 3);
+        let sam = agendas
+            .iter()
+            .find(|x| x.get_person_or_group().get_summary() == "sam")
+            .unwrap();
+        assert!(sam.is_empty());
+    }
+}
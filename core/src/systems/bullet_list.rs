@@ -0,0 +1,235 @@
+//! The list of what to work on, in order
+
+pub mod ranking_strategy;
+
+use ouroboros::self_referencing;
+
+use crate::{
+    base_data::{covering::Covering, covering_until_date_time::CoveringUntilDateTime, item::Item},
+    calculated_data::CalculatedData,
+    node::item_status::{ItemStatus, SnoozeReason},
+};
+
+use self::ranking_strategy::{BulletListSortKeys, DefaultRanking, RankingStrategy, SortKey};
+
+/// The list of what to work on, most important first
+#[self_referencing]
+pub struct BulletList {
+    calculated_data: CalculatedData,
+
+    #[borrows(calculated_data)]
+    #[covariant]
+    item_nodes: Vec<BulletListReason<'this>>, //TODO: Rename this to ordered_list or ordered_bullet_list or something to that effect
+}
+
+impl BulletList {
+    /// Ordered with DefaultRanking
+    pub fn new_bullet_list(calculated_data: CalculatedData) -> Self {
+        BulletList::new_bullet_list_ranked_by(calculated_data, &DefaultRanking)
+    }
+
+    /// Ordered with ranking_strategy
+    pub fn new_bullet_list_ranked_by(
+        calculated_data: CalculatedData,
+        ranking_strategy: &dyn RankingStrategy,
+    ) -> Self {
+        BulletListBuilder {
+            calculated_data,
+            item_nodes_builder: |calculated_data| {
+                order_for_bullet_list(calculated_data.get_item_status(), ranking_strategy)
+                    .into_iter()
+                    .cloned()
+                    .map(BulletListReason::new)
+                    .collect::<Vec<_>>()
+            },
+        }
+        .build()
+    }
+
+    /// In the order that the items should be worked on
+    pub fn get_bullet_list(&self) -> &[BulletListReason<'_>] {
+        self.borrow_item_nodes()
+    }
+
+    /// Items that are not finished
+    pub fn get_active_items(&self) -> &[&Item<'_>] {
+        self.borrow_calculated_data().get_active_items()
+    }
+
+    /// Coverings between active items
+    pub fn get_coverings(&self) -> &[Covering<'_>] {
+        self.borrow_calculated_data().get_coverings()
+    }
+
+    /// Snoozes of active items that have not ended yet
+    pub fn get_active_snoozed(&self) -> &[&CoveringUntilDateTime<'_>] {
+        self.borrow_calculated_data().get_active_snoozed()
+    }
+
+    /// The status of every active item, including the ones that are not on the bullet list
+    pub fn get_all_item_status(&self) -> &[ItemStatus<'_>] {
+        self.borrow_calculated_data().get_item_status()
+    }
+}
+
+/// Why an item is on the bullet list
+pub enum BulletListReason<'e> {
+    /// The staging needs to be set before it can be worked on
+    SetStaging(ItemStatus<'e>),
+    /// Ready to be worked on
+    WorkOn(ItemStatus<'e>),
+}
+
+impl<'e> BulletListReason<'e> {
+    /// SetStaging if the staging still needs to be set, otherwise WorkOn
+    pub fn new(item_status: ItemStatus<'e>) -> Self {
+        if item_status.is_staging_not_set() && !item_status.is_type_undeclared() {
+            BulletListReason::SetStaging(item_status)
+        } else {
+            BulletListReason::WorkOn(item_status)
+        }
+    }
+
+    /// The item status either way
+    pub fn get_item_status(&self) -> &ItemStatus<'e> {
+        match self {
+            BulletListReason::SetStaging(item_status) | BulletListReason::WorkOn(item_status) => {
+                item_status
+            }
+        }
+    }
+}
+
+/// The items that go on the bullet list in the order that they should be worked on
+fn order_for_bullet_list<'e>(
+    all_item_status: &'e [ItemStatus<'e>],
+    ranking_strategy: &dyn RankingStrategy,
+) -> Vec<&'e ItemStatus<'e>> {
+    //Note that some of these bottom items might be from detecting a circular dependency
+    let mut all_leaf_status_nodes = all_item_status
+        .iter()
+        .filter(|x| x.get_smaller().is_empty())
+        //Person or group items without a parent, meaning a reason for being on the list,
+        // should be filtered out.
+        .filter(|x| !x.is_person_or_group() || !x.get_larger().is_empty())
+        .collect::<Vec<_>>();
+
+    //This first sort is just to give a stable order to the items. Another way of sorting would
+    //work as well.
+    all_leaf_status_nodes.sort_by(|a, b| a.get_thing().cmp(b.get_thing()));
+
+    ranking_strategy.order(&mut all_leaf_status_nodes);
+    all_leaf_status_nodes
+}
+
+/// Why an item is where it is on the bullet list
+#[derive(Debug)]
+pub struct BulletListRank<'e> {
+    /// Starting from 1 for the top of the list
+    pub position: usize,
+    /// How many items are on the bullet list
+    pub out_of: usize,
+    /// The comparisons of the ranking strategy in the order that they are applied
+    pub sorted_by: &'static [SortKey],
+    /// The values that the item was sorted by
+    pub sort_keys: BulletListSortKeys<'e>,
+    /// Why it is snoozed, empty if it is not
+    pub snooze_reasons: &'e [SnoozeReason],
+    /// The item just above this one and the sort key that put it above, None if they are tied
+    pub above: Option<(&'e ItemStatus<'e>, Option<SortKey>)>,
+    /// The item just below this one and the sort key that put it below, None if they are tied
+    pub below: Option<(&'e ItemStatus<'e>, Option<SortKey>)>,
+}
+
+/// None if the item is not on the bullet list, for example because it has smaller items that come first
+pub fn explain_bullet_list_rank<'e>(
+    item_status: &ItemStatus<'_>,
+    all_item_status: &'e [ItemStatus<'e>],
+    ranking_strategy: &dyn RankingStrategy,
+) -> Option<BulletListRank<'e>> {
+    let ordered = order_for_bullet_list(all_item_status, ranking_strategy);
+    let index = ordered
+        .iter()
+        .position(|x| x.get_surreal_record_id() == item_status.get_surreal_record_id())?;
+    let this = ordered[index];
+    let above = index
+        .checked_sub(1)
+        .map(|above| ordered[above])
+        .map(|above| (above, ranking_strategy.explain(above, this)));
+    let below = ordered
+        .get(index + 1)
+        .map(|below| (*below, ranking_strategy.explain(this, below)));
+    Some(BulletListRank {
+        position: index + 1,
+        out_of: ordered.len(),
+        sorted_by: ranking_strategy.get_sort_keys(),
+        sort_keys: BulletListSortKeys::new(this),
+        snooze_reasons: this.get_snooze_reasons(),
+        above,
+        below,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use surrealdb::sql::Duration;
+
+    use crate::{
+        base_data::BaseData,
+        calculated_data::CalculatedData,
+        surrealdb_layer::{
+            surreal_item::{EnterListReason, ItemType, Staging, SurrealItemBuilder},
+            surreal_tables::SurrealTablesBuilder,
+        },
+    };
+
+    use super::{
+        explain_bullet_list_rank,
+        ranking_strategy::{DefaultRanking, SortKey},
+    };
+
+    #[test]
+    fn an_item_without_staging_is_explained_as_above_an_item_with_staging() {
+        let now = Utc::now();
+        let surreal_items = vec![
+            SurrealItemBuilder::default()
+                .id(Some(("surreal_item", "1").into()))
+                .summary("Staging set")
+                .item_type(ItemType::Action)
+                .staging(Staging::OnDeck {
+                    enter_list: EnterListReason::DateTime(now.into()),
+                    lap: Duration::from_days(1),
+                })
+                .build()
+                .unwrap(),
+            SurrealItemBuilder::default()
+                .id(Some(("surreal_item", "2").into()))
+                .summary("Staging not set")
+                .item_type(ItemType::Action)
+                .build()
+                .unwrap(),
+        ];
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(surreal_items)
+            .build()
+            .unwrap();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+        let calculated_data = CalculatedData::new_from_base_data(base_data, &now);
+        let all_item_status = calculated_data.get_item_status();
+        let staging_set = all_item_status
+            .iter()
+            .find(|x| x.get_item().get_summary() == "Staging set")
+            .unwrap();
+
+        let rank = explain_bullet_list_rank(staging_set, all_item_status, &DefaultRanking).unwrap();
+
+        assert_eq!(rank.position, 2);
+        assert_eq!(rank.out_of, 2);
+        assert!(!rank.sort_keys.is_staging_not_set);
+        let (above, sort_key) = rank.above.unwrap();
+        assert_eq!(above.get_item().get_summary(), "Staging not set");
+        assert_eq!(sort_key, Some(SortKey::StagingNotSet));
+        assert!(rank.below.is_none());
+    }
+}
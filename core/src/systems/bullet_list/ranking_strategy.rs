@@ -0,0 +1,537 @@
+//! How the bullet list is ordered. The default is what the bullet list has always done, the others are there to
+//! experiment with other ways of prioritizing. Each strategy is a list of sort keys that are compared in order so the
+//! same list can be shown to explain why an item is where it is.
+
+use std::{cmp::Ordering, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    base_data::item::Item,
+    node::item_status::{DueDateUrgency, ItemStatus},
+    surrealdb_layer::surreal_item::Staging,
+};
+
+/// The values that the bullet list can be sorted by
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulletListSortKeys<'e> {
+    /// Something to respond to rather than something to do
+    pub is_responsibility_reactive: bool,
+    /// There is at least one SnoozeReason
+    pub is_snoozed: bool,
+    /// The item does not have a type yet
+    pub is_type_undeclared: bool,
+    /// The staging has not been set yet
+    pub is_staging_not_set: bool,
+    /// The lap count is over 1
+    pub is_first_lap_finished: bool,
+    /// How often to think about the item
+    pub staging: &'e Staging,
+    /// How far along the item is in its lap, 1 is a full lap
+    pub lap_count: f32,
+    /// The lap count, squared for mentally resident items
+    pub expired_amount: f32,
+    /// Negative once the first lap is finished, None if the item's lap has not started
+    pub time_left_in_first_lap: Option<chrono::Duration>,
+    /// How soon the item is due
+    pub due_date_urgency: DueDateUrgency,
+    /// Negative once it is overdue, None if there is no due date
+    pub time_until_due: Option<chrono::Duration>,
+}
+
+impl<'e> BulletListSortKeys<'e> {
+    /// The sort keys for item_status
+    pub fn new(item_status: &'e ItemStatus<'_>) -> Self {
+        let lap_count = item_status.get_lap_count();
+        let expired_amount = if item_status.is_staging_mentally_resident() {
+            f32::powf(lap_count, 2f32)
+        } else {
+            lap_count
+        };
+        let staging = item_status.get_staging();
+        let time_left_in_first_lap = match staging {
+            Staging::OnDeck { lap, .. } | Staging::MentallyResident { lap, .. }
+                if lap_count > 0.0 =>
+            {
+                let lap: Duration = (*lap).into();
+                let lap = chrono::Duration::from_std(lap).unwrap_or(chrono::Duration::max_value());
+                let lap_seconds = lap.num_seconds() as f32;
+                Some(chrono::Duration::seconds(
+                    (lap_seconds * (1.0 - lap_count)) as i64,
+                ))
+            }
+            _ => None,
+        };
+        BulletListSortKeys {
+            is_responsibility_reactive: item_status.is_responsibility_reactive(),
+            is_snoozed: item_status.is_snoozed(),
+            is_type_undeclared: item_status.is_type_undeclared(),
+            is_staging_not_set: item_status.is_staging_not_set(),
+            is_first_lap_finished: item_status.is_first_lap_finished(),
+            staging,
+            lap_count,
+            expired_amount,
+            time_left_in_first_lap,
+            due_date_urgency: item_status.get_due_date_urgency(),
+            time_until_due: item_status.get_time_until_due(),
+        }
+    }
+}
+
+/// One of the comparisons that the bullet list can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    /// Reactive items go to the bottom so they are searchable
+    ResponsibilityReactive,
+    /// Snoozed items go to the bottom so they are searchable
+    Snoozed,
+    /// Items without a type go to the top so the type gets declared
+    TypeUndeclared,
+    /// Items without staging go to the top so the staging gets set
+    StagingNotSet,
+    /// Items whose first lap is finished go to the top
+    FirstLapFinished,
+    /// Only until the first lap is finished
+    Staging,
+    /// Highest first with mentally resident items counting for more
+    ExpiredAmount,
+    /// Highest first
+    LapCount,
+    /// Least time left first, items whose lap has not started go last
+    TimeLeftInFirstLap,
+    /// Items take turns by the top level item that they are for
+    LifeAreaTurn,
+    /// Overdue and then approaching due dates first, hard before soft
+    DueDateUrgency,
+    /// Soonest due first, items without a due date go last
+    TimeUntilDue,
+}
+
+impl SortKey {
+    /// Less means a goes higher on the bullet list
+    pub fn compare(&self, a: &BulletListSortKeys<'_>, b: &BulletListSortKeys<'_>) -> Ordering {
+        match self {
+            //TODO: I should show this in the UI that this is just for searching
+            //TODO: I should have an item to state the purpose so the User knows they are not meant to do this
+            SortKey::ResponsibilityReactive => a
+                .is_responsibility_reactive
+                .cmp(&b.is_responsibility_reactive),
+            //TODO: I should have an item to state the purpose so the User knows they are not meant to do this, only if they need to search
+            SortKey::Snoozed => a.is_snoozed.cmp(&b.is_snoozed),
+            SortKey::TypeUndeclared => b.is_type_undeclared.cmp(&a.is_type_undeclared),
+            SortKey::StagingNotSet => b.is_staging_not_set.cmp(&a.is_staging_not_set),
+            SortKey::FirstLapFinished => b.is_first_lap_finished.cmp(&a.is_first_lap_finished),
+            SortKey::Staging => {
+                if a.is_first_lap_finished && b.is_first_lap_finished {
+                    Ordering::Equal
+                } else {
+                    a.staging.cmp(b.staging)
+                }
+            }
+            SortKey::ExpiredAmount => b
+                .expired_amount
+                .partial_cmp(&a.expired_amount)
+                .unwrap_or(Ordering::Equal),
+            SortKey::LapCount => b
+                .lap_count
+                .partial_cmp(&a.lap_count)
+                .unwrap_or(Ordering::Equal),
+            SortKey::TimeLeftInFirstLap => {
+                compare_least_first(a.time_left_in_first_lap, b.time_left_in_first_lap)
+            }
+            SortKey::DueDateUrgency => b.due_date_urgency.cmp(&a.due_date_urgency),
+            SortKey::TimeUntilDue => compare_least_first(a.time_until_due, b.time_until_due),
+            //Turns are taken by RankingStrategy::order, two items side by side are not compared by this
+            SortKey::LifeAreaTurn => Ordering::Equal,
+        }
+    }
+}
+
+/// None goes last
+fn compare_least_first(a: Option<chrono::Duration>, b: Option<chrono::Duration>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// How the bullet list is ordered
+pub trait RankingStrategy {
+    /// The comparisons in the order that they are applied
+    fn get_sort_keys(&self) -> &'static [SortKey];
+
+    /// Less means a goes higher on the bullet list, also returns the sort key that decided it or None if they are tied
+    fn compare(
+        &self,
+        a: &BulletListSortKeys<'_>,
+        b: &BulletListSortKeys<'_>,
+    ) -> (Ordering, Option<SortKey>) {
+        self.get_sort_keys()
+            .iter()
+            .map(|sort_key| (sort_key.compare(a, b), sort_key))
+            .find(|(ordering, _)| ordering.is_ne())
+            .map(|(ordering, sort_key)| (ordering, Some(*sort_key)))
+            .unwrap_or((Ordering::Equal, None))
+    }
+
+    /// Puts the items in the order that they should be worked on, they come in a stable order
+    fn order<'e>(&self, items: &mut Vec<&'e ItemStatus<'e>>) {
+        items.sort_by(|a, b| {
+            let (ordering, _) =
+                self.compare(&BulletListSortKeys::new(a), &BulletListSortKeys::new(b));
+            ordering
+        });
+    }
+
+    /// The sort key that put above just above below, None if they are tied
+    fn explain(&self, above: &ItemStatus<'_>, below: &ItemStatus<'_>) -> Option<SortKey> {
+        let (_, sort_key) = self.compare(
+            &BulletListSortKeys::new(above),
+            &BulletListSortKeys::new(below),
+        );
+        sort_key
+    }
+}
+
+/// First lap finished items on top with mentally resident items rising faster, items are escalated above that as their
+/// due date approaches
+pub struct DefaultRanking;
+
+impl RankingStrategy for DefaultRanking {
+    fn get_sort_keys(&self) -> &'static [SortKey] {
+        &[
+            SortKey::ResponsibilityReactive,
+            SortKey::Snoozed,
+            SortKey::DueDateUrgency,
+            SortKey::TypeUndeclared,
+            SortKey::StagingNotSet,
+            SortKey::FirstLapFinished,
+            SortKey::Staging,
+            SortKey::ExpiredAmount,
+        ]
+    }
+}
+
+/// Just how far along in its lap each item is, staging and type are ignored
+pub struct LapPercentageRanking;
+
+impl RankingStrategy for LapPercentageRanking {
+    fn get_sort_keys(&self) -> &'static [SortKey] {
+        &[
+            SortKey::ResponsibilityReactive,
+            SortKey::Snoozed,
+            SortKey::LapCount,
+        ]
+    }
+}
+
+/// The soonest due date first and then the item whose lap is closest to running out, items without either are last
+pub struct DeadlineFirstRanking;
+
+impl RankingStrategy for DeadlineFirstRanking {
+    fn get_sort_keys(&self) -> &'static [SortKey] {
+        &[
+            SortKey::ResponsibilityReactive,
+            SortKey::Snoozed,
+            SortKey::TimeUntilDue,
+            SortKey::TimeLeftInFirstLap,
+            SortKey::TypeUndeclared,
+            SortKey::StagingNotSet,
+            SortKey::Staging,
+        ]
+    }
+}
+
+/// The default ranking but taking turns between life areas so one area does not fill the top of the list. Items are
+/// not linked to life areas yet so the top level item of the first parent chain stands in for the life area.
+pub struct LifeAreaBalancedRanking;
+
+impl RankingStrategy for LifeAreaBalancedRanking {
+    fn get_sort_keys(&self) -> &'static [SortKey] {
+        &[
+            SortKey::LifeAreaTurn,
+            SortKey::ResponsibilityReactive,
+            SortKey::Snoozed,
+            SortKey::DueDateUrgency,
+            SortKey::TypeUndeclared,
+            SortKey::StagingNotSet,
+            SortKey::FirstLapFinished,
+            SortKey::Staging,
+            SortKey::ExpiredAmount,
+        ]
+    }
+
+    fn order<'e>(&self, items: &mut Vec<&'e ItemStatus<'e>>) {
+        DefaultRanking.order(items);
+
+        //Each turn takes the highest remaining item from each life area, in the order that the life areas first appear
+        let mut life_areas: Vec<(&Item<'_>, Vec<&'e ItemStatus<'e>>)> = Vec::default();
+        //Reactive and snoozed items stay at the bottom rather than taking a turn
+        let split_at = items
+            .iter()
+            .position(|x| x.is_responsibility_reactive() || x.is_snoozed())
+            .unwrap_or(items.len());
+        let bottom = items.split_off(split_at);
+        for item in items.drain(..) {
+            let life_area = find_life_area(item);
+            match life_areas.iter_mut().find(|(x, _)| *x == life_area) {
+                Some((_, life_area_items)) => life_area_items.push(item),
+                None => life_areas.push((life_area, vec![item])),
+            }
+        }
+        let mut turn = 0;
+        while life_areas.iter().any(|(_, x)| turn < x.len()) {
+            items.extend(life_areas.iter().filter_map(|(_, x)| x.get(turn)));
+            turn += 1;
+        }
+        items.extend(bottom);
+    }
+
+    fn explain(&self, above: &ItemStatus<'_>, below: &ItemStatus<'_>) -> Option<SortKey> {
+        let is_bottom = |x: &ItemStatus<'_>| x.is_responsibility_reactive() || x.is_snoozed();
+        if find_life_area(above) == find_life_area(below) || is_bottom(below) {
+            DefaultRanking.explain(above, below)
+        } else {
+            Some(SortKey::LifeAreaTurn)
+        }
+    }
+}
+
+/// The top of the first parent chain or the item itself if it has no parent
+fn find_life_area<'a>(item_status: &'a ItemStatus<'_>) -> &'a Item<'a> {
+    let mut life_area = item_status.get_item();
+    let mut larger = item_status.get_larger().first();
+    while let Some(growing_item_node) = larger {
+        life_area = growing_item_node.item;
+        larger = growing_item_node.larger.first();
+    }
+    life_area
+}
+
+/// The ranking strategies that can be chosen in the config file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RankingStrategyName {
+    /// See DefaultRanking
+    #[default]
+    Default,
+    /// See LapPercentageRanking
+    LapPercentage,
+    /// See DeadlineFirstRanking
+    DeadlineFirst,
+    /// See LifeAreaBalancedRanking
+    LifeAreaBalanced,
+}
+
+impl RankingStrategyName {
+    /// The strategy to order the bullet list with
+    pub fn create(&self) -> Box<dyn RankingStrategy + Send + Sync> {
+        match self {
+            RankingStrategyName::Default => Box::new(DefaultRanking),
+            RankingStrategyName::LapPercentage => Box::new(LapPercentageRanking),
+            RankingStrategyName::DeadlineFirst => Box::new(DeadlineFirstRanking),
+            RankingStrategyName::LifeAreaBalanced => Box::new(LifeAreaBalancedRanking),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+    use surrealdb::sql::Duration;
+
+    use crate::{
+        base_data::BaseData,
+        calculated_data::CalculatedData,
+        surrealdb_layer::{
+            surreal_item::{
+                DueDate, DueDateStrictness, EnterListReason, ItemType, Staging, SurrealItem,
+                SurrealItemBuilder, SurrealOrderedSubItem,
+            },
+            surreal_tables::SurrealTablesBuilder,
+        },
+        systems::bullet_list::{explain_bullet_list_rank, BulletList},
+    };
+
+    use super::*;
+
+    fn action(id: &str, staging: Staging) -> SurrealItem {
+        SurrealItemBuilder::default()
+            .id(Some(("surreal_item", id).into()))
+            .summary(id)
+            .item_type(ItemType::Action)
+            .staging(staging)
+            .build()
+            .unwrap()
+    }
+
+    fn goal(id: &str, smaller: &[&str]) -> SurrealItem {
+        SurrealItemBuilder::default()
+            .id(Some(("surreal_item", id).into()))
+            .summary(id)
+            .item_type(ItemType::Goal(Default::default()))
+            .smaller_items_in_priority_order(
+                smaller
+                    .iter()
+                    .map(|x| SurrealOrderedSubItem::SubItem {
+                        surreal_item_id: ("surreal_item", *x).into(),
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    fn on_deck_since(entered: DateTime<Utc>, lap: Duration) -> Staging {
+        Staging::OnDeck {
+            enter_list: EnterListReason::DateTime(entered.into()),
+            lap,
+        }
+    }
+
+    fn rank(
+        surreal_items: Vec<SurrealItem>,
+        now: DateTime<Utc>,
+        ranking_strategy: &dyn RankingStrategy,
+    ) -> Vec<String> {
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(surreal_items)
+            .build()
+            .unwrap();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+        let calculated_data = CalculatedData::new_from_base_data(base_data, &now);
+        let bullet_list = BulletList::new_bullet_list_ranked_by(calculated_data, ranking_strategy);
+        bullet_list
+            .get_bullet_list()
+            .iter()
+            .map(|x| x.get_item_status().get_item().get_summary().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn lap_percentage_ignores_that_staging_is_not_set() {
+        let now = Utc::now();
+        let surreal_items = vec![
+            action("not set", Staging::NotSet),
+            action(
+                "half way",
+                on_deck_since(now - chrono::Duration::hours(12), Duration::from_days(1)),
+            ),
+        ];
+
+        assert_eq!(
+            rank(surreal_items.clone(), now, &DefaultRanking),
+            ["not set", "half way"]
+        );
+        assert_eq!(
+            rank(surreal_items, now, &LapPercentageRanking),
+            ["half way", "not set"]
+        );
+    }
+
+    #[test]
+    fn deadline_first_puts_the_least_time_left_first() {
+        let now = Utc::now();
+        let surreal_items = vec![
+            action(
+                "five days left",
+                on_deck_since(now - chrono::Duration::days(5), Duration::from_days(10)),
+            ),
+            action(
+                "twelve hours left",
+                on_deck_since(now - chrono::Duration::hours(12), Duration::from_days(1)),
+            ),
+            action("not started", Staging::NotSet),
+        ];
+
+        assert_eq!(
+            rank(surreal_items, now, &DeadlineFirstRanking),
+            ["twelve hours left", "five days left", "not started"]
+        );
+    }
+
+    #[test]
+    fn an_approaching_hard_due_date_on_a_parent_moves_its_child_up() {
+        let now = Utc::now();
+        let mut project = goal("project", &["slides"]);
+        let mut surreal_items = vec![
+            action(
+                "inbox",
+                on_deck_since(now - chrono::Duration::days(3), Duration::from_days(1)),
+            ),
+            action(
+                "slides",
+                on_deck_since(now - chrono::Duration::hours(1), Duration::from_days(1)),
+            ),
+        ];
+
+        project.due_date = Some(DueDate {
+            due: (now + chrono::Duration::days(30)).into(),
+            strictness: DueDateStrictness::Hard,
+        });
+        surreal_items.push(project.clone());
+        assert_eq!(
+            rank(surreal_items.clone(), now, &DefaultRanking),
+            ["inbox", "slides"]
+        );
+
+        project.due_date = Some(DueDate {
+            due: (now + chrono::Duration::days(2)).into(),
+            strictness: DueDateStrictness::Hard,
+        });
+        surreal_items.pop();
+        surreal_items.push(project);
+        assert_eq!(
+            rank(surreal_items, now, &DefaultRanking),
+            ["slides", "inbox"]
+        );
+    }
+
+    #[test]
+    fn life_area_balanced_takes_turns_between_top_level_items() {
+        let now = Utc::now();
+        let surreal_items = vec![
+            goal("work", &["report", "email"]),
+            goal("home", &["dishes"]),
+            action(
+                "report",
+                on_deck_since(now - chrono::Duration::days(3), Duration::from_days(1)),
+            ),
+            action(
+                "email",
+                on_deck_since(now - chrono::Duration::days(2), Duration::from_days(1)),
+            ),
+            action(
+                "dishes",
+                on_deck_since(now - chrono::Duration::hours(1), Duration::from_days(1)),
+            ),
+        ];
+
+        assert_eq!(
+            rank(surreal_items.clone(), now, &DefaultRanking),
+            ["report", "email", "dishes"]
+        );
+        assert_eq!(
+            rank(surreal_items.clone(), now, &LifeAreaBalancedRanking),
+            ["report", "dishes", "email"]
+        );
+
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(surreal_items)
+            .build()
+            .unwrap();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+        let calculated_data = CalculatedData::new_from_base_data(base_data, &now);
+        let all_item_status = calculated_data.get_item_status();
+        let dishes = all_item_status
+            .iter()
+            .find(|x| x.get_item().get_summary() == "dishes")
+            .unwrap();
+        let rank =
+            explain_bullet_list_rank(dishes, all_item_status, &LifeAreaBalancedRanking).unwrap();
+        assert_eq!(rank.above.unwrap().1, Some(SortKey::LifeAreaTurn));
+        assert_eq!(rank.below.unwrap().1, Some(SortKey::LifeAreaTurn));
+    }
+}
@@ -0,0 +1,180 @@
+//! The meetings and other events from the calendar that take up time, so work can be planned around them
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{base_data::item::Item, surrealdb_layer::surreal_item::Attendance};
+
+/// How long to focus for when nothing is coming up sooner
+pub const FOCUS_SESSION_LENGTH_MINUTES: i64 = 30;
+
+/// The time that an event takes up
+pub struct BusyBlock<'s> {
+    /// The item for the event
+    pub item: &'s Item<'s>,
+    /// When the event starts
+    pub start: DateTime<Utc>,
+    /// When the event ends
+    pub end: DateTime<Utc>,
+}
+
+/// Events that are not finished, not over, and that I have not said I won't attend, the soonest first
+pub fn find_busy_blocks<'s>(
+    active_items: &[&'s Item<'s>],
+    now: DateTime<Utc>,
+) -> Vec<BusyBlock<'s>> {
+    let mut busy_blocks = active_items
+        .iter()
+        .filter_map(|item| {
+            let calendar_event = item.get_calendar_event()?;
+            if calendar_event.attendance == Attendance::NotAttending {
+                return None;
+            }
+            Some(BusyBlock {
+                item,
+                start: calendar_event.start.clone().into(),
+                end: calendar_event.end.clone().into(),
+            })
+        })
+        .filter(|x| x.end > now)
+        .collect::<Vec<_>>();
+    busy_blocks.sort_by_key(|x| x.start);
+    busy_blocks
+}
+
+/// What to do instead of starting a full focus session
+pub enum FocusSuggestion<'s> {
+    /// In the middle of this busy block
+    Busy(&'s BusyBlock<'s>),
+    /// Not busy right now so a focus session can be started
+    FocusFor {
+        /// How long the focus session should be
+        length: Duration,
+        /// The busy block that cuts the focus session short, if there is one
+        before: Option<&'s BusyBlock<'s>>,
+    },
+}
+
+/// How long a focus session started now should be so it ends before the next busy block. working_on is left out of
+/// the busy blocks so starting to work on a meeting is not reported as being busy with it.
+pub fn suggest_focus_session<'s>(
+    busy_blocks: &'s [BusyBlock<'s>],
+    working_on: &Item<'_>,
+    now: DateTime<Utc>,
+) -> FocusSuggestion<'s> {
+    let mut busy_blocks = busy_blocks.iter().filter(|x| x.item != working_on);
+    let full_length = Duration::minutes(FOCUS_SESSION_LENGTH_MINUTES);
+    match busy_blocks.find(|x| x.end > now) {
+        Some(busy_block) if busy_block.start <= now => FocusSuggestion::Busy(busy_block),
+        Some(busy_block) if busy_block.start - now < full_length => FocusSuggestion::FocusFor {
+            length: busy_block.start - now,
+            before: Some(busy_block),
+        },
+        Some(_) | None => FocusSuggestion::FocusFor {
+            length: full_length,
+            before: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, Utc};
+
+    use crate::{
+        base_data::BaseData,
+        surrealdb_layer::{
+            surreal_item::{Attendance, CalendarEvent, ItemType, SurrealItem, SurrealItemBuilder},
+            surreal_tables::SurrealTablesBuilder,
+        },
+    };
+
+    use super::{find_busy_blocks, suggest_focus_session, FocusSuggestion};
+
+    fn event(
+        id: &str,
+        now: DateTime<Utc>,
+        starts_in: Duration,
+        length: Duration,
+        attendance: Attendance,
+    ) -> SurrealItem {
+        SurrealItemBuilder::default()
+            .id(Some(("surreal_item", id).into()))
+            .summary(id)
+            .item_type(ItemType::Action)
+            .calendar_event(Some(Box::new(CalendarEvent {
+                uid: id.into(),
+                start: (now + starts_in).into(),
+                end: (now + starts_in + length).into(),
+                attendance,
+            })))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn a_focus_session_ends_before_the_next_meeting_that_will_be_attended() {
+        let now = Utc::now();
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("surreal_item", "write the report").into()))
+                    .summary("write the report")
+                    .item_type(ItemType::Action)
+                    .build()
+                    .unwrap(),
+                event(
+                    "skipped",
+                    now,
+                    Duration::minutes(5),
+                    Duration::hours(1),
+                    Attendance::NotAttending,
+                ),
+                event(
+                    "standup",
+                    now,
+                    Duration::minutes(20),
+                    Duration::minutes(15),
+                    Attendance::NotDecided,
+                ),
+                event(
+                    "yesterday",
+                    now,
+                    Duration::days(-1),
+                    Duration::hours(1),
+                    Attendance::Attending,
+                ),
+            ])
+            .build()
+            .unwrap();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+        let active_items = base_data.get_active_items();
+        let write_the_report = active_items
+            .iter()
+            .find(|x| x.get_summary() == "write the report")
+            .unwrap();
+        let standup = active_items
+            .iter()
+            .find(|x| x.get_summary() == "standup")
+            .unwrap();
+
+        let busy_blocks = find_busy_blocks(active_items, now);
+        assert_eq!(busy_blocks.len(), 1);
+
+        match suggest_focus_session(&busy_blocks, write_the_report, now) {
+            FocusSuggestion::FocusFor { length, before } => {
+                assert!(length <= Duration::minutes(20));
+                assert!(length > Duration::minutes(19));
+                assert_eq!(before.unwrap().item.get_summary(), "standup");
+            }
+            FocusSuggestion::Busy(_) => panic!("The standup has not started"),
+        }
+        assert!(matches!(
+            suggest_focus_session(&busy_blocks, write_the_report, now + Duration::minutes(25)),
+            FocusSuggestion::Busy(busy_block) if busy_block.item.get_summary() == "standup"
+        ));
+        assert!(matches!(
+            suggest_focus_session(&busy_blocks, standup, now),
+            FocusSuggestion::FocusFor { before: None, .. }
+        ));
+    }
+}
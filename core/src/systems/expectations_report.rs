@@ -0,0 +1,234 @@
+//! What each person or group expects, and what is expected of myself, from the facing that is set on items. This is to
+//! help manage the expectations of others and of myself.
+
+use chrono::{DateTime, Utc};
+use surrealdb::opt::RecordId;
+
+use crate::{
+    base_data::{covering::Covering, item::Item},
+    surrealdb_layer::surreal_item::{Facing, HowWellDefined},
+};
+
+/// Who has the expectation
+pub enum Stakeholder<'s> {
+    /// A person or group that items face
+    PersonOrGroup(&'s Item<'s>),
+    /// The person or group was deleted but an item still faces them
+    Missing(&'s RecordId),
+    /// Items that face myself
+    Myself,
+}
+
+/// An item that someone expects
+pub struct Expectation<'s> {
+    /// The item that is expected
+    pub item: &'s Item<'s>,
+    /// How well the expectation is defined
+    pub how_well_defined: &'s HowWellDefined,
+    /// The last time this or anything under it was created, finished, or handed off to someone to wait on
+    pub last_activity: DateTime<Utc>,
+}
+
+/// Everything that one stakeholder expects
+pub struct StakeholderExpectations<'s> {
+    /// Who expects it
+    pub stakeholder: Stakeholder<'s>,
+    /// The ones that have gone the longest without activity first
+    pub expectations: Vec<Expectation<'s>>,
+}
+
+/// What everyone expects, grouped by who expects it
+pub struct ExpectationsReport<'s> {
+    stakeholders: Vec<StakeholderExpectations<'s>>,
+}
+
+impl<'s> ExpectationsReport<'s> {
+    /// Only the items that have facing set on themselves are included, the items under them are how the expectation is
+    /// being met so they count towards its last activity instead. Include finished items in all_items so finishing a
+    /// smaller item counts as activity.
+    pub fn new(all_items: &'s [&'s Item<'s>], coverings: &'s [Covering<'s>]) -> Self {
+        let mut stakeholders: Vec<StakeholderExpectations<'s>> = Vec::default();
+        for item in all_items
+            .iter()
+            .filter(|x| !x.is_finished() && !x.is_person_or_group())
+        {
+            for facing in item.get_facing() {
+                let (who, how_well_defined) = match facing {
+                    Facing::Others {
+                        who,
+                        how_well_defined,
+                    } => (Some(who), how_well_defined),
+                    Facing::Myself(how_well_defined) => (None, how_well_defined),
+                    Facing::InternalOrSmaller => continue,
+                };
+                let expectation = Expectation {
+                    item,
+                    how_well_defined,
+                    last_activity: calculate_last_activity(item, all_items, coverings, &mut vec![]),
+                };
+                match stakeholders
+                    .iter_mut()
+                    .find(|x| x.stakeholder.get_record_id() == who)
+                {
+                    Some(stakeholder) => stakeholder.expectations.push(expectation),
+                    None => stakeholders.push(StakeholderExpectations {
+                        stakeholder: Stakeholder::new(who, all_items),
+                        expectations: vec![expectation],
+                    }),
+                }
+            }
+        }
+
+        for stakeholder in stakeholders.iter_mut() {
+            stakeholder.expectations.sort_by_key(|x| x.last_activity);
+        }
+        stakeholders.sort_by_key(|x| match x.stakeholder {
+            Stakeholder::PersonOrGroup(item) => (0, item.get_summary().to_lowercase()),
+            Stakeholder::Missing(record_id) => (1, record_id.to_string()),
+            Stakeholder::Myself => (2, String::default()),
+        });
+        ExpectationsReport { stakeholders }
+    }
+
+    /// People and groups first, sorted by name, then missing ones and then myself
+    pub fn get_stakeholders(&self) -> &[StakeholderExpectations<'s>] {
+        &self.stakeholders
+    }
+}
+
+impl<'s> Stakeholder<'s> {
+    fn new(who: Option<&'s RecordId>, all_items: &'s [&'s Item<'s>]) -> Self {
+        match who {
+            Some(who) => match all_items.iter().find(|x| x.get_surreal_record_id() == who) {
+                Some(person_or_group) => Stakeholder::PersonOrGroup(person_or_group),
+                None => Stakeholder::Missing(who),
+            },
+            None => Stakeholder::Myself,
+        }
+    }
+
+    /// None for Myself
+    fn get_record_id(&self) -> Option<&'s RecordId> {
+        match self {
+            Stakeholder::PersonOrGroup(item) => Some(item.get_surreal_record_id()),
+            Stakeholder::Missing(record_id) => Some(record_id),
+            Stakeholder::Myself => None,
+        }
+    }
+}
+
+fn calculate_last_activity<'a>(
+    item: &'a Item<'a>,
+    all_items: &'a [&'a Item<'a>],
+    coverings: &'a [Covering<'a>],
+    visited: &mut Vec<&'a Item<'a>>,
+) -> DateTime<Utc> {
+    visited.push(item);
+    let mut last_activity = item.get_created();
+    if let Some(finished) = item.when_finished() {
+        last_activity = last_activity.max(finished);
+    }
+    if let Some(waiting_for) = item.get_waiting_for() {
+        last_activity = last_activity.max(waiting_for.since.clone().into());
+    }
+    //A person or group covering this is who it is waiting on, not work towards it
+    for child in item
+        .find_children(coverings, all_items, visited)
+        .into_iter()
+        .filter(|x| !x.is_person_or_group())
+    {
+        if !visited.contains(&child) {
+            last_activity = last_activity.max(calculate_last_activity(
+                child, all_items, coverings, visited,
+            ));
+        }
+    }
+    last_activity
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::{
+        base_data::BaseData,
+        surrealdb_layer::{
+            surreal_item::{
+                Facing, HowWellDefined, ItemType, SurrealItem, SurrealItemBuilder,
+                SurrealOrderedSubItem,
+            },
+            surreal_tables::SurrealTablesBuilder,
+        },
+    };
+
+    use super::{ExpectationsReport, Stakeholder};
+
+    fn item(id: &str, item_type: ItemType, created_days_ago: i64) -> SurrealItem {
+        SurrealItemBuilder::default()
+            .id(Some(("surreal_item", id).into()))
+            .summary(id)
+            .item_type(item_type)
+            .created(Utc::now() - Duration::days(created_days_ago))
+            .build()
+            .unwrap()
+    }
+
+    fn facing_alex(how_well_defined: HowWellDefined) -> Vec<Facing> {
+        vec![Facing::Others {
+            how_well_defined,
+            who: ("surreal_item", "alex").into(),
+        }]
+    }
+
+    #[test]
+    fn expectations_are_grouped_by_who_with_the_stalest_first_and_myself_last() {
+        let mut quarterly_report = item("quarterly report", ItemType::Goal(Default::default()), 30);
+        quarterly_report.facing = facing_alex(HowWellDefined::WellDefined);
+        quarterly_report.smaller_items_in_priority_order = vec![SurrealOrderedSubItem::SubItem {
+            surreal_item_id: ("surreal_item", "draft the numbers").into(),
+        }];
+        let mut offsite = item("plan the offsite", ItemType::Action, 10);
+        offsite.facing = facing_alex(HowWellDefined::LooselyDefined);
+        let mut exercise = item("exercise", ItemType::Motivation, 5);
+        exercise.facing = vec![Facing::Myself(HowWellDefined::RoughlyDefined)];
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                item("alex", ItemType::PersonOrGroup, 60),
+                quarterly_report,
+                item("draft the numbers", ItemType::Action, 1),
+                offsite,
+                exercise,
+            ])
+            .build()
+            .unwrap();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, Utc::now());
+
+        let report =
+            ExpectationsReport::new(base_data.get_active_items(), base_data.get_coverings());
+
+        let stakeholders = report.get_stakeholders();
+        assert_eq!(stakeholders.len(), 2);
+        assert!(matches!(
+            stakeholders[0].stakeholder,
+            Stakeholder::PersonOrGroup(alex) if alex.get_summary() == "alex"
+        ));
+        //Work on the smaller item counts as activity so the quarterly report is not the stalest
+        assert_eq!(
+            stakeholders[0]
+                .expectations
+                .iter()
+                .map(|x| x.item.get_summary())
+                .collect::<Vec<_>>(),
+            ["plan the offsite", "quarterly report"]
+        );
+        assert_eq!(
+            stakeholders[0].expectations[0].how_well_defined,
+            &HowWellDefined::LooselyDefined
+        );
+        assert!(matches!(stakeholders[1].stakeholder, Stakeholder::Myself));
+        assert_eq!(
+            stakeholders[1].expectations[0].item.get_summary(),
+            "exercise"
+        );
+    }
+}
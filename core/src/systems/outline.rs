@@ -0,0 +1,206 @@
+//! The hierarchy of items from the motivations and goals down to the smallest steps, for writing out as an outline
+
+use crate::{
+    base_data::{covering::Covering, item::Item},
+    surrealdb_layer::surreal_item::ItemType,
+};
+
+/// How an entry relates to the entry above it
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Relation {
+    /// One of the smaller items that make up the item above, in priority order
+    Smaller,
+    /// The item above can't be done until this is done
+    Covering,
+}
+
+/// One item in the outline with the items below it
+pub struct OutlineEntry<'s> {
+    /// The item for this entry
+    pub item: &'s Item<'s>,
+    /// How the item relates to the entry above
+    pub relation: Relation,
+    /// Empty when max_depth was reached even if there are smaller items
+    pub smaller: Vec<OutlineEntry<'s>>,
+}
+
+/// The items from the top of the hierarchy down
+pub struct Outline<'s> {
+    roots: Vec<OutlineEntry<'s>>,
+}
+
+impl<'s> Outline<'s> {
+    /// Without a root every item that is not smaller than, or covering, another item is a root, motivations first and
+    /// then goals. Only the items in items are included so pass in finished items to include them. A max_depth of 1 is
+    /// just the roots.
+    pub fn new(
+        items: &'s [&'s Item<'s>],
+        coverings: &'s [Covering<'s>],
+        root: Option<&'s Item<'s>>,
+        max_depth: Option<usize>,
+    ) -> Self {
+        let mut roots = match root {
+            Some(root) => vec![root],
+            None => items
+                .iter()
+                .copied()
+                .filter(|x| {
+                    !x.is_person_or_group() && x.find_parents(coverings, items, &[]).is_empty()
+                })
+                .collect(),
+        };
+        roots.sort_by_key(|x| match x.get_item_type() {
+            ItemType::Motivation => 0,
+            ItemType::Goal(_) => 1,
+            _ => 2,
+        });
+        let roots = roots
+            .into_iter()
+            .map(|x| {
+                make_entry(
+                    x,
+                    Relation::Smaller,
+                    items,
+                    coverings,
+                    max_depth,
+                    &mut vec![],
+                )
+            })
+            .collect();
+        Outline { roots }
+    }
+
+    /// The entries at the top of the outline
+    pub fn get_roots(&self) -> &[OutlineEntry<'s>] {
+        &self.roots
+    }
+}
+
+fn make_entry<'s>(
+    item: &'s Item<'s>,
+    relation: Relation,
+    items: &'s [&'s Item<'s>],
+    coverings: &'s [Covering<'s>],
+    max_depth: Option<usize>,
+    above: &mut Vec<&'s Item<'s>>,
+) -> OutlineEntry<'s> {
+    above.push(item);
+    let mut smaller = Vec::default();
+    if max_depth.is_none_or(|max_depth| above.len() < max_depth) {
+        //Without coverings this is just the smaller items, in priority order
+        for child in item.find_children(&[], items, above) {
+            smaller.push(make_entry(
+                child,
+                Relation::Smaller,
+                items,
+                coverings,
+                max_depth,
+                above,
+            ));
+        }
+        let covered_by = coverings
+            .iter()
+            .filter(|x| x.parent == item && !above.contains(&x.smaller))
+            .map(|x| x.smaller)
+            .collect::<Vec<_>>();
+        for covering in covered_by {
+            smaller.push(make_entry(
+                covering,
+                Relation::Covering,
+                items,
+                coverings,
+                max_depth,
+                above,
+            ));
+        }
+    }
+    above.pop();
+    OutlineEntry {
+        item,
+        relation,
+        smaller,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use crate::{
+        base_data::BaseData,
+        surrealdb_layer::{
+            surreal_covering::SurrealCovering,
+            surreal_item::{ItemType, SurrealItem, SurrealItemBuilder, SurrealOrderedSubItem},
+            surreal_tables::SurrealTablesBuilder,
+        },
+    };
+
+    use super::{Outline, OutlineEntry, Relation};
+
+    fn item(id: &str, item_type: ItemType, smaller: &[&str]) -> SurrealItem {
+        SurrealItemBuilder::default()
+            .id(Some(("surreal_item", id).into()))
+            .summary(id)
+            .item_type(item_type)
+            .smaller_items_in_priority_order(
+                smaller
+                    .iter()
+                    .map(|x| SurrealOrderedSubItem::SubItem {
+                        surreal_item_id: ("surreal_item", *x).into(),
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    fn summaries<'a>(entries: &'a [OutlineEntry<'a>]) -> Vec<&'a str> {
+        entries.iter().map(|x| x.item.get_summary()).collect()
+    }
+
+    #[test]
+    fn the_outline_follows_smaller_items_in_order_then_coverings_down_to_the_max_depth() {
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                item("loose end", ItemType::Action, &[]),
+                item(
+                    "ship it",
+                    ItemType::Goal(Default::default()),
+                    &["write", "test"],
+                ),
+                item("write", ItemType::Action, &["outline"]),
+                item("outline", ItemType::Action, &[]),
+                item("test", ItemType::Action, &[]),
+                item("alex", ItemType::PersonOrGroup, &[]),
+                item("be useful", ItemType::Motivation, &["ship it"]),
+            ])
+            .surreal_coverings(vec![SurrealCovering {
+                id: Some(("coverings", "1").into()),
+                smaller: ("surreal_item", "alex").into(),
+                parent: ("surreal_item", "ship it").into(),
+            }])
+            .build()
+            .unwrap();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, Utc::now());
+        let items = base_data.get_active_items();
+
+        let outline = Outline::new(items, base_data.get_coverings(), None, Some(3));
+
+        let roots = outline.get_roots();
+        assert_eq!(summaries(roots), ["be useful", "loose end"]);
+        let ship_it = &roots[0].smaller[0];
+        assert_eq!(summaries(&ship_it.smaller), ["write", "test", "alex"]);
+        assert_eq!(ship_it.smaller[2].relation, Relation::Covering);
+        //"outline" is at depth 4
+        assert!(ship_it.smaller[0].smaller.is_empty());
+
+        let ship_it = items.iter().copied().find(|x| x.get_summary() == "ship it");
+        let outline = Outline::new(items, base_data.get_coverings(), ship_it, None);
+
+        assert_eq!(summaries(outline.get_roots()), ["ship it"]);
+        assert_eq!(
+            summaries(&outline.get_roots()[0].smaller[0].smaller),
+            ["outline"]
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! When each item is expected to come up again, from its staging and from being snoozed
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    base_data::{covering_until_date_time::CoveringUntilDateTime, item::Item},
+    node::item_status::ItemStatus,
+    surrealdb_layer::surreal_item::{EnterListReason, Staging},
+};
+
+/// Why the item is coming up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpcomingReason {
+    /// The staging says it enters the bullet list at this time
+    EntersList,
+    /// The lap count reaches 1 and it moves to the top of the bullet list
+    LapEnds,
+    /// Snoozed until this time
+    SnoozeEnds,
+}
+
+/// An item that is coming up
+pub struct Upcoming<'s> {
+    /// The item that is coming up
+    pub item: &'s Item<'s>,
+    /// When it comes up
+    pub when: DateTime<Utc>,
+    /// Why it comes up
+    pub reason: UpcomingReason,
+}
+
+/// The mentally resident and on deck items and the snoozed items, soonest first. Items that were imported from a
+/// calendar are left out because the calendar already has them.
+pub fn find_upcoming<'s>(
+    all_item_status: &'s [ItemStatus<'s>],
+    active_snoozed: &[&'s CoveringUntilDateTime<'s>],
+    now: DateTime<Utc>,
+) -> Vec<Upcoming<'s>> {
+    let mut upcoming = Vec::default();
+    for item_status in all_item_status
+        .iter()
+        .filter(|x| x.get_item().get_calendar_event().is_none())
+    {
+        let item = item_status.get_item();
+        let (enter_list, lap) = match item_status.get_staging() {
+            Staging::MentallyResident { enter_list, lap } | Staging::OnDeck { enter_list, lap } => {
+                (enter_list, lap)
+            }
+            Staging::NotSet | Staging::Planned | Staging::ThinkingAbout | Staging::Released => {
+                continue;
+            }
+        };
+        let lap = chrono::Duration::from_std((*lap).into()).unwrap_or(chrono::Duration::zero());
+        let lap_count = item_status.get_lap_count();
+        let lap_ends = match enter_list {
+            EnterListReason::DateTime(enter_list) => {
+                let enter_list: DateTime<Utc> = enter_list.clone().into();
+                if enter_list > now {
+                    upcoming.push(Upcoming {
+                        item,
+                        when: enter_list,
+                        reason: UpcomingReason::EntersList,
+                    });
+                }
+                enter_list.checked_add_signed(lap)
+            }
+            //The lap only starts once it is the highest uncovered item
+            EnterListReason::HighestUncovered { .. } if lap_count > 0.0 && lap_count < 1.0 => {
+                let left = lap.num_seconds() as f32 * (1.0 - lap_count);
+                now.checked_add_signed(chrono::Duration::seconds(left as i64))
+            }
+            EnterListReason::HighestUncovered { .. } => None,
+        };
+        if let Some(lap_ends) = lap_ends.filter(|x| x > &now) {
+            upcoming.push(Upcoming {
+                item,
+                when: lap_ends,
+                reason: UpcomingReason::LapEnds,
+            });
+        }
+    }
+
+    upcoming.extend(
+        active_snoozed
+            .iter()
+            .filter(|x| x.cover_this.get_calendar_event().is_none())
+            .map(|x| Upcoming {
+                item: x.cover_this,
+                when: x.until.into(),
+                reason: UpcomingReason::SnoozeEnds,
+            })
+            .filter(|x| x.when > now),
+    );
+    upcoming.sort_by_key(|x| x.when);
+    upcoming
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use surrealdb::sql::Duration as SurrealDuration;
+
+    use crate::{
+        base_data::BaseData,
+        calculated_data::CalculatedData,
+        surrealdb_layer::{
+            surreal_covering_until_date_time::SurrealCoveringUntilDatetime,
+            surreal_item::{EnterListReason, ItemType, Staging, SurrealItemBuilder},
+            surreal_tables::SurrealTablesBuilder,
+        },
+    };
+
+    use super::{find_upcoming, UpcomingReason};
+
+    #[test]
+    fn staged_and_snoozed_items_are_upcoming_soonest_first() {
+        let now = Utc::now();
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("surreal_item", "review the budget").into()))
+                    .summary("review the budget")
+                    .item_type(ItemType::Action)
+                    .staging(Staging::OnDeck {
+                        enter_list: EnterListReason::DateTime((now + Duration::days(2)).into()),
+                        lap: SurrealDuration::from_days(1),
+                    })
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("surreal_item", "water the plants").into()))
+                    .summary("water the plants")
+                    .item_type(ItemType::Action)
+                    .staging(Staging::MentallyResident {
+                        enter_list: EnterListReason::DateTime((now - Duration::hours(1)).into()),
+                        lap: SurrealDuration::from_hours(3),
+                    })
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("surreal_item", "call the bank").into()))
+                    .summary("call the bank")
+                    .item_type(ItemType::Action)
+                    .build()
+                    .unwrap(),
+            ])
+            .surreal_coverings_until_date_time(vec![SurrealCoveringUntilDatetime {
+                id: Some(("surreal_covering_until_date_time", "1").into()),
+                cover_this: ("surreal_item", "call the bank").into(),
+                until: (now + Duration::hours(4)).into(),
+            }])
+            .build()
+            .unwrap();
+        let calculated_data = CalculatedData::new_from_base_data(
+            BaseData::new_from_surreal_tables(surreal_tables, now),
+            &now,
+        );
+
+        let upcoming = find_upcoming(
+            calculated_data.get_item_status(),
+            calculated_data.get_active_snoozed(),
+            now,
+        );
+
+        assert_eq!(
+            upcoming
+                .iter()
+                .map(|x| (x.item.get_summary(), x.reason))
+                .collect::<Vec<_>>(),
+            [
+                ("water the plants", UpcomingReason::LapEnds),
+                ("call the bank", UpcomingReason::SnoozeEnds),
+                ("review the budget", UpcomingReason::EntersList),
+                ("review the budget", UpcomingReason::LapEnds),
+            ]
+        );
+        assert_eq!(upcoming[0].when, now + Duration::hours(2));
+        assert_eq!(upcoming[3].when, now + Duration::days(3));
+    }
+}